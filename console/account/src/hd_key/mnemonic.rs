@@ -0,0 +1,217 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// The BIP-39 English wordlist, in sorted order.
+static ENGLISH_WORDLIST: &str = include_str!("english.txt");
+
+/// The number of bits encoded by each word.
+const BITS_PER_WORD: usize = 11;
+/// The number of PBKDF2 rounds used to derive the seed.
+const PBKDF2_ROUNDS: u32 = 2048;
+
+/// A BIP-39 mnemonic, which encodes the entropy of a root seed as a phrase of 12 to 24 words.
+///
+/// The entropy is zeroized on drop.
+#[derive(Clone, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
+pub struct Mnemonic {
+    /// The entropy, of 16 to 32 bytes.
+    entropy: Vec<u8>,
+}
+
+impl Mnemonic {
+    /// The number of bytes of entropy sampled for a new mnemonic, which encodes to 24 words.
+    pub const ENTROPY_SIZE_IN_BYTES: usize = 32;
+
+    /// Samples a new random mnemonic of 24 words.
+    pub fn new<R: Rng + CryptoRng>(rng: &mut R) -> Self {
+        Self { entropy: (0..Self::ENTROPY_SIZE_IN_BYTES).map(|_| rng.gen()).collect() }
+    }
+
+    /// Initializes a mnemonic from the given entropy, which must be 16, 20, 24, 28, or 32 bytes.
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self> {
+        ensure!(
+            (16..=32).contains(&entropy.len()) && entropy.len() % 4 == 0,
+            "Invalid mnemonic entropy length: found {}, expected 16, 20, 24, 28, or 32 bytes",
+            entropy.len()
+        );
+        Ok(Self { entropy: entropy.to_vec() })
+    }
+
+    /// Returns the entropy.
+    pub fn entropy(&self) -> &[u8] {
+        &self.entropy
+    }
+
+    /// Returns the words of the mnemonic.
+    pub fn words(&self) -> Vec<&'static str> {
+        // Append the checksum, i.e. the first `entropy_bits / 32` bits of the SHA-256 hash of the entropy.
+        let mut bits = bytes_to_bits(&self.entropy);
+        bits.extend(bytes_to_bits(&Sha256::digest(&self.entropy)).into_iter().take(self.entropy.len() / 4));
+        // Map each group of 11 bits to a word.
+        bits.chunks(BITS_PER_WORD)
+            .map(|chunk| {
+                let index = chunk.iter().fold(0usize, |index, bit| (index << 1) | *bit as usize);
+                // Note: The index is less than 2048, so the word always exists.
+                ENGLISH_WORDLIST.lines().nth(index).unwrap_or_default()
+            })
+            .collect()
+    }
+
+    /// Returns the 64-byte seed of the mnemonic, salted with the given passphrase.
+    ///
+    /// The passphrase is used as-is, so a non-ASCII passphrase must be NFKD-normalized by the caller.
+    pub fn to_seed(&self, passphrase: &str) -> [u8; 64] {
+        let phrase = Zeroizing::new(self.to_string());
+        let salt = Zeroizing::new(format!("mnemonic{passphrase}"));
+        let mut seed = [0u8; 64];
+        pbkdf2_hmac::<Sha512>(phrase.as_bytes(), salt.as_bytes(), PBKDF2_ROUNDS, &mut seed);
+        seed
+    }
+}
+
+impl FromStr for Mnemonic {
+    type Err = Error;
+
+    /// Reads in a mnemonic from a phrase of space-separated words.
+    fn from_str(phrase: &str) -> Result<Self, Self::Err> {
+        // Map each word to its 11 bits.
+        let mut bits = Vec::new();
+        let mut num_words = 0;
+        for word in phrase.split_whitespace() {
+            let index = match ENGLISH_WORDLIST.lines().position(|candidate| candidate == word) {
+                Some(index) => index,
+                None => bail!("Invalid mnemonic word '{word}'"),
+            };
+            bits.extend((0..BITS_PER_WORD).rev().map(|i| (index >> i) & 1 == 1));
+            num_words += 1;
+        }
+        ensure!(
+            [12, 15, 18, 21, 24].contains(&num_words),
+            "Invalid mnemonic length: found {num_words} words, expected 12, 15, 18, 21, or 24 words"
+        );
+
+        // Split the bits into the entropy and the checksum.
+        let (entropy_bits, checksum) = bits.split_at(num_words * BITS_PER_WORD * 32 / 33);
+        let entropy = entropy_bits
+            .chunks(8)
+            .map(|chunk| chunk.iter().fold(0u8, |byte, bit| (byte << 1) | *bit as u8))
+            .collect::<Vec<_>>();
+        let mnemonic = Self::from_entropy(&entropy)?;
+
+        // Ensure the checksum is valid.
+        let expected_checksum = bytes_to_bits(&Sha256::digest(mnemonic.entropy()));
+        ensure!(checksum == &expected_checksum[..checksum.len()], "Invalid mnemonic checksum");
+        Ok(mnemonic)
+    }
+}
+
+impl fmt::Debug for Mnemonic {
+    /// Prints the mnemonic, without its words or entropy.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Mnemonic").field("num_words", &(self.entropy.len() * 3 / 4)).finish_non_exhaustive()
+    }
+}
+
+impl fmt::Display for Mnemonic {
+    /// Writes the mnemonic as a phrase of space-separated words.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.words().join(" "))
+    }
+}
+
+/// Returns the bits of the given bytes, in big-endian order.
+fn bytes_to_bits(bytes: &[u8]) -> Vec<bool> {
+    bytes.iter().flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ITERATIONS: u64 = 100;
+
+    /// The test vectors from the BIP-39 reference implementation, with the passphrase "TREZOR".
+    const TEST_VECTORS: [(&str, &str, &str); 3] = [
+        (
+            "00000000000000000000000000000000",
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+        ),
+        (
+            "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+            "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+        ),
+        (
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote",
+            "dd48c104698c30cfe2b6142103248622fb7bb0ff692eebb00089b32d22484e1613912f0a5b694407be899ffd31ed3992c456cdf60f5d4564b8ba3f05a69890ad",
+        ),
+    ];
+
+    #[test]
+    fn test_vectors() -> Result<()> {
+        for (entropy, phrase, seed) in TEST_VECTORS {
+            let mnemonic = Mnemonic::from_entropy(&from_hex(entropy))?;
+            assert_eq!(phrase, mnemonic.to_string());
+            assert_eq!(mnemonic, Mnemonic::from_str(phrase)?);
+            assert_eq!(from_hex(seed), mnemonic.to_seed("TREZOR"));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_string() -> Result<()> {
+        let mut rng = TestRng::default();
+
+        for _ in 0..ITERATIONS {
+            // Sample a new mnemonic.
+            let expected = Mnemonic::new(&mut rng);
+            assert_eq!(24, expected.words().len());
+
+            // Check the string representation.
+            let candidate = expected.to_string();
+            assert_eq!(expected, Mnemonic::from_str(&candidate)?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_debug_is_redacted() {
+        let mut rng = TestRng::default();
+
+        let mnemonic = Mnemonic::new(&mut rng);
+        assert_eq!(format!("{mnemonic:?}"), "Mnemonic { num_words: 24, .. }");
+
+        let mnemonic = Mnemonic::from_entropy(&[0u8; 16]).unwrap();
+        assert_eq!(format!("{mnemonic:?}"), "Mnemonic { num_words: 12, .. }");
+    }
+
+    #[test]
+    fn test_invalid() {
+        // Ensure an invalid entropy length is rejected.
+        assert!(Mnemonic::from_entropy(&[0u8; 15]).is_err());
+        assert!(Mnemonic::from_entropy(&[0u8; 18]).is_err());
+        assert!(Mnemonic::from_entropy(&[0u8; 36]).is_err());
+
+        // Ensure an unknown word, an invalid number of words, and an invalid checksum are rejected.
+        let abandon = "abandon ".repeat(10);
+        assert!(Mnemonic::from_str(&format!("{abandon}abandon aleo")).is_err());
+        assert!(Mnemonic::from_str(&format!("{abandon}about")).is_err());
+        assert!(Mnemonic::from_str(&format!("{abandon}abandon abandon")).is_err());
+        assert!(Mnemonic::from_str(&format!("{abandon}abandon about")).is_ok());
+    }
+}
@@ -0,0 +1,244 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Hierarchical deterministic derivation of account private keys, in the style of SLIP-10.
+//!
+//! A BIP-39 mnemonic encodes the root seed, from which the master extended key is derived as
+//! `HMAC-SHA512(key = "AleoHDMasterKey0", data = seed)`. The hardened child at index `i` is derived as
+//! `HMAC-SHA512(key = chain_code, data = 0x00 || key || ser32(i))`. In both cases, the first 32 bytes
+//! of the output are the key, and the last 32 bytes are the chain code. The account seed of an extended
+//! key is `HMAC-SHA512(key = "AleoHDAccountSeed0", data = key)`, reduced modulo the field order.
+
+mod mnemonic;
+pub use mnemonic::*;
+
+mod path;
+pub use path::*;
+
+use crate::PrivateKey;
+use snarkvm_console_network::prelude::*;
+use snarkvm_console_types::Field;
+
+use hmac::{Hmac, Mac};
+use pbkdf2::pbkdf2_hmac;
+use sha2::{Digest, Sha256, Sha512};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+/// The HMAC key used to derive the master extended key from a seed.
+static HD_MASTER_KEY_DOMAIN: &str = "AleoHDMasterKey0";
+/// The HMAC key used to derive the account seed from an extended key.
+static HD_ACCOUNT_SEED_DOMAIN: &str = "AleoHDAccountSeed0";
+
+/// An extended private key, from which hardened child keys and an account private key are derived.
+///
+/// The key material is zeroized on drop, including that of the intermediate keys of a derivation path.
+#[derive(Clone, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
+pub struct ExtendedPrivateKey {
+    /// The secret key material.
+    key: [u8; 32],
+    /// The chain code.
+    chain_code: [u8; 32],
+    /// The depth in the derivation tree, which is `0` for the master key.
+    depth: u8,
+    /// The child index, which is `0` for the master key.
+    child_index: u32,
+}
+
+impl ExtendedPrivateKey {
+    /// Returns the master extended key for the given seed, which must be 16 to 64 bytes.
+    pub fn new_master(seed: &[u8]) -> Result<Self> {
+        ensure!((16..=64).contains(&seed.len()), "Invalid seed length: found {}, expected 16 to 64 bytes", seed.len());
+        let (key, chain_code) = hmac_sha512(HD_MASTER_KEY_DOMAIN.as_bytes(), &[seed])?;
+        Ok(Self { key: *key, chain_code: *chain_code, depth: 0, child_index: 0 })
+    }
+
+    /// Returns the master extended key for the given mnemonic, salted with the given passphrase.
+    pub fn from_mnemonic(mnemonic: &Mnemonic, passphrase: &str) -> Result<Self> {
+        Self::new_master(Zeroizing::new(mnemonic.to_seed(passphrase)).as_ref())
+    }
+
+    /// Returns the child extended key at the given hardened index.
+    pub fn derive_child(&self, index: u32) -> Result<Self> {
+        ensure!(index >= DerivationPath::HARDENED_OFFSET, "Invalid child index: index {index} is not hardened");
+        let depth = self.depth.checked_add(1).ok_or_else(|| anyhow!("The maximum derivation depth is exceeded"))?;
+        let (key, chain_code) = hmac_sha512(&self.chain_code, &[&[0u8], &self.key, &index.to_be_bytes()])?;
+        Ok(Self { key: *key, chain_code: *chain_code, depth, child_index: index })
+    }
+
+    /// Returns the extended key at the given derivation path, relative to this key.
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self> {
+        path.indices().iter().try_fold(self.clone(), |key, index| key.derive_child(*index))
+    }
+
+    /// Returns the account private key of this extended key.
+    pub fn to_private_key<N: Network>(&self) -> Result<PrivateKey<N>> {
+        let (left, right) = hmac_sha512(HD_ACCOUNT_SEED_DOMAIN.as_bytes(), &[&self.key])?;
+        // Reduce all 64 bytes modulo the field order, so that the account seed is (close to) uniform.
+        let seed = Field::new(N::Field::from_bytes_le_mod_order(&Zeroizing::new([*left, *right].concat())));
+        PrivateKey::try_from(seed)
+    }
+
+    /// Returns the secret key material.
+    pub const fn key(&self) -> &[u8; 32] {
+        &self.key
+    }
+
+    /// Returns the chain code.
+    pub const fn chain_code(&self) -> &[u8; 32] {
+        &self.chain_code
+    }
+
+    /// Returns the depth in the derivation tree.
+    pub const fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    /// Returns the child index.
+    pub const fn child_index(&self) -> u32 {
+        self.child_index
+    }
+}
+
+impl fmt::Debug for ExtendedPrivateKey {
+    /// Prints the extended private key, without its key material.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("ExtendedPrivateKey")
+            .field("depth", &self.depth)
+            .field("child_index", &self.child_index)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Returns the two halves of `HMAC-SHA512(key, data)`, where `data` is the concatenation of the given slices.
+/// The halves are zeroized on drop.
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> Result<(Zeroizing<[u8; 32]>, Zeroizing<[u8; 32]>)> {
+    let mut mac = Hmac::<Sha512>::new_from_slice(key).map_err(|e| anyhow!("Failed to initialize HMAC: {e}"))?;
+    data.iter().for_each(|data| mac.update(data));
+    let mut output = mac.finalize().into_bytes();
+
+    let (mut left, mut right) = (Zeroizing::new([0u8; 32]), Zeroizing::new([0u8; 32]));
+    left.copy_from_slice(&output[..32]);
+    right.copy_from_slice(&output[32..]);
+    output.as_mut_slice().zeroize();
+    Ok((left, right))
+}
+
+/// Decodes the given hex string.
+#[cfg(test)]
+fn from_hex(hex: &str) -> Vec<u8> {
+    (0..hex.len()).step_by(2).map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console_network::MainnetV0;
+
+    type CurrentNetwork = MainnetV0;
+
+    const ITERATIONS: u64 = 100;
+
+    /// The derivation test vectors, for the mnemonic "abandon ... about" with the passphrase "TREZOR".
+    /// Each vector is a path, followed by the expected key, chain code, and account seed.
+    const TEST_VECTORS: [(&str, &str, &str, &str); 3] = [
+        (
+            "m",
+            "6a410be633595e1b2452e3bbfa9cac92bd33de81101312c882fd16427b55d345",
+            "d400104b64e2ae43d54d496e92d9282a8464f31857d16937ee8fb50b05c9e6db",
+            "2597425480981814575734983511485107317033452348799289261250699138744800973249field",
+        ),
+        (
+            "m/44'/683'/0'/0'",
+            "1a4976510f2ecff8545b6c154a6ab05c6515916357472ac20f45b57916b45876",
+            "967fa4509bc3f80150691e1a1cdea468d5547848d88c429d4eb050a8c221b206",
+            "3369345862898103945025455393516232083470558535417247594806380967855471583093field",
+        ),
+        (
+            "m/44'/683'/1'/0'",
+            "f0f81142dd32a2001c71ae79fe209b73d1bef216c8601aaef333e389e66dbeb6",
+            "d09ea3b3c2f230c5c28301c1172b6f20a6d41680d2b28824cd630eee5bdc9b51",
+            "2467914761940909893218522376136585127358862460318762025664017033946095597884field",
+        ),
+    ];
+
+    #[test]
+    fn test_vectors() -> Result<()> {
+        let mnemonic = Mnemonic::from_str(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+        )?;
+        let master = ExtendedPrivateKey::from_mnemonic(&mnemonic, "TREZOR")?;
+
+        for (path, key, chain_code, seed) in TEST_VECTORS {
+            let path = DerivationPath::from_str(path)?;
+            let candidate = master.derive_path(&path)?;
+            assert_eq!(from_hex(key), candidate.key());
+            assert_eq!(from_hex(chain_code), candidate.chain_code());
+            assert_eq!(path.indices().len(), candidate.depth() as usize);
+            assert_eq!(path.indices().last().copied().unwrap_or_default(), candidate.child_index());
+
+            let private_key = candidate.to_private_key::<CurrentNetwork>()?;
+            assert_eq!(Field::from_str(seed)?, private_key.seed());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_derivation() -> Result<()> {
+        let mut rng = TestRng::default();
+
+        for _ in 0..ITERATIONS {
+            let master = ExtendedPrivateKey::from_mnemonic(&Mnemonic::new(&mut rng), "")?;
+
+            // Ensure deriving a path is the same as deriving each child in turn.
+            let index: u32 = rng.gen_range(0..DerivationPath::HARDENED_OFFSET);
+            let expected = DerivationPath::account(index)?
+                .indices()
+                .iter()
+                .try_fold(master.clone(), |key, index| key.derive_child(*index))?;
+            let candidate = master.derive_path(&DerivationPath::account(index)?)?;
+            assert_eq!(expected, candidate);
+
+            // Ensure sibling accounts are distinct.
+            let sibling = master.derive_path(&DerivationPath::account(index ^ 1)?)?;
+            assert_ne!(candidate.to_private_key::<CurrentNetwork>()?, sibling.to_private_key::<CurrentNetwork>()?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_debug_is_redacted() -> Result<()> {
+        let master = ExtendedPrivateKey::new_master(&[7u8; 64])?;
+        let child = master.derive_child(DerivationPath::HARDENED_OFFSET)?;
+
+        let debug = format!("{child:?}");
+        assert_eq!(debug, format!("ExtendedPrivateKey {{ depth: 1, child_index: {}, .. }}", child.child_index()));
+        assert!(!debug.contains(&format!("{:?}", child.key())));
+        assert!(!debug.contains(&format!("{:?}", child.chain_code())));
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid() -> Result<()> {
+        // Ensure an invalid seed length is rejected.
+        assert!(ExtendedPrivateKey::new_master(&[0u8; 15]).is_err());
+        assert!(ExtendedPrivateKey::new_master(&[0u8; 65]).is_err());
+
+        // Ensure a non-hardened child index is rejected.
+        let master = ExtendedPrivateKey::new_master(&[0u8; 64])?;
+        assert!(master.derive_child(0).is_err());
+        assert!(master.derive_child(DerivationPath::HARDENED_OFFSET - 1).is_err());
+        assert!(master.derive_child(DerivationPath::HARDENED_OFFSET).is_ok());
+        Ok(())
+    }
+}
@@ -0,0 +1,120 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// A derivation path of hardened child indices, e.g. `m/44'/683'/0'/0'`.
+///
+/// Aleo accounts have no public derivation (i.e. from a parent address to a child address),
+/// so every index in the path must be hardened.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct DerivationPath {
+    /// The child indices, with the hardened bit set.
+    indices: Vec<u32>,
+}
+
+impl DerivationPath {
+    /// The SLIP-44 coin type of Aleo.
+    pub const COIN_TYPE: u32 = 683;
+    /// The offset of hardened child indices.
+    pub const HARDENED_OFFSET: u32 = 1 << 31;
+    /// The BIP-44 purpose.
+    pub const PURPOSE: u32 = 44;
+
+    /// Initializes a derivation path from the given child indices, which must all be hardened.
+    pub fn new(indices: Vec<u32>) -> Result<Self> {
+        for index in &indices {
+            ensure!(*index >= Self::HARDENED_OFFSET, "Invalid derivation path: index {index} is not hardened");
+        }
+        Ok(Self { indices })
+    }
+
+    /// Returns the BIP-44 derivation path of the account at the given index, i.e. `m/44'/683'/{index}'/0'`.
+    pub fn account(index: u32) -> Result<Self> {
+        ensure!(index < Self::HARDENED_OFFSET, "Invalid account index: found {index}, expected less than 2^31");
+        Self::new(
+            [Self::PURPOSE, Self::COIN_TYPE, index, 0].into_iter().map(|index| index + Self::HARDENED_OFFSET).collect(),
+        )
+    }
+
+    /// Returns the child indices, with the hardened bit set.
+    pub fn indices(&self) -> &[u32] {
+        &self.indices
+    }
+}
+
+impl FromStr for DerivationPath {
+    type Err = Error;
+
+    /// Reads in a derivation path, where each index is hardened with a `'` or `h` suffix.
+    fn from_str(path: &str) -> Result<Self, Self::Err> {
+        let mut segments = path.split('/');
+        ensure!(segments.next() == Some("m"), "Invalid derivation path '{path}': expected it to start with 'm'");
+        let indices = segments
+            .map(|segment| {
+                let index = match segment.strip_suffix('\'').or_else(|| segment.strip_suffix('h')) {
+                    Some(index) => index,
+                    None => bail!("Invalid derivation path '{path}': index '{segment}' is not hardened"),
+                };
+                let index = u32::from_str(index)
+                    .map_err(|e| anyhow!("Invalid derivation path '{path}': index '{segment}' is invalid ({e})"))?;
+                ensure!(
+                    index < Self::HARDENED_OFFSET,
+                    "Invalid derivation path '{path}': index '{segment}' is too large"
+                );
+                Ok(index + Self::HARDENED_OFFSET)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Self::new(indices)
+    }
+}
+
+impl fmt::Display for DerivationPath {
+    /// Writes the derivation path, with a `'` suffix on each index.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "m")?;
+        for index in &self.indices {
+            write!(f, "/{}'", index - Self::HARDENED_OFFSET)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_string() -> Result<()> {
+        for (expected, path) in
+            [("m", "m"), ("m/44'/683'/0'/0'", "m/44'/683'/0'/0'"), ("m/0'/2147483647'", "m/0h/2147483647h")]
+        {
+            let candidate = DerivationPath::from_str(path)?;
+            assert_eq!(expected, candidate.to_string());
+            assert_eq!(candidate, DerivationPath::from_str(expected)?);
+        }
+        assert_eq!(DerivationPath::account(0)?, DerivationPath::from_str("m/44'/683'/0'/0'")?);
+        assert_eq!(DerivationPath::account(7)?, DerivationPath::from_str("m/44'/683'/7'/0'")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid() {
+        for path in ["", "44'/683'", "m/44'/683", "m/44'/-1'", "m/2147483648'", "m//0'", "m/a'", "M/0'"] {
+            assert!(DerivationPath::from_str(path).is_err(), "Path '{path}' should be invalid");
+        }
+        assert!(DerivationPath::new(vec![0]).is_err());
+        assert!(DerivationPath::account(DerivationPath::HARDENED_OFFSET).is_err());
+    }
+}
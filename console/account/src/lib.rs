@@ -31,6 +31,11 @@ pub mod graph_key;
 #[cfg(feature = "graph_key")]
 pub use graph_key::*;
 
+#[cfg(feature = "hd_key")]
+pub mod hd_key;
+#[cfg(feature = "hd_key")]
+pub use hd_key::*;
+
 #[cfg(feature = "private_key")]
 pub mod private_key;
 #[cfg(feature = "private_key")]
@@ -41,6 +46,11 @@ pub mod signature;
 #[cfg(feature = "signature")]
 pub use signature::*;
 
+#[cfg(feature = "threshold")]
+pub mod threshold;
+#[cfg(feature = "threshold")]
+pub use threshold::*;
+
 #[cfg(feature = "view_key")]
 pub mod view_key;
 #[cfg(feature = "view_key")]
@@ -0,0 +1,306 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkvm_console_types::Address;
+
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    ChaCha20Poly1305,
+    Key,
+    Nonce,
+};
+
+/// The version of the keystore format.
+const KEYSTORE_VERSION: u8 = 1;
+/// The name of the key derivation function.
+const KDF_SCRYPT: &str = "scrypt";
+/// The name of the cipher.
+const CIPHER_CHACHA20_POLY1305: &str = "chacha20-poly1305";
+
+/// The maximum base-2 logarithm of the scrypt cost parameter.
+const MAX_SCRYPT_LOG_N: u8 = 20;
+/// The maximum scrypt block size parameter.
+const MAX_SCRYPT_R: u32 = 32;
+/// The maximum scrypt parallelization parameter.
+const MAX_SCRYPT_P: u32 = 16;
+/// The maximum memory used by scrypt, in bytes, which is `128 * r * 2^log_n`.
+const MAX_SCRYPT_MEMORY: u64 = 1 << 30;
+
+/// The parameters of the key derivation function, which derives the encryption key from the password.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreKdf {
+    /// The name of the key derivation function.
+    name: String,
+    /// The base-2 logarithm of the scrypt cost parameter.
+    log_n: u8,
+    /// The scrypt block size parameter.
+    r: u32,
+    /// The scrypt parallelization parameter.
+    p: u32,
+    /// The hex-encoded salt.
+    salt: String,
+}
+
+/// The parameters of the cipher, which encrypts the private key.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeystoreCipher {
+    /// The name of the cipher.
+    name: String,
+    /// The hex-encoded nonce.
+    nonce: String,
+}
+
+/// A password-encrypted account private key, in a versioned JSON format.
+///
+/// The encryption key is derived from the password with scrypt, and the private key bytes
+/// are encrypted with ChaCha20-Poly1305. The address is stored in the clear, so that a
+/// keystore can be identified without the password, and is checked on decryption.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Keystore<N: Network> {
+    /// The version of the keystore format.
+    version: u8,
+    /// The address of the private key.
+    address: Address<N>,
+    /// The parameters of the key derivation function.
+    kdf: KeystoreKdf,
+    /// The parameters of the cipher.
+    cipher: KeystoreCipher,
+    /// The hex-encoded ciphertext, including the authentication tag.
+    ciphertext: String,
+}
+
+impl<N: Network> Keystore<N> {
+    /// The default base-2 logarithm of the scrypt cost parameter.
+    pub const DEFAULT_LOG_N: u8 = 15;
+    /// The scrypt parallelization parameter.
+    const SCRYPT_P: u32 = 1;
+    /// The scrypt block size parameter.
+    const SCRYPT_R: u32 = 8;
+
+    /// Encrypts the given private key with the given password, using the given scrypt cost parameter.
+    pub fn encrypt<R: Rng + CryptoRng>(
+        private_key: &PrivateKey<N>,
+        password: &str,
+        log_n: u8,
+        rng: &mut R,
+    ) -> Result<Self> {
+        // Sample the salt and nonce.
+        let salt: [u8; 32] = rng.gen();
+        let nonce: [u8; 12] = rng.gen();
+
+        // Derive the encryption key.
+        let kdf = KeystoreKdf {
+            name: KDF_SCRYPT.to_string(),
+            log_n,
+            r: Self::SCRYPT_R,
+            p: Self::SCRYPT_P,
+            salt: hex::encode(salt),
+        };
+        let mut key = kdf.derive_key(password)?;
+
+        // Encrypt the private key.
+        let mut plaintext = private_key.to_bytes_le()?;
+        let ciphertext = ChaCha20Poly1305::new(Key::from_slice(&key)).encrypt(Nonce::from_slice(&nonce), &*plaintext);
+        key.zeroize();
+        plaintext.zeroize();
+
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            address: Address::try_from(private_key)?,
+            kdf,
+            cipher: KeystoreCipher { name: CIPHER_CHACHA20_POLY1305.to_string(), nonce: hex::encode(nonce) },
+            ciphertext: hex::encode(ciphertext.map_err(|_| anyhow!("Failed to encrypt the private key"))?),
+        })
+    }
+
+    /// Decrypts the private key with the given password.
+    pub fn decrypt(&self, password: &str) -> Result<PrivateKey<N>> {
+        // Ensure the version and cipher are supported.
+        ensure!(self.version == KEYSTORE_VERSION, "Unsupported keystore version {}", self.version);
+        ensure!(self.cipher.name == CIPHER_CHACHA20_POLY1305, "Unsupported keystore cipher '{}'", self.cipher.name);
+
+        // Derive the encryption key.
+        let mut key = self.kdf.derive_key(password)?;
+
+        // Decrypt the private key.
+        let nonce = hex::decode(&self.cipher.nonce)?;
+        ensure!(nonce.len() == 12, "Invalid keystore nonce length: found {}, expected 12", nonce.len());
+        let plaintext = ChaCha20Poly1305::new(Key::from_slice(&key))
+            .decrypt(Nonce::from_slice(&nonce), &*hex::decode(&self.ciphertext)?);
+        key.zeroize();
+        let mut plaintext = plaintext.map_err(|_| anyhow!("Failed to decrypt the keystore: invalid password"))?;
+        let private_key = PrivateKey::from_bytes_le(&plaintext);
+        plaintext.zeroize();
+        let private_key = private_key?;
+
+        // Ensure the private key corresponds to the address.
+        ensure!(
+            Address::try_from(&private_key)? == self.address,
+            "The keystore private key does not match its address"
+        );
+        Ok(private_key)
+    }
+
+    /// Returns the address of the private key.
+    pub const fn address(&self) -> &Address<N> {
+        &self.address
+    }
+}
+
+impl KeystoreKdf {
+    /// Derives the 32-byte encryption key from the given password.
+    fn derive_key(&self, password: &str) -> Result<[u8; 32]> {
+        ensure!(self.name == KDF_SCRYPT, "Unsupported keystore key derivation function '{}'", self.name);
+        // Ensure the scrypt parameters are bounded, so that a malicious keystore cannot exhaust the memory or CPU.
+        ensure!(self.log_n <= MAX_SCRYPT_LOG_N, "Unsupported keystore scrypt cost: log_n = {}", self.log_n);
+        ensure!((1..=MAX_SCRYPT_R).contains(&self.r), "Unsupported keystore scrypt block size: r = {}", self.r);
+        ensure!((1..=MAX_SCRYPT_P).contains(&self.p), "Unsupported keystore scrypt parallelization: p = {}", self.p);
+        ensure!(
+            (128 * u64::from(self.r)) << self.log_n <= MAX_SCRYPT_MEMORY,
+            "Unsupported keystore scrypt parameters: log_n = {} and r = {} exceed {MAX_SCRYPT_MEMORY} bytes of memory",
+            self.log_n,
+            self.r
+        );
+        let params = scrypt::Params::new(self.log_n, self.r, self.p, 32)
+            .map_err(|e| anyhow!("Invalid keystore scrypt parameters: {e}"))?;
+        let mut key = [0u8; 32];
+        scrypt::scrypt(password.as_bytes(), &hex::decode(&self.salt)?, &params, &mut key)
+            .map_err(|e| anyhow!("Failed to derive the keystore key: {e}"))?;
+        Ok(key)
+    }
+}
+
+impl<N: Network> FromStr for Keystore<N> {
+    type Err = Error;
+
+    /// Reads in a keystore from a JSON string.
+    fn from_str(keystore: &str) -> Result<Self, Self::Err> {
+        Ok(serde_json::from_str(keystore)?)
+    }
+}
+
+impl<N: Network> fmt::Display for Keystore<N> {
+    /// Writes the keystore as a JSON string.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", serde_json::to_string_pretty(self).map_err(|_| fmt::Error)?)
+    }
+}
+
+impl<N: Network> PrivateKey<N> {
+    /// Returns the private key encrypted with the given password, as a JSON keystore.
+    pub fn to_keystore<R: Rng + CryptoRng>(&self, password: &str, rng: &mut R) -> Result<String> {
+        Ok(Keystore::encrypt(self, password, Keystore::<N>::DEFAULT_LOG_N, rng)?.to_string())
+    }
+
+    /// Returns the private key decrypted with the given password, from a JSON keystore.
+    pub fn from_keystore(keystore: &str, password: &str) -> Result<Self> {
+        Keystore::from_str(keystore)?.decrypt(password)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console_network::MainnetV0;
+
+    type CurrentNetwork = MainnetV0;
+
+    const ITERATIONS: u64 = 10;
+
+    /// A low scrypt cost parameter, to keep the tests fast.
+    const TEST_LOG_N: u8 = 10;
+
+    /// A keystore for `APrivateKey1zkp8cC4jgHEBnbtu3xxs1Ndja2EMizcvTRDq5Nikdkukg1p` with the password "aleo",
+    /// the salt `000102...1f`, and the nonce `000102...0b`.
+    const TEST_VECTOR: &str = r#"{
+  "version": 1,
+  "address": "aleo1wvgwnqvy46qq0zemj0k6sfp3zv0mp77rw97khvwuhac05yuwscxqmfyhwf",
+  "kdf": {
+    "name": "scrypt",
+    "log_n": 10,
+    "r": 8,
+    "p": 1,
+    "salt": "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f"
+  },
+  "cipher": {
+    "name": "chacha20-poly1305",
+    "nonce": "000102030405060708090a0b"
+  },
+  "ciphertext": "ce0c81582249187739a9efcbbb104338c5e88ce18cde2488046f02c85006ba0a1531881096e022068a096bfb1b8fe3dd"
+}"#;
+
+    #[test]
+    fn test_vector() -> Result<()> {
+        let expected =
+            PrivateKey::<CurrentNetwork>::from_str("APrivateKey1zkp8cC4jgHEBnbtu3xxs1Ndja2EMizcvTRDq5Nikdkukg1p")?;
+        let keystore = Keystore::<CurrentNetwork>::from_str(TEST_VECTOR)?;
+        assert_eq!(TEST_VECTOR, keystore.to_string());
+        assert_eq!(expected, keystore.decrypt("aleo")?);
+        assert_eq!(expected, PrivateKey::from_keystore(TEST_VECTOR, "aleo")?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_keystore() -> Result<()> {
+        let mut rng = TestRng::default();
+
+        for _ in 0..ITERATIONS {
+            // Sample a new private key.
+            let expected = PrivateKey::<CurrentNetwork>::new(&mut rng)?;
+
+            // Encrypt and decrypt the private key.
+            let keystore = Keystore::encrypt(&expected, "password", TEST_LOG_N, &mut rng)?;
+            assert_eq!(&Address::try_from(&expected)?, keystore.address());
+            assert_eq!(expected, keystore.decrypt("password")?);
+            assert_eq!(expected, PrivateKey::from_keystore(&keystore.to_string(), "password")?);
+
+            // Ensure the wrong password is rejected.
+            assert!(keystore.decrypt("wrong password").is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid() -> Result<()> {
+        let keystore = Keystore::<CurrentNetwork>::from_str(TEST_VECTOR)?;
+
+        // Ensure an unsupported version is rejected.
+        let mut candidate = keystore.clone();
+        candidate.version = 2;
+        assert!(candidate.decrypt("aleo").is_err());
+
+        // Ensure a tampered ciphertext is rejected.
+        let mut candidate = keystore.clone();
+        candidate.ciphertext.replace_range(0..2, "00");
+        assert!(candidate.decrypt("aleo").is_err());
+
+        // Ensure unbounded scrypt parameters are rejected, before deriving the key.
+        let parameters = [(MAX_SCRYPT_LOG_N + 1, 8, 1), (10, 0, 1), (10, MAX_SCRYPT_R + 1, 1), (10, 8, 0), (10, 8, 17)];
+        for (log_n, r, p) in parameters {
+            let mut candidate = keystore.clone();
+            (candidate.kdf.log_n, candidate.kdf.r, candidate.kdf.p) = (log_n, r, p);
+            assert!(candidate.decrypt("aleo").is_err());
+        }
+        let mut candidate = keystore.clone();
+        (candidate.kdf.log_n, candidate.kdf.r) = (MAX_SCRYPT_LOG_N, MAX_SCRYPT_R);
+        assert!(candidate.kdf.derive_key("aleo").is_err());
+
+        // Ensure a mismatched address is rejected.
+        let mut candidate = keystore;
+        candidate.address = Address::try_from(PrivateKey::<CurrentNetwork>::new(&mut TestRng::default())?)?;
+        assert!(candidate.decrypt("aleo").is_err());
+        Ok(())
+    }
+}
@@ -17,6 +17,11 @@ mod serialize;
 mod string;
 mod try_from;
 
+#[cfg(feature = "keystore")]
+mod keystore;
+#[cfg(feature = "keystore")]
+pub use keystore::*;
+
 #[cfg(feature = "signature")]
 mod sign;
 
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use super::*;
+use snarkvm_console_network::Multipart;
 
 impl<N: Network> FromBytes for Signature<N> {
     /// Reads an account signature from a buffer.
@@ -35,10 +36,17 @@ impl<N: Network> ToBytes for Signature<N> {
     }
 }
 
+impl<N: Network> Multipart<N> for Signature<N> {
+    /// Returns the human-readable prefix of each part of an account signature.
+    fn multipart_prefix() -> &'static str {
+        "signature"
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use snarkvm_console_network::MainnetV0;
+    use snarkvm_console_network::{MainnetV0, MULTIPART_FRAGMENT_SIZE};
 
     type CurrentNetwork = MainnetV0;
 
@@ -56,6 +64,11 @@ mod tests {
             let signature_bytes = signature.to_bytes_le()?;
             assert_eq!(signature, Signature::read_le(&signature_bytes[..])?);
             assert!(Signature::<CurrentNetwork>::read_le(&signature_bytes[1..]).is_err());
+
+            // Check the multipart representation.
+            let parts = signature.to_multipart(MULTIPART_FRAGMENT_SIZE)?;
+            assert_eq!(1, parts.len());
+            assert_eq!(signature, Signature::from_multipart(&parts)?);
         }
         Ok(())
     }
@@ -0,0 +1,198 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// Reads a `u16`-prefixed vector of group elements from the buffer.
+fn read_groups<N: Network, R: Read>(mut reader: R) -> IoResult<Vec<Group<N>>> {
+    let num_elements = u16::read_le(&mut reader)?;
+    (0..num_elements).map(|_| Group::read_le(&mut reader)).collect()
+}
+
+/// Writes a `u16`-prefixed vector of group elements to the buffer.
+fn write_groups<N: Network, W: Write>(elements: &[Group<N>], mut writer: W) -> IoResult<()> {
+    u16::try_from(elements.len()).map_err(|_| error("Too many group elements"))?.write_le(&mut writer)?;
+    elements.write_le(&mut writer)
+}
+
+impl<N: Network> FromBytes for ThresholdAccount<N> {
+    /// Reads the threshold account from a buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the version.
+        let version = u8::read_le(&mut reader)?;
+        // Ensure the version is valid.
+        if version != 1 {
+            return Err(error("Invalid threshold account version"));
+        }
+        // Read the threshold account.
+        let threshold = u16::read_le(&mut reader)?;
+        let compute_key = ComputeKey::read_le(&mut reader)?;
+        let view_key = ViewKey::read_le(&mut reader)?;
+        let verifying_shares = read_groups(&mut reader)?;
+        // Ensure the threshold is valid.
+        if threshold == 0 || threshold as usize > verifying_shares.len() {
+            return Err(error("Invalid threshold account threshold"));
+        }
+        Ok(Self { threshold, compute_key, view_key, verifying_shares })
+    }
+}
+
+impl<N: Network> ToBytes for ThresholdAccount<N> {
+    /// Writes the threshold account to a buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        1u8.write_le(&mut writer)?;
+        self.threshold.write_le(&mut writer)?;
+        self.compute_key.write_le(&mut writer)?;
+        self.view_key.write_le(&mut writer)?;
+        write_groups(&self.verifying_shares, &mut writer)
+    }
+}
+
+impl<N: Network> FromBytes for KeyShare<N> {
+    /// Reads the key share from a buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the version.
+        let version = u8::read_le(&mut reader)?;
+        // Ensure the version is valid.
+        if version != 1 {
+            return Err(error("Invalid key share version"));
+        }
+        // Read the key share.
+        let index = u16::read_le(&mut reader)?;
+        let secret = Scalar::read_le(&mut reader)?;
+        Ok(Self { index, secret })
+    }
+}
+
+impl<N: Network> ToBytes for KeyShare<N> {
+    /// Writes the key share to a buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        1u8.write_le(&mut writer)?;
+        self.index.write_le(&mut writer)?;
+        self.secret.write_le(&mut writer)
+    }
+}
+
+impl<N: Network> FromBytes for SigningCommitments<N> {
+    /// Reads the signing commitments from a buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the version.
+        let version = u8::read_le(&mut reader)?;
+        // Ensure the version is valid.
+        if version != 1 {
+            return Err(error("Invalid signing commitments version"));
+        }
+        // Read the signing commitments.
+        let index = u16::read_le(&mut reader)?;
+        let hiding = read_groups(&mut reader)?;
+        let binding = read_groups(&mut reader)?;
+        let shares = read_groups(&mut reader)?;
+        Ok(Self { index, hiding, binding, shares })
+    }
+}
+
+impl<N: Network> ToBytes for SigningCommitments<N> {
+    /// Writes the signing commitments to a buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        1u8.write_le(&mut writer)?;
+        self.index.write_le(&mut writer)?;
+        write_groups(&self.hiding, &mut writer)?;
+        write_groups(&self.binding, &mut writer)?;
+        write_groups(&self.shares, &mut writer)
+    }
+}
+
+impl<N: Network> FromBytes for SigningPackage<N> {
+    /// Reads the signing package from a buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the version.
+        let version = u8::read_le(&mut reader)?;
+        // Ensure the version is valid.
+        if version != 1 {
+            return Err(error("Invalid signing package version"));
+        }
+        // Read the signing package.
+        let context = Field::read_le(&mut reader)?;
+        let num_commitments = u16::read_le(&mut reader)?;
+        let commitments =
+            (0..num_commitments).map(|_| SigningCommitments::read_le(&mut reader)).collect::<IoResult<Vec<_>>>()?;
+        Self::new(context, commitments).map_err(|e| error(format!("Invalid signing package: {e}")))
+    }
+}
+
+impl<N: Network> ToBytes for SigningPackage<N> {
+    /// Writes the signing package to a buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        1u8.write_le(&mut writer)?;
+        self.context().write_le(&mut writer)?;
+        u16::try_from(self.commitments().len())
+            .map_err(|_| error("Too many signing commitments"))?
+            .write_le(&mut writer)?;
+        self.commitments().write_le(&mut writer)
+    }
+}
+
+impl<N: Network> FromBytes for SignatureShare<N> {
+    /// Reads the signature share from a buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the version.
+        let version = u8::read_le(&mut reader)?;
+        // Ensure the version is valid.
+        if version != 1 {
+            return Err(error("Invalid signature share version"));
+        }
+        // Read the signature share.
+        let index = u16::read_le(&mut reader)?;
+        let response = Scalar::read_le(&mut reader)?;
+        Ok(Self { index, response })
+    }
+}
+
+impl<N: Network> ToBytes for SignatureShare<N> {
+    /// Writes the signature share to a buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        1u8.write_le(&mut writer)?;
+        self.index.write_le(&mut writer)?;
+        self.response.write_le(&mut writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console_network::MainnetV0;
+
+    type CurrentNetwork = MainnetV0;
+
+    #[test]
+    fn test_bytes() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng)?;
+        let (account, key_shares) = ThresholdAccount::new_trusted_dealer(&private_key, 2, 3, rng)?;
+        assert_eq!(account, ThresholdAccount::read_le(&account.to_bytes_le()?[..])?);
+        assert_eq!(key_shares[0], KeyShare::read_le(&key_shares[0].to_bytes_le()?[..])?);
+
+        let (nonces, commitments_1) = key_shares[0].commit(&[Group::rand(rng)], &[Group::rand(rng)], rng)?;
+        let (_, commitments_2) = key_shares[1].commit(&[Group::rand(rng)], &[Group::rand(rng)], rng)?;
+        assert_eq!(commitments_1, SigningCommitments::read_le(&commitments_1.to_bytes_le()?[..])?);
+
+        let package = SigningPackage::new(Field::rand(rng), vec![commitments_1, commitments_2])?;
+        assert_eq!(package, SigningPackage::read_le(&package.to_bytes_le()?[..])?);
+
+        let share = key_shares[0].sign_share(nonces, &package, Scalar::rand(rng))?;
+        assert_eq!(share, SignatureShare::read_le(&share.to_bytes_le()?[..])?);
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// The secret (hiding, binding) nonces of a participant for one signing session.
+///
+/// Note: The nonces must be used for at most one signature share, so they are consumed when signing.
+/// They are zeroized on drop, and are not printed by the `Debug` implementation.
+#[derive(PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
+pub struct SigningNonces<N: Network> {
+    /// The index of the participant.
+    index: u16,
+    /// The hiding nonce.
+    hiding: Scalar<N>,
+    /// The binding nonce.
+    binding: Scalar<N>,
+}
+
+impl<N: Network> fmt::Debug for SigningNonces<N> {
+    /// Prints the signing nonces, without the nonces.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SigningNonces").field("index", &self.index).finish_non_exhaustive()
+    }
+}
+
+/// The public commitments of a participant for one signing session.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SigningCommitments<N: Network> {
+    /// The index of the participant.
+    pub(super) index: u16,
+    /// The hiding nonce on each base, starting with the generator `G`.
+    pub(super) hiding: Vec<Group<N>>,
+    /// The binding nonce on each base, starting with the generator `G`.
+    pub(super) binding: Vec<Group<N>>,
+    /// The key share on each share base.
+    pub(super) shares: Vec<Group<N>>,
+}
+
+impl<N: Network> KeyShare<N> {
+    /// Returns fresh nonces and their commitments on the generator `G` and the given bases,
+    /// along with the key share on the given share bases.
+    pub fn commit<R: Rng + CryptoRng>(
+        &self,
+        bases: &[Group<N>],
+        share_bases: &[Group<N>],
+        rng: &mut R,
+    ) -> Result<(SigningNonces<N>, SigningCommitments<N>)> {
+        // Sample the nonces, hedged with the key share in case the RNG is weak.
+        let mut sample_nonce = || {
+            N::hash_to_scalar_psd4(&[
+                Field::new_domain_separator(THRESHOLD_NONCE_DOMAIN),
+                self.secret.to_field()?,
+                Field::rand(rng),
+            ])
+        };
+        let nonces = SigningNonces { index: self.index, hiding: sample_nonce()?, binding: sample_nonce()? };
+
+        // Commit to the nonces on each base.
+        let commit = |nonce: &Scalar<N>| {
+            std::iter::once(N::g_scalar_multiply(nonce)).chain(bases.iter().map(|base| *base * nonce)).collect()
+        };
+        let commitments = SigningCommitments {
+            index: self.index,
+            hiding: commit(&nonces.hiding),
+            binding: commit(&nonces.binding),
+            shares: share_bases.iter().map(|base| *base * self.secret).collect(),
+        };
+        Ok((nonces, commitments))
+    }
+}
+
+impl<N: Network> SigningNonces<N> {
+    /// Returns the index of the participant.
+    pub const fn index(&self) -> u16 {
+        self.index
+    }
+
+    /// Returns the hiding nonce.
+    pub(super) const fn hiding(&self) -> Scalar<N> {
+        self.hiding
+    }
+
+    /// Returns the binding nonce.
+    pub(super) const fn binding(&self) -> Scalar<N> {
+        self.binding
+    }
+}
+
+impl<N: Network> SigningCommitments<N> {
+    /// Returns the index of the participant.
+    pub const fn index(&self) -> u16 {
+        self.index
+    }
+
+    /// Returns the hiding nonce on each base, starting with the generator `G`.
+    pub fn hiding(&self) -> &[Group<N>] {
+        &self.hiding
+    }
+
+    /// Returns the binding nonce on each base, starting with the generator `G`.
+    pub fn binding(&self) -> &[Group<N>] {
+        &self.binding
+    }
+
+    /// Returns the key share on each share base.
+    pub fn shares(&self) -> &[Group<N>] {
+        &self.shares
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console_network::MainnetV0;
+
+    type CurrentNetwork = MainnetV0;
+
+    #[test]
+    fn test_commit() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng)?;
+        let (_, key_shares) = ThresholdAccount::new_trusted_dealer(&private_key, 2, 3, rng)?;
+        let (bases, share_bases) = ([Group::rand(rng), Group::rand(rng)], [Group::rand(rng)]);
+
+        let (nonces, commitments) = key_shares[1].commit(&bases, &share_bases, rng)?;
+        assert_eq!(2, nonces.index());
+        assert_eq!(2, commitments.index());
+        assert_eq!(
+            [CurrentNetwork::g_scalar_multiply(&nonces.hiding), bases[0] * nonces.hiding, bases[1] * nonces.hiding],
+            commitments.hiding()
+        );
+        assert_eq!(
+            [CurrentNetwork::g_scalar_multiply(&nonces.binding), bases[0] * nonces.binding, bases[1] * nonces.binding],
+            commitments.binding()
+        );
+        assert_eq!([share_bases[0] * key_shares[1].secret], commitments.shares());
+
+        // Ensure fresh nonces are sampled on each commitment.
+        let (candidate, _) = key_shares[1].commit(&bases, &share_bases, rng)?;
+        assert_ne!(nonces, candidate);
+        Ok(())
+    }
+}
@@ -0,0 +1,240 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Threshold Schnorr signatures (in the style of FROST) that produce standard account signatures.
+//!
+//! The signature secret key `sk_sig` of an account is split with Shamir secret sharing among `n`
+//! participants, so that any `t` of them can jointly sign, while `pr_sig` and the view key are shared
+//! by all participants. Signing takes two rounds:
+//!   1. Each participant commits to a pair of (hiding, binding) nonces with `KeyShare::commit`.
+//!   2. Given the `SigningPackage` of `t` or more commitments, each participant computes the challenge
+//!      and its `SignatureShare` with `KeyShare::sign_share`.
+//!
+//! The coordinator then verifies and aggregates the shares into a `Signature` with `ThresholdAccount::aggregate`.
+//!
+//! Besides the generator `G`, participants can commit to their nonces on additional bases, and reveal
+//! their secret share on additional bases, which is needed to sign requests (see `ThresholdRequest`).
+
+mod bytes;
+mod commit;
+mod package;
+mod sign;
+
+pub use commit::*;
+pub use package::*;
+pub use sign::*;
+
+use crate::{Address, ComputeKey, PrivateKey, Signature, ViewKey};
+use snarkvm_console_network::prelude::*;
+use snarkvm_console_types::{Field, Group, Scalar};
+
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+
+/// The domain separator for the nonces of a participant.
+static THRESHOLD_NONCE_DOMAIN: &str = "AleoThresholdNonce0";
+/// The domain separator for the binding factors of a signing package.
+static THRESHOLD_BINDING_DOMAIN: &str = "AleoThresholdBindingFactor0";
+
+/// The account of a threshold signing group, which is known to every participant.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ThresholdAccount<N: Network> {
+    /// The minimum number of participants required to sign.
+    threshold: u16,
+    /// The compute key of the account.
+    compute_key: ComputeKey<N>,
+    /// The view key of the account.
+    view_key: ViewKey<N>,
+    /// The verifying share of each participant, i.e. `share * G`, where participant `i` is at index `i - 1`.
+    verifying_shares: Vec<Group<N>>,
+}
+
+/// The secret key share of a participant in a threshold signing group.
+///
+/// Note: The key share is zeroized on drop, and is not printed by its `Debug` implementation.
+#[derive(Clone, PartialEq, Eq, Zeroize, ZeroizeOnDrop)]
+pub struct KeyShare<N: Network> {
+    /// The index of the participant, starting from `1`.
+    index: u16,
+    /// The share of the signature secret key.
+    secret: Scalar<N>,
+}
+
+impl<N: Network> fmt::Debug for KeyShare<N> {
+    /// Prints the key share, without its secret.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("KeyShare").field("index", &self.index).finish_non_exhaustive()
+    }
+}
+
+impl<N: Network> ThresholdAccount<N> {
+    /// Splits the signature secret key of the given private key among `num_participants` participants,
+    /// so that any `threshold` of them can sign, returning the threshold account and the key shares.
+    ///
+    /// Note: The dealer learns every key share, so the private key should be discarded after the split.
+    pub fn new_trusted_dealer<R: Rng + CryptoRng>(
+        private_key: &PrivateKey<N>,
+        threshold: u16,
+        num_participants: u16,
+        rng: &mut R,
+    ) -> Result<(Self, Vec<KeyShare<N>>)> {
+        ensure!(
+            (1..=num_participants).contains(&threshold),
+            "Invalid threshold: found {threshold}, expected between 1 and {num_participants}"
+        );
+
+        // Sample the polynomial `f`, where `f(0) = sk_sig`.
+        // Note: The coefficients are zeroized on drop, as they include the signature secret key.
+        let coefficients = Zeroizing::new(
+            std::iter::once(private_key.sk_sig()).chain((1..threshold).map(|_| Scalar::rand(rng))).collect::<Vec<_>>(),
+        );
+
+        // Compute the key share of each participant as `f(index)`.
+        let key_shares = (1..=num_participants)
+            .map(|index| {
+                let x = scalar_from_u16::<N>(index);
+                let secret = coefficients.iter().rev().fold(Scalar::zero(), |sum, coefficient| sum * x + coefficient);
+                KeyShare { index, secret }
+            })
+            .collect::<Vec<_>>();
+
+        let account = Self {
+            threshold,
+            compute_key: ComputeKey::try_from(private_key)?,
+            view_key: ViewKey::try_from(private_key)?,
+            verifying_shares: key_shares.iter().map(|share| N::g_scalar_multiply(&share.secret)).collect(),
+        };
+        Ok((account, key_shares))
+    }
+
+    /// Returns the minimum number of participants required to sign.
+    pub const fn threshold(&self) -> u16 {
+        self.threshold
+    }
+
+    /// Returns the number of participants.
+    pub fn num_participants(&self) -> usize {
+        self.verifying_shares.len()
+    }
+
+    /// Returns the compute key of the account.
+    pub const fn compute_key(&self) -> &ComputeKey<N> {
+        &self.compute_key
+    }
+
+    /// Returns the view key of the account.
+    pub const fn view_key(&self) -> &ViewKey<N> {
+        &self.view_key
+    }
+
+    /// Returns the address of the account.
+    pub fn address(&self) -> Address<N> {
+        self.compute_key.to_address()
+    }
+
+    /// Returns the verifying share of the participant with the given index.
+    pub fn verifying_share(&self, index: u16) -> Result<Group<N>> {
+        match index.checked_sub(1).and_then(|i| self.verifying_shares.get(i as usize)) {
+            Some(verifying_share) => Ok(*verifying_share),
+            None => bail!("Participant {index} is not in the threshold account"),
+        }
+    }
+
+    /// Returns `true` if the given key share belongs to the threshold account.
+    pub fn is_valid_key_share(&self, key_share: &KeyShare<N>) -> bool {
+        matches!(self.verifying_share(key_share.index), Ok(share) if share == N::g_scalar_multiply(&key_share.secret))
+    }
+}
+
+impl<N: Network> KeyShare<N> {
+    /// Returns the index of the participant.
+    pub const fn index(&self) -> u16 {
+        self.index
+    }
+}
+
+/// Returns the given integer as a scalar.
+fn scalar_from_u16<N: Network>(value: u16) -> Scalar<N> {
+    Scalar::new(N::Scalar::from(value as u128))
+}
+
+/// Returns the Lagrange coefficient at zero of the participant with the given index, for the given set of indices.
+fn lagrange_coefficient<N: Network>(index: u16, indices: &[u16]) -> Result<Scalar<N>> {
+    let (mut numerator, mut denominator) = (Scalar::<N>::one(), Scalar::<N>::one());
+    for other in indices.iter().filter(|other| **other != index) {
+        numerator *= scalar_from_u16::<N>(*other);
+        denominator *= scalar_from_u16::<N>(*other) - scalar_from_u16::<N>(index);
+    }
+    Ok(numerator * denominator.inverse()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console_network::MainnetV0;
+
+    type CurrentNetwork = MainnetV0;
+
+    #[test]
+    fn test_trusted_dealer() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng)?;
+        let (account, key_shares) = ThresholdAccount::new_trusted_dealer(&private_key, 3, 5, rng)?;
+        assert_eq!(Address::try_from(&private_key)?, account.address());
+        assert_eq!(ViewKey::try_from(&private_key)?, *account.view_key());
+        assert_eq!(5, account.num_participants());
+        assert!(key_shares.iter().all(|key_share| account.is_valid_key_share(key_share)));
+
+        // Ensure any `threshold` shares interpolate to the signature secret key.
+        for indices in [[1, 2, 3], [1, 3, 5], [2, 4, 5]] {
+            let mut candidate = Scalar::zero();
+            for index in indices {
+                candidate += lagrange_coefficient(index, &indices)? * key_shares[index as usize - 1].secret;
+            }
+            assert_eq!(private_key.sk_sig(), candidate);
+        }
+
+        // Ensure fewer than `threshold` shares do not.
+        let indices = [1, 2];
+        let candidate = indices.iter().try_fold(Scalar::zero(), |sum, index| {
+            Ok::<_, Error>(sum + lagrange_coefficient(*index, &indices)? * key_shares[*index as usize - 1].secret)
+        })?;
+        assert_ne!(private_key.sk_sig(), candidate);
+
+        // Ensure an invalid threshold is rejected.
+        assert!(ThresholdAccount::new_trusted_dealer(&private_key, 0, 5, rng).is_err());
+        assert!(ThresholdAccount::new_trusted_dealer(&private_key, 6, 5, rng).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_debug_is_redacted() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng)?;
+        let (_, key_shares) = ThresholdAccount::new_trusted_dealer(&private_key, 2, 3, rng)?;
+        let key_share = &key_shares[1];
+
+        let debug = format!("{key_share:?}");
+        assert_eq!(debug, "KeyShare { index: 2, .. }");
+        assert!(!debug.contains(&key_share.secret.to_string()));
+
+        let (nonces, _) = key_share.commit(&[], &[], rng)?;
+        let debug = format!("{nonces:?}");
+        assert_eq!(debug, "SigningNonces { index: 2, .. }");
+        assert!(!debug.contains(&nonces.hiding().to_string()));
+        assert!(!debug.contains(&nonces.binding().to_string()));
+        Ok(())
+    }
+}
@@ -0,0 +1,169 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// The commitments of the participants in a signing session, bound to the context being signed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SigningPackage<N: Network> {
+    /// The context being signed, e.g. the hash of the message.
+    context: Field<N>,
+    /// The commitments of the participants, sorted by index.
+    commitments: Vec<SigningCommitments<N>>,
+    /// The digest of the context and the commitments.
+    digest: Field<N>,
+}
+
+impl<N: Network> SigningPackage<N> {
+    /// Initializes a signing package for the given context, from the commitments of the participants.
+    pub fn new(context: Field<N>, mut commitments: Vec<SigningCommitments<N>>) -> Result<Self> {
+        // Sort the commitments by index.
+        commitments.sort_by_key(|commitment| commitment.index());
+
+        // Ensure the commitments are well-formed.
+        let Some(first) = commitments.first() else { bail!("A signing package requires at least one commitment") };
+        ensure!(!first.hiding().is_empty(), "The commitments of participant {} are empty", first.index());
+        for (previous, commitment) in commitments.iter().tuple_windows() {
+            ensure!(
+                previous.index() != commitment.index(),
+                "Found duplicate commitments for participant {}",
+                previous.index()
+            );
+        }
+        for commitment in &commitments {
+            ensure!(
+                commitment.hiding().len() == first.hiding().len()
+                    && commitment.binding().len() == first.hiding().len()
+                    && commitment.shares().len() == first.shares().len(),
+                "The commitments of participant {} do not match the other participants",
+                commitment.index()
+            );
+        }
+
+        // Compute the digest as `Hash(context || index || hiding || binding || shares)`, over all participants.
+        let mut preimage = vec![Field::new_domain_separator(THRESHOLD_BINDING_DOMAIN), context];
+        for commitment in &commitments {
+            preimage.push(Field::from_u16(commitment.index()));
+            for point in commitment.hiding().iter().chain(commitment.binding()).chain(commitment.shares()) {
+                let (x, y) = point.to_xy_coordinates();
+                preimage.extend([x, y]);
+            }
+        }
+        let digest = N::hash_psd8(&preimage)?;
+
+        Ok(Self { context, commitments, digest })
+    }
+
+    /// Returns the context being signed.
+    pub const fn context(&self) -> Field<N> {
+        self.context
+    }
+
+    /// Returns the commitments of the participants, sorted by index.
+    pub fn commitments(&self) -> &[SigningCommitments<N>] {
+        &self.commitments
+    }
+
+    /// Returns the indices of the participants, in sorted order.
+    pub fn indices(&self) -> Vec<u16> {
+        self.commitments.iter().map(|commitment| commitment.index()).collect()
+    }
+
+    /// Returns the commitments of the participant with the given index.
+    pub fn commitment(&self, index: u16) -> Result<&SigningCommitments<N>> {
+        match self.commitments.binary_search_by_key(&index, |commitment| commitment.index()) {
+            Ok(position) => Ok(&self.commitments[position]),
+            Err(_) => bail!("Participant {index} is not in the signing package"),
+        }
+    }
+
+    /// Returns the binding factor of the participant with the given index, as `HashToScalar(digest || index)`.
+    pub fn binding_factor(&self, index: u16) -> Result<Scalar<N>> {
+        N::hash_to_scalar_psd4(&[
+            Field::new_domain_separator(THRESHOLD_BINDING_DOMAIN),
+            self.digest,
+            Field::from_u16(index),
+        ])
+    }
+
+    /// Returns the group nonce on each base, starting with the generator `G`,
+    /// as the sum of `hiding + binding_factor * binding` over all participants.
+    pub fn group_nonces(&self) -> Result<Vec<Group<N>>> {
+        let mut group_nonces = vec![Group::zero(); self.commitments[0].hiding().len()];
+        for commitment in &self.commitments {
+            let binding_factor = self.binding_factor(commitment.index())?;
+            for (group_nonce, (hiding, binding)) in
+                group_nonces.iter_mut().zip_eq(commitment.hiding().iter().zip_eq(commitment.binding()))
+            {
+                *group_nonce += *hiding + *binding * binding_factor;
+            }
+        }
+        Ok(group_nonces)
+    }
+
+    /// Returns the secret key on each share base, interpolated from the key shares of all participants.
+    pub fn group_shares(&self) -> Result<Vec<Group<N>>> {
+        let indices = self.indices();
+        let mut group_shares = vec![Group::zero(); self.commitments[0].shares().len()];
+        for commitment in &self.commitments {
+            let lagrange_coefficient = lagrange_coefficient(commitment.index(), &indices)?;
+            for (group_share, share) in group_shares.iter_mut().zip_eq(commitment.shares()) {
+                *group_share += *share * lagrange_coefficient;
+            }
+        }
+        Ok(group_shares)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console_network::MainnetV0;
+
+    type CurrentNetwork = MainnetV0;
+
+    #[test]
+    fn test_package() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng)?;
+        let (_, key_shares) = ThresholdAccount::new_trusted_dealer(&private_key, 2, 3, rng)?;
+        let share_base = Group::rand(rng);
+
+        // Commit with participants 3 and 1, out of order.
+        let (_, commitments_3) = key_shares[2].commit(&[], &[share_base], rng)?;
+        let (_, commitments_1) = key_shares[0].commit(&[], &[share_base], rng)?;
+        let context = Field::rand(rng);
+        let package = SigningPackage::new(context, vec![commitments_3.clone(), commitments_1.clone()])?;
+        assert_eq!(context, package.context());
+        assert_eq!(vec![1, 3], package.indices());
+        assert_eq!(&commitments_3, package.commitment(3)?);
+        assert!(package.commitment(2).is_err());
+
+        // Ensure the binding factors depend on the participant and the context.
+        assert_ne!(package.binding_factor(1)?, package.binding_factor(3)?);
+        let candidate = SigningPackage::new(Field::rand(rng), vec![commitments_1.clone(), commitments_3.clone()])?;
+        assert_ne!(package.binding_factor(1)?, candidate.binding_factor(1)?);
+
+        // Ensure the group shares interpolate the signature secret key.
+        assert_eq!(vec![share_base * private_key.sk_sig()], package.group_shares()?);
+
+        // Ensure duplicate, empty, and mismatched commitments are rejected.
+        assert!(SigningPackage::new(context, vec![commitments_1.clone(), commitments_1.clone()]).is_err());
+        assert!(SigningPackage::new(context, vec![]).is_err());
+        let (_, commitments_2) = key_shares[1].commit(&[share_base], &[share_base], rng)?;
+        assert!(SigningPackage::new(context, vec![commitments_1, commitments_2]).is_err());
+        Ok(())
+    }
+}
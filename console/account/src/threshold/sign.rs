@@ -0,0 +1,244 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// The share of a participant in the response of a threshold signature.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct SignatureShare<N: Network> {
+    /// The index of the participant.
+    pub(super) index: u16,
+    /// The share of the response.
+    pub(super) response: Scalar<N>,
+}
+
+impl<N: Network> SignatureShare<N> {
+    /// Returns the index of the participant.
+    pub const fn index(&self) -> u16 {
+        self.index
+    }
+
+    /// Returns the share of the response.
+    pub const fn response(&self) -> Scalar<N> {
+        self.response
+    }
+}
+
+impl<N: Network> KeyShare<N> {
+    /// Returns the signature share for the given challenge, where:
+    ///     response := hiding + binding_factor * binding - challenge * lagrange_coefficient * share
+    pub fn sign_share(
+        &self,
+        nonces: SigningNonces<N>,
+        package: &SigningPackage<N>,
+        challenge: Scalar<N>,
+    ) -> Result<SignatureShare<N>> {
+        ensure!(nonces.index() == self.index, "The nonces do not belong to participant {}", self.index);
+
+        // Ensure the package contains the commitments to the nonces.
+        let commitment = package.commitment(self.index)?;
+        ensure!(
+            commitment.hiding()[0] == N::g_scalar_multiply(&nonces.hiding())
+                && commitment.binding()[0] == N::g_scalar_multiply(&nonces.binding()),
+            "The signing package does not contain the commitments of participant {}",
+            self.index
+        );
+
+        // Compute the response share.
+        let binding_factor = package.binding_factor(self.index)?;
+        let lagrange_coefficient = lagrange_coefficient(self.index, &package.indices())?;
+        let response =
+            nonces.hiding() + binding_factor * nonces.binding() - challenge * lagrange_coefficient * self.secret;
+        // Note: The nonces are consumed, and zeroized when dropped.
+
+        Ok(SignatureShare { index: self.index, response })
+    }
+}
+
+impl<N: Network> SigningPackage<N> {
+    /// Returns the context for signing the given message.
+    pub fn message_context(message: &[Field<N>]) -> Result<Field<N>> {
+        N::hash_psd8(message)
+    }
+}
+
+impl<N: Network> ThresholdAccount<N> {
+    /// Returns the challenge for signing the given message with the given signing package, where:
+    ///     challenge := HashToScalar(nonce * G, pk_sig, pr_sig, address, message)
+    pub fn message_challenge(&self, package: &SigningPackage<N>, message: &[Field<N>]) -> Result<Scalar<N>> {
+        // Ensure the number of field elements does not exceed the maximum allowed size.
+        if message.len() > N::MAX_DATA_SIZE_IN_FIELDS as usize {
+            bail!("Cannot sign the message: the message exceeds maximum allowed size")
+        }
+        // Ensure the signing package is for the message.
+        ensure!(
+            package.context() == SigningPackage::message_context(message)?,
+            "The signing package is not for the given message"
+        );
+
+        // Construct the hash input as (nonce * G, pk_sig, pr_sig, address, message).
+        let g_r = package.group_nonces()?[0];
+        let mut preimage = Vec::with_capacity(4 + message.len());
+        preimage.extend(
+            [g_r, self.compute_key.pk_sig(), self.compute_key.pr_sig(), *self.address()]
+                .map(|point| point.to_x_coordinate()),
+        );
+        preimage.extend(message);
+
+        // Compute the verifier challenge.
+        N::hash_to_scalar_psd8(&preimage)
+    }
+
+    /// Verifies the signature shares of the participants in the signing package,
+    /// and returns the aggregated signature for the given challenge.
+    pub fn aggregate(
+        &self,
+        package: &SigningPackage<N>,
+        challenge: Scalar<N>,
+        shares: &[SignatureShare<N>],
+    ) -> Result<Signature<N>> {
+        let indices = package.indices();
+        ensure!(
+            indices.len() >= self.threshold as usize,
+            "Found {} participants, but the threshold is {}",
+            indices.len(),
+            self.threshold
+        );
+        ensure!(
+            shares.len() == indices.len(),
+            "Found {} signature shares, expected one for each of the {} participants",
+            shares.len(),
+            indices.len()
+        );
+
+        let mut response = Scalar::zero();
+        for commitment in package.commitments() {
+            let index = commitment.index();
+            let Some(share) = shares.iter().find(|share| share.index == index) else {
+                bail!("Missing the signature share of participant {index}")
+            };
+            // Ensure the share is valid, i.e. `response * G + challenge * lagrange_coefficient * verifying_share`
+            // equals `hiding * G + binding_factor * binding * G`.
+            let expected = commitment.hiding()[0] + commitment.binding()[0] * package.binding_factor(index)?;
+            let candidate = N::g_scalar_multiply(&share.response)
+                + self.verifying_share(index)? * (challenge * lagrange_coefficient(index, &indices)?);
+            ensure!(candidate == expected, "Invalid signature share from participant {index}");
+
+            response += share.response;
+        }
+
+        Ok(Signature::from((challenge, response, self.compute_key)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console_network::MainnetV0;
+
+    type CurrentNetwork = MainnetV0;
+
+    const ITERATIONS: u64 = 10;
+
+    /// Signs the message with the given participants, and returns the aggregated signature.
+    fn sign_message(
+        account: &ThresholdAccount<CurrentNetwork>,
+        key_shares: &[&KeyShare<CurrentNetwork>],
+        message: &[Field<CurrentNetwork>],
+        rng: &mut TestRng,
+    ) -> Result<Signature<CurrentNetwork>> {
+        // Round 1: Each participant commits to its nonces.
+        let (nonces, commitments): (Vec<_>, Vec<_>) = key_shares
+            .iter()
+            .map(|key_share| key_share.commit(&[], &[], rng))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .unzip();
+        let package = SigningPackage::new(SigningPackage::message_context(message)?, commitments)?;
+
+        // Round 2: Each participant signs its share.
+        let challenge = account.message_challenge(&package, message)?;
+        let shares = key_shares
+            .iter()
+            .zip_eq(nonces)
+            .map(|(key_share, nonces)| key_share.sign_share(nonces, &package, challenge))
+            .collect::<Result<Vec<_>>>()?;
+
+        account.aggregate(&package, challenge, &shares)
+    }
+
+    #[test]
+    fn test_sign_and_verify() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        for (threshold, num_participants) in [(1, 1), (2, 3), (3, 5)] {
+            for _ in 0..ITERATIONS {
+                let private_key = PrivateKey::<CurrentNetwork>::new(rng)?;
+                let (account, key_shares) =
+                    ThresholdAccount::new_trusted_dealer(&private_key, threshold, num_participants, rng)?;
+                let message = (0..rng.gen_range(0..10)).map(|_| Field::rand(rng)).collect::<Vec<_>>();
+
+                // Sign with the first `threshold` participants, and with all participants.
+                for signers in [&key_shares[..threshold as usize], &key_shares[..]] {
+                    let signers = signers.iter().collect::<Vec<_>>();
+                    let signature = sign_message(&account, &signers, &message, rng)?;
+                    assert!(signature.verify(&account.address(), &message));
+                    assert!(!signature.verify(&account.address(), &[Field::rand(rng)]));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_shares() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng)?;
+        let (account, key_shares) = ThresholdAccount::new_trusted_dealer(&private_key, 2, 3, rng)?;
+        let message = [Field::rand(rng)];
+
+        // Ensure fewer than `threshold` participants cannot sign.
+        assert!(sign_message(&account, &[&key_shares[0]], &message, rng).is_err());
+
+        let (nonces_1, commitments_1) = key_shares[0].commit(&[], &[], rng)?;
+        let (nonces_3, commitments_3) = key_shares[2].commit(&[], &[], rng)?;
+        let package =
+            SigningPackage::new(SigningPackage::message_context(&message)?, vec![commitments_1, commitments_3])?;
+        let challenge = account.message_challenge(&package, &message)?;
+
+        // Ensure the challenge is bound to the message.
+        assert!(account.message_challenge(&package, &[Field::rand(rng)]).is_err());
+        // Ensure a participant cannot sign with the nonces of another participant.
+        let (other_nonces, _) = key_shares[2].commit(&[], &[], rng)?;
+        assert!(key_shares[0].sign_share(other_nonces, &package, challenge).is_err());
+        // Ensure a participant cannot sign with nonces that are not in the package.
+        let (other_nonces, _) = key_shares[0].commit(&[], &[], rng)?;
+        assert!(key_shares[0].sign_share(other_nonces, &package, challenge).is_err());
+
+        let share_1 = key_shares[0].sign_share(nonces_1, &package, challenge)?;
+        let share_3 = key_shares[2].sign_share(nonces_3, &package, challenge)?;
+
+        // Ensure a tampered or missing share is rejected.
+        let tampered = SignatureShare { index: 3, response: share_3.response() + Scalar::one() };
+        assert!(account.aggregate(&package, challenge, &[share_1, tampered]).is_err());
+        assert!(account.aggregate(&package, challenge, &[share_1]).is_err());
+        assert!(account.aggregate(&package, challenge, &[share_1, share_1]).is_err());
+
+        // Ensure the valid shares aggregate to a valid signature.
+        let signature = account.aggregate(&package, challenge, &[share_3, share_1])?;
+        assert!(signature.verify(&account.address(), &message));
+        Ok(())
+    }
+}
@@ -15,5 +15,8 @@
 mod id;
 pub use id::*;
 
+mod multipart;
+pub use multipart::*;
+
 mod object;
 pub use object::*;
@@ -0,0 +1,186 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::prelude::*;
+
+use anyhow::Result;
+use bech32::{self, FromBase32, ToBase32};
+
+/// The default maximum number of bytes of an object in each part of a multipart encoding.
+/// Note: Each part is then under 500 characters, so that it fits in a QR code that is easily scanned.
+pub const MULTIPART_FRAGMENT_SIZE: usize = 256;
+
+/// The number of bytes in the header of each part of a multipart encoding.
+const MULTIPART_HEADER_SIZE: usize = 8;
+
+/// A compact, checksummed encoding of an object as a sequence of bech32m strings,
+/// so that it can be transferred to and from an air-gapped device, e.g. as a sequence of QR codes.
+///
+/// Each part is a bech32m string of the form `<prefix>1<data>`, where the data is the index of the part,
+/// the number of parts, a checksum of the object, and the next fragment of the object bytes.
+/// Each part is checksummed by bech32m, while the object checksum ensures that the parts are of the same object.
+/// Parts may be decoded in any order, and may be in uppercase, to use the QR alphanumeric mode.
+pub trait Multipart<N: Network>: ToBytes + FromBytes {
+    /// Returns the human-readable prefix of each part.
+    fn multipart_prefix() -> &'static str;
+
+    /// Returns the object as a sequence of parts, each with at most the given number of bytes of the object.
+    fn to_multipart(&self, fragment_size: usize) -> Result<Vec<String>> {
+        ensure!(fragment_size > 0, "The multipart fragment size must be nonzero");
+
+        // Convert the object to bytes, and compute its checksum.
+        let bytes = self.to_bytes_le()?;
+        let checksum = multipart_checksum::<N>(&bytes)?;
+        // Split the bytes into fragments.
+        let fragments = bytes.chunks(fragment_size).collect::<Vec<_>>();
+        let count =
+            u16::try_from(fragments.len()).map_err(|_| anyhow!("The object requires more than {} parts", u16::MAX))?;
+
+        // Encode each fragment, with the header, into bech32m.
+        fragments
+            .iter()
+            .zip(0u16..)
+            .map(|(fragment, index)| {
+                let mut data = Vec::with_capacity(MULTIPART_HEADER_SIZE + fragment.len());
+                index.write_le(&mut data)?;
+                count.write_le(&mut data)?;
+                checksum.write_le(&mut data)?;
+                data.extend_from_slice(fragment);
+                Ok(bech32::encode(Self::multipart_prefix(), data.to_base32(), bech32::Variant::Bech32m)?)
+            })
+            .collect()
+    }
+
+    /// Returns the object from the given parts, in any order.
+    fn from_multipart<S: AsRef<str>>(parts: &[S]) -> Result<Self>
+    where
+        Self: Sized,
+    {
+        ensure!(!parts.is_empty(), "Found no multipart parts to decode");
+
+        let mut header = None;
+        let mut fragments = Vec::<Option<Vec<u8>>>::new();
+        for part in parts {
+            // Decode the part from bech32m.
+            let (hrp, data, variant) = bech32::decode(part.as_ref())?;
+            if hrp != Self::multipart_prefix() {
+                bail!("Failed to decode multipart: '{hrp}' is an invalid prefix")
+            } else if variant != bech32::Variant::Bech32m {
+                bail!("Found a multipart part that is not bech32m encoded")
+            }
+            let data = Vec::from_base32(&data)?;
+            ensure!(data.len() > MULTIPART_HEADER_SIZE, "Found a multipart part without a fragment");
+
+            // Parse the header, and ensure it matches the header of the other parts.
+            let mut reader = &data[..];
+            let index = u16::read_le(&mut reader)? as usize;
+            let count = u16::read_le(&mut reader)?;
+            let checksum = u32::read_le(&mut reader)?;
+            match header {
+                None => {
+                    header = Some((count, checksum));
+                    fragments.resize(count as usize, None);
+                }
+                Some(expected) => ensure!(expected == (count, checksum), "Found parts of different objects"),
+            }
+
+            // Store the fragment.
+            let fragment = reader.to_vec();
+            match fragments.get_mut(index) {
+                Some(Some(existing)) => ensure!(*existing == fragment, "Found conflicting parts at index {index}"),
+                Some(entry) => *entry = Some(fragment),
+                None => bail!("Found a multipart part at index {index}, out of {count} parts"),
+            }
+        }
+
+        // Ensure all of the parts are present.
+        let (count, checksum) = header.ok_or_else(|| anyhow!("Found no multipart header"))?;
+        let num_missing = fragments.iter().filter(|fragment| fragment.is_none()).count();
+        ensure!(num_missing == 0, "Missing {num_missing} of {count} multipart parts");
+
+        // Concatenate the fragments, and ensure the checksum matches.
+        let bytes = fragments.into_iter().flatten().flatten().collect::<Vec<_>>();
+        ensure!(multipart_checksum::<N>(&bytes)? == checksum, "The multipart checksum does not match");
+        Self::from_bytes_le(&bytes)
+    }
+}
+
+/// Returns the checksum of the given object bytes, as the first 32 bits of its SHA3-256 hash.
+fn multipart_checksum<N: Network>(bytes: &[u8]) -> Result<u32> {
+    let hash = N::hash_sha3_256(&bytes.to_bits_le())?;
+    u32::from_bits_le(&hash[..32])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MainnetV0;
+
+    type CurrentNetwork = MainnetV0;
+
+    /// A sample object, encoded as its bytes.
+    #[derive(Debug, PartialEq, Eq)]
+    struct Sample(Vec<u8>);
+
+    impl FromBytes for Sample {
+        fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+            let mut bytes = vec![];
+            reader.read_to_end(&mut bytes)?;
+            Ok(Self(bytes))
+        }
+    }
+
+    impl ToBytes for Sample {
+        fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+            writer.write_all(&self.0)
+        }
+    }
+
+    impl Multipart<CurrentNetwork> for Sample {
+        fn multipart_prefix() -> &'static str {
+            "sample"
+        }
+    }
+
+    #[test]
+    fn test_multipart() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        let sample = Sample((0..1000).map(|_| rng.gen()).collect());
+        let mut parts = sample.to_multipart(MULTIPART_FRAGMENT_SIZE)?;
+        assert_eq!(4, parts.len());
+        assert!(parts.iter().all(|part| part.starts_with("sample1") && part.len() < 500));
+
+        // Ensure the parts decode in any order, with duplicates, and in uppercase.
+        assert_eq!(sample, Sample::from_multipart(&parts)?);
+        parts.reverse();
+        parts.push(parts[0].to_uppercase());
+        assert_eq!(sample, Sample::from_multipart(&parts)?);
+
+        // Ensure a missing part is rejected.
+        assert!(Sample::from_multipart(&parts[1..3]).is_err());
+        // Ensure a part of a different object is rejected.
+        let other = Sample((0..1000).map(|_| rng.gen()).collect()).to_multipart(MULTIPART_FRAGMENT_SIZE)?;
+        assert!(Sample::from_multipart(&[&parts[0], &parts[1], &parts[2], &other[0]]).is_err());
+        // Ensure a corrupted part is rejected.
+        let mut corrupted = parts.clone();
+        let last = match corrupted[0].pop() {
+            Some('q') => 'p',
+            _ => 'q',
+        };
+        corrupted[0].push(last);
+        assert!(Sample::from_multipart(&corrupted).is_err());
+        Ok(())
+    }
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use super::*;
+use snarkvm_console_network::Multipart;
 
 impl<N: Network> FromBytes for Request<N> {
     /// Reads the request from a buffer.
@@ -113,9 +114,17 @@ impl<N: Network> ToBytes for Request<N> {
     }
 }
 
+impl<N: Network> Multipart<N> for Request<N> {
+    /// Returns the human-readable prefix of each part of a request.
+    fn multipart_prefix() -> &'static str {
+        "request"
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use snarkvm_console_network::MULTIPART_FRAGMENT_SIZE;
 
     #[test]
     fn test_bytes() {
@@ -125,6 +134,11 @@ mod tests {
             // Check the byte representation.
             let expected_bytes = expected.to_bytes_le().unwrap();
             assert_eq!(expected, Request::read_le(&expected_bytes[..]).unwrap());
+
+            // Check the multipart representation.
+            let parts = expected.to_multipart(MULTIPART_FRAGMENT_SIZE).unwrap();
+            assert_eq!(parts.len(), expected_bytes.len().div_ceil(MULTIPART_FRAGMENT_SIZE));
+            assert_eq!(expected, Request::from_multipart(&parts).unwrap());
         }
     }
 }
@@ -19,8 +19,11 @@ mod bytes;
 mod serialize;
 mod sign;
 mod string;
+mod threshold;
 mod verify;
 
+pub use threshold::*;
+
 use crate::{compute_function_id, Identifier, Plaintext, ProgramID, Record, Value, ValueType};
 use snarkvm_console_account::{Address, ComputeKey, GraphKey, PrivateKey, Signature, ViewKey};
 use snarkvm_console_network::Network;
@@ -28,25 +28,11 @@ impl<N: Network> Request<N> {
         is_root: bool,
         rng: &mut R,
     ) -> Result<Self> {
-        // Ensure the number of inputs matches the number of input types.
-        if input_types.len() != inputs.len() {
-            bail!(
-                "'{program_id}/{function_name}' expects {} inputs, but {} were provided.",
-                input_types.len(),
-                inputs.len()
-            )
-        }
-
         // Retrieve `sk_sig`.
         let sk_sig = private_key.sk_sig();
 
         // Derive the compute key.
         let compute_key = ComputeKey::try_from(private_key)?;
-        // Retrieve `pk_sig`.
-        let pk_sig = compute_key.pk_sig();
-        // Retrieve `pr_sig`.
-        let pr_sig = compute_key.pr_sig();
-
         // Derive the view key.
         let view_key = ViewKey::try_from((private_key, &compute_key))?;
         // Derive `sk_tag` from the graph key.
@@ -58,11 +44,60 @@ impl<N: Network> Request<N> {
         let r = N::hash_to_scalar_psd4(&[N::serial_number_domain(), sk_sig.to_field()?, nonce])?;
         // Compute `g_r` as `r * G`. Note: This is the transition public key `tpk`.
         let g_r = N::g_scalar_multiply(&r);
+        // Compute the transition view key `tvk` as `r * signer`.
+        let tvk = (*compute_key.to_address() * r).to_x_coordinate();
+
+        Self::sign_with(
+            compute_key,
+            sk_tag,
+            program_id,
+            function_name,
+            inputs,
+            input_types,
+            root_tvk,
+            is_root,
+            g_r,
+            tvk,
+            // Compute `h_r` as `r * H`, and `gamma` as `sk_sig * H`.
+            |h| Ok((h * r, h * sk_sig)),
+            // Compute `response` as `r - challenge * sk_sig`.
+            |challenge| Ok(r - challenge * sk_sig),
+        )
+    }
+
+    /// Returns the request for the given compute key, `sk_tag`, program ID, function name, inputs, input types,
+    /// transition public key `g_r`, and transition view key `tvk`, where `record_nonce` returns `(r * H, sk_sig * H)`
+    /// for the generator `H` of each record input, and `respond` returns the response for the challenge.
+    pub(super) fn sign_with(
+        compute_key: ComputeKey<N>,
+        sk_tag: Field<N>,
+        program_id: ProgramID<N>,
+        function_name: Identifier<N>,
+        inputs: impl ExactSizeIterator<Item = impl TryInto<Value<N>>>,
+        input_types: &[ValueType<N>],
+        root_tvk: Option<Field<N>>,
+        is_root: bool,
+        g_r: Group<N>,
+        tvk: Field<N>,
+        mut record_nonce: impl FnMut(Group<N>) -> Result<(Group<N>, Group<N>)>,
+        respond: impl FnOnce(Scalar<N>) -> Result<Scalar<N>>,
+    ) -> Result<Self> {
+        // Ensure the number of inputs matches the number of input types.
+        if input_types.len() != inputs.len() {
+            bail!(
+                "'{program_id}/{function_name}' expects {} inputs, but {} were provided.",
+                input_types.len(),
+                inputs.len()
+            )
+        }
+
+        // Retrieve `pk_sig`.
+        let pk_sig = compute_key.pk_sig();
+        // Retrieve `pr_sig`.
+        let pr_sig = compute_key.pr_sig();
 
         // Derive the signer from the compute key.
         let signer = Address::try_from(compute_key)?;
-        // Compute the transition view key `tvk` as `r * signer`.
-        let tvk = (*signer * r).to_x_coordinate();
         // Compute the transition commitment `tcm` as `Hash(tvk)`.
         let tcm = N::hash_psd2(&[tvk])?;
         // Compute the signer commitment `scm` as `Hash(signer || root_tvk)`.
@@ -179,10 +214,8 @@ impl<N: Network> Request<N> {
 
                     // Compute the generator `H` as `HashToGroup(commitment)`.
                     let h = N::hash_to_group_psd2(&[N::serial_number_domain(), commitment])?;
-                    // Compute `h_r` as `r * H`.
-                    let h_r = h * r;
-                    // Compute `gamma` as `sk_sig * H`.
-                    let gamma = h * sk_sig;
+                    // Compute `h_r` as `r * H`, and `gamma` as `sk_sig * H`.
+                    let (h_r, gamma) = record_nonce(h)?;
 
                     // Compute the `serial_number` from `gamma`.
                     let serial_number = Record::<N, Plaintext<N>>::serial_number_from_gamma(&gamma, commitment)?;
@@ -224,8 +257,8 @@ impl<N: Network> Request<N> {
 
         // Compute `challenge` as `HashToScalar(r * G, pk_sig, pr_sig, signer, [tvk, tcm, function ID, input IDs])`.
         let challenge = N::hash_to_scalar_psd8(&message)?;
-        // Compute `response` as `r - challenge * sk_sig`.
-        let response = r - challenge * sk_sig;
+        // Compute the `response` for the challenge.
+        let response = respond(challenge)?;
 
         Ok(Self {
             signer,
@@ -0,0 +1,375 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use snarkvm_console_account::{
+    KeyShare,
+    SignatureShare,
+    SigningCommitments,
+    SigningNonces,
+    SigningPackage,
+    ThresholdAccount,
+};
+
+/// The domain separator for the context of a threshold request.
+static THRESHOLD_REQUEST_DOMAIN: &str = "AleoThresholdRequest0";
+
+/// A request that is jointly signed by the participants of a threshold account.
+///
+/// The transition secret key `r` is the sum of the nonces of the participants, so each participant
+/// commits to its nonces on `G` (for `tpk`), on the signer (for `tvk`), and on the generator `H` of
+/// each record input (for `r * H`), and reveals its key share on each `H` (for `gamma`).
+/// Once every participant has signed its share, the coordinator aggregates the shares into a `Request`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ThresholdRequest<N: Network> {
+    /// The threshold account of the signer.
+    account: ThresholdAccount<N>,
+    /// The program ID.
+    program_id: ProgramID<N>,
+    /// The function name.
+    function_name: Identifier<N>,
+    /// The function inputs.
+    inputs: Vec<Value<N>>,
+    /// The function input types.
+    input_types: Vec<ValueType<N>>,
+    /// The root transition view key, if the request is not the root.
+    root_tvk: Option<Field<N>>,
+    /// Whether the request is the root of the execution.
+    is_root: bool,
+    /// The generator `H` of each record input.
+    generators: Vec<Group<N>>,
+    /// The context that the signing package is bound to.
+    context: Field<N>,
+}
+
+impl<N: Network> ThresholdRequest<N> {
+    /// Initializes a threshold request for the given threshold account, program ID, function name, and inputs.
+    pub fn new(
+        account: ThresholdAccount<N>,
+        program_id: ProgramID<N>,
+        function_name: Identifier<N>,
+        inputs: Vec<Value<N>>,
+        input_types: Vec<ValueType<N>>,
+        root_tvk: Option<Field<N>>,
+        is_root: bool,
+    ) -> Result<Self> {
+        // Ensure the number of inputs matches the number of input types.
+        if input_types.len() != inputs.len() {
+            bail!(
+                "'{program_id}/{function_name}' expects {} inputs, but {} were provided.",
+                input_types.len(),
+                inputs.len()
+            )
+        }
+
+        // Compute the function ID.
+        let function_id = compute_function_id(&U16::new(N::ID), &program_id, &function_name)?;
+        let signer = account.address();
+
+        // Construct the context as `Hash(signer || function ID || is_root || root_tvk || inputs)`.
+        let mut preimage = vec![
+            Field::new_domain_separator(THRESHOLD_REQUEST_DOMAIN),
+            signer.to_x_coordinate(),
+            function_id,
+            if is_root { Field::one() } else { Field::zero() },
+            if root_tvk.is_some() { Field::one() } else { Field::zero() },
+            root_tvk.unwrap_or_else(Field::zero),
+        ];
+
+        // Compute the generator `H` of each record input.
+        let mut generators = Vec::new();
+        for (input, input_type) in inputs.iter().zip_eq(&input_types) {
+            preimage.extend(input.to_fields()?);
+            if let (ValueType::Record(record_name), Value::Record(record)) = (input_type, input) {
+                // Ensure the record belongs to the signer.
+                ensure!(**record.owner() == signer, "Input record for '{program_id}' must belong to the signer");
+                // Compute the generator `H` as `HashToGroup(commitment)`.
+                let commitment = record.to_commitment(&program_id, record_name)?;
+                generators.push(N::hash_to_group_psd2(&[N::serial_number_domain(), commitment])?);
+            }
+        }
+        let context = N::hash_psd8(&preimage)?;
+
+        Ok(Self { account, program_id, function_name, inputs, input_types, root_tvk, is_root, generators, context })
+    }
+
+    /// Returns the threshold account of the signer.
+    pub const fn account(&self) -> &ThresholdAccount<N> {
+        &self.account
+    }
+
+    /// Returns the program ID.
+    pub const fn program_id(&self) -> &ProgramID<N> {
+        &self.program_id
+    }
+
+    /// Returns the function name.
+    pub const fn function_name(&self) -> &Identifier<N> {
+        &self.function_name
+    }
+
+    /// Returns the function inputs.
+    pub fn inputs(&self) -> &[Value<N>] {
+        &self.inputs
+    }
+
+    /// Returns the function input types.
+    pub fn input_types(&self) -> &[ValueType<N>] {
+        &self.input_types
+    }
+
+    /// Returns `true` if the request is the root of the execution.
+    pub const fn is_root(&self) -> bool {
+        self.is_root
+    }
+
+    /// Returns the context that the signing package is bound to.
+    pub const fn context(&self) -> Field<N> {
+        self.context
+    }
+
+    /// Returns fresh nonces and their commitments for the participant with the given key share (round 1).
+    pub fn commit<R: Rng + CryptoRng>(
+        &self,
+        key_share: &KeyShare<N>,
+        rng: &mut R,
+    ) -> Result<(SigningNonces<N>, SigningCommitments<N>)> {
+        // Commit on the signer and the record generators, and reveal the key share on the record generators.
+        key_share.commit(&self.bases(), &self.generators, rng)
+    }
+
+    /// Returns the bases of the nonce commitments, besides `G`, i.e. the signer and the record generators.
+    fn bases(&self) -> Vec<Group<N>> {
+        std::iter::once(*self.account.address()).chain(self.generators.iter().copied()).collect()
+    }
+
+    /// Returns the signing package for the given commitments of the participants.
+    pub fn signing_package(&self, commitments: Vec<SigningCommitments<N>>) -> Result<SigningPackage<N>> {
+        SigningPackage::new(self.context, commitments)
+    }
+
+    /// Returns the challenge of the request for the given signing package.
+    pub fn challenge(&self, package: &SigningPackage<N>) -> Result<Scalar<N>> {
+        // Note: The response is not needed to compute the challenge.
+        Ok(self.to_request(package, |_| Ok(Scalar::zero()))?.signature().challenge())
+    }
+
+    /// Returns the signature share of the participant with the given key share (round 2).
+    pub fn sign_share(
+        &self,
+        key_share: &KeyShare<N>,
+        nonces: SigningNonces<N>,
+        package: &SigningPackage<N>,
+    ) -> Result<SignatureShare<N>> {
+        key_share.sign_share(nonces, package, self.challenge(package)?)
+    }
+
+    /// Verifies and aggregates the signature shares of the participants into a request.
+    pub fn aggregate(&self, package: &SigningPackage<N>, shares: &[SignatureShare<N>]) -> Result<Request<N>> {
+        let request =
+            self.to_request(package, |challenge| Ok(self.account.aggregate(package, challenge, shares)?.response()))?;
+        // Ensure the request is valid. Note: The shares are verified on `G` only, so this catches
+        // a participant that committed to inconsistent nonces or key shares on the record generators.
+        ensure!(
+            request.verify(&self.input_types, self.is_root),
+            "The aggregated request for '{}/{}' is invalid",
+            self.program_id,
+            self.function_name
+        );
+        Ok(request)
+    }
+
+    /// Returns the request for the given signing package, where `respond` returns the response for the challenge.
+    fn to_request(
+        &self,
+        package: &SigningPackage<N>,
+        respond: impl FnOnce(Scalar<N>) -> Result<Scalar<N>>,
+    ) -> Result<Request<N>> {
+        ensure!(package.context() == self.context, "The signing package is not for this request");
+        let view_key = self.account.view_key();
+        for commitment in package.commitments() {
+            ensure!(
+                commitment.hiding().len() == 2 + self.generators.len()
+                    && commitment.shares().len() == self.generators.len(),
+                "The commitments of participant {} do not match the request",
+                commitment.index()
+            );
+            // Ensure the nonces on the signer are consistent with the nonces on `G`, as `signer = view_key * G`.
+            ensure!(
+                commitment.hiding()[1] == commitment.hiding()[0] * **view_key
+                    && commitment.binding()[1] == commitment.binding()[0] * **view_key,
+                "Participant {} committed to inconsistent nonces",
+                commitment.index()
+            );
+        }
+
+        // Compute `g_r`, `r * signer`, and `r * H` from the group nonces, and `sk_sig * H` from the group shares.
+        let group_nonces = package.group_nonces()?;
+        let (g_r, tvk) = (group_nonces[0], group_nonces[1].to_x_coordinate());
+        let mut record_nonces = self
+            .generators
+            .iter()
+            .zip_eq(group_nonces[2..].iter().zip_eq(package.group_shares()?))
+            .map(|(generator, (h_r, gamma))| (*generator, *h_r, gamma));
+
+        Request::sign_with(
+            *self.account.compute_key(),
+            GraphKey::try_from(view_key)?.sk_tag(),
+            self.program_id,
+            self.function_name,
+            self.inputs.iter().cloned(),
+            &self.input_types,
+            self.root_tvk,
+            self.is_root,
+            g_r,
+            tvk,
+            |h| match record_nonces.next() {
+                Some((generator, h_r, gamma)) if generator == h => Ok((h_r, gamma)),
+                _ => bail!("Mismatching record generator in the threshold request"),
+            },
+            respond,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use snarkvm_console_network::MainnetV0;
+
+    type CurrentNetwork = MainnetV0;
+
+    /// Returns a threshold request for `token.aleo/transfer`, along with the private key and the key shares.
+    fn sample_threshold_request(
+        threshold: u16,
+        num_participants: u16,
+        rng: &mut TestRng,
+    ) -> Result<(ThresholdRequest<CurrentNetwork>, PrivateKey<CurrentNetwork>, Vec<KeyShare<CurrentNetwork>>)> {
+        let private_key = PrivateKey::new(rng)?;
+        let (account, key_shares) =
+            ThresholdAccount::new_trusted_dealer(&private_key, threshold, num_participants, rng)?;
+        let address = account.address();
+
+        // Prepare three records belonging to the address.
+        let record = |amount: u64| {
+            Value::from_str(&format!(
+                "{{ owner: {address}.private, token_amount: {amount}u64.private, _nonce: 2293253577170800572742339369209137467208538700597121244293392265726446806023group.public }}"
+            ))
+        };
+        let inputs = vec![
+            Value::from_str("{ token_amount: 1u128 }")?,
+            Value::from_str("{ token_amount: 2u128 }")?,
+            Value::from_str("{ token_amount: 3u128 }")?,
+            record(4)?,
+            record(5)?,
+            record(6)?,
+        ];
+        let input_types = vec![
+            ValueType::from_str("amount.constant")?,
+            ValueType::from_str("amount.public")?,
+            ValueType::from_str("amount.private")?,
+            ValueType::from_str("token.record")?,
+            ValueType::from_str("token.record")?,
+            ValueType::from_str("token.aleo/token.record")?,
+        ];
+
+        let request = ThresholdRequest::new(
+            account,
+            ProgramID::from_str("token.aleo")?,
+            Identifier::from_str("transfer")?,
+            inputs,
+            input_types,
+            None,
+            true,
+        )?;
+        Ok((request, private_key, key_shares))
+    }
+
+    #[test]
+    fn test_threshold_request() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        for (threshold, num_participants) in [(1, 1), (2, 3), (3, 5)] {
+            let (request, private_key, key_shares) = sample_threshold_request(threshold, num_participants, rng)?;
+            let signers = &key_shares[(num_participants - threshold) as usize..];
+
+            // Round 1: Each participant commits to its nonces.
+            let (nonces, commitments): (Vec<_>, Vec<_>) = signers
+                .iter()
+                .map(|key_share| request.commit(key_share, rng))
+                .collect::<Result<Vec<_>>>()?
+                .into_iter()
+                .unzip();
+            let package = request.signing_package(commitments)?;
+
+            // Round 2: Each participant signs its share.
+            let shares = signers
+                .iter()
+                .zip_eq(nonces)
+                .map(|(key_share, nonces)| request.sign_share(key_share, nonces, &package))
+                .collect::<Result<Vec<_>>>()?;
+
+            // Aggregate the shares into a request.
+            let candidate = request.aggregate(&package, &shares)?;
+            assert!(candidate.verify(request.input_types(), true));
+            assert_eq!(request.account().address(), *candidate.signer());
+            assert_eq!(request.inputs(), candidate.inputs());
+
+            // Ensure the transition view key is `r * signer`, so the view key can decrypt the transition.
+            let tpk = package.group_nonces()?[0];
+            assert_eq!((tpk * **request.account().view_key()).to_x_coordinate(), *candidate.tvk());
+
+            // Ensure the serial numbers match those derived from the private key.
+            for input_id in &candidate.input_ids()[3..5] {
+                let InputID::Record(commitment, _, serial_number, _) = input_id else { bail!("Expected a record") };
+                assert_eq!(Record::<_, Plaintext<_>>::serial_number(private_key, *commitment)?, *serial_number);
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_threshold_request() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        let (request, _, key_shares) = sample_threshold_request(2, 3, rng)?;
+        let bases = request.bases();
+
+        // Ensure a participant that reveals an inconsistent key share is caught on aggregation.
+        let (nonces_1, commitments_1) = request.commit(&key_shares[0], rng)?;
+        let (nonces_2, commitments_2) =
+            key_shares[1].commit(&bases, &[Group::rand(rng), request.generators[1]], rng)?;
+        let package = request.signing_package(vec![commitments_1, commitments_2])?;
+        let shares = vec![
+            request.sign_share(&key_shares[0], nonces_1, &package)?,
+            request.sign_share(&key_shares[1], nonces_2, &package)?,
+        ];
+        assert!(request.aggregate(&package, &shares).is_err());
+
+        // Ensure a participant that commits to an inconsistent nonce on the signer is rejected.
+        let (_, commitments_1) = request.commit(&key_shares[0], rng)?;
+        let mut tampered_bases = bases.clone();
+        tampered_bases[0] = Group::rand(rng);
+        let (nonces_2, commitments_2) = key_shares[1].commit(&tampered_bases, &request.generators, rng)?;
+        let package = request.signing_package(vec![commitments_1, commitments_2])?;
+        assert!(request.challenge(&package).is_err());
+        assert!(request.sign_share(&key_shares[1], nonces_2, &package).is_err());
+
+        // Ensure a signing package for a different context is rejected.
+        let (_, commitments_1) = request.commit(&key_shares[0], rng)?;
+        let package = SigningPackage::new(Field::rand(rng), vec![commitments_1])?;
+        assert!(request.challenge(&package).is_err());
+        Ok(())
+    }
+}
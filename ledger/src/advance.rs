@@ -116,8 +116,101 @@ impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
             }
         }
 
+        // Scan the block for the records of each subscribed record scanner, in the background.
+        self.scan_records_in_background();
+
+        // Send the events of the block to the event subscribers.
+        self.notify_event_subscribers(block);
+
         Ok(())
     }
+
+    /// Rolls back the ledger to the given block height, by removing each block after it (along with its
+    /// transactions, transitions, committee, and finalize effects), and reloads the latest state.
+    ///
+    /// Note: This method requires the mapping history of every removed block (see `HistoryRetention`).
+    pub fn rollback_to(&self, height: u32) -> Result<()> {
+        // Acquire the write lock on the current block, so that no block is added during the rollback.
+        let mut current_block = self.current_block.write();
+        let previous_height = current_block.height();
+
+        // Remove the blocks from the VM.
+        let result = self.vm.rollback_to(height);
+        // Reload the latest state, as blocks may have been removed even if the rollback failed midway.
+        let latest_height = self.load_latest_state_into(&mut current_block)?;
+        // Rewind the event subscriptions, if any block was removed.
+        if latest_height < previous_height {
+            self.rewind_event_subscriptions(latest_height);
+        }
+        // Drop the write lock on the current block, so that a background scan in progress can finish.
+        drop(current_block);
+
+        // Rewind the subscribed record scanners.
+        // Note: The lock on the record scanners is not held, as rewinding waits for any scan in progress.
+        let record_scanners = self.record_scanners.read().clone();
+        for scanner in record_scanners {
+            scanner.rewind_to(latest_height)?;
+        }
+        result
+    }
+
+    /// Subscribes the given record scanner to new blocks, after scanning the blocks it has not scanned yet.
+    pub fn subscribe_record_scanner(&self, scanner: RecordScanner<N>) -> Result<()> {
+        // A helper function to ensure the view key is not already subscribed.
+        let ensure_not_subscribed = |record_scanners: &[RecordScanner<N>]| {
+            ensure!(
+                !record_scanners.iter().any(|subscribed| subscribed.address() == scanner.address()),
+                "A record scanner for '{}' is already subscribed",
+                scanner.address()
+            );
+            Ok(())
+        };
+
+        // Ensure the view key is not already subscribed.
+        ensure_not_subscribed(self.record_scanners.read().as_slice())?;
+        // Scan the existing blocks, without holding the lock on the record scanners.
+        scanner.scan(self)?;
+
+        // Acquire the write lock, and ensure the view key was not subscribed during the scan.
+        let mut record_scanners = self.record_scanners.write();
+        ensure_not_subscribed(record_scanners.as_slice())?;
+        // Subscribe the scanner.
+        record_scanners.push(scanner);
+        drop(record_scanners);
+
+        // Scan the blocks that were added during the scan, in the background.
+        self.scan_records_in_background();
+        Ok(())
+    }
+
+    /// Unsubscribes the record scanner for the given address, returning it if it was subscribed.
+    pub fn unsubscribe_record_scanner(&self, address: &Address<N>) -> Option<RecordScanner<N>> {
+        let mut record_scanners = self.record_scanners.write();
+        let index = record_scanners.iter().position(|scanner| &scanner.address() == address)?;
+        Some(record_scanners.remove(index))
+    }
+
+    /// Scans the new blocks for the records of each subscribed record scanner, on a background thread.
+    /// If a background scan is already running, it scans the new blocks once it finishes its current scan.
+    pub(crate) fn scan_records_in_background(&self) {
+        // If there are no subscribed record scanners, or a background scan is already running, return early.
+        if self.record_scanners.read().is_empty() || !self.record_scan_signal.request() {
+            return;
+        }
+
+        let ledger = self.clone();
+        std::thread::spawn(move || {
+            while ledger.record_scan_signal.take() {
+                // Note: The lock on the record scanners is not held while scanning.
+                let record_scanners = ledger.record_scanners.read().clone();
+                for scanner in record_scanners {
+                    if let Err(e) = scanner.scan(&ledger) {
+                        warn!("Failed to scan the new blocks for the records of '{}': {e}", scanner.address());
+                    }
+                }
+            }
+        });
+    }
 }
 
 /// Splits candidate solutions into a collection of accepted ones and aborted ones.
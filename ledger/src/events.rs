@@ -0,0 +1,327 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use synthesizer::program::FinalizeOperation;
+
+use std::{
+    collections::VecDeque,
+    sync::mpsc::{self, Receiver, SyncSender, TryRecvError},
+};
+
+/// The events of a block, as sent to the event subscribers, along with the number of rollbacks of the ledger
+/// when they were sent and the height of the block.
+pub(crate) type BlockEvents<N> = (usize, u32, Arc<[LedgerEvent<N>]>);
+/// The sender of the events of new blocks to an event subscriber.
+pub(crate) type EventSender<N> = SyncSender<BlockEvents<N>>;
+
+/// An event emitted by the ledger when a block is added, or when the ledger is rolled back.
+///
+/// The events of a block are emitted in the order of its transactions, and end with `BlockCommitted`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LedgerEvent<N: Network> {
+    /// The block was committed to the ledger.
+    BlockCommitted { height: u32, block_hash: N::BlockHash },
+    /// The transaction was accepted in the block.
+    TransactionAccepted { height: u32, transaction_id: N::TransactionID },
+    /// The transaction was rejected in the block, and its fee is included as the given confirmed transaction.
+    TransactionRejected { height: u32, unconfirmed_id: N::TransactionID, transaction_id: N::TransactionID },
+    /// The transaction was aborted in the block.
+    TransactionAborted { height: u32, transaction_id: N::TransactionID },
+    /// The program was deployed by the transaction.
+    ProgramDeployed { height: u32, program_id: ProgramID<N>, transaction_id: N::TransactionID },
+    /// A mapping of a program was updated by the finalize operation of the transaction,
+    /// or by the finalize operation of the block ratifications (such as the staking and puzzle rewards),
+    /// in which case the transaction ID is `None`.
+    MappingUpdated { height: u32, transaction_id: Option<N::TransactionID>, operation: FinalizeOperation<N> },
+    /// The committee changed in the block.
+    CommitteeChanged { height: u32, committee: Committee<N> },
+    /// The ledger was rolled back to the block at the given height, reverting the events of the later blocks.
+    /// This event is only emitted by the subscriptions, before the events of the new blocks at those heights.
+    RolledBack { height: u32 },
+}
+
+impl<N: Network> LedgerEvent<N> {
+    /// Returns the height of the block of the event.
+    pub const fn height(&self) -> u32 {
+        match self {
+            Self::BlockCommitted { height, .. }
+            | Self::TransactionAccepted { height, .. }
+            | Self::TransactionRejected { height, .. }
+            | Self::TransactionAborted { height, .. }
+            | Self::ProgramDeployed { height, .. }
+            | Self::MappingUpdated { height, .. }
+            | Self::CommitteeChanged { height, .. }
+            | Self::RolledBack { height } => *height,
+        }
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
+    /// Subscribes to the events of the ledger, starting from the block at the given height.
+    /// The events of the blocks already in the ledger are replayed, followed by the events of the new blocks.
+    ///
+    /// Up to `capacity` new blocks are buffered for the subscription. If the subscriber falls further behind,
+    /// the ledger does not wait for it, and the subscription instead catches up from storage.
+    pub fn subscribe_events(&self, start_height: u32, capacity: usize) -> Result<EventSubscription<N, C>> {
+        // Ensure the capacity is non-zero.
+        ensure!(capacity > 0, "The capacity of the event subscription must be non-zero");
+        // Ensure the start height is at most the next block height.
+        let next_height = self.latest_height().saturating_add(1);
+        ensure!(
+            start_height <= next_height,
+            "Cannot subscribe to events from block {start_height} (the next block is {next_height})"
+        );
+
+        // Note: The rollbacks are counted before registering, so a concurrent rollback is applied to the subscription.
+        let rollbacks = self.event_rollbacks.read().len();
+        Ok(EventSubscription {
+            ledger: self.clone(),
+            capacity,
+            receiver: Some(self.register_event_sender(capacity)),
+            next_height: start_height,
+            pending: Default::default(),
+            rollbacks,
+        })
+    }
+
+    /// Returns the events of the block at the given height.
+    pub fn get_events(&self, height: u32) -> Result<Vec<LedgerEvent<N>>> {
+        self.derive_events(&self.get_block(height)?)
+    }
+
+    /// Sends the events of the given block to the event subscribers.
+    /// The subscribers whose channel is full are unsubscribed, so they catch up from storage instead.
+    pub(crate) fn notify_event_subscribers(&self, block: &Block<N>) {
+        // Return early if there are no subscribers.
+        if self.event_senders.read().is_empty() {
+            return;
+        }
+
+        // Derive the events of the block.
+        let events: Arc<[LedgerEvent<N>]> = match self.derive_events(block) {
+            Ok(events) => events.into(),
+            Err(e) => {
+                // Unsubscribe every subscriber, so the error is reported to them when they catch up from storage.
+                warn!("Failed to derive the events of block {}: {e}", block.height());
+                self.event_senders.write().clear();
+                return;
+            }
+        };
+
+        // Send the events, and unsubscribe the subscribers which are full or dropped.
+        let rollbacks = self.event_rollbacks.read().len();
+        self.event_senders
+            .write()
+            .retain(|sender| sender.try_send((rollbacks, block.height(), events.clone())).is_ok());
+    }
+
+    /// Records that the ledger was rolled back to the given height, so the event subscriptions
+    /// which are past it rewind, and emit the events of the new blocks at the reverted heights.
+    pub(crate) fn rewind_event_subscriptions(&self, height: u32) {
+        self.event_rollbacks.write().push(height);
+    }
+
+    /// Registers a new event subscriber, and returns the receiver for the events of new blocks.
+    fn register_event_sender(&self, capacity: usize) -> Receiver<BlockEvents<N>> {
+        let (sender, receiver) = mpsc::sync_channel(capacity);
+        self.event_senders.write().push(sender);
+        receiver
+    }
+
+    /// Returns the events of the given block.
+    fn derive_events(&self, block: &Block<N>) -> Result<Vec<LedgerEvent<N>>> {
+        let height = block.height();
+        let mut events = Vec::new();
+
+        for confirmed in block.transactions().iter() {
+            let transaction_id = confirmed.id();
+            // Emit the outcome of the transaction.
+            match confirmed.is_accepted() {
+                true => events.push(LedgerEvent::TransactionAccepted { height, transaction_id }),
+                false => {
+                    let unconfirmed_id = confirmed.to_unconfirmed_transaction_id()?;
+                    events.push(LedgerEvent::TransactionRejected { height, unconfirmed_id, transaction_id })
+                }
+            }
+            // Emit the deployed program.
+            if let (true, Some(deployment)) = (confirmed.is_accepted(), confirmed.transaction().deployment()) {
+                events.push(LedgerEvent::ProgramDeployed {
+                    height,
+                    program_id: *deployment.program_id(),
+                    transaction_id,
+                });
+            }
+            // Emit the mapping updates.
+            for operation in confirmed.finalize_operations() {
+                events.push(LedgerEvent::MappingUpdated {
+                    height,
+                    transaction_id: Some(transaction_id),
+                    operation: *operation,
+                });
+            }
+        }
+
+        // Emit the mapping updates of the ratifications, in the order of the finalize root (after the transactions).
+        // Note: The ratified finalize operations are not recorded for blocks added by an older version.
+        let ratified_finalize_operations = self.vm.block_store().get_ratified_finalize_operations(&block.hash())?;
+        for operation in ratified_finalize_operations.into_iter().flatten() {
+            events.push(LedgerEvent::MappingUpdated { height, transaction_id: None, operation });
+        }
+
+        // Emit the aborted transactions.
+        for transaction_id in block.aborted_transaction_ids() {
+            events.push(LedgerEvent::TransactionAborted { height, transaction_id: *transaction_id });
+        }
+
+        // Emit the committee, if it changed in this block.
+        let committee_store = self.vm.finalize_store().committee_store();
+        if let Some(committee) = committee_store.get_committee(height)? {
+            let previous = match height {
+                0 => None,
+                _ => committee_store.get_committee(height - 1)?,
+            };
+            if previous.as_ref() != Some(&committee) {
+                events.push(LedgerEvent::CommitteeChanged { height, committee });
+            }
+        }
+
+        events.push(LedgerEvent::BlockCommitted { height, block_hash: block.hash() });
+        Ok(events)
+    }
+}
+
+/// A subscription to the events of the ledger, which emits the events of each block in order, exactly once.
+///
+/// The events of new blocks are received over a bounded channel. If the subscription falls behind and its
+/// channel fills up, it is unsubscribed by the ledger, and it catches up by replaying the blocks from storage.
+pub struct EventSubscription<N: Network, C: ConsensusStorage<N>> {
+    /// The ledger.
+    ledger: Ledger<N, C>,
+    /// The capacity of the channel, in blocks.
+    capacity: usize,
+    /// The receiver for the events of new blocks, or `None` if the ledger unsubscribed it.
+    receiver: Option<Receiver<BlockEvents<N>>>,
+    /// The height of the next block whose events are emitted.
+    next_height: u32,
+    /// The events which remain to be emitted.
+    pending: VecDeque<LedgerEvent<N>>,
+    /// The number of rollbacks of the ledger which were applied to the subscription.
+    rollbacks: usize,
+}
+
+impl<N: Network, C: ConsensusStorage<N>> EventSubscription<N, C> {
+    /// Returns the height of the next block whose events are emitted.
+    /// A consumer which reconnects can resume the subscription from this height.
+    pub const fn next_height(&self) -> u32 {
+        self.next_height
+    }
+
+    /// Returns the next event, or `None` if there are no new events.
+    pub fn try_recv(&mut self) -> Result<Option<LedgerEvent<N>>> {
+        loop {
+            // Rewind the subscription, if the ledger was rolled back.
+            self.apply_rollbacks();
+
+            // Emit the pending events first.
+            if let Some(event) = self.pending.pop_front() {
+                return Ok(Some(event));
+            }
+
+            // Subscribe again, if the subscription was unsubscribed for falling behind.
+            let receiver = self.receiver.get_or_insert_with(|| self.ledger.register_event_sender(self.capacity));
+
+            match receiver.try_recv() {
+                Ok((rollbacks, height, events)) => self.receive(rollbacks, height, &events)?,
+                Err(TryRecvError::Disconnected) => self.receiver = None,
+                Err(TryRecvError::Empty) => match self.next_height <= self.ledger.latest_height() {
+                    // Replay the next block from storage.
+                    true => self.replay_next()?,
+                    false => return Ok(None),
+                },
+            }
+        }
+    }
+
+    /// Returns the next event, waiting for a new block if there are no new events.
+    pub fn recv(&mut self) -> Result<LedgerEvent<N>> {
+        loop {
+            if let Some(event) = self.try_recv()? {
+                return Ok(event);
+            }
+            // Wait for the events of the next block.
+            if let Some(receiver) = &self.receiver {
+                match receiver.recv() {
+                    Ok((rollbacks, height, events)) => self.receive(rollbacks, height, &events)?,
+                    Err(_) => self.receiver = None,
+                }
+            }
+        }
+    }
+
+    /// Rewinds the subscription to the lowest height the ledger was rolled back to since the last applied rollback.
+    /// If the subscription is past that height, the pending events of the reverted blocks are discarded,
+    /// and a `RolledBack` event is queued.
+    fn apply_rollbacks(&mut self) {
+        let rollbacks = self.ledger.event_rollbacks.read();
+        let height = rollbacks.get(self.rollbacks..).and_then(|heights| heights.iter().min().copied());
+        self.rollbacks = rollbacks.len();
+        drop(rollbacks);
+
+        if let Some(height) = height {
+            if self.next_height > height.saturating_add(1) {
+                self.pending.retain(|event| event.height() <= height);
+                self.pending.push_back(LedgerEvent::RolledBack { height });
+                self.next_height = height.saturating_add(1);
+            }
+        }
+    }
+
+    /// Queues the received events of the block at the given height, unless they were already emitted.
+    /// The events sent before a rollback which was applied to the subscription are discarded.
+    fn receive(&mut self, rollbacks: usize, height: u32, events: &[LedgerEvent<N>]) -> Result<()> {
+        // Discard the events sent before an applied rollback, and apply the rollbacks before the events.
+        match rollbacks.cmp(&self.rollbacks) {
+            Ordering::Less => return Ok(()),
+            Ordering::Greater => self.apply_rollbacks(),
+            Ordering::Equal => (),
+        }
+        // Replay the blocks which were missed, if any.
+        while self.next_height < height {
+            self.replay_next()?;
+        }
+        if self.next_height == height {
+            self.pending.extend(events.iter().cloned());
+            self.next_height += 1;
+        }
+        Ok(())
+    }
+
+    /// Queues the events of the next block from storage.
+    fn replay_next(&mut self) -> Result<()> {
+        self.pending.extend(self.ledger.get_events(self.next_height)?);
+        self.next_height += 1;
+        Ok(())
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>> Iterator for EventSubscription<N, C> {
+    type Item = Result<LedgerEvent<N>>;
+
+    /// Returns the next event, waiting for a new block if there are no new events.
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.recv())
+    }
+}
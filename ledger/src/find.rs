@@ -70,58 +70,92 @@ impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
             };
 
             // Determine whether to decrypt this record (or not), based on the filter.
-            let commitment = match filter {
-                RecordsFilter::All => Ok(Some(commitment)),
-                RecordsFilter::Spent => Record::<N, Plaintext<N>>::tag(sk_tag, commitment).and_then(|tag| {
-                    // Determine if the record is spent.
-                    self.contains_tag(&tag).map(|is_spent| match is_spent {
-                        true => Some(commitment),
-                        false => None,
-                    })
-                }),
-                RecordsFilter::Unspent => Record::<N, Plaintext<N>>::tag(sk_tag, commitment).and_then(|tag| {
-                    // Determine if the record is spent.
-                    self.contains_tag(&tag).map(|is_spent| match is_spent {
-                        true => None,
-                        false => Some(commitment),
-                    })
-                }),
-                RecordsFilter::SlowSpent(private_key) => {
-                    Record::<N, Plaintext<N>>::serial_number(private_key, commitment).and_then(|serial_number| {
-                        // Determine if the record is spent.
-                        self.contains_serial_number(&serial_number).map(|is_spent| match is_spent {
-                            true => Some(commitment),
-                            false => None,
-                        })
-                    })
-                }
-                RecordsFilter::SlowUnspent(private_key) => {
-                    Record::<N, Plaintext<N>>::serial_number(private_key, commitment).and_then(|serial_number| {
-                        // Determine if the record is spent.
-                        self.contains_serial_number(&serial_number).map(|is_spent| match is_spent {
-                            true => None,
-                            false => Some(commitment),
-                        })
-                    })
+            match self.is_record_selected(commitment, filter, sk_tag) {
+                Ok(true) => match record.is_owner_with_address_x_coordinate(view_key, &address_x_coordinate) {
+                    true => Some((commitment, record)),
+                    false => None,
+                },
+                Ok(false) => None,
+                Err(e) => {
+                    warn!("Failed to process 'find_record_ciphertexts({:?})': {e}", filter);
+                    None
                 }
-            };
+            }
+        }))
+    }
 
-            match commitment {
-                Ok(Some(commitment)) => {
-                    match record.is_owner_with_address_x_coordinate(view_key, &address_x_coordinate) {
-                        true => Some((commitment, record)),
-                        false => None,
-                    }
-                }
-                Ok(None) => None,
+    /// Returns the record ciphertexts found by the given record scanner.
+    ///
+    /// Unlike `find_record_ciphertexts`, this only looks up the records in the owned commitment index
+    /// of the scanner, instead of checking the ownership of every record in the ledger.
+    pub fn find_scanned_record_ciphertexts<'a>(
+        &'a self,
+        scanner: &RecordScanner<N>,
+        view_key: &ViewKey<N>,
+        filter: RecordsFilter<N>,
+    ) -> Result<impl 'a + Iterator<Item = (Field<N>, Record<N, Ciphertext<N>>)>> {
+        // Ensure the scanner belongs to the given view key.
+        ensure!(scanner.address() == view_key.to_address(), "The record scanner does not belong to the view key");
+        // Derive the `sk_tag` from the graph key.
+        let sk_tag = match GraphKey::try_from(view_key) {
+            Ok(graph_key) => graph_key.sk_tag(),
+            Err(e) => bail!("Failed to derive the graph key from the view key: {e}"),
+        };
+
+        Ok(scanner.commitments().into_keys().flat_map(move |commitment| {
+            // Determine whether to retrieve this record (or not), based on the filter.
+            let record = match self.is_record_selected(commitment, filter, sk_tag) {
+                Ok(true) => self.vm.transition_store().get_record(&commitment),
+                Ok(false) => Ok(None),
+                Err(e) => Err(e),
+            };
+            match record {
+                Ok(record) => record.map(|record| (commitment, record)),
                 Err(e) => {
-                    warn!("Failed to process 'find_record_ciphertexts({:?})': {e}", filter);
+                    warn!("Failed to process 'find_scanned_record_ciphertexts({:?})': {e}", filter);
                     None
                 }
             }
         }))
     }
 
+    /// Returns the history of the transitions that the account of the given view key took part in,
+    /// within the given range of block heights, decoded with the view key.
+    ///
+    /// Note: A spent record is only decoded if the record was created within the range,
+    /// so the range should start from the genesis block to decode every spent record.
+    pub fn find_transition_history(
+        &self,
+        view_key: &ViewKey<N>,
+        heights: Range<u32>,
+    ) -> Result<Vec<TransitionHistory<N>>> {
+        // Ensure the range does not exceed the latest block.
+        let heights = heights.start..heights.end.min(self.latest_height().saturating_add(1));
+
+        let mut decoder = HistoryDecoder::new(view_key)?;
+        let mut history = Vec::new();
+        for height in heights {
+            history.extend(decoder.decode_block(&self.get_block(height)?)?);
+        }
+        Ok(history)
+    }
+
+    /// Returns `true` if the record with the given commitment is selected by the filter.
+    fn is_record_selected(&self, commitment: Field<N>, filter: RecordsFilter<N>, sk_tag: Field<N>) -> Result<bool> {
+        match filter {
+            RecordsFilter::All => Ok(true),
+            // Determine if the record is spent.
+            RecordsFilter::Spent => self.contains_tag(&Record::<N, Plaintext<N>>::tag(sk_tag, commitment)?),
+            RecordsFilter::Unspent => Ok(!self.contains_tag(&Record::<N, Plaintext<N>>::tag(sk_tag, commitment)?)?),
+            RecordsFilter::SlowSpent(private_key) => {
+                self.contains_serial_number(&Record::<N, Plaintext<N>>::serial_number(private_key, commitment)?)
+            }
+            RecordsFilter::SlowUnspent(private_key) => {
+                Ok(!self.contains_serial_number(&Record::<N, Plaintext<N>>::serial_number(private_key, commitment)?)?)
+            }
+        }
+    }
+
     /// Returns the records that belong to the given view key.
     pub fn find_records<'a>(
         &'a self,
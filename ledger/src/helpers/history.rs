@@ -0,0 +1,265 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use console::{
+    account::{Address, GraphKey, ViewKey},
+    network::prelude::*,
+    program::{compute_function_id, Argument, Ciphertext, Literal, Plaintext, ProgramID, Record, Value},
+    types::{Field, U16},
+};
+use ledger_block::{Block, Input, Output, Transaction, Transition};
+
+use indexmap::IndexMap;
+
+/// A transition that the account of a view key took part in, decoded with the view key.
+///
+/// A transition is included if it was signed by the account, if it creates a record owned by the account,
+/// or if it changes the public `credits.aleo` balance of the account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransitionHistory<N: Network> {
+    /// The height of the block that contains the transition.
+    block_height: u32,
+    /// The ID of the transaction that contains the transition.
+    transaction_id: N::TransactionID,
+    /// The transition.
+    transition: Transition<N>,
+    /// Whether the transition was signed by the account.
+    is_signer: bool,
+    /// The decoded inputs, or `None` for the inputs that are not visible to the view key.
+    inputs: Vec<Option<Value<N>>>,
+    /// The decoded outputs, or `None` for the outputs that are not visible to the view key.
+    outputs: Vec<Option<Value<N>>>,
+    /// The fee in microcredits, if the transition is a fee.
+    fee: Option<u64>,
+    /// The change in the public `credits.aleo` balance of the account, in microcredits.
+    public_balance_delta: i128,
+}
+
+impl<N: Network> TransitionHistory<N> {
+    /// Returns the height of the block that contains the transition.
+    pub const fn block_height(&self) -> u32 {
+        self.block_height
+    }
+
+    /// Returns the ID of the transaction that contains the transition.
+    pub const fn transaction_id(&self) -> &N::TransactionID {
+        &self.transaction_id
+    }
+
+    /// Returns the transition.
+    pub const fn transition(&self) -> &Transition<N> {
+        &self.transition
+    }
+
+    /// Returns `true` if the transition was signed by the account.
+    pub const fn is_signer(&self) -> bool {
+        self.is_signer
+    }
+
+    /// Returns the decoded inputs, or `None` for the inputs that are not visible to the view key.
+    ///
+    /// Note: A spent record is only decoded if the record was created within the decoded blocks.
+    pub fn inputs(&self) -> &[Option<Value<N>>] {
+        &self.inputs
+    }
+
+    /// Returns the decoded outputs, or `None` for the outputs that are not visible to the view key.
+    pub fn outputs(&self) -> &[Option<Value<N>>] {
+        &self.outputs
+    }
+
+    /// Returns the fee in microcredits, if the transition is a fee.
+    pub const fn fee(&self) -> Option<u64> {
+        self.fee
+    }
+
+    /// Returns the change in the public `credits.aleo` balance of the account, in microcredits.
+    ///
+    /// Note: Only the transfers, bonds, and fees of `credits.aleo` are included, as the other public balance
+    /// changes (e.g. claiming unbonded credits or staking rewards) are not determined by the transition.
+    pub const fn public_balance_delta(&self) -> i128 {
+        self.public_balance_delta
+    }
+}
+
+/// Decodes the transitions of a view key, block by block.
+///
+/// The decoder remembers the records it decrypts by their tag, so that a record is decoded when it is spent.
+pub(crate) struct HistoryDecoder<'a, N: Network> {
+    /// The view key.
+    view_key: &'a ViewKey<N>,
+    /// The address of the view key.
+    address: Address<N>,
+    /// The x-coordinate of the address.
+    address_x_coordinate: Field<N>,
+    /// The tag secret key.
+    sk_tag: Field<N>,
+    /// The 'credits.aleo' program ID.
+    credits_program_id: ProgramID<N>,
+    /// The records owned by the view key, mapped by their tag.
+    records: IndexMap<Field<N>, Record<N, Plaintext<N>>>,
+}
+
+impl<'a, N: Network> HistoryDecoder<'a, N> {
+    /// Initializes a new history decoder for the given view key.
+    pub(crate) fn new(view_key: &'a ViewKey<N>) -> Result<Self> {
+        let address = view_key.to_address();
+        let sk_tag = match GraphKey::try_from(view_key) {
+            Ok(graph_key) => graph_key.sk_tag(),
+            Err(e) => bail!("Failed to derive the graph key from the view key: {e}"),
+        };
+        Ok(Self {
+            view_key,
+            address,
+            address_x_coordinate: address.to_x_coordinate(),
+            sk_tag,
+            credits_program_id: ProgramID::from_str("credits.aleo")?,
+            records: Default::default(),
+        })
+    }
+
+    /// Returns the history of the transitions in the given block.
+    pub(crate) fn decode_block(&mut self, block: &Block<N>) -> Result<Vec<TransitionHistory<N>>> {
+        let mut history = Vec::new();
+        for confirmed in block.transactions().iter() {
+            // Note: The transitions of a rejected transaction are not included, as only its fee was applied.
+            let transaction = confirmed.transaction();
+            for transition in transaction.transitions() {
+                if let Some(entry) = self.decode_transition(block.height(), transaction, transition)? {
+                    history.push(entry);
+                }
+            }
+        }
+        Ok(history)
+    }
+
+    /// Returns the history of the given transition, if the account took part in it.
+    fn decode_transition(
+        &mut self,
+        block_height: u32,
+        transaction: &Transaction<N>,
+        transition: &Transition<N>,
+    ) -> Result<Option<TransitionHistory<N>>> {
+        // Determine if the account signed the transition, by recomputing the transition commitment.
+        let tvk = (*transition.tpk() * **self.view_key).to_x_coordinate();
+        let is_signer = N::hash_psd2(&[tvk])? == *transition.tcm();
+        let function_id = compute_function_id(&U16::new(N::ID), transition.program_id(), transition.function_name())?;
+        // Decrypts the private input or output at the given index, with the transition view key.
+        let decrypt = |index: usize, ciphertext: &Ciphertext<N>| -> Result<Option<Value<N>>> {
+            let plaintext_view_key = N::hash_psd4(&[function_id, tvk, Field::from_u16(u16::try_from(index)?)])?;
+            Ok(Some(Value::Plaintext(ciphertext.decrypt_symmetric(plaintext_view_key)?)))
+        };
+
+        // Decode the inputs.
+        let inputs = transition
+            .inputs()
+            .iter()
+            .enumerate()
+            .map(|(index, input)| match input {
+                Input::Constant(_, plaintext) | Input::Public(_, plaintext) => {
+                    Ok(plaintext.clone().map(Value::Plaintext))
+                }
+                Input::Private(_, Some(ciphertext)) if is_signer => decrypt(index, ciphertext),
+                Input::Record(_, tag) => Ok(self.records.get(tag).cloned().map(Value::Record)),
+                _ => Ok(None),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Decode the outputs.
+        let num_inputs = transition.inputs().len();
+        let mut is_owner = false;
+        let mut outputs = Vec::with_capacity(transition.outputs().len());
+        for (index, output) in transition.outputs().iter().enumerate() {
+            outputs.push(match output {
+                Output::Constant(_, plaintext) | Output::Public(_, plaintext) => {
+                    plaintext.clone().map(Value::Plaintext)
+                }
+                Output::Private(_, Some(ciphertext)) if is_signer => decrypt(num_inputs + index, ciphertext)?,
+                Output::Record(commitment, _, Some(record))
+                    if record.is_owner_with_address_x_coordinate(self.view_key, &self.address_x_coordinate) =>
+                {
+                    let record = record.decrypt(self.view_key)?;
+                    // Remember the record, so that it is decoded when it is spent.
+                    self.records.insert(Record::<N, Plaintext<N>>::tag(self.sk_tag, *commitment)?, record.clone());
+                    is_owner = true;
+                    Some(Value::Record(record))
+                }
+                Output::Future(_, future) => future.clone().map(Value::Future),
+                _ => None,
+            });
+        }
+
+        // Compute the change in the public balance.
+        let public_balance_delta = self.public_balance_delta(transition);
+
+        // Skip the transition if the account did not take part in it.
+        if !is_signer && !is_owner && public_balance_delta == 0 {
+            return Ok(None);
+        }
+
+        // Retrieve the fee, if the transition is a fee.
+        let fee = match transaction.fee_transition() {
+            Some(fee) if fee.transition_id() == transition.id() => Some(*fee.amount()?),
+            _ => None,
+        };
+
+        Ok(Some(TransitionHistory {
+            block_height,
+            transaction_id: transaction.id(),
+            transition: transition.clone(),
+            is_signer,
+            inputs,
+            outputs,
+            fee,
+            public_balance_delta,
+        }))
+    }
+
+    /// Returns the change in the public `credits.aleo` balance of the account, from the finalize arguments
+    /// of the given transition.
+    fn public_balance_delta(&self, transition: &Transition<N>) -> i128 {
+        if transition.program_id() != &self.credits_program_id {
+            return 0;
+        }
+        // Retrieve the finalize arguments.
+        let arguments = match transition.outputs().last() {
+            Some(Output::Future(_, Some(future))) => future.arguments(),
+            _ => return 0,
+        };
+        // Returns `true` if the argument at the given index is the address of the account.
+        let is_account = |index: usize| {
+            matches!(
+                arguments.get(index),
+                Some(Argument::Plaintext(Plaintext::Literal(Literal::Address(address), _))) if *address == self.address
+            )
+        };
+        // Returns the amount at the given index, or zero if it is not an amount.
+        let amount = |index: usize| match arguments.get(index) {
+            Some(Argument::Plaintext(Plaintext::Literal(Literal::U64(amount), _))) => **amount as i128,
+            _ => 0,
+        };
+
+        // Note: The arguments are in the order of the `async` calls in `credits.aleo`.
+        match transition.function_name().to_string().as_str() {
+            "transfer_public" | "transfer_public_as_signer" => {
+                (is_account(1) as i128 - is_account(0) as i128) * amount(2)
+            }
+            "transfer_private_to_public" => is_account(0) as i128 * amount(1),
+            "transfer_public_to_private" | "fee_public" => -(is_account(0) as i128) * amount(1),
+            "bond_public" => -(is_account(0) as i128) * amount(3),
+            "bond_validator" => -(is_account(0) as i128) * amount(2),
+            _ => 0,
+        }
+    }
+}
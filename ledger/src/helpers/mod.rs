@@ -15,5 +15,23 @@
 mod bft;
 pub use bft::*;
 
+mod history;
+pub use history::*;
+
+mod record_scanner;
+pub use record_scanner::*;
+
+mod record_selection;
+pub use record_selection::*;
+
+mod replay_report;
+pub use replay_report::*;
+
+mod reserves;
+pub use reserves::*;
+
+mod storage_report;
+pub use storage_report::*;
+
 mod supply;
 pub use supply::*;
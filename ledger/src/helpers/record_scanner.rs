@@ -0,0 +1,337 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::Ledger;
+use console::{
+    account::{Address, ViewKey},
+    network::prelude::*,
+    types::Field,
+};
+use ledger_store::ConsensusStorage;
+
+use indexmap::IndexMap;
+use parking_lot::{Mutex, RwLock};
+use std::{
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+#[cfg(not(feature = "serial"))]
+use rayon::prelude::*;
+
+/// The number of blocks scanned in parallel, before the cursor is advanced and persisted.
+const NUM_BLOCKS_PER_BATCH: u32 = 256;
+
+/// The cursor and owned commitments of a record scanner.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+struct ScannerState<N: Network> {
+    /// The hash of the genesis block of the scanned ledger, or `None` if no block was scanned yet.
+    genesis_hash: Option<N::BlockHash>,
+    /// The height of the next block to scan.
+    next_height: u32,
+    /// The commitments of the owned records, mapped to the height of the block that contains them.
+    commitments: IndexMap<Field<N>, u32>,
+}
+
+impl<N: Network> FromBytes for ScannerState<N> {
+    /// Reads the scanner state from a buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the version.
+        let version = u8::read_le(&mut reader)?;
+        // Ensure the version is valid.
+        if version != 1 {
+            return Err(error("Invalid record scanner version"));
+        }
+        // Read the genesis hash.
+        let genesis_hash = match u8::read_le(&mut reader)? {
+            0 => None,
+            1 => Some(N::BlockHash::read_le(&mut reader)?),
+            _ => return Err(error("Invalid record scanner genesis hash")),
+        };
+        // Read the next height.
+        let next_height = u32::read_le(&mut reader)?;
+        // Read the commitments.
+        let num_commitments = u32::read_le(&mut reader)?;
+        let mut commitments = IndexMap::new();
+        for _ in 0..num_commitments {
+            let commitment = Field::read_le(&mut reader)?;
+            let height = u32::read_le(&mut reader)?;
+            commitments.insert(commitment, height);
+        }
+        Ok(Self { genesis_hash, next_height, commitments })
+    }
+}
+
+impl<N: Network> ToBytes for ScannerState<N> {
+    /// Writes the scanner state to a buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        // Write the version.
+        1u8.write_le(&mut writer)?;
+        // Write the genesis hash.
+        match &self.genesis_hash {
+            None => 0u8.write_le(&mut writer)?,
+            Some(genesis_hash) => {
+                1u8.write_le(&mut writer)?;
+                genesis_hash.write_le(&mut writer)?;
+            }
+        }
+        // Write the next height.
+        self.next_height.write_le(&mut writer)?;
+        // Write the commitments.
+        u32::try_from(self.commitments.len()).map_err(error)?.write_le(&mut writer)?;
+        for (commitment, height) in &self.commitments {
+            commitment.write_le(&mut writer)?;
+            height.write_le(&mut writer)?;
+        }
+        Ok(())
+    }
+}
+
+/// An incremental scanner for the records that belong to a view key.
+///
+/// The scanner keeps a cursor (the height of the next block to scan) and an index of the commitments of owned
+/// records, so that each block is only scanned once. Block ranges are scanned in parallel, and if the scanner
+/// is opened from a directory, its state is persisted after every batch, so that scanning resumes after a restart.
+/// The state is bound to the genesis block of the first ledger it scans, and is rejected by any other ledger.
+/// Once subscribed to the ledger with `Ledger::subscribe_record_scanner`, new blocks are scanned in the background.
+#[derive(Clone)]
+pub struct RecordScanner<N: Network> {
+    /// The view key.
+    view_key: ViewKey<N>,
+    /// The x-coordinate of the address corresponding to the view key.
+    address_x_coordinate: Field<N>,
+    /// The path of the file that persists the state, if any.
+    path: Option<PathBuf>,
+    /// The cursor and owned commitments.
+    state: Arc<RwLock<ScannerState<N>>>,
+    /// The lock held while scanning, to ensure blocks are scanned once.
+    scan_lock: Arc<Mutex<()>>,
+}
+
+impl<N: Network> RecordScanner<N> {
+    /// Initializes a new in-memory record scanner for the given view key, starting from the genesis block.
+    pub fn new(view_key: ViewKey<N>) -> Self {
+        let address_x_coordinate = view_key.to_address().to_x_coordinate();
+        Self { view_key, address_x_coordinate, path: None, state: Default::default(), scan_lock: Default::default() }
+    }
+
+    /// Opens the record scanner for the given view key, persisting its state in the given directory.
+    /// If the directory contains the state of a previous scan, scanning resumes from its cursor.
+    pub fn open<P: AsRef<Path>>(view_key: ViewKey<N>, directory: P) -> Result<Self> {
+        let directory = directory.as_ref();
+        // Ensure the directory exists.
+        std::fs::create_dir_all(directory)?;
+
+        // Initialize the scanner.
+        let mut scanner = Self::new(view_key);
+        let path = directory.join(format!("{}.scan", view_key.to_address()));
+        // Load the previous state, if it exists.
+        if path.exists() {
+            let state = ScannerState::from_bytes_le(&std::fs::read(&path)?)
+                .map_err(|e| anyhow!("Failed to load the record scanner from '{}': {e}", path.display()))?;
+            scanner.state = Arc::new(RwLock::new(state));
+        }
+        scanner.path = Some(path);
+        Ok(scanner)
+    }
+
+    /// Returns the address of the view key.
+    pub fn address(&self) -> Address<N> {
+        self.view_key.to_address()
+    }
+
+    /// Returns the height of the next block to scan.
+    pub fn next_height(&self) -> u32 {
+        self.state.read().next_height
+    }
+
+    /// Returns the number of owned records found so far.
+    pub fn num_records(&self) -> usize {
+        self.state.read().commitments.len()
+    }
+
+    /// Returns the commitments of the owned records, mapped to the height of the block that contains them.
+    pub fn commitments(&self) -> IndexMap<Field<N>, u32> {
+        self.state.read().commitments.clone()
+    }
+
+    /// Returns `true` if the record with the given commitment is owned by the view key.
+    pub fn contains_commitment(&self, commitment: &Field<N>) -> bool {
+        self.state.read().commitments.contains_key(commitment)
+    }
+
+    /// Scans the blocks from the cursor up to the latest block in the ledger, returning the number of new records.
+    pub fn scan<C: ConsensusStorage<N>>(&self, ledger: &Ledger<N, C>) -> Result<usize> {
+        // Acquire the scan lock, so that concurrent scans do not scan the same blocks.
+        let _scan_lock = self.scan_lock.lock();
+
+        // Ensure the state belongs to the ledger, binding it to the genesis block on the first scan.
+        {
+            let genesis_hash = ledger.genesis_block.hash();
+            let mut state = self.state.write();
+            match state.genesis_hash {
+                Some(expected) => ensure!(
+                    expected == genesis_hash,
+                    "The record scanner belongs to the ledger with genesis block '{expected}', not '{genesis_hash}'"
+                ),
+                None => state.genesis_hash = Some(genesis_hash),
+            }
+        }
+
+        let latest_height = ledger.latest_height();
+        let mut num_records = 0;
+        loop {
+            // Determine the next batch of blocks to scan.
+            let start_height = self.next_height();
+            if start_height > latest_height {
+                break;
+            }
+            let end_height = start_height.saturating_add(NUM_BLOCKS_PER_BATCH).min(latest_height.saturating_add(1));
+
+            // Scan the batch of blocks in parallel.
+            let commitments = cfg_into_iter!(start_height..end_height)
+                .map(|height| {
+                    let block = ledger.get_block(height)?;
+                    Ok(block
+                        .records()
+                        .filter(|(_, record)| {
+                            record.is_owner_with_address_x_coordinate(&self.view_key, &self.address_x_coordinate)
+                        })
+                        .map(|(commitment, _)| (*commitment, height))
+                        .collect::<Vec<_>>())
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            // Advance the cursor.
+            let mut state = self.state.write();
+            for (commitment, height) in commitments.into_iter().flatten() {
+                num_records += state.commitments.insert(commitment, height).is_none() as usize;
+            }
+            state.next_height = end_height;
+            // Persist the state.
+            self.persist(&state)?;
+        }
+        Ok(num_records)
+    }
+
+    /// Rewinds the cursor to the block after the given height, and forgets the records found after it.
+    pub(crate) fn rewind_to(&self, height: u32) -> Result<()> {
+        // Acquire the scan lock, so that a concurrent scan does not advance the cursor.
+        let _scan_lock = self.scan_lock.lock();
+
+        let mut state = self.state.write();
+        state.commitments.retain(|_, record_height| *record_height <= height);
+        state.next_height = state.next_height.min(height.saturating_add(1));
+        // Persist the state.
+        self.persist(&state)
+    }
+
+    /// Persists the given state to disk, if the scanner was opened from a directory.
+    fn persist(&self, state: &ScannerState<N>) -> Result<()> {
+        if let Some(path) = &self.path {
+            // Write to a temporary file, and rename it, so that a crash does not leave a partial state.
+            let temp_path = path.with_extension("scan.tmp");
+            std::fs::write(&temp_path, state.to_bytes_le()?)?;
+            std::fs::rename(&temp_path, path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Coordinates the background scans of the subscribed record scanners, so that at most one runs at a time.
+#[derive(Default)]
+pub(crate) struct ScanSignal {
+    /// Whether new blocks are waiting to be scanned.
+    pending: AtomicBool,
+    /// Whether a background scan is running.
+    running: AtomicBool,
+}
+
+impl ScanSignal {
+    /// Requests a scan, returning `true` if the caller must start a background scan,
+    /// or `false` if the running background scan will perform it.
+    pub(crate) fn request(&self) -> bool {
+        self.pending.store(true, Ordering::SeqCst);
+        !self.running.swap(true, Ordering::SeqCst)
+    }
+
+    /// Takes the pending request, returning `true` if the background scan must scan again,
+    /// or `false` if it must stop.
+    pub(crate) fn take(&self) -> bool {
+        loop {
+            if self.pending.swap(false, Ordering::SeqCst) {
+                return true;
+            }
+            self.running.store(false, Ordering::SeqCst);
+            // Stop, unless a request arrived before the scan stopped running, and no other scan started for it.
+            if !self.pending.load(Ordering::SeqCst) || self.running.swap(true, Ordering::SeqCst) {
+                return false;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use console::network::MainnetV0;
+
+    type CurrentNetwork = MainnetV0;
+
+    #[test]
+    fn test_state_bytes() {
+        let rng = &mut TestRng::default();
+
+        let mut expected = ScannerState::<CurrentNetwork> {
+            genesis_hash: Some(Uniform::rand(rng)),
+            next_height: rng.gen(),
+            ..Default::default()
+        };
+        for _ in 0..10 {
+            expected.commitments.insert(Uniform::rand(rng), rng.gen());
+        }
+
+        let candidate = ScannerState::from_bytes_le(&expected.to_bytes_le().unwrap()).unwrap();
+        assert_eq!(expected, candidate);
+
+        // Ensure an unknown version is rejected.
+        let mut bytes = expected.to_bytes_le().unwrap();
+        bytes[0] = 2;
+        assert!(ScannerState::<CurrentNetwork>::from_bytes_le(&bytes).is_err());
+
+        // Ensure a state without a genesis hash round-trips.
+        let expected = ScannerState::<CurrentNetwork> { genesis_hash: None, ..expected };
+        let candidate = ScannerState::from_bytes_le(&expected.to_bytes_le().unwrap()).unwrap();
+        assert_eq!(expected, candidate);
+    }
+
+    #[test]
+    fn test_scan_signal() {
+        let signal = ScanSignal::default();
+
+        // The first request starts a background scan, and later requests are left to it.
+        assert!(signal.request());
+        assert!(!signal.request());
+        assert!(signal.take());
+        assert!(!signal.request());
+        assert!(signal.take());
+        // Once there are no pending requests, the background scan stops.
+        assert!(!signal.take());
+        // The next request starts a new background scan.
+        assert!(signal.request());
+    }
+}
@@ -0,0 +1,323 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::RecordMap;
+use console::{
+    network::prelude::*,
+    program::{Entry, Identifier, Literal, Plaintext, Record},
+    types::Field,
+};
+
+use rand::seq::SliceRandom;
+
+/// The maximum number of subsets searched by the exact-match strategy.
+const MAX_EXACT_MATCH_ITERATIONS: usize = 100_000;
+
+/// A candidate record, with its commitment and amount of microcredits.
+type Candidate<'a, N> = (Field<N>, &'a Record<N, Plaintext<N>>, u64);
+
+/// The strategy used to select the records that cover an amount.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RecordSelectionStrategy {
+    /// Selects the largest records first, until the amount is covered.
+    LargestFirst,
+    /// Selects records that sum to exactly the amount, so that no change record is created.
+    ExactMatch,
+    /// Selects the fewest records, and among those, the ones that leave the least change.
+    MinimizeCount,
+    /// Selects a single random record that covers the amount, so that no records are linked by a join,
+    /// and falls back to random records if no single record covers the amount.
+    PrivacyPreserving,
+}
+
+/// The records selected to cover an amount, and optionally a private fee.
+///
+/// A transfer spends a single record, so if more than one record is selected, the records must first be
+/// combined with `credits.aleo/join`. Each round of joins halves the number of records, and once the joins
+/// are confirmed, selecting again yields fewer (and larger) records. The fees of the joins are paid from the
+/// public balance, so the fee record is left for the transfer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordPlan<N: Network> {
+    /// The records selected to cover the amount.
+    records: Vec<(Field<N>, Record<N, Plaintext<N>>)>,
+    /// The record selected to cover the fee, if a private fee is paid.
+    fee_record: Option<(Field<N>, Record<N, Plaintext<N>>)>,
+}
+
+impl<N: Network> RecordPlan<N> {
+    /// Selects the records to cover the given amount, and the record to cover the given private fee (if any),
+    /// from the given unspent `credits.aleo` records.
+    ///
+    /// Note: The fee record is selected first, as the smallest record that covers the fee,
+    /// so that it is never combined with the records for the amount.
+    pub fn new<R: Rng + CryptoRng>(
+        records: &RecordMap<N>,
+        amount_in_microcredits: u64,
+        fee_in_microcredits: Option<u64>,
+        strategy: RecordSelectionStrategy,
+        rng: &mut R,
+    ) -> Result<Self> {
+        ensure!(amount_in_microcredits > 0, "The amount to select records for must be nonzero");
+
+        // Retrieve the amount of each record.
+        let mut candidates = records
+            .iter()
+            .map(|(commitment, record)| Ok((*commitment, record, microcredits(record)?)))
+            .collect::<Result<Vec<_>>>()?;
+
+        // Select the fee record.
+        let fee_record = match fee_in_microcredits {
+            Some(fee) => {
+                let sufficient = candidates.iter().enumerate().filter(|(_, (_, _, amount))| *amount >= fee);
+                let index = match strategy {
+                    RecordSelectionStrategy::PrivacyPreserving => {
+                        sufficient.map(|(index, _)| index).collect::<Vec<_>>().choose(rng).copied()
+                    }
+                    _ => sufficient.min_by_key(|(_, (_, _, amount))| *amount).map(|(index, _)| index),
+                };
+                match index {
+                    Some(index) => Some(candidates.swap_remove(index)),
+                    None => bail!("No single record covers the fee of {fee} microcredits"),
+                }
+            }
+            None => None,
+        };
+
+        // Ensure the remaining records cover the amount.
+        let balance = candidates.iter().map(|(_, _, amount)| *amount as u128).sum::<u128>();
+        ensure!(
+            balance >= amount_in_microcredits as u128,
+            "Insufficient balance: the records hold {balance} microcredits, but {amount_in_microcredits} are required"
+        );
+
+        // Sort the records from largest to smallest.
+        candidates.sort_by_key(|(_, _, amount)| core::cmp::Reverse(*amount));
+
+        // Select the records for the amount.
+        let target = amount_in_microcredits as u128;
+        let selected = match strategy {
+            RecordSelectionStrategy::LargestFirst => take_until_covered(candidates, target),
+            RecordSelectionStrategy::ExactMatch => match find_exact_match(&candidates, target) {
+                Some(indices) => indices.into_iter().map(|index| candidates[index]).collect(),
+                None => bail!("No combination of records sums to exactly {amount_in_microcredits} microcredits"),
+            },
+            RecordSelectionStrategy::MinimizeCount => {
+                // Determine the fewest records that cover the amount, which are the largest ones.
+                let count = take_until_covered(candidates.clone(), target).len();
+                // Keep all but the last of the largest records, and complete them with the smallest sufficient record.
+                let mut selected = candidates[..count - 1].to_vec();
+                let remainder = target - selected.iter().map(|(_, _, amount)| *amount as u128).sum::<u128>();
+                let last = candidates[count - 1..]
+                    .iter()
+                    .filter(|(_, _, amount)| *amount as u128 >= remainder)
+                    .min_by_key(|(_, _, amount)| *amount)
+                    .copied();
+                selected.extend(last);
+                selected
+            }
+            RecordSelectionStrategy::PrivacyPreserving => {
+                let sufficient =
+                    candidates.iter().filter(|(_, _, amount)| *amount as u128 >= target).copied().collect::<Vec<_>>();
+                match sufficient.choose(rng) {
+                    Some(candidate) => vec![*candidate],
+                    None => {
+                        candidates.shuffle(rng);
+                        take_until_covered(candidates, target)
+                    }
+                }
+            }
+        };
+
+        let into_owned = |(commitment, record, _): Candidate<'_, N>| (commitment, record.clone());
+        Ok(Self { records: selected.into_iter().map(into_owned).collect(), fee_record: fee_record.map(into_owned) })
+    }
+
+    /// Returns the records selected to cover the amount.
+    pub fn records(&self) -> &[(Field<N>, Record<N, Plaintext<N>>)] {
+        &self.records
+    }
+
+    /// Returns the record selected to cover the fee, if a private fee is paid.
+    pub fn fee_record(&self) -> Option<&(Field<N>, Record<N, Plaintext<N>>)> {
+        self.fee_record.as_ref()
+    }
+
+    /// Returns the total amount of the records selected to cover the amount, in microcredits.
+    pub fn total_in_microcredits(&self) -> Result<u64> {
+        self.records.iter().try_fold(0u64, |total, (_, record)| {
+            total.checked_add(microcredits(record)?).ok_or_else(|| anyhow!("The total amount overflowed"))
+        })
+    }
+
+    /// Returns `true` if the selected records must be combined with `credits.aleo/join` before the transfer.
+    pub fn requires_join(&self) -> bool {
+        self.records.len() > 1
+    }
+
+    /// Returns the pairs of records to combine with `credits.aleo/join` in the next round.
+    /// If the number of records is odd, the last record is carried over to the following round.
+    pub fn join_pairs(&self) -> impl '_ + Iterator<Item = (&Record<N, Plaintext<N>>, &Record<N, Plaintext<N>>)> {
+        self.records.chunks_exact(2).map(|pair| (&pair[0].1, &pair[1].1))
+    }
+}
+
+/// Returns the amount of microcredits in the given `credits.aleo` record.
+fn microcredits<N: Network>(record: &Record<N, Plaintext<N>>) -> Result<u64> {
+    match record.data().get(&Identifier::from_str("microcredits")?) {
+        Some(Entry::Private(Plaintext::Literal(Literal::U64(amount), _))) => Ok(**amount),
+        _ => bail!("The record is not a 'credits.aleo' record"),
+    }
+}
+
+/// Returns the candidates, in order, until their amounts cover the target.
+fn take_until_covered<N: Network>(candidates: Vec<Candidate<'_, N>>, target: u128) -> Vec<Candidate<'_, N>> {
+    let mut total = 0u128;
+    candidates
+        .into_iter()
+        .take_while(|(_, _, amount)| {
+            let is_covered = total >= target;
+            total += *amount as u128;
+            !is_covered
+        })
+        .collect()
+}
+
+/// Returns the indices of the candidates that sum to exactly the target, if they are found within
+/// `MAX_EXACT_MATCH_ITERATIONS`. The candidates are searched depth-first, in order.
+fn find_exact_match<N: Network>(candidates: &[Candidate<'_, N>], target: u128) -> Option<Vec<usize>> {
+    // Compute the suffix sums, to prune the branches that cannot reach the target.
+    let mut suffix_sums = vec![0u128; candidates.len() + 1];
+    for index in (0..candidates.len()).rev() {
+        suffix_sums[index] = suffix_sums[index + 1] + candidates[index].2 as u128;
+    }
+
+    /// Searches for a subset of the candidates from the given index that sums to the remaining amount.
+    fn search<N: Network>(
+        candidates: &[Candidate<'_, N>],
+        suffix_sums: &[u128],
+        index: usize,
+        remaining: u128,
+        selected: &mut Vec<usize>,
+        iterations: &mut usize,
+    ) -> bool {
+        if remaining == 0 {
+            return true;
+        }
+        *iterations += 1;
+        if index == candidates.len() || suffix_sums[index] < remaining || *iterations > MAX_EXACT_MATCH_ITERATIONS {
+            return false;
+        }
+        // Include the candidate, if it does not exceed the remaining amount.
+        let amount = candidates[index].2 as u128;
+        if amount <= remaining {
+            selected.push(index);
+            if search(candidates, suffix_sums, index + 1, remaining - amount, selected, iterations) {
+                return true;
+            }
+            selected.pop();
+        }
+        // Exclude the candidate.
+        search(candidates, suffix_sums, index + 1, remaining, selected, iterations)
+    }
+
+    let mut selected = Vec::new();
+    search(candidates, &suffix_sums, 0, target, &mut selected, &mut 0).then_some(selected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use console::network::MainnetV0;
+
+    type CurrentNetwork = MainnetV0;
+
+    /// Returns sample `credits.aleo` records with the given amounts.
+    fn sample_records(amounts: &[u64], rng: &mut TestRng) -> RecordMap<CurrentNetwork> {
+        amounts
+            .iter()
+            .map(|amount| {
+                let record = Record::from_str(&format!(
+                    "{{ owner: aleo1d5hg2z3ma00382pngntdp68e74zv54jdxy249qhaujhks9c72yrs33ddah.private, microcredits: {amount}u64.private, _nonce: 0group.public }}"
+                ))
+                .unwrap();
+                (Field::rand(rng), record)
+            })
+            .collect()
+    }
+
+    /// Returns the amounts of the records selected by the plan, and the amount of the fee record.
+    fn amounts(plan: &RecordPlan<CurrentNetwork>) -> (Vec<u64>, Option<u64>) {
+        let records = plan.records().iter().map(|(_, record)| microcredits(record).unwrap()).collect();
+        (records, plan.fee_record().map(|(_, record)| microcredits(record).unwrap()))
+    }
+
+    #[test]
+    fn test_select_records() {
+        let rng = &mut TestRng::default();
+        let records = sample_records(&[5, 40, 10, 30, 20], rng);
+
+        let select = |amount, fee, strategy, rng: &mut TestRng| RecordPlan::new(&records, amount, fee, strategy, rng);
+
+        // Largest first.
+        let plan = select(45, None, RecordSelectionStrategy::LargestFirst, rng).unwrap();
+        assert_eq!(amounts(&plan), (vec![40, 30], None));
+        assert!(plan.requires_join());
+        assert_eq!(plan.total_in_microcredits().unwrap(), 70);
+
+        // Exact match.
+        let plan = select(45, None, RecordSelectionStrategy::ExactMatch, rng).unwrap();
+        assert_eq!(amounts(&plan), (vec![40, 5], None));
+        assert!(select(46, None, RecordSelectionStrategy::ExactMatch, rng).is_err());
+
+        // Minimize count.
+        let plan = select(45, None, RecordSelectionStrategy::MinimizeCount, rng).unwrap();
+        assert_eq!(amounts(&plan), (vec![40, 5], None));
+        let plan = select(15, None, RecordSelectionStrategy::MinimizeCount, rng).unwrap();
+        assert_eq!(amounts(&plan), (vec![20], None));
+        assert!(!plan.requires_join());
+
+        // Privacy preserving, which selects a single record whenever one covers the amount.
+        for _ in 0..10 {
+            let plan = select(25, None, RecordSelectionStrategy::PrivacyPreserving, rng).unwrap();
+            let (records, _) = amounts(&plan);
+            assert!(records == [30] || records == [40]);
+        }
+        let plan = select(100, None, RecordSelectionStrategy::PrivacyPreserving, rng).unwrap();
+        assert!(plan.total_in_microcredits().unwrap() >= 100);
+
+        // Ensure the fee record is the smallest sufficient record, and is not selected for the amount.
+        let plan = select(45, Some(8), RecordSelectionStrategy::LargestFirst, rng).unwrap();
+        assert_eq!(amounts(&plan), (vec![40, 30], Some(10)));
+        assert!(select(10, Some(50), RecordSelectionStrategy::LargestFirst, rng).is_err());
+
+        // Ensure the balance must cover the amount.
+        assert!(select(106, None, RecordSelectionStrategy::LargestFirst, rng).is_err());
+        assert!(select(100, Some(20), RecordSelectionStrategy::LargestFirst, rng).is_err());
+        assert!(select(0, None, RecordSelectionStrategy::LargestFirst, rng).is_err());
+    }
+
+    #[test]
+    fn test_join_pairs() {
+        let rng = &mut TestRng::default();
+        let records = sample_records(&[1, 2, 3, 4, 5], rng);
+
+        // Ensure the records are joined in pairs, and the odd record is carried over.
+        let plan = RecordPlan::new(&records, 15, None, RecordSelectionStrategy::LargestFirst, rng).unwrap();
+        let pairs = plan
+            .join_pairs()
+            .map(|(first, second)| (microcredits(first).unwrap(), microcredits(second).unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(pairs, [(5, 4), (3, 2)]);
+    }
+}
@@ -0,0 +1,147 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use console::network::prelude::*;
+use ledger_block::ConfirmedTransaction;
+
+/// A transaction whose replayed outcome differs from the outcome recorded in the block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TransactionDiff<N: Network> {
+    /// The ID of the unconfirmed transaction.
+    transaction_id: N::TransactionID,
+    /// The confirmed transaction recorded in the block, if any.
+    recorded: Option<ConfirmedTransaction<N>>,
+    /// The confirmed transaction produced by the replay, if any.
+    replayed: Option<ConfirmedTransaction<N>>,
+}
+
+impl<N: Network> TransactionDiff<N> {
+    /// Initializes a new transaction diff.
+    pub(crate) fn new(
+        transaction_id: N::TransactionID,
+        recorded: Option<ConfirmedTransaction<N>>,
+        replayed: Option<ConfirmedTransaction<N>>,
+    ) -> Self {
+        Self { transaction_id, recorded, replayed }
+    }
+
+    /// Returns the ID of the unconfirmed transaction.
+    pub const fn transaction_id(&self) -> N::TransactionID {
+        self.transaction_id
+    }
+
+    /// Returns the confirmed transaction recorded in the block, if any.
+    pub const fn recorded(&self) -> Option<&ConfirmedTransaction<N>> {
+        self.recorded.as_ref()
+    }
+
+    /// Returns the confirmed transaction produced by the replay, if any.
+    pub const fn replayed(&self) -> Option<&ConfirmedTransaction<N>> {
+        self.replayed.as_ref()
+    }
+}
+
+impl<N: Network> Display for TransactionDiff<N> {
+    /// Displays the outcomes of the transaction, followed by each finalize operation that differs.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        // Returns the index and variant of the given outcome.
+        let outcome = |transaction: Option<&ConfirmedTransaction<N>>| match transaction {
+            Some(transaction) => format!("{} (index {})", transaction.variant(), transaction.index()),
+            None => "missing".to_string(),
+        };
+        writeln!(f, "Transaction '{}'", self.transaction_id)?;
+        writeln!(f, "  recorded: {}", outcome(self.recorded()))?;
+        writeln!(f, "  replayed: {}", outcome(self.replayed()))?;
+
+        // Retrieve the finalize operations of each outcome.
+        let recorded = self.recorded.as_ref().map(|transaction| transaction.finalize_operations().as_slice());
+        let replayed = self.replayed.as_ref().map(|transaction| transaction.finalize_operations().as_slice());
+        let (recorded, replayed) = (recorded.unwrap_or_default(), replayed.unwrap_or_default());
+        // Display the finalize operations which differ.
+        for index in 0..recorded.len().max(replayed.len()) {
+            let (recorded, replayed) = (recorded.get(index), replayed.get(index));
+            if recorded != replayed {
+                writeln!(f, "  finalize operation {index}:")?;
+                match recorded {
+                    Some(operation) => writeln!(f, "    - recorded: {operation}")?,
+                    None => writeln!(f, "    - recorded: none")?,
+                }
+                match replayed {
+                    Some(operation) => writeln!(f, "    + replayed: {operation}")?,
+                    None => writeln!(f, "    + replayed: none")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The first divergence found when replaying a block.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReplayDivergence<N: Network> {
+    /// The height of the block.
+    height: u32,
+    /// The hash of the block.
+    block_hash: N::BlockHash,
+    /// The descriptions of the block-level mismatches.
+    mismatches: Vec<String>,
+    /// The transactions whose replayed outcome differs from the recorded outcome.
+    transactions: Vec<TransactionDiff<N>>,
+}
+
+impl<N: Network> ReplayDivergence<N> {
+    /// Initializes a new replay divergence.
+    pub(crate) fn new(
+        height: u32,
+        block_hash: N::BlockHash,
+        mismatches: Vec<String>,
+        transactions: Vec<TransactionDiff<N>>,
+    ) -> Self {
+        Self { height, block_hash, mismatches, transactions }
+    }
+
+    /// Returns the height of the block.
+    pub const fn height(&self) -> u32 {
+        self.height
+    }
+
+    /// Returns the hash of the block.
+    pub const fn block_hash(&self) -> N::BlockHash {
+        self.block_hash
+    }
+
+    /// Returns the descriptions of the block-level mismatches.
+    pub fn mismatches(&self) -> &[String] {
+        &self.mismatches
+    }
+
+    /// Returns the transactions whose replayed outcome differs from the recorded outcome.
+    pub fn transactions(&self) -> &[TransactionDiff<N>] {
+        &self.transactions
+    }
+}
+
+impl<N: Network> Display for ReplayDivergence<N> {
+    /// Displays the mismatches of the block, followed by the diff of each diverging transaction.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        writeln!(f, "Block {} ('{}') diverges from its replay", self.height, self.block_hash)?;
+        for mismatch in &self.mismatches {
+            writeln!(f, "  - {mismatch}")?;
+        }
+        for transaction in &self.transactions {
+            write!(f, "\n{transaction}")?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,423 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use console::{
+    account::{Address, PrivateKey, Signature},
+    network::prelude::*,
+    types::Field,
+};
+use synthesizer::{DisclosurePredicate, RecordDisclosure};
+
+use std::collections::HashSet;
+
+/// The reserves held by an account, as its public balance and its disclosed private records.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AccountReserves<N: Network> {
+    /// The address of the account.
+    address: Address<N>,
+    /// The public balance of the account, in microcredits.
+    public_balance: u64,
+    /// The disclosures of the unspent `credits.aleo` records of the account.
+    records: Vec<RecordDisclosure<N>>,
+    /// The signature of the account over the attested statement.
+    signature: Signature<N>,
+}
+
+impl<N: Network> AccountReserves<N> {
+    /// Initializes the reserves of the account of the given private key, signed over the given statement.
+    pub(crate) fn new<R: Rng + CryptoRng>(
+        private_key: &PrivateKey<N>,
+        statement: &[Field<N>],
+        public_balance: u64,
+        records: Vec<RecordDisclosure<N>>,
+        rng: &mut R,
+    ) -> Result<Self> {
+        let address = Address::try_from(private_key)?;
+        let private_balance = private_balance(&records)?;
+        let message = account_message(statement, &address, public_balance, private_balance);
+        let signature = Signature::sign(private_key, &message, rng)?;
+        Ok(Self { address, public_balance, records, signature })
+    }
+
+    /// Returns the address of the account.
+    pub const fn address(&self) -> &Address<N> {
+        &self.address
+    }
+
+    /// Returns the public balance of the account, in microcredits.
+    pub const fn public_balance(&self) -> u64 {
+        self.public_balance
+    }
+
+    /// Returns the disclosures of the private records of the account.
+    pub fn records(&self) -> &[RecordDisclosure<N>] {
+        &self.records
+    }
+
+    /// Returns the private balance of the account, in microcredits.
+    pub fn private_balance(&self) -> Result<u64> {
+        private_balance(&self.records)
+    }
+
+    /// Returns the signature of the account over the attested statement.
+    pub const fn signature(&self) -> &Signature<N> {
+        &self.signature
+    }
+}
+
+/// The opening of a reserves attestation, which is shared with an auditor instead of being published.
+///
+/// The opening reveals the addresses, balances, and record disclosures of each account,
+/// and the randomizer that blinds the commitment to them in the attestation.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReservesOpening<N: Network> {
+    /// The randomizer of the commitment.
+    randomizer: Field<N>,
+    /// The reserves of each account.
+    accounts: Vec<AccountReserves<N>>,
+}
+
+impl<N: Network> ReservesOpening<N> {
+    /// Initializes a new opening of the given account reserves, with a random commitment randomizer.
+    pub(crate) fn new<R: Rng + CryptoRng>(accounts: Vec<AccountReserves<N>>, rng: &mut R) -> Self {
+        Self { randomizer: Uniform::rand(rng), accounts }
+    }
+
+    /// Returns the randomizer of the commitment.
+    pub const fn randomizer(&self) -> Field<N> {
+        self.randomizer
+    }
+
+    /// Returns the reserves of each account.
+    pub fn accounts(&self) -> &[AccountReserves<N>] {
+        &self.accounts
+    }
+
+    /// Returns the total public balance across all accounts, in microcredits.
+    pub fn public_balance(&self) -> Result<u64> {
+        self.accounts.iter().try_fold(0u64, |total, account| {
+            total.checked_add(account.public_balance).ok_or_else(|| anyhow!("The total public balance overflows"))
+        })
+    }
+
+    /// Returns the total private balance across all accounts, in microcredits.
+    pub fn private_balance(&self) -> Result<u64> {
+        self.accounts.iter().try_fold(0u64, |total, account| {
+            total.checked_add(account.private_balance()?).ok_or_else(|| anyhow!("The total private balance overflows"))
+        })
+    }
+
+    /// Returns the commitment to the given statement and the reserves of each account,
+    /// as `Hash( randomizer || statement || (address || public balance || private balance || serial numbers)* )`.
+    fn to_commitment(&self, statement: &[Field<N>]) -> Result<Field<N>> {
+        let mut preimage = vec![self.randomizer];
+        preimage.extend_from_slice(statement);
+        for account in &self.accounts {
+            preimage.extend([
+                account.address.to_x_coordinate(),
+                Field::from_u64(account.public_balance),
+                Field::from_u64(account.private_balance()?),
+            ]);
+            preimage.extend(account.records.iter().filter_map(|record| record.serial_number().copied()));
+        }
+        N::hash_psd8(&preimage)
+    }
+}
+
+/// A signed attestation of the total reserves held by a set of accounts, at a given block height.
+///
+/// The attestation publishes only the total public and private balances, and a blinded commitment to the reserves
+/// of each account. The opening of the commitment is shared with an auditor, who checks that it sums to the totals,
+/// that the public balances match the `credits.aleo/account` mapping at the block height, and that each record
+/// disclosure is valid and unspent at the block height. The attestor signs the attestation as a whole.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReservesAttestation<N: Network> {
+    /// The block height of the attestation.
+    block_height: u32,
+    /// The block hash at the block height.
+    block_hash: N::BlockHash,
+    /// The global state root at the block height.
+    state_root: N::StateRoot,
+    /// The commitment to the reserves of each account.
+    commitment: Field<N>,
+    /// The total public balance, in microcredits.
+    public_balance: u64,
+    /// The total private balance, in microcredits.
+    private_balance: u64,
+    /// The signature of the attestor.
+    signature: Signature<N>,
+}
+
+impl<N: Network> ReservesAttestation<N> {
+    /// Returns the statement at the given block, which every account signs.
+    pub(crate) fn statement(block_height: u32, block_hash: N::BlockHash, state_root: N::StateRoot) -> Vec<Field<N>> {
+        vec![Field::from_u32(block_height), *block_hash, *state_root]
+    }
+
+    /// Initializes a new attestation of the given opening, signed by the given attestor.
+    pub(crate) fn new<R: Rng + CryptoRng>(
+        attestor: &PrivateKey<N>,
+        block_height: u32,
+        block_hash: N::BlockHash,
+        state_root: N::StateRoot,
+        opening: &ReservesOpening<N>,
+        rng: &mut R,
+    ) -> Result<Self> {
+        let commitment = opening.to_commitment(&Self::statement(block_height, block_hash, state_root))?;
+        let public_balance = opening.public_balance()?;
+        let private_balance = opening.private_balance()?;
+        let message =
+            attestor_message(block_height, block_hash, state_root, commitment, public_balance, private_balance);
+        let signature = Signature::sign(attestor, &message, rng)?;
+        Ok(Self { block_height, block_hash, state_root, commitment, public_balance, private_balance, signature })
+    }
+
+    /// Returns the block height of the attestation.
+    pub const fn block_height(&self) -> u32 {
+        self.block_height
+    }
+
+    /// Returns the block hash at the block height.
+    pub const fn block_hash(&self) -> N::BlockHash {
+        self.block_hash
+    }
+
+    /// Returns the global state root at the block height.
+    pub const fn state_root(&self) -> N::StateRoot {
+        self.state_root
+    }
+
+    /// Returns the commitment to the reserves of each account.
+    pub const fn commitment(&self) -> Field<N> {
+        self.commitment
+    }
+
+    /// Returns the total public balance, in microcredits.
+    pub const fn public_balance(&self) -> u64 {
+        self.public_balance
+    }
+
+    /// Returns the total private balance, in microcredits.
+    pub const fn private_balance(&self) -> u64 {
+        self.private_balance
+    }
+
+    /// Returns the address of the attestor.
+    pub fn attestor(&self) -> Address<N> {
+        self.signature.to_address()
+    }
+
+    /// Returns the signature of the attestor.
+    pub const fn signature(&self) -> &Signature<N> {
+        &self.signature
+    }
+
+    /// Returns the total reserves, in microcredits.
+    pub fn total_balance(&self) -> Result<u64> {
+        self.public_balance.checked_add(self.private_balance).ok_or_else(|| anyhow!("The total reserves overflow"))
+    }
+
+    /// Checks the signature of the attestor. On failure, returns an error.
+    pub fn check_signature(&self) -> Result<()> {
+        let message = attestor_message(
+            self.block_height,
+            self.block_hash,
+            self.state_root,
+            self.commitment,
+            self.public_balance,
+            self.private_balance,
+        );
+        ensure!(self.signature.verify(&self.attestor(), &message), "Invalid attestor signature");
+        Ok(())
+    }
+
+    /// Checks the signatures of the attestation, that the given opening matches the commitment and the totals,
+    /// and that each record disclosure is for the exact balance of a distinct `credits.aleo` record of its account,
+    /// committed at the attested state root. On failure, returns an error.
+    ///
+    /// Note: This does *not* verify the disclosure proofs, nor the balances and state against the ledger.
+    pub fn check_opening(&self, opening: &ReservesOpening<N>) -> Result<()> {
+        // Ensure the attestor signed the attestation.
+        self.check_signature()?;
+        // Ensure the opening matches the commitment and the totals.
+        let statement = Self::statement(self.block_height, self.block_hash, self.state_root);
+        ensure!(opening.to_commitment(&statement)? == self.commitment, "The opening does not match the commitment");
+        ensure!(opening.public_balance()? == self.public_balance, "The opening does not match the public balance");
+        ensure!(opening.private_balance()? == self.private_balance, "The opening does not match the private balance");
+
+        let mut addresses = HashSet::with_capacity(opening.accounts.len());
+        let mut serial_numbers = HashSet::new();
+        for account in &opening.accounts {
+            // Ensure each account is attested once.
+            ensure!(addresses.insert(account.address), "Account '{}' is attested more than once", account.address);
+            // Ensure each record disclosure is for the microcredits of a `credits.aleo` record of the account.
+            for record in &account.records {
+                ensure!(
+                    record.program_id().to_string() == "credits.aleo"
+                        && record.record_name().to_string() == "credits"
+                        && record.entry().to_string() == "microcredits",
+                    "Account '{}' discloses a record that is not 'credits.aleo/credits'",
+                    account.address
+                );
+                ensure!(
+                    matches!(record.predicate(), DisclosurePredicate::Equal(..)),
+                    "Account '{}' discloses a record without its exact balance",
+                    account.address
+                );
+                ensure!(record.owner() == &account.address, "A record of '{}' is owned by another", account.address);
+                ensure!(
+                    record.global_state_root() == self.state_root,
+                    "A record of '{}' is not at the state root",
+                    account.address
+                );
+                // Ensure each record is counted once.
+                let Some(serial_number) = record.serial_number() else {
+                    bail!("A record of '{}' does not reveal its serial number", account.address)
+                };
+                ensure!(serial_numbers.insert(*serial_number), "A record is attested more than once");
+            }
+            // Ensure the account signed its balances.
+            let message =
+                account_message(&statement, &account.address, account.public_balance, account.private_balance()?);
+            ensure!(
+                account.signature.verify(&account.address, &message),
+                "Invalid signature for '{}'",
+                account.address
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Returns the private balance disclosed by the given records, in microcredits.
+fn private_balance<N: Network>(records: &[RecordDisclosure<N>]) -> Result<u64> {
+    records.iter().try_fold(0u64, |total, record| {
+        total.checked_add(record.predicate().bound()).ok_or_else(|| anyhow!("The private balance overflows"))
+    })
+}
+
+/// Returns the message signed by an account, as `(statement || address || public balance || private balance)`.
+fn account_message<N: Network>(
+    statement: &[Field<N>],
+    address: &Address<N>,
+    public_balance: u64,
+    private_balance: u64,
+) -> Vec<Field<N>> {
+    let mut message = statement.to_vec();
+    message.extend([address.to_x_coordinate(), Field::from_u64(public_balance), Field::from_u64(private_balance)]);
+    message
+}
+
+/// Returns the message signed by the attestor, as `(statement || commitment || public balance || private balance)`.
+fn attestor_message<N: Network>(
+    block_height: u32,
+    block_hash: N::BlockHash,
+    state_root: N::StateRoot,
+    commitment: Field<N>,
+    public_balance: u64,
+    private_balance: u64,
+) -> Vec<Field<N>> {
+    let mut message = ReservesAttestation::statement(block_height, block_hash, state_root);
+    message.extend([commitment, Field::from_u64(public_balance), Field::from_u64(private_balance)]);
+    message
+}
+
+impl<N: Network> FromBytes for AccountReserves<N> {
+    /// Reads the account reserves from a buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        let address = FromBytes::read_le(&mut reader)?;
+        let public_balance = FromBytes::read_le(&mut reader)?;
+        let num_records = u32::read_le(&mut reader)?;
+        let records = (0..num_records).map(|_| FromBytes::read_le(&mut reader)).collect::<IoResult<Vec<_>>>()?;
+        let signature = FromBytes::read_le(&mut reader)?;
+        Ok(Self { address, public_balance, records, signature })
+    }
+}
+
+impl<N: Network> ToBytes for AccountReserves<N> {
+    /// Writes the account reserves to a buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.address.write_le(&mut writer)?;
+        self.public_balance.write_le(&mut writer)?;
+        u32::try_from(self.records.len()).map_err(error)?.write_le(&mut writer)?;
+        self.records.iter().try_for_each(|record| record.write_le(&mut writer))?;
+        self.signature.write_le(&mut writer)
+    }
+}
+
+impl<N: Network> FromBytes for ReservesOpening<N> {
+    /// Reads the reserves opening from a buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the version.
+        let version = u8::read_le(&mut reader)?;
+        // Ensure the version is valid.
+        if version != 1 {
+            return Err(error("Invalid reserves opening version"));
+        }
+        // Read the opening.
+        let randomizer = FromBytes::read_le(&mut reader)?;
+        let num_accounts = u32::read_le(&mut reader)?;
+        let accounts = (0..num_accounts).map(|_| FromBytes::read_le(&mut reader)).collect::<IoResult<Vec<_>>>()?;
+        Ok(Self { randomizer, accounts })
+    }
+}
+
+impl<N: Network> ToBytes for ReservesOpening<N> {
+    /// Writes the reserves opening to a buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        // Write the version.
+        1u8.write_le(&mut writer)?;
+        // Write the opening.
+        self.randomizer.write_le(&mut writer)?;
+        u32::try_from(self.accounts.len()).map_err(error)?.write_le(&mut writer)?;
+        self.accounts.iter().try_for_each(|account| account.write_le(&mut writer))
+    }
+}
+
+impl<N: Network> FromBytes for ReservesAttestation<N> {
+    /// Reads the reserves attestation from a buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the version.
+        let version = u8::read_le(&mut reader)?;
+        // Ensure the version is valid.
+        if version != 1 {
+            return Err(error("Invalid reserves attestation version"));
+        }
+        // Read the attestation.
+        let block_height = FromBytes::read_le(&mut reader)?;
+        let block_hash = FromBytes::read_le(&mut reader)?;
+        let state_root = FromBytes::read_le(&mut reader)?;
+        let commitment = FromBytes::read_le(&mut reader)?;
+        let public_balance = FromBytes::read_le(&mut reader)?;
+        let private_balance = FromBytes::read_le(&mut reader)?;
+        let signature = FromBytes::read_le(&mut reader)?;
+        Ok(Self { block_height, block_hash, state_root, commitment, public_balance, private_balance, signature })
+    }
+}
+
+impl<N: Network> ToBytes for ReservesAttestation<N> {
+    /// Writes the reserves attestation to a buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        // Write the version.
+        1u8.write_le(&mut writer)?;
+        // Write the attestation.
+        self.block_height.write_le(&mut writer)?;
+        self.block_hash.write_le(&mut writer)?;
+        self.state_root.write_le(&mut writer)?;
+        self.commitment.write_le(&mut writer)?;
+        self.public_balance.write_le(&mut writer)?;
+        self.private_balance.write_le(&mut writer)?;
+        self.signature.write_le(&mut writer)
+    }
+}
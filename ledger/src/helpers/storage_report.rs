@@ -0,0 +1,122 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use console::{network::prelude::*, types::Field};
+
+/// An inconsistency found in the ledger storage.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StorageIssue<N: Network> {
+    /// An entry is missing, or does not match the index that refers to it.
+    /// These issues can not be repaired in place, and require the ledger to be resynced.
+    Inconsistent(String),
+    /// A transaction which is not included in any block.
+    OrphanedTransaction(N::TransactionID),
+    /// A transition which is not included in any transaction of a block.
+    OrphanedTransition(N::TransitionID),
+}
+
+impl<N: Network> StorageIssue<N> {
+    /// Returns `true` if the issue can be repaired, by removing the orphaned entry.
+    pub const fn is_repairable(&self) -> bool {
+        matches!(self, Self::OrphanedTransaction(..) | Self::OrphanedTransition(..))
+    }
+}
+
+impl<N: Network> Display for StorageIssue<N> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Inconsistent(description) => write!(f, "{description}"),
+            Self::OrphanedTransaction(transaction_id) => {
+                write!(f, "Transaction '{transaction_id}' is not included in any block")
+            }
+            Self::OrphanedTransition(transition_id) => {
+                write!(f, "Transition '{transition_id}' is not included in any block")
+            }
+        }
+    }
+}
+
+/// The outcome of a verification of the ledger storage.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StorageReport<N: Network> {
+    /// The latest block height in storage.
+    latest_height: u32,
+    /// The state root recomputed from the block hashes in storage.
+    state_root: N::StateRoot,
+    /// The checksum recomputed from the finalize storage.
+    finalize_checksum: Field<N>,
+    /// The number of transactions included in the blocks.
+    num_transactions: usize,
+    /// The number of transitions included in the blocks.
+    num_transitions: usize,
+    /// The issues which remain in storage.
+    issues: Vec<StorageIssue<N>>,
+    /// The issues which were repaired.
+    repaired: Vec<StorageIssue<N>>,
+}
+
+impl<N: Network> StorageReport<N> {
+    /// Initializes a new storage report.
+    pub(crate) fn new(
+        latest_height: u32,
+        state_root: N::StateRoot,
+        finalize_checksum: Field<N>,
+        num_transactions: usize,
+        num_transitions: usize,
+        issues: Vec<StorageIssue<N>>,
+        repaired: Vec<StorageIssue<N>>,
+    ) -> Self {
+        Self { latest_height, state_root, finalize_checksum, num_transactions, num_transitions, issues, repaired }
+    }
+
+    /// Returns `true` if no issues remain in storage.
+    pub fn is_consistent(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    /// Returns the latest block height in storage.
+    pub const fn latest_height(&self) -> u32 {
+        self.latest_height
+    }
+
+    /// Returns the state root recomputed from the block hashes in storage.
+    pub const fn state_root(&self) -> N::StateRoot {
+        self.state_root
+    }
+
+    /// Returns the checksum recomputed from the finalize storage.
+    pub const fn finalize_checksum(&self) -> Field<N> {
+        self.finalize_checksum
+    }
+
+    /// Returns the number of transactions included in the blocks.
+    pub const fn num_transactions(&self) -> usize {
+        self.num_transactions
+    }
+
+    /// Returns the number of transitions included in the blocks.
+    pub const fn num_transitions(&self) -> usize {
+        self.num_transitions
+    }
+
+    /// Returns the issues which remain in storage.
+    pub fn issues(&self) -> &[StorageIssue<N>] {
+        &self.issues
+    }
+
+    /// Returns the issues which were repaired.
+    pub fn repaired(&self) -> &[StorageIssue<N>] {
+        &self.repaired
+    }
+}
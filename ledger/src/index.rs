@@ -0,0 +1,112 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use ledger_store::StorageIndex;
+
+impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
+    /// Returns `true` if the given secondary index is enabled.
+    pub fn is_index_enabled(&self, index: StorageIndex) -> Result<bool> {
+        self.vm.transition_store().is_index_enabled(index)
+    }
+
+    /// Enables the given secondary index, and populates it with the transactions of every block in the ledger.
+    /// Once enabled, the index is updated with each new block, and remains enabled when the ledger is reloaded.
+    pub fn enable_index(&self, index: StorageIndex) -> Result<()> {
+        let timer = timer!("Ledger::enable_index");
+
+        // Acquire the write lock on the current block, so that no block is added while the index is populated.
+        let current_block = self.current_block.write();
+        // If the index is already enabled, return early.
+        if self.is_index_enabled(index)? {
+            return Ok(());
+        }
+
+        let transaction_store = self.vm.transaction_store();
+        // Remove the entries of a previous (interrupted) attempt, if any.
+        transaction_store.reset_index(index)?;
+        lap!(timer, "Reset the index");
+
+        // Populate the index with the transactions of each block, in order.
+        for height in 0..=current_block.height() {
+            let block = self.get_block(height)?;
+            for confirmed in block.transactions().iter() {
+                transaction_store.insert_index_entries(index, confirmed.transaction())?;
+            }
+        }
+        lap!(timer, "Populate the index");
+
+        // Enable the index.
+        transaction_store.transition_store().set_index_enabled(index, true)?;
+        finish!(timer, "Enable the index");
+        Ok(())
+    }
+
+    /// Disables the given secondary index, and removes all of its entries.
+    pub fn disable_index(&self, index: StorageIndex) -> Result<()> {
+        // Acquire the write lock on the current block, so that no block is added while the index is removed.
+        let _current_block = self.current_block.write();
+        self.vm.transaction_store().reset_index(index)
+    }
+
+    /// Returns up to `limit` IDs of the transitions with a public input or output of the given address,
+    /// starting from the given offset, in the order they were indexed.
+    ///
+    /// Note: This method requires the address index to be enabled.
+    pub fn find_transition_ids_by_address(
+        &self,
+        address: &Address<N>,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<N::TransitionID>> {
+        self.ensure_index_enabled(StorageIndex::Address)?;
+        self.vm.transition_store().find_transition_ids_by_address(address, offset, limit)
+    }
+
+    /// Returns up to `limit` IDs of the transitions of the given function,
+    /// starting from the given offset, in the order they were indexed.
+    ///
+    /// Note: This method requires the locator index to be enabled.
+    pub fn find_transition_ids_by_function(
+        &self,
+        program_id: &ProgramID<N>,
+        function_name: &Identifier<N>,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<N::TransitionID>> {
+        self.ensure_index_enabled(StorageIndex::Locator)?;
+        self.vm.transition_store().find_transition_ids_by_function(program_id, function_name, offset, limit)
+    }
+
+    /// Returns up to `limit` IDs of the transactions whose fee is paid publicly by the given address,
+    /// starting from the given offset, in the order they were indexed.
+    ///
+    /// Note: This method requires the fee payer index to be enabled.
+    pub fn find_transaction_ids_by_fee_payer(
+        &self,
+        fee_payer: &Address<N>,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<N::TransactionID>> {
+        self.ensure_index_enabled(StorageIndex::FeePayer)?;
+        self.vm.transaction_store().find_transaction_ids_by_fee_payer(fee_payer, offset, limit)
+    }
+
+    /// Ensures the given secondary index is enabled.
+    fn ensure_index_enabled(&self, index: StorageIndex) -> Result<()> {
+        ensure!(self.is_index_enabled(index)?, "The {index} index is not enabled");
+        Ok(())
+    }
+}
@@ -34,13 +34,22 @@ pub use ledger_test_helpers;
 mod helpers;
 pub use helpers::*;
 
+mod events;
+pub use events::*;
+
+mod secondary;
+pub use secondary::*;
+
 mod advance;
 mod check_next_block;
 mod check_transaction_basic;
 mod contains;
 mod find;
 mod get;
+mod index;
 mod iterators;
+mod replay;
+mod verify_storage;
 
 #[cfg(test)]
 mod tests;
@@ -60,6 +69,8 @@ use ledger_store::{ConsensusStorage, ConsensusStore};
 use synthesizer::{
     program::{FinalizeGlobalState, Program},
     vm::VM,
+    Authorization,
+    DisclosurePredicate,
 };
 
 use aleo_std::{
@@ -105,6 +116,14 @@ pub struct Ledger<N: Network, C: ConsensusStorage<N>> {
     current_committee: Arc<RwLock<Option<Committee<N>>>>,
     /// The current block.
     current_block: Arc<RwLock<Block<N>>>,
+    /// The record scanners that are advanced in the background with each new block.
+    record_scanners: Arc<RwLock<Vec<RecordScanner<N>>>>,
+    /// The signal that coordinates the background scans of the record scanners.
+    record_scan_signal: Arc<ScanSignal>,
+    /// The senders of the events of new blocks to the event subscribers.
+    event_senders: Arc<RwLock<Vec<EventSender<N>>>>,
+    /// The heights the ledger was rolled back to, in order, so the event subscriptions can rewind.
+    event_rollbacks: Arc<RwLock<Vec<u32>>>,
 }
 
 impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
@@ -152,46 +171,74 @@ impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
         };
         lap!(timer, "Load consensus store");
 
+        // Initialize the ledger.
+        let ledger = Self::from_store(genesis_block.clone(), store)?;
+        lap!(timer, "Initialize a new VM");
+
+        // If the block store is empty, initialize the genesis block.
+        if ledger.vm.block_store().heights().max().is_none() {
+            // Add the genesis block.
+            ledger.advance_to_next_block(&genesis_block)?;
+        }
+        lap!(timer, "Initialize genesis");
+
+        // Set the current block, committee, and epoch hash.
+        ledger.load_latest_state()?;
+
+        finish!(timer, "Initialize ledger");
+        Ok(ledger)
+    }
+
+    /// Initializes the ledger from the given consensus store, with the genesis block as its current block.
+    pub(crate) fn from_store(genesis_block: Block<N>, store: ConsensusStore<N, C>) -> Result<Self> {
         // Initialize a new VM.
         let vm = VM::from(store)?;
-        lap!(timer, "Initialize a new VM");
 
         // Retrieve the current committee.
         let current_committee = vm.finalize_store().committee_store().current_committee().ok();
 
         // Initialize the ledger.
-        let mut ledger = Self {
+        Ok(Self {
             vm,
             genesis_block: genesis_block.clone(),
             current_epoch_hash: Default::default(),
             current_committee: Arc::new(RwLock::new(current_committee)),
-            current_block: Arc::new(RwLock::new(genesis_block.clone())),
-        };
+            current_block: Arc::new(RwLock::new(genesis_block)),
+            record_scanners: Default::default(),
+            record_scan_signal: Default::default(),
+            event_senders: Default::default(),
+            event_rollbacks: Default::default(),
+        })
+    }
 
-        // If the block store is empty, initialize the genesis block.
-        if ledger.vm.block_store().heights().max().is_none() {
-            // Add the genesis block.
-            ledger.advance_to_next_block(&genesis_block)?;
-        }
-        lap!(timer, "Initialize genesis");
+    /// Sets the current block, committee, and epoch hash to the latest ones in storage,
+    /// and returns the latest block height.
+    pub(crate) fn load_latest_state(&self) -> Result<u32> {
+        self.load_latest_state_into(&mut self.current_block.write())
+    }
 
+    /// Sets the given current block, along with the current committee and epoch hash, to the latest ones
+    /// in storage, and returns the latest block height.
+    fn load_latest_state_into(&self, current_block: &mut Block<N>) -> Result<u32> {
         // Retrieve the latest height.
         let latest_height =
-            *ledger.vm.block_store().heights().max().ok_or_else(|| anyhow!("Failed to load blocks from the ledger"))?;
+            *self.vm.block_store().heights().max().ok_or_else(|| anyhow!("Failed to load blocks from the ledger"))?;
         // Fetch the latest block.
-        let block = ledger
-            .get_block(latest_height)
-            .map_err(|_| anyhow!("Failed to load block {latest_height} from the ledger"))?;
+        let block =
+            self.get_block(latest_height).map_err(|_| anyhow!("Failed to load block {latest_height} from the ledger"))?;
+        // Retrieve the latest committee (and ensure it exists).
+        let committee = self.vm.finalize_store().committee_store().current_committee()?;
+        // Retrieve the latest epoch hash.
+        let epoch_hash = self.get_epoch_hash(latest_height)?;
 
         // Set the current block.
-        ledger.current_block = Arc::new(RwLock::new(block));
-        // Set the current committee (and ensures the latest committee exists).
-        ledger.current_committee = Arc::new(RwLock::new(Some(ledger.latest_committee()?)));
+        *current_block = block;
+        // Set the current committee.
+        *self.current_committee.write() = Some(committee);
         // Set the current epoch hash.
-        ledger.current_epoch_hash = Arc::new(RwLock::new(Some(ledger.get_epoch_hash(latest_height)?)));
+        *self.current_epoch_hash.write() = Some(epoch_hash);
 
-        finish!(timer, "Initialize ledger");
-        Ok(ledger)
+        Ok(latest_height)
     }
 
     /// Returns the VM.
@@ -317,6 +364,203 @@ impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
             .collect::<IndexMap<_, _>>())
     }
 
+    /// Selects the unspent `credits.aleo` records of the given view key that cover the given amount,
+    /// and the record that covers the given private fee (if any), using the given strategy.
+    pub fn select_records<R: Rng + CryptoRng>(
+        &self,
+        view_key: &ViewKey<N>,
+        amount_in_microcredits: u64,
+        fee_in_microcredits: Option<u64>,
+        strategy: RecordSelectionStrategy,
+        rng: &mut R,
+    ) -> Result<RecordPlan<N>> {
+        let records = self.find_unspent_credits_records(view_key)?;
+        RecordPlan::new(&records, amount_in_microcredits, fee_in_microcredits, strategy, rng)
+    }
+
+    /// Authorizes the next round of `credits.aleo/join` calls, which combine the selected records of the plan in pairs,
+    /// and returns each join authorization with the authorization of its fee.
+    ///
+    /// Note: The fee of each join is paid from the public balance, so that the selected records and the fee record
+    /// of the plan are left for the transfer. The base fee of a join is only known once it is proven, so as with
+    /// `prepare_offline_execution`, the caller provides a base fee that covers the execution cost of a join.
+    pub fn authorize_joins<R: Rng + CryptoRng>(
+        &self,
+        private_key: &PrivateKey<N>,
+        plan: &RecordPlan<N>,
+        base_fee_in_microcredits: u64,
+        priority_fee_in_microcredits: u64,
+        rng: &mut R,
+    ) -> Result<Vec<(Authorization<N>, Authorization<N>)>> {
+        // Ensure the public balance covers the fees of every join in the round.
+        let num_joins = plan.join_pairs().count() as u64;
+        if num_joins > 0 {
+            let fee_in_microcredits = base_fee_in_microcredits
+                .checked_add(priority_fee_in_microcredits)
+                .and_then(|fee| fee.checked_mul(num_joins))
+                .ok_or_else(|| anyhow!("The fees of the joins overflowed"))?;
+            let public_balance = self.get_public_balance(&Address::try_from(private_key)?)?;
+            ensure!(
+                public_balance >= fee_in_microcredits,
+                "The public balance ({public_balance} microcredits) does not cover the fees of {num_joins} join(s) \
+                 ({fee_in_microcredits} microcredits)"
+            );
+        }
+
+        plan.join_pairs()
+            .map(|(first, second)| {
+                // Authorize the join.
+                let inputs = [Value::Record(first.clone()), Value::Record(second.clone())];
+                let authorization = self.vm.authorize(private_key, "credits.aleo", "join", inputs, rng)?;
+                // Authorize the fee of the join.
+                let execution_id = authorization.to_execution_id()?;
+                let fee_authorization = self.vm.authorize_fee_public(
+                    private_key,
+                    base_fee_in_microcredits,
+                    priority_fee_in_microcredits,
+                    execution_id,
+                    rng,
+                )?;
+                Ok((authorization, fee_authorization))
+            })
+            .collect()
+    }
+
+    /// Returns an attestation of the reserves held by the accounts of the given private keys, at the latest block,
+    /// signed by the given attestor, and the opening of the attestation.
+    ///
+    /// The attestation only publishes the total public and private balances, and a commitment to the reserves
+    /// of each account. The opening reveals each account, and should only be shared with an auditor.
+    /// Each unspent `credits.aleo` record of an account is disclosed with its exact balance, which requires a proof.
+    pub fn attest_reserves<R: Rng + CryptoRng>(
+        &self,
+        attestor: &PrivateKey<N>,
+        private_keys: &[PrivateKey<N>],
+        rng: &mut R,
+    ) -> Result<(ReservesAttestation<N>, ReservesOpening<N>)> {
+        // Retrieve the latest block and state.
+        let block_height = self.latest_height();
+        let block_hash = self.latest_hash();
+        let state_root = self.latest_state_root();
+        let statement = ReservesAttestation::statement(block_height, block_hash, state_root);
+
+        let mut accounts = Vec::with_capacity(private_keys.len());
+        for private_key in private_keys {
+            // Retrieve the public balance.
+            let public_balance = self.get_public_balance(&Address::try_from(private_key)?)?;
+            // Disclose the exact balance of each unspent record, with its serial number for the auditor.
+            let records = self
+                .find_unspent_credits_records(&ViewKey::try_from(private_key)?)?
+                .values()
+                .map(|record| {
+                    let microcredits = match record.find(&[Identifier::<N>::from_str("microcredits")?])? {
+                        Entry::Private(Plaintext::Literal(Literal::U64(amount), _)) => *amount,
+                        _ => bail!("Invalid microcredits entry in a 'credits.aleo' record"),
+                    };
+                    let predicate = DisclosurePredicate::Equal(microcredits);
+                    self.vm.prove_record_disclosure(
+                        private_key,
+                        "credits.aleo",
+                        "credits",
+                        record,
+                        "microcredits",
+                        predicate,
+                        true,
+                        rng,
+                    )
+                })
+                .collect::<Result<Vec<_>>>()?;
+            accounts.push(AccountReserves::new(private_key, &statement, public_balance, records, rng)?);
+        }
+
+        // Ensure the ledger did not advance while the records were disclosed.
+        ensure!(self.latest_hash() == block_hash, "The ledger advanced while the reserves were attested");
+        let opening = ReservesOpening::new(accounts, rng);
+        let attestation = ReservesAttestation::new(attestor, block_height, block_hash, state_root, &opening, rng)?;
+        Ok((attestation, opening))
+    }
+
+    /// Verifies the given reserves attestation against its opening, and returns the total reserves in microcredits.
+    ///
+    /// The public balances are verified against the `credits.aleo/account` mapping as of the attested block height,
+    /// and the disclosed records must be unspent as of the attested block height.
+    /// Note: This method requires the mapping history of every block after the attested block height.
+    pub fn verify_reserves_attestation(
+        &self,
+        attestation: &ReservesAttestation<N>,
+        opening: &ReservesOpening<N>,
+    ) -> Result<u64> {
+        // Ensure the opening is consistent with the attestation.
+        attestation.check_opening(opening)?;
+
+        // Ensure the attested block and state are in the ledger.
+        let block_height = attestation.block_height();
+        ensure!(
+            self.get_hash(block_height)? == attestation.block_hash(),
+            "The attested block hash does not match block {block_height}"
+        );
+        ensure!(
+            self.get_state_root(block_height)? == Some(attestation.state_root()),
+            "The attested state root does not match block {block_height}"
+        );
+
+        for account in opening.accounts() {
+            // Ensure the public balance matches the `credits.aleo/account` mapping at the attested block height.
+            ensure!(
+                self.get_public_balance_at_height(account.address(), block_height)? == account.public_balance(),
+                "The public balance of '{}' does not match the ledger at block {block_height}",
+                account.address()
+            );
+            for record in account.records() {
+                // Ensure the record is unspent as of the attested block height.
+                if let Some(serial_number) = record.serial_number() {
+                    if let Some(spent_height) = self.find_spent_height(serial_number)? {
+                        ensure!(spent_height > block_height, "A disclosed record is spent at block {spent_height}");
+                    }
+                }
+                // Ensure the record disclosure is valid.
+                self.vm.verify_record_disclosure_proof(record)?;
+            }
+        }
+        attestation.total_balance()
+    }
+
+    /// Returns the public balance of the given address in the `credits.aleo/account` mapping, in microcredits.
+    fn get_public_balance(&self, address: &Address<N>) -> Result<u64> {
+        self.get_public_balance_at_height(address, self.latest_height())
+    }
+
+    /// Returns the public balance of the given address in the `credits.aleo/account` mapping, in microcredits,
+    /// as of the given block height.
+    fn get_public_balance_at_height(&self, address: &Address<N>, height: u32) -> Result<u64> {
+        let key = Plaintext::from(Literal::Address(*address));
+        match self.vm.finalize_store().get_value_at_height(
+            ProgramID::from_str("credits.aleo")?,
+            Identifier::from_str("account")?,
+            &key,
+            height,
+        )? {
+            Some(Value::Plaintext(Plaintext::Literal(Literal::U64(amount), _))) => Ok(*amount),
+            None => Ok(0),
+            _ => bail!("Invalid public balance for '{address}'"),
+        }
+    }
+
+    /// Returns the height of the block that spends the given serial number, if it is spent.
+    fn find_spent_height(&self, serial_number: &Field<N>) -> Result<Option<u32>> {
+        if !self.contains_serial_number(serial_number)? {
+            return Ok(None);
+        }
+        let transition_id = self.find_transition_id(serial_number)?;
+        let Some(transaction_id) = self.find_transaction_id_from_transition_id(&transition_id)? else {
+            bail!("Missing the transaction of transition '{transition_id}'")
+        };
+        let Some(block_hash) = self.find_block_hash(&transaction_id)? else {
+            bail!("Missing the block of transaction '{transaction_id}'")
+        };
+        self.get_height(&block_hash).map(Some)
+    }
+
     /// Creates a deploy transaction.
     ///
     /// The `priority_fee_in_microcredits` is an additional fee **on top** of the deployment fee.
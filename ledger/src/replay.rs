@@ -0,0 +1,148 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
+    /// Re-executes the given block on top of the latest block, and compares the outcome with the block.
+    ///
+    /// The ratifications, solutions, and (unconfirmed) transactions of the block are speculated over,
+    /// as when the block was proposed. If the replayed ratifications, confirmed transactions, and
+    /// finalize root match the block, the block is added to the ledger and `None` is returned.
+    /// Otherwise, the ledger is left unchanged, and the divergence is returned.
+    ///
+    /// Note that the transactions aborted by the block are not stored, so they are not replayed,
+    /// and any transaction aborted by the replay is reported as a divergence.
+    pub fn replay_block<R: Rng + CryptoRng>(
+        &self,
+        block: &Block<N>,
+        rng: &mut R,
+    ) -> Result<Option<ReplayDivergence<N>>> {
+        let timer = timer!("Ledger::replay_block");
+
+        // Retrieve the latest block as the previous block.
+        let previous_block = self.latest_block();
+        // Ensure the block extends the latest block.
+        ensure!(
+            block.height() == previous_block.height().saturating_add(1),
+            "Cannot replay block {}, as the latest block is {}",
+            block.height(),
+            previous_block.height()
+        );
+        ensure!(
+            block.previous_hash() == previous_block.hash(),
+            "Cannot replay block {}, as its previous hash does not match the latest block",
+            block.height()
+        );
+
+        // Compute the coinbase reward, as when the block was proposed.
+        let combined_proof_target = match block.solutions().deref() {
+            Some(solutions) => self.puzzle().get_combined_proof_target(solutions)?,
+            None => 0u128,
+        };
+        let coinbase_reward = coinbase_reward(
+            block.height(),
+            N::STARTING_SUPPLY,
+            N::ANCHOR_HEIGHT,
+            N::BLOCK_TIME,
+            combined_proof_target,
+            u64::try_from(previous_block.cumulative_proof_target())?,
+            previous_block.coinbase_target(),
+        )?;
+
+        // Construct the finalize state.
+        let state = FinalizeGlobalState::new::<N>(
+            block.round(),
+            block.height(),
+            block.cumulative_weight(),
+            block.cumulative_proof_target(),
+            block.previous_hash(),
+        )?;
+        // Reconstruct the candidate ratifications, without the rewards (which are recomputed by the speculation).
+        let candidate_ratifications = block
+            .ratifications()
+            .iter()
+            .filter(|ratify| !matches!(ratify, Ratify::BlockReward(..) | Ratify::PuzzleReward(..)))
+            .cloned()
+            .collect();
+        // Reconstruct the candidate transactions.
+        let candidate_transactions = block
+            .transactions()
+            .iter()
+            .map(|transaction| transaction.to_unconfirmed_transaction())
+            .collect::<Result<Vec<_>>>()?;
+        lap!(timer, "Prepare the candidates");
+
+        // Speculate over the ratifications, solutions, and transactions.
+        let (ratifications, transactions, aborted_transaction_ids, ratified_finalize_operations) = self.vm.speculate(
+            state,
+            Some(coinbase_reward),
+            candidate_ratifications,
+            block.solutions(),
+            candidate_transactions.iter(),
+            rng,
+        )?;
+        lap!(timer, "Speculate over the block");
+
+        // Compare the outcome of the replay with the block.
+        let mut mismatches = Vec::new();
+        if ratifications != *block.ratifications() {
+            mismatches.push(format!(
+                "The replayed ratifications {} do not match the recorded ratifications {}",
+                ratifications,
+                block.ratifications()
+            ));
+        }
+        if !aborted_transaction_ids.is_empty() {
+            let aborted = aborted_transaction_ids.iter().map(|id| format!("'{id}'")).collect::<Vec<_>>().join(", ");
+            mismatches.push(format!("The replay aborted the confirmed transactions {aborted}"));
+        }
+        let finalize_root = transactions.to_finalize_root(ratified_finalize_operations)?;
+        if finalize_root != block.header().finalize_root() {
+            mismatches.push(format!(
+                "The replayed finalize root '{finalize_root}' does not match the recorded finalize root '{}'",
+                block.header().finalize_root()
+            ));
+        }
+
+        // Compare the confirmed transactions, by their unconfirmed transaction ID.
+        let mut replayed = transactions
+            .iter()
+            .map(|transaction| Ok((transaction.to_unconfirmed_transaction_id()?, transaction)))
+            .collect::<Result<IndexMap<_, _>>>()?;
+        let mut diffs = Vec::new();
+        for recorded in block.transactions().iter() {
+            let transaction_id = recorded.to_unconfirmed_transaction_id()?;
+            let replayed = replayed.shift_remove(&transaction_id);
+            if replayed != Some(recorded) {
+                diffs.push(TransactionDiff::new(transaction_id, Some(recorded.clone()), replayed.cloned()));
+            }
+        }
+        for (transaction_id, replayed) in replayed {
+            diffs.push(TransactionDiff::new(transaction_id, None, Some(replayed.clone())));
+        }
+        lap!(timer, "Compare the outcome");
+
+        // If the replay diverges, return the divergence.
+        if !mismatches.is_empty() || !diffs.is_empty() {
+            finish!(timer, "Found a divergence");
+            return Ok(Some(ReplayDivergence::new(block.height(), block.hash(), mismatches, diffs)));
+        }
+
+        // Otherwise, add the block to the ledger.
+        self.advance_to_next_block(block)?;
+        finish!(timer, "Added the block");
+        Ok(None)
+    }
+}
@@ -0,0 +1,479 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use ledger_store::StorageIndex;
+
+use indexmap::IndexSet;
+use std::{
+    path::Path,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+/// Implements the given read methods of the ledger on the secondary ledger, by delegating them to the inner ledger.
+macro_rules! delegate_reads {
+    ($(
+        $(#[doc = $doc:expr])*
+        fn $name:ident$(<$lt:lifetime>)?(&$($self_lt:lifetime)? self $(, $arg:ident: $ty:ty)* $(,)?) -> $ret:ty;
+    )*) => {
+        $(
+            $(#[doc = $doc])*
+            pub fn $name$(<$lt>)?(&$($self_lt)? self $(, $arg: $ty)*) -> $ret {
+                self.ledger.$name($($arg),*)
+            }
+        )*
+    };
+}
+
+/// A read-only instance of the ledger, attached to the storage of a ledger which is written by another process.
+///
+/// The secondary instance does not take the lock of the storage, so indexers and RPC processes can read the ledger
+/// of a running node. It observes the blocks of the primary instance when it catches up with it, either on demand
+/// with `catch_up`, or periodically with `spawn_catch_up`. The methods which write to the ledger are not exposed.
+#[derive(Clone)]
+pub struct SecondaryLedger<N: Network, C: ConsensusStorage<N>> {
+    /// The ledger, whose write methods are not exposed.
+    ledger: Ledger<N, C>,
+    /// The secondary instance of the consensus store.
+    store: ConsensusStore<N, C>,
+}
+
+impl<N: Network, C: ConsensusStorage<N>> SecondaryLedger<N, C> {
+    /// Loads a secondary instance of the ledger for the given storage mode, which keeps its own state in the
+    /// `secondary_dir` directory. The ledger must have been initialized by its primary instance.
+    ///
+    /// Note: The storage can only be opened once per process, so this must be called before the ledger is loaded.
+    pub fn load<P: AsRef<Path>>(genesis_block: Block<N>, storage_mode: StorageMode, secondary_dir: P) -> Result<Self> {
+        let timer = timer!("SecondaryLedger::load");
+
+        info!("Loading a secondary instance of the ledger from storage...");
+        // Initialize the consensus store.
+        let store = match ConsensusStore::<N, C>::open_secondary(storage_mode, secondary_dir) {
+            Ok(store) => store,
+            Err(e) => bail!("Failed to load a secondary instance of the ledger\n\n{e}\n"),
+        };
+        lap!(timer, "Load consensus store");
+
+        // Initialize the ledger.
+        let genesis_hash = genesis_block.hash();
+        let ledger = Ledger::from_store(genesis_block, store.clone())?;
+        lap!(timer, "Initialize a new VM");
+
+        // Ensure the ledger contains the correct genesis block.
+        // Note: Unlike the primary instance, the secondary instance can not write the genesis block.
+        if !ledger.contains_block_hash(&genesis_hash)? {
+            bail!("Incorrect genesis block, or the ledger has not been initialized by its primary instance")
+        }
+
+        // Set the current block, committee, and epoch hash.
+        ledger.load_latest_state()?;
+
+        finish!(timer, "Initialize ledger");
+        Ok(Self { ledger, store })
+    }
+
+    /// Catches up with the blocks written by the primary instance, and returns the latest block height.
+    pub fn catch_up(&self) -> Result<u32> {
+        let previous_height = self.ledger.latest_height();
+        // Catch up with the primary instance, and update the block tree.
+        self.store.catch_up_with_primary()?;
+        // Update the current block, committee, and epoch hash.
+        let latest_height = self.ledger.load_latest_state()?;
+
+        // Send the events of the new blocks to the event subscribers.
+        if !self.ledger.event_senders.read().is_empty() {
+            for height in previous_height.saturating_add(1)..=latest_height {
+                self.ledger.notify_event_subscribers(&self.ledger.get_block(height)?);
+            }
+        }
+
+        // Scan the new blocks for the records of each subscribed record scanner, in the background.
+        self.ledger.scan_records_in_background();
+
+        Ok(latest_height)
+    }
+
+    /// Spawns a thread which catches up with the primary instance at the given interval.
+    /// The thread is stopped when the returned handle is dropped.
+    pub fn spawn_catch_up(&self, interval: Duration) -> CatchUpHandle {
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let ledger = self.clone();
+        let stop_ = stop.clone();
+        let thread = std::thread::spawn(move || {
+            while !stop_.load(Ordering::SeqCst) {
+                if let Err(e) = ledger.catch_up() {
+                    warn!("Failed to catch up with the primary instance of the ledger: {e}");
+                }
+                // Note: The thread is unparked when the handle is dropped.
+                std::thread::park_timeout(interval);
+            }
+        });
+
+        CatchUpHandle { stop, thread: Some(thread) }
+    }
+
+    /// Subscribes the given record scanner to new blocks, after scanning the blocks it has not scanned yet.
+    pub fn subscribe_record_scanner(&self, scanner: RecordScanner<N>) -> Result<()> {
+        self.ledger.subscribe_record_scanner(scanner)
+    }
+
+    /// Unsubscribes the record scanner for the given address, returning it if it was subscribed.
+    pub fn unsubscribe_record_scanner(&self, address: &Address<N>) -> Option<RecordScanner<N>> {
+        self.ledger.unsubscribe_record_scanner(address)
+    }
+
+    /// Subscribes to the events of the ledger, starting from the block at the given height.
+    /// The events of new blocks are emitted when the secondary instance catches up with the primary instance.
+    pub fn subscribe_events(&self, start_height: u32, capacity: usize) -> Result<EventSubscription<N, C>> {
+        self.ledger.subscribe_events(start_height, capacity)
+    }
+
+    /// Returns the events of the block at the given height.
+    pub fn get_events(&self, height: u32) -> Result<Vec<LedgerEvent<N>>> {
+        self.ledger.get_events(height)
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>> SecondaryLedger<N, C> {
+    /// Returns the mapping names for the given `program ID`.
+    pub fn get_mapping_names(&self, program_id: &ProgramID<N>) -> Result<Option<IndexSet<Identifier<N>>>> {
+        self.ledger.vm.finalize_store().get_mapping_names_confirmed(program_id)
+    }
+
+    /// Returns the mapping entries for the given `program ID` and `mapping name`.
+    pub fn get_mapping(
+        &self,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+    ) -> Result<Vec<(Plaintext<N>, Value<N>)>> {
+        self.ledger.vm.finalize_store().get_mapping_confirmed(program_id, mapping_name)
+    }
+
+    /// Returns the mapping value for the given `program ID`, `mapping name`, and `key`.
+    pub fn get_mapping_value(
+        &self,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        key: &Plaintext<N>,
+    ) -> Result<Option<Value<N>>> {
+        self.ledger.vm.finalize_store().get_value_confirmed(program_id, mapping_name, key)
+    }
+
+    /// Returns the mapping entries for the given `program ID` and `mapping name`, as of the given block height.
+    ///
+    /// Note: This method requires the mapping history of every block after the given height.
+    pub fn get_mapping_at_height(
+        &self,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        height: u32,
+    ) -> Result<Option<Vec<(Plaintext<N>, Value<N>)>>> {
+        self.ledger.vm.finalize_store().get_mapping_at_height(program_id, mapping_name, height)
+    }
+
+    /// Returns the mapping value for the given `program ID`, `mapping name`, and `key`, as of the given block height.
+    ///
+    /// Note: This method requires the mapping history of every block after the given height.
+    pub fn get_mapping_value_at_height(
+        &self,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        key: &Plaintext<N>,
+        height: u32,
+    ) -> Result<Option<Value<N>>> {
+        self.ledger.vm.finalize_store().get_value_at_height(program_id, mapping_name, key, height)
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>> SecondaryLedger<N, C> {
+    delegate_reads! {
+        /// Returns the puzzle.
+        fn puzzle(&self) -> &Puzzle<N>;
+        /// Returns the latest committee.
+        fn latest_committee(&self) -> Result<Committee<N>>;
+        /// Returns the latest state root.
+        fn latest_state_root(&self) -> N::StateRoot;
+        /// Returns the latest epoch number.
+        fn latest_epoch_number(&self) -> u32;
+        /// Returns the latest epoch hash.
+        fn latest_epoch_hash(&self) -> Result<N::BlockHash>;
+        /// Returns the latest block.
+        fn latest_block(&self) -> Block<N>;
+        /// Returns the latest round number.
+        fn latest_round(&self) -> u64;
+        /// Returns the latest block height.
+        fn latest_height(&self) -> u32;
+        /// Returns the latest block hash.
+        fn latest_hash(&self) -> N::BlockHash;
+        /// Returns the latest block header.
+        fn latest_header(&self) -> Header<N>;
+        /// Returns the latest block cumulative weight.
+        fn latest_cumulative_weight(&self) -> u128;
+        /// Returns the latest block cumulative proof target.
+        fn latest_cumulative_proof_target(&self) -> u128;
+        /// Returns the latest block solutions root.
+        fn latest_solutions_root(&self) -> Field<N>;
+        /// Returns the latest block coinbase target.
+        fn latest_coinbase_target(&self) -> u64;
+        /// Returns the latest block proof target.
+        fn latest_proof_target(&self) -> u64;
+        /// Returns the last coinbase target.
+        fn last_coinbase_target(&self) -> u64;
+        /// Returns the last coinbase timestamp.
+        fn last_coinbase_timestamp(&self) -> i64;
+        /// Returns the latest block timestamp.
+        fn latest_timestamp(&self) -> i64;
+        /// Returns the latest block transactions.
+        fn latest_transactions(&self) -> Transactions<N>;
+        /// Returns the unspent `credits.aleo` records.
+        fn find_unspent_credits_records(&self, view_key: &ViewKey<N>) -> Result<RecordMap<N>>;
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>> SecondaryLedger<N, C> {
+    delegate_reads! {
+        /// Returns the committee for the given `block height`.
+        fn get_committee(&self, block_height: u32) -> Result<Option<Committee<N>>>;
+        /// Returns the committee for the given `round`.
+        fn get_committee_for_round(&self, round: u64) -> Result<Option<Committee<N>>>;
+        /// Returns the state root that contains the given `block height`.
+        fn get_state_root(&self, block_height: u32) -> Result<Option<N::StateRoot>>;
+        /// Returns a state path for the given commitment.
+        fn get_state_path_for_commitment(&self, commitment: &Field<N>) -> Result<StatePath<N>>;
+        /// Returns the epoch hash for the given block height.
+        fn get_epoch_hash(&self, block_height: u32) -> Result<N::BlockHash>;
+        /// Returns the block for the given block height.
+        fn get_block(&self, height: u32) -> Result<Block<N>>;
+        /// Returns the blocks in the given block range.
+        /// The range is inclusive of the start and exclusive of the end.
+        fn get_blocks(&self, heights: Range<u32>) -> Result<Vec<Block<N>>>;
+        /// Returns the block for the given block hash.
+        fn get_block_by_hash(&self, block_hash: &N::BlockHash) -> Result<Block<N>>;
+        /// Returns the block height for the given block hash.
+        fn get_height(&self, block_hash: &N::BlockHash) -> Result<u32>;
+        /// Returns the block hash for the given block height.
+        fn get_hash(&self, height: u32) -> Result<N::BlockHash>;
+        /// Returns the previous block hash for the given block height.
+        fn get_previous_hash(&self, height: u32) -> Result<N::BlockHash>;
+        /// Returns the block header for the given block height.
+        fn get_header(&self, height: u32) -> Result<Header<N>>;
+        /// Returns the block transactions for the given block height.
+        fn get_transactions(&self, height: u32) -> Result<Transactions<N>>;
+        /// Returns the aborted transaction IDs for the given block height.
+        fn get_aborted_transaction_ids(&self, height: u32) -> Result<Vec<N::TransactionID>>;
+        /// Returns the transaction for the given transaction ID.
+        fn get_transaction(&self, transaction_id: N::TransactionID) -> Result<Transaction<N>>;
+        /// Returns the confirmed transaction for the given transaction ID.
+        fn get_confirmed_transaction(&self, transaction_id: N::TransactionID) -> Result<ConfirmedTransaction<N>>;
+        /// Returns the unconfirmed transaction for the given `transaction ID`.
+        fn get_unconfirmed_transaction(&self, transaction_id: &N::TransactionID) -> Result<Transaction<N>>;
+        /// Returns the program for the given program ID.
+        fn get_program(&self, program_id: ProgramID<N>) -> Result<Program<N>>;
+        /// Returns the block solutions for the given block height.
+        fn get_solutions(&self, height: u32) -> Result<Solutions<N>>;
+        /// Returns the solution for the given solution ID.
+        fn get_solution(&self, solution_id: &SolutionID<N>) -> Result<Solution<N>>;
+        /// Returns the block authority for the given block height.
+        fn get_authority(&self, height: u32) -> Result<Authority<N>>;
+        /// Returns the batch certificate for the given `certificate ID`.
+        fn get_batch_certificate(&self, certificate_id: &Field<N>) -> Result<Option<BatchCertificate<N>>>;
+        /// Returns the delegators for the given validator.
+        fn get_delegators_for_validator(&self, validator: &Address<N>) -> Result<Vec<Address<N>>>;
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>> SecondaryLedger<N, C> {
+    delegate_reads! {
+        /// Returns the block height that contains the given `state root`.
+        fn find_block_height_from_state_root(&self, state_root: N::StateRoot) -> Result<Option<u32>>;
+        /// Returns the block hash that contains the given `transaction ID`.
+        fn find_block_hash(&self, transaction_id: &N::TransactionID) -> Result<Option<N::BlockHash>>;
+        /// Returns the block height that contains the given `solution ID`.
+        fn find_block_height_from_solution_id(&self, solution_id: &SolutionID<N>) -> Result<Option<u32>>;
+        /// Returns the transaction ID that contains the given `program ID`.
+        fn find_transaction_id_from_program_id(&self, program_id: &ProgramID<N>) -> Result<Option<N::TransactionID>>;
+        /// Returns the transaction ID that contains the given `transition ID`.
+        fn find_transaction_id_from_transition_id(
+            &self,
+            transition_id: &N::TransitionID,
+        ) -> Result<Option<N::TransactionID>>;
+        /// Returns the transition ID that contains the given `input ID` or `output ID`.
+        fn find_transition_id(&self, id: &Field<N>) -> Result<N::TransitionID>;
+        /// Returns the record ciphertexts that belong to the given view key.
+        fn find_record_ciphertexts<'a>(
+            &'a self,
+            view_key: &'a ViewKey<N>,
+            filter: RecordsFilter<N>,
+        ) -> Result<impl '_ + Iterator<Item = (Field<N>, Cow<'_, Record<N, Ciphertext<N>>>)>>;
+        /// Returns the record ciphertexts found by the given record scanner.
+        ///
+        /// Unlike `find_record_ciphertexts`, this only looks up the records in the owned commitment index
+        /// of the scanner, instead of checking the ownership of every record in the ledger.
+        fn find_scanned_record_ciphertexts<'a>(
+            &'a self,
+            scanner: &RecordScanner<N>,
+            view_key: &ViewKey<N>,
+            filter: RecordsFilter<N>,
+        ) -> Result<impl 'a + Iterator<Item = (Field<N>, Record<N, Ciphertext<N>>)>>;
+        /// Returns the history of the transitions that the account of the given view key took part in,
+        /// within the given range of block heights, decoded with the view key.
+        ///
+        /// Note: A spent record is only decoded if the record was created within the range,
+        /// so the range should start from the genesis block to decode every spent record.
+        fn find_transition_history(
+            &self,
+            view_key: &ViewKey<N>,
+            heights: Range<u32>,
+        ) -> Result<Vec<TransitionHistory<N>>>;
+        /// Returns the records that belong to the given view key.
+        fn find_records<'a>(
+            &'a self,
+            view_key: &'a ViewKey<N>,
+            filter: RecordsFilter<N>,
+        ) -> Result<impl '_ + Iterator<Item = (Field<N>, Record<N, Plaintext<N>>)>>;
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>> SecondaryLedger<N, C> {
+    delegate_reads! {
+        /// Returns `true` if the given secondary index is enabled.
+        fn is_index_enabled(&self, index: StorageIndex) -> Result<bool>;
+        /// Returns up to `limit` IDs of the transitions with a public input or output of the given address,
+        /// starting from the given offset, in the order they were indexed.
+        fn find_transition_ids_by_address(
+            &self,
+            address: &Address<N>,
+            offset: u32,
+            limit: u32,
+        ) -> Result<Vec<N::TransitionID>>;
+        /// Returns up to `limit` IDs of the transitions of the given function,
+        /// starting from the given offset, in the order they were indexed.
+        fn find_transition_ids_by_function(
+            &self,
+            program_id: &ProgramID<N>,
+            function_name: &Identifier<N>,
+            offset: u32,
+            limit: u32,
+        ) -> Result<Vec<N::TransitionID>>;
+        /// Returns up to `limit` IDs of the transactions whose fee is paid publicly by the given address,
+        /// starting from the given offset, in the order they were indexed.
+        fn find_transaction_ids_by_fee_payer(
+            &self,
+            fee_payer: &Address<N>,
+            offset: u32,
+            limit: u32,
+        ) -> Result<Vec<N::TransactionID>>;
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>> SecondaryLedger<N, C> {
+    delegate_reads! {
+        /// Returns `true` if the given state root exists.
+        fn contains_state_root(&self, state_root: &N::StateRoot) -> Result<bool>;
+        /// Returns `true` if the given block height exists.
+        fn contains_block_height(&self, height: u32) -> Result<bool>;
+        /// Returns `true` if the given block hash exists.
+        fn contains_block_hash(&self, block_hash: &N::BlockHash) -> Result<bool>;
+        /// Returns `true` if the given batch certificate ID exists.
+        fn contains_certificate(&self, certificate_id: &Field<N>) -> Result<bool>;
+        /// Returns `true` if the given program ID exists.
+        fn contains_program_id(&self, program_id: &ProgramID<N>) -> Result<bool>;
+        /// Returns `true` if the transmission exists in the ledger.
+        fn contains_transmission(&self, transmission_id: &TransmissionID<N>) -> Result<bool>;
+        /// Returns `true` if the given solution ID exists.
+        fn contains_solution_id(&self, solution_id: &SolutionID<N>) -> Result<bool>;
+        /// Returns `true` if the given transaction ID exists.
+        fn contains_transaction_id(&self, transaction_id: &N::TransactionID) -> Result<bool>;
+        /// Returns `true` if the given transition ID exists.
+        fn contains_transition_id(&self, transition_id: &N::TransitionID) -> Result<bool>;
+        /// Returns `true` if the given input ID exists.
+        fn contains_input_id(&self, input_id: &Field<N>) -> Result<bool>;
+        /// Returns `true` if the given serial number exists.
+        fn contains_serial_number(&self, serial_number: &Field<N>) -> Result<bool>;
+        /// Returns `true` if the given tag exists.
+        fn contains_tag(&self, tag: &Field<N>) -> Result<bool>;
+        /// Returns `true` if the given output ID exists.
+        fn contains_output_id(&self, output_id: &Field<N>) -> Result<bool>;
+        /// Returns `true` if the given commitment exists.
+        fn contains_commitment(&self, commitment: &Field<N>) -> Result<bool>;
+        /// Returns `true` if the given checksum exists.
+        fn contains_checksum(&self, checksum: &Field<N>) -> bool;
+        /// Returns `true` if the given nonce exists.
+        fn contains_nonce(&self, nonce: &Group<N>) -> Result<bool>;
+        /// Returns `true` if the given transition public key exists.
+        fn contains_tpk(&self, tpk: &Group<N>) -> Result<bool>;
+        /// Returns `true` if the given transition commitment exists.
+        fn contains_tcm(&self, tcm: &Field<N>) -> Result<bool>;
+    }
+}
+
+impl<N: Network, C: ConsensusStorage<N>> SecondaryLedger<N, C> {
+    delegate_reads! {
+        /// Returns an iterator over the state roots, for all blocks in `self`.
+        fn state_roots(&self) -> impl '_ + Iterator<Item = Cow<'_, N::StateRoot>>;
+        /// Returns an iterator over the solution IDs, for all blocks in `self`.
+        fn solution_ids(&self) -> impl '_ + Iterator<Item = Cow<'_, SolutionID<N>>>;
+        /// Returns an iterator over the program IDs, for all transactions in `self`.
+        fn program_ids(&self) -> impl '_ + Iterator<Item = Cow<'_, ProgramID<N>>>;
+        /// Returns an iterator over the programs, for all transactions in `self`.
+        fn programs(&self) -> impl '_ + Iterator<Item = Cow<'_, Program<N>>>;
+        /// Returns an iterator over the transaction IDs, for all transactions in `self`.
+        fn transaction_ids(&self) -> impl '_ + Iterator<Item = Cow<'_, N::TransactionID>>;
+        /// Returns an iterator over the transition IDs, for all transitions.
+        fn transition_ids(&self) -> impl '_ + Iterator<Item = Cow<'_, N::TransitionID>>;
+        /// Returns an iterator over the input IDs, for all transition inputs.
+        fn input_ids(&self) -> impl '_ + Iterator<Item = Cow<'_, Field<N>>>;
+        /// Returns an iterator over the serial numbers, for all transition inputs that are records.
+        fn serial_numbers(&self) -> impl '_ + Iterator<Item = Cow<'_, Field<N>>>;
+        /// Returns an iterator over the tags, for all transition inputs that are records.
+        fn tags(&self) -> impl '_ + Iterator<Item = Cow<'_, Field<N>>>;
+        /// Returns an iterator over the output IDs, for all transition outputs that are records.
+        fn output_ids(&self) -> impl '_ + Iterator<Item = Cow<'_, Field<N>>>;
+        /// Returns an iterator over the commitments, for all transition outputs that are records.
+        fn commitments(&self) -> impl '_ + Iterator<Item = Cow<'_, Field<N>>>;
+        /// Returns an iterator over the nonces, for all transition outputs that are records.
+        fn nonces(&self) -> impl '_ + Iterator<Item = Cow<'_, Group<N>>>;
+        /// Returns an iterator over the `(commitment, record)` pairs, for all transition outputs that are records.
+        fn records(&self) -> impl '_ + Iterator<Item = (Cow<'_, Field<N>>, Cow<'_, Record<N, Ciphertext<N>>>)>;
+        /// Returns an iterator over the transition public keys, for all transactions.
+        fn transition_public_keys(&self) -> impl '_ + Iterator<Item = Cow<'_, Group<N>>>;
+    }
+}
+
+/// A handle to the thread which periodically catches up a secondary ledger with its primary instance.
+/// The thread is stopped when the handle is dropped.
+pub struct CatchUpHandle {
+    /// The flag indicating whether the thread must stop.
+    stop: Arc<AtomicBool>,
+    /// The handle of the thread.
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Drop for CatchUpHandle {
+    fn drop(&mut self) {
+        // Stop the thread, and wait for it to finish.
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            thread.thread().unpark();
+            let _ = thread.join();
+        }
+    }
+}
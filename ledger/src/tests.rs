@@ -15,20 +15,31 @@
 use crate::{
     advance::split_candidate_solutions,
     test_helpers::{CurrentLedger, CurrentNetwork},
+    AccountReserves,
     Ledger,
+    LedgerEvent,
+    RecordScanner,
+    RecordSelectionStrategy,
     RecordsFilter,
+    ReservesAttestation,
+    ReservesOpening,
+    StorageIssue,
 };
 use aleo_std::StorageMode;
 use console::{
-    account::{Address, PrivateKey},
+    account::{Address, PrivateKey, ViewKey},
     network::{prelude::*, MainnetV0},
     program::{Entry, Identifier, Literal, Plaintext, ProgramID, Value},
-    types::U16,
+    types::{Field, U16},
 };
-use ledger_block::{ConfirmedTransaction, Execution, Ratify, Rejected, Transaction};
+use ledger_block::{Block, ConfirmedTransaction, Execution, Ratify, Rejected, Transaction};
 use ledger_committee::{Committee, MIN_VALIDATOR_STAKE};
-use ledger_store::{helpers::memory::ConsensusMemory, ConsensusStore};
-use synthesizer::{program::Program, vm::VM, Stack};
+use ledger_store::{helpers::memory::ConsensusMemory, ConsensusStore, HistoryRetention, StorageIndex};
+use synthesizer::{
+    program::{FinalizeOperation, Program},
+    vm::VM,
+    Stack,
+};
 
 use indexmap::IndexMap;
 use rand::seq::SliceRandom;
@@ -38,6 +49,23 @@ fn sample_vm() -> VM<CurrentNetwork, ConsensusMemory<CurrentNetwork>> {
     VM::from(ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None).unwrap()).unwrap()
 }
 
+/// Adds a block with a public transfer of the given amount to the given recipient, and returns the transfer.
+fn add_transfer_public_block(
+    ledger: &CurrentLedger,
+    private_key: &PrivateKey<CurrentNetwork>,
+    recipient: &Address<CurrentNetwork>,
+    amount: u64,
+    rng: &mut TestRng,
+) -> Transaction<CurrentNetwork> {
+    let inputs = [Value::from_str(&format!("{recipient}")).unwrap(), Value::from_str(&format!("{amount}u64")).unwrap()];
+    let transfer =
+        ledger.vm.execute(private_key, ("credits.aleo", "transfer_public"), inputs.iter(), None, 0, None, rng).unwrap();
+    let block =
+        ledger.prepare_advance_to_next_beacon_block(private_key, vec![], vec![], vec![transfer.clone()], rng).unwrap();
+    ledger.advance_to_next_block(&block).unwrap();
+    transfer
+}
+
 #[test]
 fn test_load() {
     let rng = &mut TestRng::default();
@@ -2669,3 +2697,776 @@ mod valid_solutions {
         assert_eq!(block_aborted_solution_ids, expected_aborted_solutions, "Aborted solutions do not match");
     }
 }
+
+/// Waits until the given record scanner has scanned the block at the given height, in the background.
+fn wait_for_record_scanner(scanner: &RecordScanner<CurrentNetwork>, height: u32) {
+    let start = std::time::Instant::now();
+    while scanner.next_height() <= height {
+        assert!(start.elapsed() < std::time::Duration::from_secs(60), "Timed out waiting for the record scanner");
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+}
+
+#[test]
+fn test_record_scanner() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, view_key, address, .. } =
+        crate::test_helpers::sample_test_env(rng);
+
+    // A helper function to find the commitments of the records, without the scanner.
+    let find_commitments = |filter| {
+        ledger.find_record_ciphertexts(&view_key, filter).unwrap().map(|(commitment, _)| commitment).collect::<Vec<_>>()
+    };
+
+    // Subscribe a record scanner, which scans the genesis block.
+    let scanner = RecordScanner::new(view_key);
+    ledger.subscribe_record_scanner(scanner.clone()).unwrap();
+    assert_eq!(scanner.next_height(), 1);
+    assert!(scanner.num_records() > 0);
+    assert_eq!(scanner.commitments().into_keys().collect::<Vec<_>>(), find_commitments(RecordsFilter::All));
+    // Ensure the same view key cannot be subscribed twice.
+    assert!(ledger.subscribe_record_scanner(RecordScanner::new(view_key)).is_err());
+
+    // Create a transfer transaction, which spends a record and creates new ones.
+    let transaction = ledger.create_transfer(&private_key, address, 100, 0, None, rng).unwrap();
+    let block =
+        ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![transaction], rng).unwrap();
+    ledger.advance_to_next_block(&block).unwrap();
+
+    // Ensure the subscribed scanner scanned the new block.
+    wait_for_record_scanner(&scanner, 1);
+    assert_eq!(scanner.next_height(), 2);
+    assert_eq!(scanner.commitments().into_keys().collect::<Vec<_>>(), find_commitments(RecordsFilter::All));
+    for (commitment, _) in block.records() {
+        assert_eq!(scanner.commitments().get(commitment), Some(&1));
+    }
+
+    // Ensure the scanned records match the records found without the scanner.
+    for filter in [RecordsFilter::All, RecordsFilter::Spent, RecordsFilter::Unspent] {
+        let candidate = ledger
+            .find_scanned_record_ciphertexts(&scanner, &view_key, filter)
+            .unwrap()
+            .map(|(commitment, _)| commitment)
+            .collect::<Vec<_>>();
+        assert_eq!(candidate, find_commitments(filter));
+    }
+    assert_eq!(find_commitments(RecordsFilter::Spent).len(), 2);
+
+    // Ensure a scanner for another view key is rejected.
+    let other_view_key = ViewKey::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+    assert!(ledger.find_scanned_record_ciphertexts(&scanner, &other_view_key, RecordsFilter::All).is_err());
+
+    // Unsubscribe the scanner, and ensure it is no longer advanced.
+    assert!(ledger.unsubscribe_record_scanner(&address).is_some());
+    let block = ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![], rng).unwrap();
+    ledger.advance_to_next_block(&block).unwrap();
+    assert_eq!(scanner.next_height(), 2);
+    assert_eq!(scanner.scan(&ledger).unwrap(), 0);
+    assert_eq!(scanner.next_height(), 3);
+}
+
+#[test]
+fn test_record_scanner_resume() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, view_key, .. } = crate::test_helpers::sample_test_env(rng);
+    let directory = std::env::temp_dir().join(format!("snarkvm_record_scanner_{}", rng.gen::<u64>()));
+
+    // Scan the genesis block, and persist the state.
+    let scanner = RecordScanner::open(view_key, &directory).unwrap();
+    assert_eq!(scanner.next_height(), 0);
+    let num_records = scanner.scan(&ledger).unwrap();
+    assert!(num_records > 0);
+
+    // Advance the ledger, while the scanner is not running.
+    let block = ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![], rng).unwrap();
+    ledger.advance_to_next_block(&block).unwrap();
+
+    // Ensure the reopened scanner resumes from its cursor.
+    let scanner = RecordScanner::open(view_key, &directory).unwrap();
+    assert_eq!(scanner.next_height(), 1);
+    assert_eq!(scanner.num_records(), num_records);
+    assert_eq!(scanner.scan(&ledger).unwrap(), 0);
+    assert_eq!(scanner.next_height(), 2);
+
+    // Ensure the state is rejected by a ledger with another genesis block.
+    let other_ledger = crate::test_helpers::sample_ledger(PrivateKey::new(rng).unwrap(), rng);
+    let scanner = RecordScanner::open(view_key, &directory).unwrap();
+    assert!(scanner.scan(&other_ledger).is_err());
+    assert_eq!(scanner.next_height(), 2);
+
+    std::fs::remove_dir_all(directory).unwrap();
+}
+
+#[test]
+fn test_find_transition_history() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, view_key, .. } = crate::test_helpers::sample_test_env(rng);
+
+    // Sample the recipient.
+    let recipient_private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+    let recipient_view_key = ViewKey::try_from(&recipient_private_key).unwrap();
+    let recipient_address = Address::try_from(&recipient_private_key).unwrap();
+
+    // Transfer to the recipient privately, with a private fee, and publicly, with a public fee.
+    let transfer_private = ledger.create_transfer(&private_key, recipient_address, 100, 0, None, rng).unwrap();
+    let inputs = [Value::from_str(&format!("{recipient_address}")).unwrap(), Value::from_str("200u64").unwrap()];
+    let transfer_public = ledger
+        .vm
+        .execute(&private_key, ("credits.aleo", "transfer_public"), inputs.into_iter(), None, 0, None, rng)
+        .unwrap();
+    let public_fee = *transfer_public.fee_transition().unwrap().amount().unwrap() as i128;
+    let transactions = vec![transfer_private.clone(), transfer_public.clone()];
+    let block = ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], transactions, rng).unwrap();
+    ledger.advance_to_next_block(&block).unwrap();
+
+    // Ensure the recipient sees the private transfer and the public transfer, but not the fees.
+    let history = ledger.find_transition_history(&recipient_view_key, 0..u32::MAX).unwrap();
+    let function_names = history.iter().map(|entry| entry.transition().function_name().to_string()).collect::<Vec<_>>();
+    assert_eq!(function_names, ["transfer_private", "transfer_public"]);
+    assert!(history.iter().all(|entry| !entry.is_signer() && entry.block_height() == 1));
+    // Ensure the private transfer decodes the received record, and only the received record.
+    let entry = &history[0];
+    assert_eq!(entry.transaction_id(), &transfer_private.id());
+    assert!(entry.inputs().iter().all(Option::is_none));
+    let records = entry.outputs().iter().flatten().filter(|output| matches!(output, Value::Record(..))).count();
+    assert_eq!(records, 1);
+    assert_eq!(entry.public_balance_delta(), 0);
+    // Ensure the public transfer credits the recipient.
+    assert_eq!(history[1].public_balance_delta(), 200);
+    assert!(history[1].inputs().iter().all(Option::is_some));
+
+    // Ensure the sender sees the transfers and the fees, with the spent records and private inputs decoded.
+    let history = ledger.find_transition_history(&view_key, 1..2).unwrap();
+    let function_names = history.iter().map(|entry| entry.transition().function_name().to_string()).collect::<Vec<_>>();
+    assert_eq!(function_names, ["transfer_private", "fee_private", "transfer_public", "fee_public"]);
+    assert!(history.iter().all(|entry| entry.is_signer()));
+    // Ensure the private inputs are decoded, but not the spent record, as it was created before the range.
+    assert!(history[0].inputs()[0].is_none());
+    assert!(history[0].inputs()[1..].iter().all(Option::is_some));
+    // Ensure the fees and public balance changes are decoded.
+    assert!(history[1].fee().is_some());
+    assert_eq!(history[1].public_balance_delta(), 0);
+    assert_eq!(history[2].public_balance_delta(), -200);
+    assert_eq!(history[3].fee(), Some(public_fee as u64));
+    assert_eq!(history[3].public_balance_delta(), -public_fee);
+
+    // Ensure the spent record is decoded, when the range includes the block that created it.
+    let history = ledger.find_transition_history(&view_key, 0..2).unwrap();
+    let entry = history.iter().find(|entry| entry.transaction_id() == &transfer_private.id()).unwrap();
+    assert!(matches!(entry.inputs()[0], Some(Value::Record(..))));
+}
+
+#[test]
+fn test_select_records_and_authorize_joins() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, view_key, address } =
+        crate::test_helpers::sample_test_env(rng);
+    let records = ledger.find_unspent_credits_records(&view_key).unwrap();
+    assert!(records.len() >= 3);
+
+    // Ensure a small amount is covered by a single record, with a separate fee record.
+    let plan = ledger.select_records(&view_key, 100, Some(100), RecordSelectionStrategy::MinimizeCount, rng).unwrap();
+    assert_eq!(plan.records().len(), 1);
+    assert_ne!(plan.records()[0].0, plan.fee_record().unwrap().0);
+    assert!(ledger.authorize_joins(&private_key, &plan, 1_000_000, 0, rng).unwrap().is_empty());
+
+    // Ensure an amount above the largest record requires a join, and authorize it with its fee.
+    let largest = records
+        .values()
+        .map(|record| match record.data().get(&Identifier::from_str("microcredits").unwrap()) {
+            Some(Entry::Private(Plaintext::Literal(Literal::U64(amount), _))) => **amount,
+            _ => unreachable!(),
+        })
+        .max()
+        .unwrap();
+    let fee = 1_000_000;
+    let plan =
+        ledger.select_records(&view_key, largest + 1, Some(fee), RecordSelectionStrategy::LargestFirst, rng).unwrap();
+    assert_eq!(plan.records().len(), 2);
+    assert!(plan.requires_join());
+    let (fee_commitment, fee_record) = plan.fee_record().cloned().unwrap();
+    let authorizations = ledger.authorize_joins(&private_key, &plan, 1_000_000, 0, rng).unwrap();
+    assert_eq!(authorizations.len(), 1);
+    assert_eq!(authorizations[0].0.peek_next().unwrap().function_name().to_string(), "join");
+    assert_eq!(authorizations[0].1.peek_next().unwrap().function_name().to_string(), "fee_public");
+
+    // Ensure a join fails early if the public balance does not cover its fee.
+    assert!(ledger.authorize_joins(&private_key, &plan, u64::MAX / 2, 0, rng).is_err());
+
+    // Submit the joins.
+    let transactions = authorizations
+        .into_iter()
+        .map(|(authorization, fee_authorization)| {
+            ledger.vm.execute_authorization(authorization, Some(fee_authorization), None, rng).unwrap()
+        })
+        .collect();
+    let block = ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], transactions, rng).unwrap();
+    assert_eq!(block.transactions().num_accepted(), 1);
+    ledger.advance_to_next_block(&block).unwrap();
+
+    // Ensure a single record covers the amount once the join is confirmed, and the fee record is unspent.
+    let plan =
+        ledger.select_records(&view_key, largest + 1, Some(fee), RecordSelectionStrategy::LargestFirst, rng).unwrap();
+    assert_eq!(plan.records().len(), 1);
+    assert!(!plan.requires_join());
+    assert!(ledger.find_unspent_credits_records(&view_key).unwrap().contains_key(&fee_commitment));
+
+    // Ensure the transfer can pay its fee with the fee record reserved by the original plan.
+    let inputs = [
+        Value::Record(plan.records()[0].1.clone()),
+        Value::from_str(&format!("{address}")).unwrap(),
+        Value::from_str(&format!("{}u64", largest + 1)).unwrap(),
+    ];
+    let transaction = ledger
+        .vm
+        .execute(&private_key, ("credits.aleo", "transfer_private"), inputs.iter(), Some(fee_record), 0, None, rng)
+        .unwrap();
+    let block =
+        ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![transaction], rng).unwrap();
+    assert_eq!(block.transactions().num_accepted(), 1);
+    ledger.advance_to_next_block(&block).unwrap();
+    assert!(!ledger.find_unspent_credits_records(&view_key).unwrap().contains_key(&fee_commitment));
+}
+
+#[test]
+fn test_attest_reserves() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, .. } = crate::test_helpers::sample_test_env(rng);
+
+    // Fund a new account with a public balance and a private record.
+    let account_private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+    let account_address = Address::try_from(&account_private_key).unwrap();
+    let inputs = [Value::from_str(&format!("{account_address}")).unwrap(), Value::from_str("1000u64").unwrap()];
+    let transfer_public = ledger
+        .vm
+        .execute(&private_key, ("credits.aleo", "transfer_public"), inputs.iter(), None, 0, None, rng)
+        .unwrap();
+    let inputs = [Value::from_str(&format!("{account_address}")).unwrap(), Value::from_str("2000u64").unwrap()];
+    let transfer_public_to_private = ledger
+        .vm
+        .execute(&private_key, ("credits.aleo", "transfer_public_to_private"), inputs.iter(), None, 0, None, rng)
+        .unwrap();
+    let transactions = vec![transfer_public, transfer_public_to_private];
+    let block = ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], transactions, rng).unwrap();
+    ledger.advance_to_next_block(&block).unwrap();
+
+    // Enable the mapping history, so the attestation can be verified after the ledger advances.
+    ledger.vm.finalize_store().set_history_retention(HistoryRetention::Unlimited);
+
+    // Attest to the reserves of the account.
+    let (attestation, opening) = ledger.attest_reserves(&private_key, &[account_private_key], rng).unwrap();
+    assert_eq!(attestation.attestor(), Address::try_from(&private_key).unwrap());
+    assert_eq!(attestation.block_height(), 1);
+    assert_eq!(attestation.public_balance(), 1000);
+    assert_eq!(attestation.private_balance(), 2000);
+    let account = &opening.accounts()[0];
+    assert_eq!(account.address(), &account_address);
+    assert_eq!(account.public_balance(), 1000);
+    assert_eq!(account.records().len(), 1);
+    assert_eq!(account.private_balance().unwrap(), 2000);
+    assert_eq!(ledger.verify_reserves_attestation(&attestation, &opening).unwrap(), 3000);
+
+    // Ensure the published attestation does not reveal the account.
+    let bytes = attestation.to_bytes_le().unwrap();
+    let address_bytes = account_address.to_x_coordinate().to_bytes_le().unwrap();
+    assert!(!bytes.windows(address_bytes.len()).any(|window| window == address_bytes));
+
+    // Ensure the attestation and opening round trip through bytes.
+    let candidate = ReservesAttestation::<CurrentNetwork>::from_bytes_le(&bytes).unwrap();
+    assert_eq!(attestation, candidate);
+    let candidate = ReservesOpening::<CurrentNetwork>::from_bytes_le(&opening.to_bytes_le().unwrap()).unwrap();
+    assert_eq!(opening, candidate);
+
+    // Ensure an overstated public balance, even if correctly signed, is rejected.
+    let statement =
+        ReservesAttestation::statement(attestation.block_height(), attestation.block_hash(), attestation.state_root());
+    let account =
+        AccountReserves::new(&account_private_key, &statement, 2000, account.records().to_vec(), rng).unwrap();
+    let tampered_opening = ReservesOpening::new(vec![account], rng);
+    let tampered = ReservesAttestation::new(
+        &private_key,
+        attestation.block_height(),
+        attestation.block_hash(),
+        attestation.state_root(),
+        &tampered_opening,
+        rng,
+    )
+    .unwrap();
+    tampered.check_opening(&tampered_opening).unwrap();
+    assert!(ledger.verify_reserves_attestation(&tampered, &tampered_opening).is_err());
+    // Ensure the opening does not match another attestation.
+    assert!(attestation.check_opening(&tampered_opening).is_err());
+
+    // Ensure the attestation is still verified at its block height once the ledger advances,
+    // even after the public balance changes.
+    add_transfer_public_block(&ledger, &private_key, &account_address, 500, rng);
+    assert_eq!(ledger.get_public_balance(&account_address).unwrap(), 1500);
+    assert_eq!(ledger.verify_reserves_attestation(&attestation, &opening).unwrap(), 3000);
+}
+
+#[test]
+fn test_verify_storage() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, address, .. } = crate::test_helpers::sample_test_env(rng);
+
+    // Add a block with a transfer.
+    let transaction = ledger.create_transfer(&private_key, address, 100, 0, None, rng).unwrap();
+    let block =
+        ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![transaction], rng).unwrap();
+    ledger.advance_to_next_block(&block).unwrap();
+
+    // Ensure the storage is consistent.
+    let report = ledger.verify_storage(false).unwrap();
+    assert!(report.is_consistent());
+    assert_eq!(report.latest_height(), 1);
+    assert_eq!(report.state_root(), ledger.latest_state_root());
+    assert_eq!(report.finalize_checksum(), ledger.vm.finalize_store().get_checksum_confirmed().unwrap());
+    assert_eq!(report.num_transactions(), ledger.vm.transaction_store().transaction_ids().count());
+    assert_eq!(report.num_transitions(), ledger.vm.transition_store().transition_ids().count());
+
+    // Ensure finalize operations which do not match the finalize root of the latest block are reported.
+    let block_store = ledger.vm.block_store();
+    let operations = block_store.get_ratified_finalize_operations(&block.hash()).unwrap().unwrap();
+    let mut tampered = operations.clone();
+    tampered.push(FinalizeOperation::InitializeMapping(Field::zero()));
+    block_store.insert_ratified_finalize_operations(&block.hash(), tampered).unwrap();
+    let report = ledger.verify_storage(false).unwrap();
+    assert_eq!(report.issues().len(), 1);
+    assert!(matches!(&report.issues()[0], StorageIssue::Inconsistent(..)));
+    block_store.insert_ratified_finalize_operations(&block.hash(), operations).unwrap();
+    assert!(ledger.verify_storage(false).unwrap().is_consistent());
+
+    // Store a transaction which is not included in any block, as if a node crashed while adding a block.
+    let orphan = ledger.create_transfer(&private_key, address, 100, 0, None, rng).unwrap();
+    ledger.vm.transaction_store().insert(&orphan).unwrap();
+
+    // Ensure the orphaned transaction and its transitions are reported.
+    let report = ledger.verify_storage(false).unwrap();
+    assert!(!report.is_consistent());
+    assert!(report.repaired().is_empty());
+    assert!(report.issues().contains(&StorageIssue::OrphanedTransaction(orphan.id())));
+    for transition_id in orphan.transition_ids() {
+        assert!(report.issues().contains(&StorageIssue::OrphanedTransition(*transition_id)));
+    }
+    assert_eq!(report.issues().len(), 1 + orphan.transition_ids().count());
+    assert!(report.issues().iter().all(|issue| issue.is_repairable()));
+
+    // Repair the storage, and ensure it is consistent again.
+    let report = ledger.verify_storage(true).unwrap();
+    assert!(report.is_consistent());
+    assert_eq!(report.repaired().len(), 1 + orphan.transition_ids().count());
+    assert!(!ledger.vm.transaction_store().contains_transaction_id(&orphan.id()).unwrap());
+    assert!(ledger.verify_storage(false).unwrap().is_consistent());
+}
+
+#[test]
+fn test_subscribe_events() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, address, .. } = crate::test_helpers::sample_test_env(rng);
+
+    // Subscribe to the events from the genesis block, with room for a single new block.
+    let mut subscription = ledger.subscribe_events(0, 1).unwrap();
+    assert!(ledger.subscribe_events(2, 1).is_err());
+
+    // Add a block with a transfer.
+    let transfer = ledger.create_transfer(&private_key, address, 100, 0, None, rng).unwrap();
+    let transfer_id = transfer.id();
+    let block = ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![transfer], rng).unwrap();
+    ledger.advance_to_next_block(&block).unwrap();
+
+    // Add a block with a deployment, which overflows the channel of the subscription.
+    let program = Program::<CurrentNetwork>::from_str(
+        "
+program dummy_program.aleo;
+function foo:
+    input r0 as u8.private;
+    output r0 as u8.private;",
+    )
+    .unwrap();
+    let deployment = ledger.vm.deploy(&private_key, &program, None, 0, None, rng).unwrap();
+    let deployment_id = deployment.id();
+    let block =
+        ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![deployment], rng).unwrap();
+    ledger.advance_to_next_block(&block).unwrap();
+
+    // Ensure the events of every block are emitted in order, and end with the committed block.
+    let mut events = vec![];
+    while let Some(event) = subscription.try_recv().unwrap() {
+        events.push(event);
+    }
+    let committed = events
+        .iter()
+        .filter_map(|event| match event {
+            LedgerEvent::BlockCommitted { height, block_hash } => Some((*height, *block_hash)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let expected = (0..=2).map(|height| (height, ledger.get_hash(height).unwrap())).collect::<Vec<_>>();
+    assert_eq!(committed, expected);
+    assert_eq!(subscription.next_height(), 3);
+
+    // Ensure the events match the events of each block.
+    let expected = (0..=2).flat_map(|height| ledger.get_events(height).unwrap()).collect::<Vec<_>>();
+    assert_eq!(events, expected);
+    let committee = ledger.get_committee(0).unwrap().unwrap();
+    assert!(events.contains(&LedgerEvent::CommitteeChanged { height: 0, committee }));
+    assert!(events.contains(&LedgerEvent::TransactionAccepted { height: 1, transaction_id: transfer_id }));
+    assert!(events.contains(&LedgerEvent::ProgramDeployed {
+        height: 2,
+        program_id: *program.id(),
+        transaction_id: deployment_id
+    }));
+
+    // Ensure the finalize operations of the transfer are emitted as mapping updates.
+    let operations = events
+        .iter()
+        .filter_map(|event| match event {
+            LedgerEvent::MappingUpdated { height: 1, transaction_id, operation }
+                if *transaction_id == Some(transfer_id) =>
+            {
+                Some(*operation)
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let confirmed = ledger.get_confirmed_transaction(transfer_id).unwrap();
+    assert!(!operations.is_empty());
+    assert_eq!(&operations, confirmed.finalize_operations());
+
+    // Ensure the finalize operations of the block rewards are emitted as mapping updates, without a transaction.
+    let ratified_operations = events
+        .iter()
+        .filter_map(|event| match event {
+            LedgerEvent::MappingUpdated { height: 1, transaction_id: None, operation } => Some(*operation),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let block_hash = ledger.get_hash(1).unwrap();
+    let expected = ledger.vm.block_store().get_ratified_finalize_operations(&block_hash).unwrap().unwrap();
+    assert_eq!(ratified_operations, expected);
+    // Ensure the staking rewards replace the `credits.aleo/bonded` mapping.
+    let mut preimage = Vec::new();
+    ProgramID::<CurrentNetwork>::from_str("credits.aleo").unwrap().write_bits_le(&mut preimage);
+    false.write_bits_le(&mut preimage);
+    Identifier::<CurrentNetwork>::from_str("bonded").unwrap().write_bits_le(&mut preimage);
+    let bonded_mapping_id = CurrentNetwork::hash_bhp1024(&preimage).unwrap();
+    assert!(ratified_operations.contains(&FinalizeOperation::ReplaceMapping(bonded_mapping_id)));
+
+    // Ensure a reconnecting subscriber can resume from a given height.
+    let mut resumed = ledger.subscribe_events(2, 1).unwrap();
+    let mut resumed_events = vec![];
+    while let Some(event) = resumed.try_recv().unwrap() {
+        resumed_events.push(event);
+    }
+    assert_eq!(resumed_events, ledger.get_events(2).unwrap());
+
+    // Ensure the events of a new block are received once the subscription has caught up.
+    let block = ledger.prepare_advance_to_next_beacon_block(&private_key, vec![], vec![], vec![], rng).unwrap();
+    ledger.advance_to_next_block(&block).unwrap();
+    let events = (0..ledger.get_events(3).unwrap().len()).map(|_| subscription.recv().unwrap()).collect::<Vec<_>>();
+    assert_eq!(events, ledger.get_events(3).unwrap());
+    assert_eq!(events.last(), Some(&LedgerEvent::BlockCommitted { height: 3, block_hash: block.hash() }));
+    assert!(subscription.try_recv().unwrap().is_none());
+}
+
+#[test]
+fn test_secondary_indexes() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, address, .. } = crate::test_helpers::sample_test_env(rng);
+    let recipient = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+
+    // Adds a block with a public transfer to the recipient, and returns the transfer.
+    let add_transfer = |rng: &mut TestRng| add_transfer_public_block(&ledger, &private_key, &recipient, 10, rng);
+
+    // Add a transfer before the indexes are enabled.
+    let transfer_1 = add_transfer(rng);
+    for index in StorageIndex::ALL {
+        assert!(!ledger.is_index_enabled(index).unwrap());
+    }
+    assert!(ledger.find_transition_ids_by_address(&recipient, 0, 10).is_err());
+
+    // Enable the indexes, which populates them with the existing blocks.
+    for index in StorageIndex::ALL {
+        ledger.enable_index(index).unwrap();
+        assert!(ledger.is_index_enabled(index).unwrap());
+    }
+    // Add a transfer after the indexes are enabled.
+    let transfer_2 = add_transfer(rng);
+
+    // Retrieve the IDs of the transfer transitions.
+    let transfer_ids = [&transfer_1, &transfer_2].map(|transfer| *transfer.transitions().next().unwrap().id());
+
+    // Ensure the address index contains both transfers, in order, and can be paginated.
+    assert_eq!(ledger.find_transition_ids_by_address(&recipient, 0, 10).unwrap(), transfer_ids);
+    assert_eq!(ledger.find_transition_ids_by_address(&recipient, 1, 10).unwrap(), transfer_ids[1..]);
+    assert_eq!(ledger.find_transition_ids_by_address(&recipient, 0, 1).unwrap(), transfer_ids[..1]);
+    assert!(ledger.find_transition_ids_by_address(&recipient, 2, 10).unwrap().is_empty());
+
+    // Ensure the locator index ends with both transfers.
+    let program_id = ProgramID::from_str("credits.aleo").unwrap();
+    let function_name = Identifier::from_str("transfer_public").unwrap();
+    let transitions = ledger.find_transition_ids_by_function(&program_id, &function_name, 0, u32::MAX).unwrap();
+    assert!(transitions.ends_with(&transfer_ids));
+
+    // Ensure the fee payer index ends with both transfers.
+    let transactions = ledger.find_transaction_ids_by_fee_payer(&address, 0, u32::MAX).unwrap();
+    assert!(transactions.ends_with(&[transfer_1.id(), transfer_2.id()]));
+
+    // Ensure a disabled index is cleared, and is repopulated once it is enabled again.
+    ledger.disable_index(StorageIndex::Address).unwrap();
+    assert!(!ledger.is_index_enabled(StorageIndex::Address).unwrap());
+    assert!(ledger.find_transition_ids_by_address(&recipient, 0, 10).is_err());
+    ledger.enable_index(StorageIndex::Address).unwrap();
+    assert_eq!(ledger.find_transition_ids_by_address(&recipient, 0, 10).unwrap(), transfer_ids);
+
+    // Add a third transfer.
+    let transfer_3 = add_transfer(rng);
+    let transfer_id_3 = *transfer_3.transitions().next().unwrap().id();
+
+    // Ensure removing a transition removes it from the indexes, and preserves the order of the other entries.
+    let transition_store = ledger.vm.transition_store();
+    transition_store.remove(&transfer_ids[0]).unwrap();
+    assert_eq!(ledger.find_transition_ids_by_address(&recipient, 0, 10).unwrap(), [transfer_ids[1], transfer_id_3]);
+    assert_eq!(ledger.find_transition_ids_by_address(&recipient, 1, 10).unwrap(), [transfer_id_3]);
+    let transitions = ledger.find_transition_ids_by_function(&program_id, &function_name, 0, u32::MAX).unwrap();
+    assert!(!transitions.contains(&transfer_ids[0]));
+    assert!(transitions.ends_with(&[transfer_ids[1], transfer_id_3]));
+}
+
+#[test]
+fn test_mapping_history() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, .. } = crate::test_helpers::sample_test_env(rng);
+    let recipient = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+
+    // Adds a block with a public transfer of the given amount to the recipient.
+    let add_transfer = |amount: u64, rng: &mut TestRng| {
+        add_transfer_public_block(&ledger, &private_key, &recipient, amount, rng);
+    };
+
+    // Enable the mapping history, and add two transfers.
+    let finalize_store = ledger.vm.finalize_store();
+    finalize_store.set_history_retention(HistoryRetention::Unlimited);
+    let start_height = ledger.latest_height();
+    add_transfer(10, rng);
+    add_transfer(20, rng);
+
+    // Ensure the public balance of the recipient is returned as of each height.
+    let program_id = ProgramID::from_str("credits.aleo").unwrap();
+    let mapping_name = Identifier::from_str("account").unwrap();
+    let key = Plaintext::from(Literal::Address(recipient));
+    let balance_at = |height| finalize_store.get_value_at_height(program_id, mapping_name, &key, height).unwrap();
+    assert_eq!(balance_at(start_height), None);
+    assert_eq!(balance_at(start_height + 1), Some(Value::from_str("10u64").unwrap()));
+    assert_eq!(balance_at(start_height + 2), Some(Value::from_str("30u64").unwrap()));
+    let mapping = finalize_store.get_mapping_at_height(program_id, mapping_name, start_height + 1).unwrap().unwrap();
+    assert!(mapping.contains(&(key.clone(), Value::from_str("10u64").unwrap())));
+
+    // Disable the mapping history, and add another transfer.
+    finalize_store.set_history_retention(HistoryRetention::Disabled);
+    add_transfer(30, rng);
+    // Ensure the heights before the unrecorded block are no longer available.
+    assert_eq!(balance_at(start_height + 3), Some(Value::from_str("60u64").unwrap()));
+    assert!(finalize_store.get_value_at_height(program_id, mapping_name, &key, start_height + 2).is_err());
+}
+
+#[test]
+fn test_rollback_to() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, .. } = crate::test_helpers::sample_test_env(rng);
+    let recipient = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+
+    // Adds a block with a public transfer to the recipient, and returns the transfer.
+    let add_transfer = |rng: &mut TestRng| add_transfer_public_block(&ledger, &private_key, &recipient, 10, rng);
+
+    // Enable the mapping history, and save the current state.
+    let finalize_store = ledger.vm.finalize_store();
+    finalize_store.set_history_retention(HistoryRetention::Unlimited);
+    let start_height = ledger.latest_height();
+    let start_hash = ledger.latest_hash();
+    let start_state_root = ledger.latest_state_root();
+    let start_checksum = finalize_store.get_checksum_confirmed().unwrap();
+
+    // Add two transfers.
+    let transfers = [add_transfer(rng), add_transfer(rng)];
+    assert_eq!(ledger.latest_height(), start_height + 2);
+    assert_eq!(ledger.get_public_balance(&recipient).unwrap(), 20);
+
+    // Roll back the ledger, and ensure the state matches the saved state.
+    ledger.rollback_to(start_height).unwrap();
+    assert_eq!(ledger.latest_height(), start_height);
+    assert_eq!(ledger.latest_hash(), start_hash);
+    assert_eq!(ledger.latest_state_root(), start_state_root);
+    assert_eq!(finalize_store.get_checksum_confirmed().unwrap(), start_checksum);
+    assert_eq!(finalize_store.committee_store().current_height().unwrap(), start_height);
+    assert_eq!(ledger.get_public_balance(&recipient).unwrap(), 0);
+    for transfer in &transfers {
+        assert!(!ledger.contains_transaction_id(&transfer.id()).unwrap());
+    }
+
+    // Ensure new blocks can be added after the rollback.
+    add_transfer(rng);
+    assert_eq!(ledger.latest_height(), start_height + 1);
+    assert_eq!(ledger.get_public_balance(&recipient).unwrap(), 10);
+
+    // Ensure a block without a mapping history cannot be rolled back, and that no block is removed.
+    finalize_store.set_history_retention(HistoryRetention::Disabled);
+    add_transfer(rng);
+    assert!(ledger.rollback_to(start_height).is_err());
+    assert_eq!(ledger.latest_height(), start_height + 2);
+    assert_eq!(ledger.get_public_balance(&recipient).unwrap(), 20);
+}
+
+#[test]
+fn test_rollback_to_keeps_block_on_failure() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, .. } = crate::test_helpers::sample_test_env(rng);
+    let recipient = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+
+    // Enable the mapping history, and add a transfer.
+    let finalize_store = ledger.vm.finalize_store();
+    finalize_store.set_history_retention(HistoryRetention::Unlimited);
+    let start_height = ledger.latest_height();
+    add_transfer_public_block(&ledger, &private_key, &recipient, 10, rng);
+    let latest_height = start_height + 1;
+    let checksum = finalize_store.get_checksum_confirmed().unwrap();
+
+    // Shrink the block tree, so that the removal of the block fails after its finalize effects are undone.
+    ledger.vm.block_store().remove_last_n_from_tree_only(1).unwrap();
+    assert!(ledger.rollback_to(start_height).is_err());
+
+    // Ensure the block is kept, along with its finalize effects, mapping history, and committee.
+    assert!(ledger.vm.block_store().get_block_hash(latest_height).unwrap().is_some());
+    assert_eq!(finalize_store.get_checksum_confirmed().unwrap(), checksum);
+    assert!(finalize_store.contains_history(latest_height).unwrap());
+    assert_eq!(finalize_store.committee_store().current_height().unwrap(), latest_height);
+    assert_eq!(ledger.get_public_balance(&recipient).unwrap(), 10);
+}
+
+#[test]
+fn test_rollback_events() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, .. } = crate::test_helpers::sample_test_env(rng);
+    let recipient = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+
+    // Subscribe to the events of the new blocks, with a subscription which is read before the rollback,
+    // and another which is not.
+    ledger.vm.finalize_store().set_history_retention(HistoryRetention::Unlimited);
+    let start_height = ledger.latest_height();
+    let mut subscription = ledger.subscribe_events(start_height + 1, 1).unwrap();
+    let mut unread = ledger.subscribe_events(start_height + 1, 2).unwrap();
+
+    // Add two transfers, and read their events.
+    add_transfer_public_block(&ledger, &private_key, &recipient, 10, rng);
+    add_transfer_public_block(&ledger, &private_key, &recipient, 10, rng);
+    while subscription.try_recv().unwrap().is_some() {}
+    assert_eq!(subscription.next_height(), start_height + 3);
+
+    // Roll back the ledger, and ensure the subscription rewinds to the rollback height.
+    ledger.rollback_to(start_height).unwrap();
+    assert_eq!(subscription.try_recv().unwrap(), Some(LedgerEvent::RolledBack { height: start_height }));
+    assert!(subscription.try_recv().unwrap().is_none());
+    assert_eq!(subscription.next_height(), start_height + 1);
+    // Ensure the unread subscription discards the events of the removed blocks, without rewinding.
+    assert!(unread.try_recv().unwrap().is_none());
+    assert_eq!(unread.next_height(), start_height + 1);
+
+    // Add a new block at the reverted height, and ensure both subscriptions emit its events.
+    add_transfer_public_block(&ledger, &private_key, &recipient, 20, rng);
+    let expected = ledger.get_events(start_height + 1).unwrap();
+    for subscription in [&mut subscription, &mut unread] {
+        let events = (0..expected.len()).map(|_| subscription.recv().unwrap()).collect::<Vec<_>>();
+        assert_eq!(events, expected);
+        assert!(subscription.try_recv().unwrap().is_none());
+    }
+}
+
+#[test]
+fn test_replay_block() {
+    let rng = &mut TestRng::default();
+
+    // Initialize the test environment.
+    let crate::test_helpers::TestEnv { ledger, private_key, .. } = crate::test_helpers::sample_test_env(rng);
+    let recipient = Address::try_from(PrivateKey::<CurrentNetwork>::new(rng).unwrap()).unwrap();
+
+    // Add two blocks with a public transfer to the recipient.
+    for _ in 0..2 {
+        add_transfer_public_block(&ledger, &private_key, &recipient, 10, rng);
+    }
+    let latest_height = ledger.latest_height();
+
+    // Initialize a fresh ledger with the same genesis block.
+    let genesis = ledger.get_block(0).unwrap();
+    let replay = CurrentLedger::load(genesis, StorageMode::Production).unwrap();
+
+    // Replay all but the latest block, and ensure there is no divergence.
+    for height in 1..latest_height {
+        assert!(replay.replay_block(&ledger.get_block(height).unwrap(), rng).unwrap().is_none());
+    }
+    assert_eq!(replay.latest_height(), latest_height - 1);
+
+    // Tamper with the finalize operations of the transfer in the latest block.
+    let block = ledger.get_block(latest_height).unwrap();
+    let transactions = block
+        .transactions()
+        .iter()
+        .map(|confirmed| {
+            ConfirmedTransaction::accepted_execute(confirmed.index(), confirmed.transaction().clone(), vec![]).unwrap()
+        })
+        .collect();
+    let tampered = Block::from_unchecked(
+        block.hash(),
+        block.previous_hash(),
+        *block.header(),
+        block.authority().clone(),
+        block.ratifications().clone(),
+        block.solutions().clone(),
+        block.aborted_solution_ids().clone(),
+        transactions,
+        block.aborted_transaction_ids().clone(),
+    )
+    .unwrap();
+
+    // Ensure the replay of the tampered block diverges, and that the block is not added.
+    let divergence = replay.replay_block(&tampered, rng).unwrap().unwrap();
+    assert_eq!(divergence.height(), latest_height);
+    assert_eq!(divergence.mismatches().len(), 1);
+    assert_eq!(divergence.transactions().len(), 1);
+    let diff = &divergence.transactions()[0];
+    assert!(diff.recorded().unwrap().finalize_operations().is_empty());
+    assert_eq!(diff.replayed(), block.transactions().iter().next());
+    assert_eq!(replay.latest_height(), latest_height - 1);
+
+    // Replay the recorded block, and ensure the ledgers match.
+    assert!(replay.replay_block(&block, rng).unwrap().is_none());
+    assert_eq!(replay.latest_hash(), ledger.latest_hash());
+    assert_eq!(replay.latest_state_root(), ledger.latest_state_root());
+    assert_eq!(
+        replay.vm.finalize_store().get_checksum_confirmed().unwrap(),
+        ledger.vm.finalize_store().get_checksum_confirmed().unwrap()
+    );
+}
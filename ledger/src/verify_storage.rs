@@ -0,0 +1,289 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use console::program::BlockTree;
+
+use indexmap::IndexSet;
+
+impl<N: Network, C: ConsensusStorage<N>> Ledger<N, C> {
+    /// Verifies the consistency of the ledger storage, and returns a report of the issues found.
+    ///
+    /// Each block in storage is loaded, and the transactions, transitions, inputs, and outputs it contains
+    /// are checked against their reverse indexes. The state root of each block and the finalize checksum
+    /// are recomputed, and the finalize operations of the latest block are checked against its finalize root
+    /// (unless the block was added by an older version, which did not record its ratified finalize operations).
+    /// Transactions and transitions which are not included in any block are reported
+    /// as orphaned, and are removed from storage if `repair` is `true`.
+    pub fn verify_storage(&self, repair: bool) -> Result<StorageReport<N>> {
+        let timer = timer!("Ledger::verify_storage");
+
+        let block_store = self.vm.block_store();
+        let transaction_store = self.vm.transaction_store();
+        let transition_store = self.vm.transition_store();
+        let finalize_store = self.vm.finalize_store();
+
+        // Retrieve the latest block height.
+        let Some(latest_height) = block_store.heights().max().map(|height| *height) else {
+            bail!("The ledger storage does not contain any blocks")
+        };
+
+        let mut issues = Vec::new();
+        // The IDs of the transactions and transitions included in the blocks.
+        let mut transaction_ids = IndexSet::new();
+        let mut transition_ids = IndexSet::new();
+
+        // Recompute the block tree while walking the blocks, until a block hash is missing.
+        let mut tree: Option<BlockTree<N>> = Some(N::merkle_tree_bhp(&[])?);
+        let mut previous_hash = None;
+        // The latest block, if it is in storage.
+        let mut latest_block = None;
+
+        for height in 0..=latest_height {
+            // Retrieve the block hash.
+            let Some(block_hash) = block_store.get_block_hash(height)? else {
+                issues.push(StorageIssue::Inconsistent(format!("Missing block hash for block {height}")));
+                tree = None;
+                previous_hash = None;
+                continue;
+            };
+
+            // Ensure the block hash is indexed to the block height.
+            if block_store.get_block_height(&block_hash)? != Some(height) {
+                issues.push(StorageIssue::Inconsistent(format!(
+                    "Block hash '{block_hash}' is not indexed to block {height}"
+                )));
+            }
+
+            // Ensure the state root matches the recomputed block tree.
+            if let Some(current_tree) = &tree {
+                let updated_tree = current_tree.prepare_append(&[block_hash.to_bits_le()])?;
+                let state_root: N::StateRoot = (*updated_tree.root()).into();
+                if block_store.get_state_root(height)? != Some(state_root) {
+                    issues.push(StorageIssue::Inconsistent(format!("Incorrect state root for block {height}")));
+                } else if block_store.find_block_height_from_state_root(state_root)? != Some(height) {
+                    issues.push(StorageIssue::Inconsistent(format!(
+                        "State root '{state_root}' is not indexed to block {height}"
+                    )));
+                }
+                tree = Some(updated_tree);
+            }
+
+            // Retrieve the block.
+            let block = match block_store.get_block(&block_hash) {
+                Ok(Some(block)) => block,
+                Ok(None) => {
+                    issues.push(StorageIssue::Inconsistent(format!("Missing block {height} ('{block_hash}')")));
+                    previous_hash = None;
+                    continue;
+                }
+                Err(error) => {
+                    issues.push(StorageIssue::Inconsistent(format!(
+                        "Malformed block {height} ('{block_hash}'): {error}"
+                    )));
+                    previous_hash = None;
+                    continue;
+                }
+            };
+
+            // Ensure the block is linked to the previous block.
+            if block.height() != height || previous_hash.is_some_and(|hash| hash != block.previous_hash()) {
+                issues.push(StorageIssue::Inconsistent(format!(
+                    "Block {height} ('{block_hash}') is not linked to the previous block"
+                )));
+            }
+            previous_hash = Some(block_hash);
+            if height == latest_height {
+                latest_block = Some(block.clone());
+            }
+
+            // Ensure the solutions are indexed to the block.
+            for solution_id in block.solutions().solution_ids().chain(block.aborted_solution_ids()) {
+                if block_store.find_block_height_from_solution_id(solution_id)? != Some(height) {
+                    issues.push(StorageIssue::Inconsistent(format!(
+                        "Solution '{solution_id}' is not indexed to block {height}"
+                    )));
+                }
+            }
+
+            // Ensure the aborted transactions are indexed to the block.
+            for transaction_id in block.aborted_transaction_ids() {
+                if block_store.find_block_hash(transaction_id)? != Some(block_hash) {
+                    issues.push(StorageIssue::Inconsistent(format!(
+                        "Aborted transaction '{transaction_id}' is not indexed to block {height}"
+                    )));
+                }
+            }
+
+            for confirmed in block.transactions().iter() {
+                let transaction = confirmed.transaction();
+                let transaction_id = transaction.id();
+                transaction_ids.insert(transaction_id);
+
+                // Ensure the transaction is indexed to the block.
+                if block_store.find_block_hash(&transaction_id)? != Some(block_hash) {
+                    issues.push(StorageIssue::Inconsistent(format!(
+                        "Transaction '{transaction_id}' is not indexed to block {height}"
+                    )));
+                }
+                // Ensure the unconfirmed ID of a rejected transaction is indexed to the block.
+                if confirmed.is_rejected() {
+                    let unconfirmed_id = confirmed.to_unconfirmed_transaction_id()?;
+                    if block_store.find_block_hash(&unconfirmed_id)? != Some(block_hash) {
+                        issues.push(StorageIssue::Inconsistent(format!(
+                            "Rejected transaction '{unconfirmed_id}' is not indexed to block {height}"
+                        )));
+                    }
+                }
+
+                for transition in transaction.transitions() {
+                    let transition_id = *transition.id();
+                    transition_ids.insert(transition_id);
+
+                    // Ensure the transition is indexed to the transaction.
+                    let indexed_transaction_id =
+                        transaction_store.find_transaction_id_from_transition_id(&transition_id)?;
+                    if indexed_transaction_id != Some(transaction_id) {
+                        issues.push(StorageIssue::Inconsistent(format!(
+                            "Transition '{transition_id}' is not indexed to transaction '{transaction_id}'"
+                        )));
+                    }
+
+                    // Ensure the inputs and outputs, including serial numbers and commitments, are indexed.
+                    let input_ids = transition.inputs().iter().map(|input| input.id());
+                    let output_ids = transition.outputs().iter().map(|output| output.id());
+                    for id in input_ids.chain(output_ids) {
+                        if transition_store.find_transition_id(id).ok() != Some(transition_id) {
+                            issues.push(StorageIssue::Inconsistent(format!(
+                                "Input or output '{id}' is not indexed to transition '{transition_id}'"
+                            )));
+                        }
+                    }
+                    // Ensure the tags are indexed.
+                    for tag in transition.tags() {
+                        if !transition_store.contains_tag(tag)? {
+                            issues.push(StorageIssue::Inconsistent(format!(
+                                "Tag '{tag}' of transition '{transition_id}' is not indexed"
+                            )));
+                        }
+                    }
+                }
+            }
+        }
+        lap!(timer, "Verify the blocks");
+
+        // Ensure the in-memory block tree matches the recomputed block tree.
+        let state_root = match tree {
+            Some(tree) => (*tree.root()).into(),
+            None => block_store.current_state_root(),
+        };
+        if block_store.current_state_root() != state_root {
+            issues.push(StorageIssue::Inconsistent(format!(
+                "The block tree does not match the state root '{state_root}'"
+            )));
+        }
+
+        // Find the transactions and transitions which are not included in any block.
+        let mut orphans = Vec::new();
+        for transaction_id in transaction_store.transaction_ids() {
+            if !transaction_ids.contains(&*transaction_id) {
+                orphans.push(StorageIssue::OrphanedTransaction(*transaction_id));
+            }
+        }
+        let mut orphaned_transition_ids = IndexSet::new();
+        for transition_id in transition_store.transition_ids() {
+            if !transition_ids.contains(&*transition_id) {
+                orphaned_transition_ids.insert(*transition_id);
+                orphans.push(StorageIssue::OrphanedTransition(*transition_id));
+            }
+        }
+
+        // Ensure the serial numbers and commitments in storage belong to a transition (orphaned or not).
+        let serial_numbers = transition_store.serial_numbers().map(|id| ("Serial number", id));
+        let commitments = transition_store.commitments().map(|id| ("Commitment", id));
+        for (kind, id) in serial_numbers.chain(commitments) {
+            let is_known = transition_store.find_transition_id(&id).is_ok_and(|transition_id| {
+                transition_ids.contains(&transition_id) || orphaned_transition_ids.contains(&transition_id)
+            });
+            if !is_known {
+                issues.push(StorageIssue::Inconsistent(format!("{kind} '{id}' does not belong to any transition")));
+            }
+        }
+        lap!(timer, "Find the orphaned entries");
+
+        // Ensure the mappings of each deployed program are initialized.
+        for program in transaction_store.programs() {
+            let expected = program.mappings().keys().copied().collect::<IndexSet<_>>();
+            if finalize_store.get_mapping_names_confirmed(program.id())?.unwrap_or_default() != expected {
+                issues.push(StorageIssue::Inconsistent(format!(
+                    "The mappings of program '{}' do not match its deployment",
+                    program.id()
+                )));
+            }
+        }
+        // Recompute the finalize checksum.
+        let finalize_checksum = finalize_store.get_checksum_confirmed()?;
+        // Ensure the finalize operations of the latest block, including those of its ratifications,
+        // match the finalize root recorded in its header.
+        // Note: The ratified finalize operations are not recorded for blocks added by an older version,
+        // in which case the finalize root of the latest block cannot be checked.
+        if let Some(block) = &latest_block {
+            match block_store.get_ratified_finalize_operations(&block.hash())? {
+                Some(ratified_finalize_operations) => {
+                    let finalize_root = block.transactions().to_finalize_root(ratified_finalize_operations)?;
+                    if finalize_root != block.finalize_root() {
+                        issues.push(StorageIssue::Inconsistent(format!(
+                            "The finalize operations of block {latest_height} do not match its finalize root '{}'",
+                            block.finalize_root()
+                        )));
+                    }
+                }
+                None => warn!(
+                    "Skipping the finalize root check of block {latest_height}, as its ratified finalize operations \
+                     are not recorded"
+                ),
+            }
+        }
+        lap!(timer, "Verify the finalize storage");
+
+        // Remove the orphaned entries, if requested.
+        let mut repaired = Vec::new();
+        for orphan in orphans {
+            match (repair, &orphan) {
+                (true, StorageIssue::OrphanedTransaction(transaction_id)) => {
+                    transaction_store.remove(transaction_id)?;
+                    repaired.push(orphan);
+                }
+                (true, StorageIssue::OrphanedTransition(transition_id)) => {
+                    // Note: The transition may have already been removed with its orphaned transaction.
+                    transition_store.remove(transition_id)?;
+                    repaired.push(orphan);
+                }
+                _ => issues.push(orphan),
+            }
+        }
+        finish!(timer, "Repair the orphaned entries");
+
+        Ok(StorageReport::new(
+            latest_height,
+            state_root,
+            finalize_checksum,
+            transaction_ids.len(),
+            transition_ids.len(),
+            issues,
+            repaired,
+        ))
+    }
+}
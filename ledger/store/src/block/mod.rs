@@ -139,6 +139,8 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
     type ConfirmedTransactionsMap: for<'a> Map<'a, N::TransactionID, (N::BlockHash, ConfirmedTxType<N>, Vec<FinalizeOperation<N>>)>;
     /// The rejected deployment or execution map.
     type RejectedDeploymentOrExecutionMap: for<'a> Map<'a, Field<N>, Rejected<N>>;
+    /// The mapping of `block hash` to the finalize operations of the block ratifications.
+    type RatifiedFinalizeOperationsMap: for<'a> Map<'a, N::BlockHash, Vec<FinalizeOperation<N>>>;
     /// The transaction storage.
     type TransactionStorage: TransactionStorage<N, TransitionStorage = Self::TransitionStorage>;
     /// The transition storage.
@@ -181,6 +183,8 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
     fn confirmed_transactions_map(&self) -> &Self::ConfirmedTransactionsMap;
     /// Returns the rejected deployment or execution map.
     fn rejected_deployment_or_execution_map(&self) -> &Self::RejectedDeploymentOrExecutionMap;
+    /// Returns the ratified finalize operations map.
+    fn ratified_finalize_operations_map(&self) -> &Self::RatifiedFinalizeOperationsMap;
     /// Returns the transaction store.
     fn transaction_store(&self) -> &TransactionStore<N, Self::TransactionStorage>;
 
@@ -213,6 +217,7 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
         self.rejected_or_aborted_transaction_id_map().start_atomic();
         self.confirmed_transactions_map().start_atomic();
         self.rejected_deployment_or_execution_map().start_atomic();
+        self.ratified_finalize_operations_map().start_atomic();
         self.transaction_store().start_atomic();
     }
 
@@ -235,6 +240,7 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
             || self.rejected_or_aborted_transaction_id_map().is_atomic_in_progress()
             || self.confirmed_transactions_map().is_atomic_in_progress()
             || self.rejected_deployment_or_execution_map().is_atomic_in_progress()
+            || self.ratified_finalize_operations_map().is_atomic_in_progress()
             || self.transaction_store().is_atomic_in_progress()
     }
 
@@ -257,6 +263,7 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
         self.rejected_or_aborted_transaction_id_map().atomic_checkpoint();
         self.confirmed_transactions_map().atomic_checkpoint();
         self.rejected_deployment_or_execution_map().atomic_checkpoint();
+        self.ratified_finalize_operations_map().atomic_checkpoint();
         self.transaction_store().atomic_checkpoint();
     }
 
@@ -279,6 +286,7 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
         self.rejected_or_aborted_transaction_id_map().clear_latest_checkpoint();
         self.confirmed_transactions_map().clear_latest_checkpoint();
         self.rejected_deployment_or_execution_map().clear_latest_checkpoint();
+        self.ratified_finalize_operations_map().clear_latest_checkpoint();
         self.transaction_store().clear_latest_checkpoint();
     }
 
@@ -301,6 +309,7 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
         self.rejected_or_aborted_transaction_id_map().atomic_rewind();
         self.confirmed_transactions_map().atomic_rewind();
         self.rejected_deployment_or_execution_map().atomic_rewind();
+        self.ratified_finalize_operations_map().atomic_rewind();
         self.transaction_store().atomic_rewind();
     }
 
@@ -323,6 +332,7 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
         self.rejected_or_aborted_transaction_id_map().abort_atomic();
         self.confirmed_transactions_map().abort_atomic();
         self.rejected_deployment_or_execution_map().abort_atomic();
+        self.ratified_finalize_operations_map().abort_atomic();
         self.transaction_store().abort_atomic();
     }
 
@@ -345,6 +355,7 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
         self.rejected_or_aborted_transaction_id_map().finish_atomic()?;
         self.confirmed_transactions_map().finish_atomic()?;
         self.rejected_deployment_or_execution_map().finish_atomic()?;
+        self.ratified_finalize_operations_map().finish_atomic()?;
         self.transaction_store().finish_atomic()
     }
 
@@ -540,8 +551,9 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
                 self.certificate_map().remove(certificate_id)?;
             }
 
-            // Remove the block ratifications.
+            // Remove the block ratifications, and their finalize operations.
             self.ratifications_map().remove(block_hash)?;
+            self.ratified_finalize_operations_map().remove(block_hash)?;
 
             // Remove the block solutions.
             self.solutions_map().remove(block_hash)?;
@@ -590,6 +602,16 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
         })
     }
 
+    /// Stores the finalize operations of the ratifications of the given block, once the block is finalized.
+    fn insert_ratified_finalize_operations(
+        &self,
+        block_hash: &N::BlockHash,
+        operations: Vec<FinalizeOperation<N>>,
+    ) -> Result<()> {
+        // Note: The atomic batch scope ensures the operations belong to the paused batch of the block, if any.
+        atomic_batch_scope!(self, { self.ratified_finalize_operations_map().insert(*block_hash, operations) })
+    }
+
     /// Returns `true` if the given transaction ID exists.
     fn contains_transaction_id(&self, transaction_id: &N::TransactionID) -> Result<bool> {
         Ok(self.transaction_store().contains_transaction_id(transaction_id)?
@@ -817,6 +839,15 @@ pub trait BlockStorage<N: Network>: 'static + Clone + Send + Sync {
         }
     }
 
+    /// Returns the finalize operations of the block ratifications for the given `block hash`,
+    /// or `None` if they were not recorded.
+    fn get_ratified_finalize_operations(&self, block_hash: &N::BlockHash) -> Result<Option<Vec<FinalizeOperation<N>>>> {
+        match self.ratified_finalize_operations_map().get_confirmed(block_hash)? {
+            Some(operations) => Ok(Some(cow_to_cloned!(operations))),
+            None => Ok(None),
+        }
+    }
+
     /// Returns the block solutions for the given `block hash`.
     fn get_block_solutions(&self, block_hash: &N::BlockHash) -> Result<Solutions<N>> {
         match self.solutions_map().get_confirmed(block_hash)? {
@@ -1013,27 +1044,59 @@ impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
         let storage = B::open(storage)?;
 
         // Compute the block tree.
-        let tree = {
-            // Prepare an iterator over the block heights.
-            let heights = storage.id_map().keys_confirmed();
-            // Prepare the leaves of the block tree.
-            let hashes = match heights.max() {
-                Some(height) => cfg_into_iter!(0..=cow_to_copied!(height))
-                    .map(|height| match storage.get_block_hash(height)? {
-                        Some(hash) => Ok(hash.to_bits_le()),
-                        None => bail!("Missing block hash for block {height}"),
-                    })
-                    .collect::<Result<Vec<Vec<bool>>>>()?,
-                None => vec![],
-            };
-            // Construct the block tree.
-            Arc::new(RwLock::new(N::merkle_tree_bhp(&hashes)?))
-        };
+        let tree = Arc::new(RwLock::new(Self::compute_tree(&storage, 0)?));
 
         // Return the block store.
         Ok(Self { storage, tree })
     }
 
+    /// Computes the leaves of the block tree, for the blocks in storage starting from the given height.
+    fn compute_leaves(storage: &B, start_height: u32) -> Result<Vec<Vec<bool>>> {
+        // Prepare the leaves of the block tree.
+        match storage.id_map().keys_confirmed().max() {
+            Some(height) => cfg_into_iter!(start_height..=cow_to_copied!(height))
+                .map(|height| match storage.get_block_hash(height)? {
+                    Some(hash) => Ok(hash.to_bits_le()),
+                    None => bail!("Missing block hash for block {height}"),
+                })
+                .collect::<Result<Vec<Vec<bool>>>>(),
+            None => Ok(vec![]),
+        }
+    }
+
+    /// Computes the block tree for the blocks in storage.
+    fn compute_tree(storage: &B, start_height: u32) -> Result<BlockTree<N>> {
+        N::merkle_tree_bhp(&Self::compute_leaves(storage, start_height)?)
+    }
+
+    /// Synchronizes the block tree with the blocks in storage, which may have been written or removed
+    /// by another instance of the storage, such as the primary instance of a secondary database.
+    pub fn sync_tree(&self) -> Result<()> {
+        // Acquire the write lock on the block tree.
+        let mut tree = self.tree.write();
+
+        // Retrieve the latest block height in storage.
+        let Some(height) = self.storage.id_map().keys_confirmed().max().map(|height| cow_to_copied!(height)) else {
+            *tree = N::merkle_tree_bhp(&[])?;
+            return Ok(());
+        };
+
+        // Update the block tree with the blocks which were added or removed.
+        let num_leaves = u32::try_from(tree.number_of_leaves())?;
+        let updated_tree = match num_leaves.cmp(&(height + 1)) {
+            Ordering::Less => tree.prepare_append(&Self::compute_leaves(&self.storage, num_leaves)?)?,
+            Ordering::Equal => tree.clone(),
+            Ordering::Greater => tree.prepare_remove_last_n(usize::try_from(num_leaves - height - 1)?)?,
+        };
+
+        // If the blocks were replaced instead, recompute the block tree from scratch.
+        *tree = match self.storage.get_state_root(height)? == Some((*updated_tree.root()).into()) {
+            true => updated_tree,
+            false => Self::compute_tree(&self.storage, 0)?,
+        };
+        Ok(())
+    }
+
     /// Stores the given block into storage.
     pub fn insert(&self, block: &Block<N>) -> Result<()> {
         // Acquire the write lock on the block tree.
@@ -1052,6 +1115,15 @@ impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
         Ok(())
     }
 
+    /// Stores the finalize operations of the ratifications of the given block, once the block is finalized.
+    pub fn insert_ratified_finalize_operations(
+        &self,
+        block_hash: &N::BlockHash,
+        operations: Vec<FinalizeOperation<N>>,
+    ) -> Result<()> {
+        self.storage.insert_ratified_finalize_operations(block_hash, operations)
+    }
+
     /// Reverts the Merkle tree to its shape before the insertion of the last 'n' blocks.
     pub fn remove_last_n_from_tree_only(&self, n: u32) -> Result<()> {
         // Ensure 'n' is non-zero.
@@ -1244,6 +1316,15 @@ impl<N: Network, B: BlockStorage<N>> BlockStore<N, B> {
         self.storage.get_block_ratifications(block_hash)
     }
 
+    /// Returns the finalize operations of the block ratifications for the given `block hash`,
+    /// or `None` if they were not recorded.
+    pub fn get_ratified_finalize_operations(
+        &self,
+        block_hash: &N::BlockHash,
+    ) -> Result<Option<Vec<FinalizeOperation<N>>>> {
+        self.storage.get_ratified_finalize_operations(block_hash)
+    }
+
     /// Returns the block solutions for the given `block hash`.
     pub fn get_block_solutions(&self, block_hash: &N::BlockHash) -> Result<Solutions<N>> {
         self.storage.get_block_solutions(block_hash)
@@ -1508,4 +1589,35 @@ mod tests {
             );
         }
     }
+
+    #[cfg(feature = "rocks")]
+    #[test]
+    fn test_ratified_finalize_operations_belong_to_paused_batch() {
+        use crate::helpers::rocksdb::BlockDB;
+
+        let rng = &mut TestRng::default();
+
+        // Sample the block hash and the finalize operations.
+        let block_hash = ledger_test_helpers::sample_genesis_block(rng).hash();
+        let operations = vec![FinalizeOperation::InitializeMapping(Field::from_u64(rng.gen()))];
+
+        // Initialize a new block store.
+        let temp_dir = tempfile::tempdir().expect("Failed to open temporary directory").into_path();
+        let block_store = BlockStore::<CurrentNetwork, BlockDB<_>>::open(StorageMode::from(temp_dir)).unwrap();
+
+        // Store the operations while the atomic writes are paused, as when a block is added.
+        block_store.pause_atomic_writes().unwrap();
+        block_store.insert_ratified_finalize_operations(&block_hash, operations.clone()).unwrap();
+        assert!(block_store.get_ratified_finalize_operations(&block_hash).unwrap().is_none());
+
+        // Discard the paused batch, as when unpausing fails, and ensure nothing was stored.
+        block_store.unpause_atomic_writes::<true>().unwrap();
+        assert!(block_store.get_ratified_finalize_operations(&block_hash).unwrap().is_none());
+
+        // Ensure the operations are stored once the paused batch is written.
+        block_store.pause_atomic_writes().unwrap();
+        block_store.insert_ratified_finalize_operations(&block_hash, operations.clone()).unwrap();
+        block_store.unpause_atomic_writes::<false>().unwrap();
+        assert_eq!(block_store.get_ratified_finalize_operations(&block_hash).unwrap(), Some(operations));
+    }
 }
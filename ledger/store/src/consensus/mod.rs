@@ -27,6 +27,7 @@ use console::network::prelude::*;
 use aleo_std_storage::StorageMode;
 use anyhow::Result;
 use core::marker::PhantomData;
+use std::path::Path;
 
 /// A trait for consensus storage.
 pub trait ConsensusStorage<N: Network>: 'static + Clone + Send + Sync {
@@ -101,6 +102,27 @@ pub trait ConsensusStorage<N: Network>: 'static + Clone + Send + Sync {
         self.finalize_store().finish_atomic()?;
         self.block_store().finish_atomic()
     }
+
+    /// Creates a consistent, point-in-time checkpoint of the storage in the given directory.
+    fn create_checkpoint(&self, _path: &Path) -> Result<()> {
+        bail!("The storage does not support checkpoints")
+    }
+
+    /// Restores the storage for the given storage mode from the checkpoint in the given directory.
+    fn restore_checkpoint<S: Clone + Into<StorageMode>>(_checkpoint: &Path, _storage: S) -> Result<()> {
+        bail!("The storage does not support checkpoints")
+    }
+
+    /// Opens the storage for the given storage mode as a read-only secondary instance, which keeps its own
+    /// state in the `secondary` directory, and can be opened while the storage is written by another process.
+    fn open_secondary<S: Clone + Into<StorageMode>>(_storage: S, _secondary: &Path) -> Result<Self> {
+        bail!("The storage does not support secondary instances")
+    }
+
+    /// Catches up with the writes of the primary instance, if the storage is a secondary instance.
+    fn catch_up_with_primary(&self) -> Result<()> {
+        bail!("The storage does not support secondary instances")
+    }
 }
 
 /// The consensus store.
@@ -121,6 +143,18 @@ impl<N: Network, C: ConsensusStorage<N>> ConsensusStore<N, C> {
         Ok(Self { storage, _phantom: PhantomData })
     }
 
+    /// Initializes the consensus store as a read-only secondary instance of the storage, which may be written
+    /// by another (primary) process at the same time. The secondary instance keeps its own state in the given
+    /// directory, and only observes the writes of the primary instance when it catches up with it.
+    ///
+    /// Note: This must be called before the storage is opened, and writes to the store are rejected.
+    pub fn open_secondary<S: Clone + Into<StorageMode>, P: AsRef<Path>>(storage: S, secondary: P) -> Result<Self> {
+        // Initialize the consensus storage.
+        let storage = C::open_secondary(storage, secondary.as_ref())?;
+        // Return the consensus store.
+        Ok(Self { storage, _phantom: PhantomData })
+    }
+
     /// Initializes a consensus store from storage.
     pub fn from(storage: C) -> Self {
         Self { storage, _phantom: PhantomData }
@@ -185,4 +219,56 @@ impl<N: Network, C: ConsensusStorage<N>> ConsensusStore<N, C> {
     pub fn storage_mode(&self) -> &StorageMode {
         self.storage.storage_mode()
     }
+
+    /// Catches up with the writes of the primary instance, and updates the block tree accordingly.
+    /// Note: This may only be called on a secondary instance of the storage.
+    pub fn catch_up_with_primary(&self) -> Result<()> {
+        self.storage.catch_up_with_primary()?;
+        self.storage.block_store().sync_tree()
+    }
+
+    /// Creates a consistent, point-in-time checkpoint of the storage in the given directory, which must not exist.
+    /// The ledger may continue to advance while the checkpoint is created, and it only contains complete blocks.
+    pub fn create_checkpoint<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.storage.create_checkpoint(path.as_ref())
+    }
+
+    /// Restores the storage for the given storage mode from the checkpoint in the given directory,
+    /// and returns the consensus store once the latest block of the checkpoint is validated.
+    ///
+    /// Note: This must be called before the storage is opened, and the storage must not exist.
+    pub fn restore_checkpoint<P: AsRef<Path>, S: Clone + Into<StorageMode>>(checkpoint: P, storage: S) -> Result<Self> {
+        // Restore the storage.
+        C::restore_checkpoint(checkpoint.as_ref(), storage.clone())?;
+        // Open the consensus store.
+        let store = Self::open(storage)?;
+
+        // Retrieve the latest block.
+        let block_store = store.block_store();
+        let Some(height) = block_store.heights().max().map(|height| *height) else {
+            bail!("The checkpoint does not contain any blocks")
+        };
+        let Some(block_hash) = block_store.get_block_hash(height)? else {
+            bail!("The checkpoint is missing the hash of its latest block {height}")
+        };
+        let Some(block) = block_store.get_block(&block_hash)? else {
+            bail!("The checkpoint is missing its latest block {height} ('{block_hash}')")
+        };
+
+        // Ensure the latest block is consistent with the rest of the checkpoint.
+        ensure!(
+            block.height() == height && block.hash() == block_hash,
+            "The latest block {height} of the checkpoint does not match its hash '{block_hash}'"
+        );
+        ensure!(
+            block_store.get_previous_block_hash(height)? == Some(block.previous_hash()),
+            "The latest block {height} of the checkpoint is not linked to the previous block"
+        );
+        ensure!(
+            block_store.get_state_root(height)? == Some(block_store.current_state_root()),
+            "The state root of the latest block {height} of the checkpoint is incorrect"
+        );
+
+        Ok(store)
+    }
 }
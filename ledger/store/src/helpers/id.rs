@@ -12,7 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-/// The RocksDB map prefix broken down into the entry category and the specific type of the entry.
+/// The storage map prefix broken down into the entry category and the specific type of the entry.
 // Note: the order of these variants can be changed at any point in time.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(u16)]
@@ -28,6 +28,7 @@ pub enum MapID {
     TransitionInput(TransitionInputMap),
     TransitionOutput(TransitionOutputMap),
     Program(ProgramMap),
+    Metadata(MetadataMap),
     #[cfg(test)]
     Test(TestMap),
 }
@@ -46,13 +47,14 @@ impl From<MapID> for u16 {
             MapID::TransitionInput(id) => id as u16,
             MapID::TransitionOutput(id) => id as u16,
             MapID::Program(id) => id as u16,
+            MapID::Metadata(id) => id as u16,
             #[cfg(test)]
             MapID::Test(id) => id as u16,
         }
     }
 }
 
-/// The RocksDB map prefix for BFT-related entries.
+/// The storage map prefix for BFT-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -62,7 +64,7 @@ pub enum BFTMap {
     AbortedTransmissionIDs = DataID::BFTAbortedTransmissionIDsMap as u16,
 }
 
-/// The RocksDB map prefix for block-related entries.
+/// The storage map prefix for block-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -85,9 +87,10 @@ pub enum BlockMap {
     RejectedOrAbortedTransactionID = DataID::BlockRejectedOrAbortedTransactionIDMap as u16,
     ConfirmedTransactions = DataID::BlockConfirmedTransactionsMap as u16,
     RejectedDeploymentOrExecution = DataID::BlockRejectedDeploymentOrExecutionMap as u16,
+    RatifiedFinalizeOperations = DataID::BlockRatifiedFinalizeOperationsMap as u16,
 }
 
-/// The RocksDB map prefix for committee-related entries.
+/// The storage map prefix for committee-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -98,7 +101,7 @@ pub enum CommitteeMap {
     Committee = DataID::CommitteeMap as u16,
 }
 
-/// The RocksDB map prefix for deployment-related entries.
+/// The storage map prefix for deployment-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -113,7 +116,7 @@ pub enum DeploymentMap {
     Certificate = DataID::DeploymentCertificateMap as u16,
 }
 
-/// The RocksDB map prefix for execution-related entries.
+/// The storage map prefix for execution-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -124,7 +127,7 @@ pub enum ExecutionMap {
     Inclusion = DataID::ExecutionInclusionMap as u16,
 }
 
-/// The RocksDB map prefix for fee-related entries.
+/// The storage map prefix for fee-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -134,7 +137,7 @@ pub enum FeeMap {
     ReverseFee = DataID::FeeReverseFeeMap as u16,
 }
 
-/// The RocksDB map prefix for transition input entries.
+/// The storage map prefix for transition input entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -150,7 +153,7 @@ pub enum TransitionInputMap {
     ExternalRecord = DataID::InputExternalRecordMap as u16,
 }
 
-/// The RocksDB map prefix for transition output entries.
+/// The storage map prefix for transition output entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -167,16 +170,18 @@ pub enum TransitionOutputMap {
     Future = DataID::OutputFutureMap as u16,
 }
 
-/// The RocksDB map prefix for transaction-related entries.
+/// The storage map prefix for transaction-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[repr(u16)]
 pub enum TransactionMap {
     ID = DataID::TransactionIDMap as u16,
+    FeePayerIndex = DataID::TransactionFeePayerIndexMap as u16,
+    FeePayerIndexLength = DataID::TransactionFeePayerIndexLengthMap as u16,
 }
 
-/// The RocksDB map prefix for transition-related entries.
+/// The storage map prefix for transition-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -188,9 +193,14 @@ pub enum TransitionMap {
     TCM = DataID::TransitionTCMMap as u16,
     ReverseTCM = DataID::TransitionReverseTCMMap as u16,
     SCM = DataID::TransitionSCMMap as u16,
+    AddressIndex = DataID::TransitionAddressIndexMap as u16,
+    LocatorIndex = DataID::TransitionLocatorIndexMap as u16,
+    AddressIndexLength = DataID::TransitionAddressIndexLengthMap as u16,
+    LocatorIndexLength = DataID::TransitionLocatorIndexLengthMap as u16,
+    Indexes = DataID::TransitionIndexesMap as u16,
 }
 
-/// The RocksDB map prefix for program-related entries.
+/// The storage map prefix for program-related entries.
 // Note: the order of these variants can be changed at any point in time,
 // as long as the corresponding DataID values remain the same.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -198,9 +208,21 @@ pub enum TransitionMap {
 pub enum ProgramMap {
     ProgramID = DataID::ProgramIDMap as u16,
     KeyValueID = DataID::KeyValueMap as u16,
+    HistoryHeights = DataID::ProgramHistoryHeightsMap as u16,
+    History = DataID::ProgramHistoryMap as u16,
 }
 
-/// The RocksDB map prefix for test-related entries.
+/// The storage map prefix for the metadata of the storage itself.
+// Note: the order of these variants can be changed at any point in time,
+// as long as the corresponding DataID values remain the same.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u16)]
+pub enum MetadataMap {
+    SchemaVersion = DataID::SchemaVersionMap as u16,
+    MigrationCursor = DataID::MigrationCursorMap as u16,
+}
+
+/// The storage map prefix for test-related entries.
 // Note: the order of these variants can be changed at any point in time.
 #[cfg(test)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
@@ -213,7 +235,7 @@ pub enum TestMap {
     Test5 = DataID::Test5 as u16,
 }
 
-/// The RocksDB map prefix.
+/// The storage map prefix.
 // Note: the order of these variants can NOT be changed once the database is populated:
 // - any new variant MUST be added as the last one (ignoring the Test one)
 // - any deprecated variant MUST remain in its position (it can't be removed)
@@ -292,6 +314,23 @@ enum DataID {
     // Program
     ProgramIDMap,
     KeyValueMap,
+    // Metadata
+    SchemaVersionMap,
+    MigrationCursorMap,
+    // Index
+    TransitionIndexesMap,
+    TransitionAddressIndexMap,
+    TransitionLocatorIndexMap,
+    TransactionFeePayerIndexMap,
+    // History
+    ProgramHistoryHeightsMap,
+    ProgramHistoryMap,
+    // Block (ratified finalize operations)
+    BlockRatifiedFinalizeOperationsMap,
+    // Index (lengths)
+    TransitionAddressIndexLengthMap,
+    TransitionLocatorIndexLengthMap,
+    TransactionFeePayerIndexLengthMap,
 
     // Testing
     #[cfg(test)]
@@ -19,6 +19,7 @@ use crate::{
     CommitteeStorage,
     CommitteeStore,
     FinalizeStorage,
+    MappingChange,
 };
 use console::{
     prelude::*,
@@ -38,6 +39,10 @@ pub struct FinalizeMemory<N: Network> {
     program_id_map: MemoryMap<ProgramID<N>, IndexSet<Identifier<N>>>,
     /// The key-value map.
     key_value_map: NestedMemoryMap<(ProgramID<N>, Identifier<N>), Plaintext<N>, Value<N>>,
+    /// The history heights map.
+    history_heights_map: MemoryMap<u32, u32>,
+    /// The history map.
+    history_map: NestedMemoryMap<u32, u32, MappingChange<N>>,
     /// The storage mode.
     storage_mode: StorageMode,
 }
@@ -47,6 +52,8 @@ impl<N: Network> FinalizeStorage<N> for FinalizeMemory<N> {
     type CommitteeStorage = CommitteeMemory<N>;
     type ProgramIDMap = MemoryMap<ProgramID<N>, IndexSet<Identifier<N>>>;
     type KeyValueMap = NestedMemoryMap<(ProgramID<N>, Identifier<N>), Plaintext<N>, Value<N>>;
+    type HistoryHeightsMap = MemoryMap<u32, u32>;
+    type HistoryMap = NestedMemoryMap<u32, u32, MappingChange<N>>;
 
     /// Initializes the finalize storage.
     fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
@@ -57,6 +64,8 @@ impl<N: Network> FinalizeStorage<N> for FinalizeMemory<N> {
             committee_store,
             program_id_map: MemoryMap::default(),
             key_value_map: NestedMemoryMap::default(),
+            history_heights_map: MemoryMap::default(),
+            history_map: NestedMemoryMap::default(),
             storage_mode: storage.into(),
         })
     }
@@ -82,6 +91,16 @@ impl<N: Network> FinalizeStorage<N> for FinalizeMemory<N> {
         &self.key_value_map
     }
 
+    /// Returns the history heights map.
+    fn history_heights_map(&self) -> &Self::HistoryHeightsMap {
+        &self.history_heights_map
+    }
+
+    /// Returns the history map.
+    fn history_map(&self) -> &Self::HistoryMap {
+        &self.history_map
+    }
+
     /// Returns the storage mode.
     fn storage_mode(&self) -> &StorageMode {
         &self.storage_mode
@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::{
-    helpers::memory::{MemoryMap, TransitionMemory},
+    helpers::memory::{MemoryMap, NestedMemoryMap, TransitionMemory},
     DeploymentStorage,
     DeploymentStore,
     ExecutionStorage,
@@ -27,6 +27,7 @@ use crate::{
 use console::{
     prelude::*,
     program::{Identifier, ProgramID, ProgramOwner},
+    types::Address,
 };
 use synthesizer_program::Program;
 use synthesizer_snark::{Certificate, Proof, VerifyingKey};
@@ -42,6 +43,10 @@ pub struct TransactionMemory<N: Network> {
     execution_store: ExecutionStore<N, ExecutionMemory<N>>,
     /// The fee store.
     fee_store: FeeStore<N, FeeMemory<N>>,
+    /// The fee payer index map.
+    fee_payer_index_map: NestedMemoryMap<Address<N>, u32, N::TransactionID>,
+    /// The fee payer index length map.
+    fee_payer_index_length_map: MemoryMap<Address<N>, u32>,
 }
 
 #[rustfmt::skip]
@@ -51,6 +56,8 @@ impl<N: Network> TransactionStorage<N> for TransactionMemory<N> {
     type ExecutionStorage = ExecutionMemory<N>;
     type FeeStorage = FeeMemory<N>;
     type TransitionStorage = TransitionMemory<N>;
+    type FeePayerIndexMap = NestedMemoryMap<Address<N>, u32, N::TransactionID>;
+    type FeePayerIndexLengthMap = MemoryMap<Address<N>, u32>;
 
     /// Initializes the transaction storage.
     fn open(transition_store: TransitionStore<N, Self::TransitionStorage>) -> Result<Self> {
@@ -61,7 +68,14 @@ impl<N: Network> TransactionStorage<N> for TransactionMemory<N> {
         // Initialize the execution store.
         let execution_store = ExecutionStore::<N, ExecutionMemory<N>>::open(fee_store.clone())?;
         // Return the transaction storage.
-        Ok(Self { id_map: MemoryMap::default(), deployment_store, execution_store, fee_store })
+        Ok(Self {
+            id_map: MemoryMap::default(),
+            deployment_store,
+            execution_store,
+            fee_store,
+            fee_payer_index_map: NestedMemoryMap::default(),
+            fee_payer_index_length_map: MemoryMap::default(),
+        })
     }
 
     /// Returns the ID map.
@@ -83,6 +97,16 @@ impl<N: Network> TransactionStorage<N> for TransactionMemory<N> {
     fn fee_store(&self) -> &FeeStore<N, Self::FeeStorage> {
         &self.fee_store
     }
+
+    /// Returns the fee payer index map.
+    fn fee_payer_index_map(&self) -> &Self::FeePayerIndexMap {
+        &self.fee_payer_index_map
+    }
+
+    /// Returns the fee payer index length map.
+    fn fee_payer_index_length_map(&self) -> &Self::FeePayerIndexLengthMap {
+        &self.fee_payer_index_length_map
+    }
 }
 
 /// An in-memory deployment storage.
@@ -12,11 +12,19 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{helpers::memory::MemoryMap, InputStorage, InputStore, OutputStorage, OutputStore, TransitionStorage};
+use crate::{
+    helpers::memory::{MemoryMap, NestedMemoryMap},
+    InputStorage,
+    InputStore,
+    OutputStorage,
+    OutputStore,
+    StorageIndex,
+    TransitionStorage,
+};
 use console::{
     prelude::*,
     program::{Ciphertext, Future, Identifier, Plaintext, ProgramID, Record},
-    types::{Field, Group},
+    types::{Address, Field, Group},
 };
 
 use aleo_std_storage::StorageMode;
@@ -40,6 +48,16 @@ pub struct TransitionMemory<N: Network> {
     reverse_tcm_map: MemoryMap<Field<N>, N::TransitionID>,
     /// The signer commitments.
     scm_map: MemoryMap<N::TransitionID, Field<N>>,
+    /// The address index map.
+    address_index_map: NestedMemoryMap<Address<N>, u32, N::TransitionID>,
+    /// The locator index map.
+    locator_index_map: NestedMemoryMap<(ProgramID<N>, Identifier<N>), u32, N::TransitionID>,
+    /// The address index length map.
+    address_index_length_map: MemoryMap<Address<N>, u32>,
+    /// The locator index length map.
+    locator_index_length_map: MemoryMap<(ProgramID<N>, Identifier<N>), u32>,
+    /// The map of enabled secondary indexes.
+    indexes_map: MemoryMap<StorageIndex, bool>,
 }
 
 #[rustfmt::skip]
@@ -52,6 +70,11 @@ impl<N: Network> TransitionStorage<N> for TransitionMemory<N> {
     type TCMMap = MemoryMap<N::TransitionID, Field<N>>;
     type ReverseTCMMap = MemoryMap<Field<N>, N::TransitionID>;
     type SCMMap = MemoryMap<N::TransitionID, Field<N>>;
+    type AddressIndexMap = NestedMemoryMap<Address<N>, u32, N::TransitionID>;
+    type LocatorIndexMap = NestedMemoryMap<(ProgramID<N>, Identifier<N>), u32, N::TransitionID>;
+    type AddressIndexLengthMap = MemoryMap<Address<N>, u32>;
+    type LocatorIndexLengthMap = MemoryMap<(ProgramID<N>, Identifier<N>), u32>;
+    type IndexesMap = MemoryMap<StorageIndex, bool>;
 
     /// Initializes the transition storage.
     fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
@@ -64,6 +87,11 @@ impl<N: Network> TransitionStorage<N> for TransitionMemory<N> {
             tcm_map: MemoryMap::default(),
             reverse_tcm_map: MemoryMap::default(),
             scm_map: MemoryMap::default(),
+            address_index_map: NestedMemoryMap::default(),
+            locator_index_map: NestedMemoryMap::default(),
+            address_index_length_map: MemoryMap::default(),
+            locator_index_length_map: MemoryMap::default(),
+            indexes_map: MemoryMap::default(),
         })
     }
 
@@ -106,6 +134,31 @@ impl<N: Network> TransitionStorage<N> for TransitionMemory<N> {
     fn scm_map(&self) -> &Self::SCMMap {
         &self.scm_map
     }
+
+    /// Returns the address index map.
+    fn address_index_map(&self) -> &Self::AddressIndexMap {
+        &self.address_index_map
+    }
+
+    /// Returns the locator index map.
+    fn locator_index_map(&self) -> &Self::LocatorIndexMap {
+        &self.locator_index_map
+    }
+
+    /// Returns the address index length map.
+    fn address_index_length_map(&self) -> &Self::AddressIndexLengthMap {
+        &self.address_index_length_map
+    }
+
+    /// Returns the locator index length map.
+    fn locator_index_length_map(&self) -> &Self::LocatorIndexLengthMap {
+        &self.locator_index_length_map
+    }
+
+    /// Returns the map of enabled secondary indexes.
+    fn indexes_map(&self) -> &Self::IndexesMap {
+        &self.indexes_map
+    }
 }
 
 /// An in-memory transition input storage.
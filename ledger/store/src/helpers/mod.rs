@@ -15,6 +15,11 @@
 pub mod memory;
 #[cfg(feature = "rocks")]
 pub mod rocksdb;
+#[cfg(feature = "sled")]
+pub mod sled;
+
+#[cfg(any(feature = "rocks", feature = "sled"))]
+mod id;
 
 #[cfg(test)]
 pub(crate) mod test_helpers;
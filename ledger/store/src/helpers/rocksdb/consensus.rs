@@ -13,7 +13,7 @@
 // limitations under the License.
 
 use crate::{
-    helpers::rocksdb::{BlockDB, FinalizeDB, TransactionDB, TransitionDB},
+    helpers::rocksdb::{BlockDB, Database, FinalizeDB, RocksDB, TransactionDB, TransitionDB},
     BlockStore,
     ConsensusStorage,
     FinalizeStore,
@@ -21,6 +21,7 @@ use crate::{
 use console::prelude::*;
 
 use aleo_std_storage::StorageMode;
+use std::path::Path;
 
 /// An RocksDB consensus storage.
 #[derive(Clone)]
@@ -60,4 +61,28 @@ impl<N: Network> ConsensusStorage<N> for ConsensusDB<N> {
     fn block_store(&self) -> &BlockStore<N, Self::BlockStorage> {
         &self.block_store
     }
+
+    /// Creates a consistent, point-in-time checkpoint of the storage in the given directory.
+    fn create_checkpoint(&self, path: &Path) -> Result<()> {
+        // Note: All of the maps share the same database, so its checkpoint is consistent across the stores.
+        RocksDB::open(N::ID, self.storage_mode().clone())?.create_checkpoint(path)
+    }
+
+    /// Restores the storage for the given storage mode from the checkpoint in the given directory.
+    fn restore_checkpoint<S: Clone + Into<StorageMode>>(checkpoint: &Path, storage: S) -> Result<()> {
+        let destination = aleo_std_storage::aleo_ledger_dir(N::ID, storage.into());
+        RocksDB::restore_checkpoint(checkpoint, destination, N::ID)
+    }
+
+    /// Opens the storage for the given storage mode as a read-only secondary instance.
+    fn open_secondary<S: Clone + Into<StorageMode>>(storage: S, secondary: &Path) -> Result<Self> {
+        // Note: All of the maps share the same database, which is opened as a secondary instance.
+        RocksDB::set_secondary_dir(secondary)?;
+        Self::open(storage)
+    }
+
+    /// Catches up with the writes of the primary instance, if the storage is a secondary instance.
+    fn catch_up_with_primary(&self) -> Result<()> {
+        RocksDB::open(N::ID, self.storage_mode().clone())?.try_catch_up_with_primary()
+    }
 }
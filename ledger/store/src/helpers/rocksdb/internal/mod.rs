@@ -12,8 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-mod id;
-pub use id::*;
+pub use crate::helpers::id::*;
 
 mod map;
 pub use map::*;
@@ -21,11 +20,14 @@ pub use map::*;
 mod nested_map;
 pub use nested_map::*;
 
+mod schema;
+pub use schema::*;
+
 #[cfg(test)]
 mod tests;
 
 use aleo_std_storage::StorageMode;
-use anyhow::{bail, ensure, Result};
+use anyhow::{anyhow, bail, ensure, Result};
 use once_cell::sync::OnceCell;
 use parking_lot::Mutex;
 use serde::{de::DeserializeOwned, Serialize};
@@ -34,6 +36,7 @@ use std::{
     marker::PhantomData,
     mem,
     ops::Deref,
+    path::{Path, PathBuf},
     sync::{
         atomic::{AtomicBool, AtomicUsize, Ordering},
         Arc,
@@ -42,6 +45,11 @@ use std::{
 
 pub const PREFIX_LEN: usize = 4; // N::ID (u16) + DataID (u16)
 
+/// The database instance of this process.
+static DB: OnceCell<RocksDB> = OnceCell::new();
+/// The directory of the secondary instance, if the database is opened as a secondary instance.
+static SECONDARY_DIR: OnceCell<PathBuf> = OnceCell::new();
+
 pub trait Database {
     /// Opens the database.
     fn open<S: Clone + Into<StorageMode>>(network_id: u16, storage: S) -> Result<Self>
@@ -82,6 +90,8 @@ pub struct RocksDB {
     network_id: u16,
     /// The storage mode.
     storage_mode: StorageMode,
+    /// A flag indicating whether the database is a read-only secondary instance.
+    is_secondary: bool,
     /// The low-level database transaction that gets executed atomically at the end
     /// of a real-run `atomic_finalize` or the outermost `atomic_batch_scope`.
     pub(super) atomic_batch: Arc<Mutex<rocksdb::WriteBatch>>,
@@ -100,6 +110,7 @@ impl Clone for RocksDB {
             rocksdb: self.rocksdb.clone(),
             network_id: self.network_id,
             storage_mode: self.storage_mode.clone(),
+            is_secondary: self.is_secondary,
             atomic_batch: self.atomic_batch.clone(),
             atomic_depth: self.atomic_depth.clone(),
             atomic_writes_paused: self.atomic_writes_paused.clone(),
@@ -121,38 +132,14 @@ impl Database for RocksDB {
     ///
     /// In production mode, the database opens directory `~/.aleo/storage/ledger-{network}`.
     /// In development mode, the database opens directory `/path/to/repo/.ledger-{network}-{id}`.
+    /// If a secondary directory is set, the database is opened as a read-only secondary instance.
     fn open<S: Clone + Into<StorageMode>>(network_id: u16, storage: S) -> Result<Self> {
-        static DB: OnceCell<RocksDB> = OnceCell::new();
-
         // Retrieve the database.
         let database = DB
             .get_or_try_init(|| {
-                // Customize database options.
-                let mut options = rocksdb::Options::default();
-                options.set_compression_type(rocksdb::DBCompressionType::Lz4);
-
-                // Register the prefix length.
-                let prefix_extractor = rocksdb::SliceTransform::create_fixed_prefix(PREFIX_LEN);
-                options.set_prefix_extractor(prefix_extractor);
-
                 let primary = aleo_std_storage::aleo_ledger_dir(network_id, storage.clone().into());
-                let rocksdb = {
-                    options.increase_parallelism(2);
-                    options.set_max_background_jobs(4);
-                    options.create_if_missing(true);
-
-                    Arc::new(rocksdb::DB::open(&options, primary)?)
-                };
-
-                Ok::<_, anyhow::Error>(RocksDB {
-                    rocksdb,
-                    network_id,
-                    storage_mode: storage.clone().into(),
-                    atomic_batch: Default::default(),
-                    atomic_depth: Default::default(),
-                    atomic_writes_paused: Default::default(),
-                    default_readopts: Default::default(),
-                })
+                let secondary = SECONDARY_DIR.get().map(PathBuf::as_path);
+                RocksDB::open_at(primary, secondary, network_id, storage.clone().into())
             })?
             .clone();
 
@@ -222,6 +209,137 @@ impl Database for RocksDB {
 }
 
 impl RocksDB {
+    /// Opens the database in the given directory, and ensures it has the current schema version.
+    /// If a `secondary` directory is given, the database is opened as a secondary instance, which
+    /// keeps its own logs in that directory, and does not take the lock of the primary instance.
+    fn open_at(primary: PathBuf, secondary: Option<&Path>, network_id: u16, storage_mode: StorageMode) -> Result<Self> {
+        // Customize database options.
+        let mut options = rocksdb::Options::default();
+        options.set_compression_type(rocksdb::DBCompressionType::Lz4);
+
+        // Register the prefix length.
+        let prefix_extractor = rocksdb::SliceTransform::create_fixed_prefix(PREFIX_LEN);
+        options.set_prefix_extractor(prefix_extractor);
+
+        let rocksdb = {
+            options.increase_parallelism(2);
+            options.set_max_background_jobs(4);
+            options.create_if_missing(true);
+
+            // Keep these around as options for configuration testing.
+
+            // options.set_max_subcompactions(4);
+            // options.set_use_direct_io_for_flush_and_compaction(true);
+            // options.set_bytes_per_sync(1 << 28);
+            // options.set_compaction_readahead_size(1 << 28);
+            // options.set_max_write_buffer_number(16);
+            // options.set_min_write_buffer_number_to_merge(8);
+            // options.set_compression_type(rocksdb::DBCompressionType::None);
+            // options.set_bottommost_compression_type(rocksdb::DBCompressionType::None);
+            // options.set_write_buffer_size(1 << 28);
+
+            match secondary {
+                Some(secondary) => {
+                    // A secondary instance must keep all the files of the primary instance open.
+                    options.set_max_open_files(-1);
+                    Arc::new(rocksdb::DB::open_as_secondary(&options, &primary, secondary)?)
+                }
+                None => Arc::new(rocksdb::DB::open(&options, primary)?),
+            }
+        };
+
+        let database = RocksDB {
+            rocksdb,
+            network_id,
+            storage_mode,
+            is_secondary: secondary.is_some(),
+            atomic_batch: Default::default(),
+            atomic_depth: Default::default(),
+            atomic_writes_paused: Default::default(),
+            default_readopts: Default::default(),
+        };
+
+        // Ensure the database is written with the current schema version, migrating it if necessary.
+        // Note: A secondary instance can not migrate the database, which is left to the primary instance.
+        match database.is_secondary {
+            true => database.ensure_schema_version()?,
+            false => database.check_schema()?,
+        }
+
+        Ok(database)
+    }
+
+    /// Sets the directory of the secondary instance, so that the database of this process is opened
+    /// as a read-only secondary instance of the database written by another (primary) process.
+    /// Note: This must be called before the database is opened.
+    pub fn set_secondary_dir<P: Into<PathBuf>>(secondary: P) -> Result<()> {
+        ensure!(DB.get().is_none(), "The database is already open");
+        SECONDARY_DIR.set(secondary.into()).map_err(|_| anyhow!("The secondary directory is already set"))
+    }
+
+    /// Returns `true` if the database is a read-only secondary instance.
+    pub const fn is_secondary(&self) -> bool {
+        self.is_secondary
+    }
+
+    /// Catches up with the writes of the primary instance, which become visible to subsequent reads.
+    /// Note: Writes which are still pending in an atomic batch of the primary instance are not visible.
+    pub fn try_catch_up_with_primary(&self) -> Result<()> {
+        ensure!(self.is_secondary, "The database is not a secondary instance");
+        self.rocksdb.try_catch_up_with_primary()?;
+        Ok(())
+    }
+
+    /// Creates a consistent, point-in-time checkpoint of the database in the given directory, which must not exist.
+    ///
+    /// The SST files of the checkpoint are hard-linked when it is on the same filesystem as the database,
+    /// so it is cheap to create, and the database may continue to be written to in the meantime.
+    /// Note: Writes which are still pending in an atomic batch are not included in the checkpoint.
+    pub fn create_checkpoint<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        // Ensure the checkpoint directory does not exist.
+        ensure!(!path.exists(), "The checkpoint directory '{}' already exists", path.display());
+        // Create the checkpoint.
+        rocksdb::checkpoint::Checkpoint::new(&self.rocksdb)?.create_checkpoint(path)?;
+        Ok(())
+    }
+
+    /// Restores the checkpoint in the given directory into the `destination` directory, which must not exist.
+    ///
+    /// The checkpoint is copied, so it remains usable as a backup, and the restored database is opened
+    /// to ensure it has a supported schema version. If this fails, the `destination` directory is removed.
+    /// Note: This must be called before the database in the `destination` directory is opened.
+    pub fn restore_checkpoint<P: AsRef<Path>, Q: AsRef<Path>>(
+        checkpoint: P,
+        destination: Q,
+        network_id: u16,
+    ) -> Result<()> {
+        let (checkpoint, destination) = (checkpoint.as_ref(), destination.as_ref());
+        // Ensure the checkpoint exists, and the destination does not.
+        ensure!(checkpoint.is_dir(), "The checkpoint directory '{}' does not exist", checkpoint.display());
+        ensure!(!destination.exists(), "The destination directory '{}' already exists", destination.display());
+
+        let restore = || -> Result<()> {
+            // Copy the checkpoint files.
+            std::fs::create_dir_all(destination)?;
+            for entry in std::fs::read_dir(checkpoint)? {
+                let entry = entry?;
+                let path = entry.path();
+                ensure!(entry.file_type()?.is_file(), "Unexpected entry '{}' in the checkpoint", path.display());
+                std::fs::copy(&path, destination.join(entry.file_name()))?;
+            }
+            // Open the restored database, which checks its schema version.
+            Self::open_at(destination.to_path_buf(), None, network_id, StorageMode::from(destination.to_path_buf()))?;
+            Ok(())
+        };
+
+        // Remove the partially-restored database on failure.
+        restore().map_err(|error| {
+            let _ = std::fs::remove_dir_all(destination);
+            error.context(format!("Failed to restore the checkpoint '{}'", checkpoint.display()))
+        })
+    }
+
     /// Pause the execution of atomic writes for the entire database.
     fn pause_atomic_writes(&self) -> Result<()> {
         // This operation is only intended to be performed before or after
@@ -289,45 +407,7 @@ impl RocksDB {
         // Prepare the storage mode.
         let storage_mode = StorageMode::from(primary.clone());
 
-        let database = {
-            // Customize database options.
-            let mut options = rocksdb::Options::default();
-            options.set_compression_type(rocksdb::DBCompressionType::Lz4);
-
-            // Register the prefix length.
-            let prefix_extractor = rocksdb::SliceTransform::create_fixed_prefix(PREFIX_LEN);
-            options.set_prefix_extractor(prefix_extractor);
-
-            let rocksdb = {
-                options.increase_parallelism(2);
-                options.set_max_background_jobs(4);
-                options.create_if_missing(true);
-
-                // Keep these around as options for configuration testing.
-
-                // options.set_max_subcompactions(4);
-                // options.set_use_direct_io_for_flush_and_compaction(true);
-                // options.set_bytes_per_sync(1 << 28);
-                // options.set_compaction_readahead_size(1 << 28);
-                // options.set_max_write_buffer_number(16);
-                // options.set_min_write_buffer_number_to_merge(8);
-                // options.set_compression_type(rocksdb::DBCompressionType::None);
-                // options.set_bottommost_compression_type(rocksdb::DBCompressionType::None);
-                // options.set_write_buffer_size(1 << 28);
-
-                Arc::new(rocksdb::DB::open(&options, primary)?)
-            };
-
-            Ok::<_, anyhow::Error>(RocksDB {
-                rocksdb,
-                network_id: u16::MAX,
-                storage_mode: storage_mode.clone(),
-                atomic_batch: Default::default(),
-                atomic_depth: Default::default(),
-                atomic_writes_paused: Default::default(),
-                default_readopts: Default::default(),
-            })
-        }?;
+        let database = Self::open_at(primary, None, u16::MAX, storage_mode.clone())?;
 
         // Ensure the database storage mode match.
         match database.storage_mode == storage_mode {
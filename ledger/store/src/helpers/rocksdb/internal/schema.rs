@@ -0,0 +1,312 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{MapID, MetadataMap, RocksDB};
+
+use anyhow::{bail, ensure, Result};
+use tracing::info;
+
+/// The version of the storage schema written by this version of the library.
+/// Databases written before the schema was versioned are considered to be at version `0`.
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// The registered storage migrations, in ascending order of version.
+/// Migration `i` upgrades a database from schema version `i - 1` to version `i`.
+pub const MIGRATIONS: &[Migration] = &[
+    // The layout of version 1 is identical to the unversioned layout; only the schema version is recorded.
+    Migration {
+        version: 1,
+        description: "record the storage schema version",
+        migrate: |_, _, _| Ok(MigrationStep::Done),
+    },
+];
+
+/// The outcome of a single step of a migration.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MigrationStep {
+    /// The migration is complete.
+    Done,
+    /// The migration must continue from the given cursor.
+    Continue(Vec<u8>),
+}
+
+/// A storage migration, which upgrades the database from `version - 1` to `version`.
+///
+/// A migration is performed in steps; each step receives the cursor returned by the previous step
+/// (or `None` for the first step), and stages its writes in the given batch. Each batch is written
+/// atomically together with the new cursor, so an interrupted migration resumes from its last step.
+pub struct Migration {
+    /// The schema version this migration upgrades the database to.
+    pub version: u32,
+    /// A short description of the migration.
+    pub description: &'static str,
+    /// Performs a single step of the migration.
+    pub migrate: fn(&RocksDB, Option<&[u8]>, &mut rocksdb::WriteBatch) -> Result<MigrationStep>,
+}
+
+impl RocksDB {
+    /// Returns the schema version of the database, or `None` if it has not been recorded.
+    pub fn schema_version(&self) -> Result<Option<u32>> {
+        match self.rocksdb.get_pinned(self.metadata_key(MetadataMap::SchemaVersion))? {
+            Some(bytes) => match <[u8; 4]>::try_from(&bytes[..]) {
+                Ok(bytes) => Ok(Some(u32::from_le_bytes(bytes))),
+                Err(_) => bail!("The storage schema version is malformed"),
+            },
+            None => Ok(None),
+        }
+    }
+
+    /// Ensures the database is at the current schema version, performing any outstanding migrations.
+    pub(super) fn check_schema(&self) -> Result<()> {
+        self.migrate_schema(MIGRATIONS, SCHEMA_VERSION)
+    }
+
+    /// Ensures the database is at the current schema version, without performing any migrations.
+    pub(super) fn ensure_schema_version(&self) -> Result<()> {
+        match self.schema_version()? {
+            Some(SCHEMA_VERSION) => Ok(()),
+            Some(version) => {
+                bail!("The storage schema version ({version}) is not the supported version ({SCHEMA_VERSION})")
+            }
+            None => bail!("The storage schema version is not recorded - the primary instance must be opened first"),
+        }
+    }
+
+    /// Upgrades the database to the `target` schema version, using the given migrations.
+    fn migrate_schema(&self, migrations: &[Migration], target: u32) -> Result<()> {
+        // Ensure the migrations are registered in order, and lead up to the target version.
+        ensure!(
+            migrations.len() == target as usize && migrations.iter().zip(1..).all(|(m, version)| m.version == version),
+            "The storage migrations must be registered in order, up to version {target}"
+        );
+
+        let version_key = self.metadata_key(MetadataMap::SchemaVersion);
+        let cursor_key = self.metadata_key(MetadataMap::MigrationCursor);
+
+        // Determine the current schema version.
+        let current = match self.schema_version()? {
+            Some(version) => version,
+            // A new database is written with the target layout, so it only needs to record the version.
+            None if self.is_empty_network() => {
+                self.rocksdb.put(&version_key, target.to_le_bytes())?;
+                return Ok(());
+            }
+            // A database without a recorded version predates the schema versioning.
+            None => 0,
+        };
+        ensure!(
+            current <= target,
+            "The storage schema version ({current}) is newer than the supported version ({target})"
+        );
+
+        // Perform the outstanding migrations, in order.
+        for migration in &migrations[current as usize..] {
+            // Resume the migration from the last completed step, if it was interrupted.
+            let mut cursor = self.rocksdb.get(&cursor_key)?;
+            let action = if cursor.is_some() { "Resuming" } else { "Performing" };
+            info!("{action} storage migration to version {}: {}", migration.version, migration.description);
+
+            loop {
+                let mut batch = rocksdb::WriteBatch::default();
+                match (migration.migrate)(self, cursor.as_deref(), &mut batch)? {
+                    MigrationStep::Continue(next) => {
+                        batch.put(&cursor_key, &next);
+                        self.rocksdb.write(batch)?;
+                        cursor = Some(next);
+                    }
+                    MigrationStep::Done => {
+                        batch.delete(&cursor_key);
+                        batch.put(&version_key, migration.version.to_le_bytes());
+                        self.rocksdb.write(batch)?;
+                        break;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns the raw key of the given metadata entry.
+    fn metadata_key(&self, map: MetadataMap) -> Vec<u8> {
+        let mut key = self.network_id.to_le_bytes().to_vec();
+        key.extend_from_slice(&u16::from(MapID::Metadata(map)).to_le_bytes());
+        key
+    }
+
+    /// Returns `true` if the database contains no entries for its network.
+    fn is_empty_network(&self) -> bool {
+        let prefix = self.network_id.to_le_bytes();
+        // A raw iterator doesn't allocate.
+        let mut iter = self.rocksdb.raw_iterator();
+        iter.seek(prefix);
+        !matches!(iter.key(), Some(key) if key.starts_with(&prefix))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::helpers::rocksdb::{internal::tests::temp_dir, TestMap};
+    use aleo_std_storage::StorageMode;
+
+    use serial_test::serial;
+    use std::{
+        path::Path,
+        sync::atomic::{AtomicBool, Ordering},
+    };
+
+    /// Determines whether the test migration should be interrupted.
+    static INTERRUPT: AtomicBool = AtomicBool::new(false);
+
+    /// Returns the raw key of the given entry in the test map.
+    fn raw_key(key: u8) -> Vec<u8> {
+        let mut raw_key = u16::MAX.to_le_bytes().to_vec();
+        raw_key.extend_from_slice(&u16::from(MapID::Test(TestMap::Test)).to_le_bytes());
+        raw_key.push(key);
+        raw_key
+    }
+
+    /// Creates a fixture database in the given directory, written by an older version of the library.
+    fn create_fixture(path: &Path, version: Option<u32>, entries: &[(u8, &[u8])]) {
+        let mut options = rocksdb::Options::default();
+        options.create_if_missing(true);
+        let database = rocksdb::DB::open(&options, path).expect("Failed to create the fixture database");
+        for (key, value) in entries {
+            database.put(raw_key(*key), value).unwrap();
+        }
+        if let Some(version) = version {
+            let mut key = u16::MAX.to_le_bytes().to_vec();
+            key.extend_from_slice(&u16::from(MapID::Metadata(MetadataMap::SchemaVersion)).to_le_bytes());
+            database.put(key, version.to_le_bytes()).unwrap();
+        }
+    }
+
+    /// Opens the test database in the given directory.
+    fn open(path: &Path) -> Result<RocksDB> {
+        RocksDB::open_at(path.to_path_buf(), None, u16::MAX, StorageMode::from(path.to_path_buf()))
+    }
+
+    /// A test migration which appends a marker to each value of the test map, one entry per step.
+    fn append_marker(
+        database: &RocksDB,
+        cursor: Option<&[u8]>,
+        batch: &mut rocksdb::WriteBatch,
+    ) -> Result<MigrationStep> {
+        let prefix = &raw_key(0)[..4];
+
+        // Find the first entry after the cursor.
+        let mut iter = database.raw_iterator();
+        match cursor {
+            Some(cursor) => {
+                iter.seek(cursor);
+                if iter.key() == Some(cursor) {
+                    iter.next();
+                }
+            }
+            None => iter.seek(prefix),
+        }
+
+        match (iter.key(), iter.value()) {
+            (Some(key), Some(value)) if key.starts_with(prefix) => {
+                ensure!(!(INTERRUPT.load(Ordering::SeqCst) && key == raw_key(3)), "The migration was interrupted");
+                batch.put(key, [value, b"!"].concat());
+                Ok(MigrationStep::Continue(key.to_vec()))
+            }
+            _ => Ok(MigrationStep::Done),
+        }
+    }
+
+    /// The migrations used for testing, which extend the registered migrations with `append_marker`.
+    const TEST_MIGRATIONS: &[Migration] = &[
+        Migration {
+            version: 1,
+            description: "record the storage schema version",
+            migrate: |_, _, _| Ok(MigrationStep::Done),
+        },
+        Migration { version: 2, description: "append a marker to each value", migrate: append_marker },
+    ];
+
+    #[test]
+    #[serial]
+    fn test_new_database_version() {
+        let database = RocksDB::open_testing(temp_dir(), None).expect("Failed to open storage");
+        assert_eq!(Some(SCHEMA_VERSION), database.schema_version().unwrap());
+    }
+
+    #[test]
+    #[serial]
+    fn test_open_unversioned_fixture() {
+        let path = temp_dir();
+        create_fixture(&path, None, &[(1, b"a"), (2, b"b")]);
+
+        // Ensure the legacy database is upgraded to the current version, and its contents are preserved.
+        let database = open(&path).expect("Failed to open the fixture database");
+        assert_eq!(Some(SCHEMA_VERSION), database.schema_version().unwrap());
+        assert_eq!(Some(b"a".to_vec()), database.get(raw_key(1)).unwrap());
+        assert_eq!(Some(b"b".to_vec()), database.get(raw_key(2)).unwrap());
+    }
+
+    #[test]
+    #[serial]
+    fn test_open_newer_fixture() {
+        let path = temp_dir();
+        create_fixture(&path, Some(SCHEMA_VERSION + 1), &[(1, b"a")]);
+
+        // Ensure a database written with a newer layout is rejected.
+        assert!(open(&path).is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_misordered_migrations() {
+        let database = RocksDB::open_testing(temp_dir(), None).expect("Failed to open storage");
+
+        assert!(database.migrate_schema(&TEST_MIGRATIONS[1..], 2).is_err());
+        assert!(database.migrate_schema(TEST_MIGRATIONS, 3).is_err());
+        assert_eq!(Some(SCHEMA_VERSION), database.schema_version().unwrap());
+    }
+
+    #[test]
+    #[serial]
+    fn test_resumable_migration() {
+        let path = temp_dir();
+        create_fixture(&path, None, &[(1, b"a"), (2, b"b"), (3, b"c"), (4, b"d")]);
+        let database = open(&path).expect("Failed to open the fixture database");
+        assert_eq!(Some(1), database.schema_version().unwrap());
+
+        // Interrupt the migration midway.
+        INTERRUPT.store(true, Ordering::SeqCst);
+        assert!(database.migrate_schema(TEST_MIGRATIONS, 2).is_err());
+        INTERRUPT.store(false, Ordering::SeqCst);
+
+        // Ensure the completed steps are persisted, but the version is unchanged.
+        assert_eq!(Some(1), database.schema_version().unwrap());
+        assert_eq!(Some(raw_key(2)), database.get(database.metadata_key(MetadataMap::MigrationCursor)).unwrap());
+        assert_eq!(Some(b"b!".to_vec()), database.get(raw_key(2)).unwrap());
+        assert_eq!(Some(b"c".to_vec()), database.get(raw_key(3)).unwrap());
+
+        // Resume the migration, and ensure each entry was migrated exactly once.
+        database.migrate_schema(TEST_MIGRATIONS, 2).expect("Failed to resume the migration");
+        assert_eq!(Some(2), database.schema_version().unwrap());
+        assert_eq!(None, database.get(database.metadata_key(MetadataMap::MigrationCursor)).unwrap());
+        for (key, value) in [(1, b"a!"), (2, b"b!"), (3, b"c!"), (4, b"d!")] {
+            assert_eq!(Some(value.to_vec()), database.get(raw_key(key)).unwrap());
+        }
+
+        // Ensure a database at the target version is left unchanged.
+        database.migrate_schema(TEST_MIGRATIONS, 2).unwrap();
+        assert_eq!(Some(b"a!".to_vec()), database.get(raw_key(1)).unwrap());
+    }
+}
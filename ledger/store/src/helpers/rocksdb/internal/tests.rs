@@ -13,10 +13,11 @@
 // limitations under the License.
 
 use crate::helpers::{
-    rocksdb::{MapID, RocksDB, TestMap as TestMapID},
+    rocksdb::{MapID, MetadataMap, RocksDB, TestMap as TestMapID, SCHEMA_VERSION},
     Map,
     MapRead,
 };
+use aleo_std_storage::StorageMode;
 use console::{
     network::{MainnetV0, Network},
     prelude::{TestRng, Uniform},
@@ -214,3 +215,87 @@ fn test_iterator_ordering() {
         assert_eq!(&*v1, v2);
     }
 }
+
+#[test]
+#[serial]
+fn test_checkpoint() {
+    // Returns the raw key of the given map and key in the test database.
+    let raw_key = |map_id: MapID, key: &[u8]| {
+        let mut raw_key = u16::MAX.to_le_bytes().to_vec();
+        raw_key.extend_from_slice(&u16::from(map_id).to_le_bytes());
+        raw_key.extend_from_slice(key);
+        raw_key
+    };
+    let test_key = |key: u8| raw_key(MapID::Test(TestMapID::Test), &[key]);
+
+    let database = RocksDB::open_testing(temp_dir(), None).expect("Failed to open storage");
+    database.put(test_key(1), b"a").unwrap();
+
+    // Create a checkpoint, and keep writing to the database.
+    let checkpoint = temp_dir().join("checkpoint");
+    database.create_checkpoint(&checkpoint).expect("Failed to create a checkpoint");
+    database.put(test_key(2), b"b").unwrap();
+    // Ensure an existing checkpoint is not overwritten.
+    assert!(database.create_checkpoint(&checkpoint).is_err());
+
+    // Restore the checkpoint, and ensure it contains the entries at the time of its creation.
+    let destination = temp_dir().join("restored");
+    RocksDB::restore_checkpoint(&checkpoint, &destination, u16::MAX).expect("Failed to restore the checkpoint");
+    {
+        let restored =
+            RocksDB::open_at(destination.clone(), None, u16::MAX, StorageMode::from(destination.clone())).unwrap();
+        assert_eq!(Some(b"a".to_vec()), restored.get(test_key(1)).unwrap());
+        assert_eq!(None, restored.get(test_key(2)).unwrap());
+        assert_eq!(Some(SCHEMA_VERSION), restored.schema_version().unwrap());
+    }
+    // Ensure the checkpoint is not restored over an existing directory.
+    assert!(RocksDB::restore_checkpoint(&checkpoint, &destination, u16::MAX).is_err());
+
+    // Ensure a checkpoint with a newer schema version is rejected, and nothing is left behind.
+    let version_key = raw_key(MapID::Metadata(MetadataMap::SchemaVersion), &[]);
+    database.put(version_key, (SCHEMA_VERSION + 1).to_le_bytes()).unwrap();
+    let checkpoint = temp_dir().join("checkpoint");
+    database.create_checkpoint(&checkpoint).expect("Failed to create a checkpoint");
+    let destination = temp_dir().join("restored");
+    assert!(RocksDB::restore_checkpoint(&checkpoint, &destination, u16::MAX).is_err());
+    assert!(!destination.exists());
+}
+
+#[test]
+#[serial]
+fn test_secondary() {
+    // Returns the raw key of the given entry in the test map.
+    let test_key = |key: u8| {
+        let mut raw_key = u16::MAX.to_le_bytes().to_vec();
+        raw_key.extend_from_slice(&u16::from(MapID::Test(TestMapID::Test)).to_le_bytes());
+        raw_key.push(key);
+        raw_key
+    };
+
+    let path = temp_dir();
+    let storage_mode = StorageMode::from(path.clone());
+
+    // Ensure a secondary instance can't be opened before the primary instance initializes the database.
+    let secondary_dir = temp_dir();
+    assert!(RocksDB::open_at(path.clone(), Some(&secondary_dir), u16::MAX, storage_mode.clone()).is_err());
+
+    let primary = RocksDB::open_at(path.clone(), None, u16::MAX, storage_mode.clone()).unwrap();
+    primary.put(test_key(1), b"a").unwrap();
+    primary.flush().unwrap();
+    assert!(primary.try_catch_up_with_primary().is_err());
+
+    // Open a secondary instance while the primary instance is still open.
+    let secondary = RocksDB::open_at(path, Some(&secondary_dir), u16::MAX, storage_mode).unwrap();
+    assert!(secondary.is_secondary());
+    assert_eq!(Some(b"a".to_vec()), secondary.get(test_key(1)).unwrap());
+
+    // Ensure the writes of the primary instance are only visible once the secondary instance catches up.
+    primary.put(test_key(2), b"b").unwrap();
+    assert_eq!(None, secondary.get(test_key(2)).unwrap());
+    secondary.try_catch_up_with_primary().unwrap();
+    assert_eq!(Some(b"b".to_vec()), secondary.get(test_key(2)).unwrap());
+
+    // Ensure the secondary instance rejects writes.
+    assert!(secondary.put(test_key(3), b"c").is_err());
+    assert_eq!(None, primary.get(test_key(3)).unwrap());
+}
@@ -21,6 +21,7 @@ use crate::{
         ExecutionMap,
         FeeMap,
         MapID,
+        NestedDataMap,
         TransactionMap,
         TransitionDB,
     },
@@ -37,6 +38,7 @@ use crate::{
 use console::{
     prelude::*,
     program::{Identifier, ProgramID, ProgramOwner},
+    types::Address,
 };
 use synthesizer_program::Program;
 use synthesizer_snark::{Certificate, Proof, VerifyingKey};
@@ -52,6 +54,10 @@ pub struct TransactionDB<N: Network> {
     execution_store: ExecutionStore<N, ExecutionDB<N>>,
     /// The fee store.
     fee_store: FeeStore<N, FeeDB<N>>,
+    /// The fee payer index map.
+    fee_payer_index_map: NestedDataMap<Address<N>, u32, N::TransactionID>,
+    /// The fee payer index length map.
+    fee_payer_index_length_map: DataMap<Address<N>, u32>,
 }
 
 #[rustfmt::skip]
@@ -61,6 +67,8 @@ impl<N: Network> TransactionStorage<N> for TransactionDB<N> {
     type ExecutionStorage = ExecutionDB<N>;
     type FeeStorage = FeeDB<N>;
     type TransitionStorage = TransitionDB<N>;
+    type FeePayerIndexMap = NestedDataMap<Address<N>, u32, N::TransactionID>;
+    type FeePayerIndexLengthMap = DataMap<Address<N>, u32>;
 
     /// Initializes the transaction storage.
     fn open(transition_store: TransitionStore<N, Self::TransitionStorage>) -> Result<Self> {
@@ -70,8 +78,17 @@ impl<N: Network> TransactionStorage<N> for TransactionDB<N> {
         let deployment_store = DeploymentStore::<N, DeploymentDB<N>>::open(fee_store.clone())?;
         // Initialize the execution store.
         let execution_store = ExecutionStore::<N, ExecutionDB<N>>::open(fee_store.clone())?;
+        // Retrieve the storage mode.
+        let storage_mode = execution_store.storage_mode().clone();
         // Return the transaction storage.
-        Ok(Self { id_map: rocksdb::RocksDB::open_map(N::ID, execution_store.storage_mode().clone(), MapID::Transaction(TransactionMap::ID))?, deployment_store, execution_store, fee_store })
+        Ok(Self {
+            id_map: rocksdb::RocksDB::open_map(N::ID, storage_mode.clone(), MapID::Transaction(TransactionMap::ID))?,
+            deployment_store,
+            execution_store,
+            fee_store,
+            fee_payer_index_map: rocksdb::RocksDB::open_nested_map(N::ID, storage_mode.clone(), MapID::Transaction(TransactionMap::FeePayerIndex))?,
+            fee_payer_index_length_map: rocksdb::RocksDB::open_map(N::ID, storage_mode, MapID::Transaction(TransactionMap::FeePayerIndexLength))?,
+        })
     }
 
     /// Returns the ID map.
@@ -93,6 +110,16 @@ impl<N: Network> TransactionStorage<N> for TransactionDB<N> {
     fn fee_store(&self) -> &FeeStore<N, Self::FeeStorage> {
         &self.fee_store
     }
+
+    /// Returns the fee payer index map.
+    fn fee_payer_index_map(&self) -> &Self::FeePayerIndexMap {
+        &self.fee_payer_index_map
+    }
+
+    /// Returns the fee payer index length map.
+    fn fee_payer_index_length_map(&self) -> &Self::FeePayerIndexLengthMap {
+        &self.fee_payer_index_length_map
+    }
 }
 
 /// A database deployment storage.
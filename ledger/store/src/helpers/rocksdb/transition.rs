@@ -13,17 +13,27 @@
 // limitations under the License.
 
 use crate::{
-    helpers::rocksdb::{self, DataMap, Database, MapID, TransitionInputMap, TransitionMap, TransitionOutputMap},
+    helpers::rocksdb::{
+        self,
+        DataMap,
+        Database,
+        MapID,
+        NestedDataMap,
+        TransitionInputMap,
+        TransitionMap,
+        TransitionOutputMap,
+    },
     InputStorage,
     InputStore,
     OutputStorage,
     OutputStore,
+    StorageIndex,
     TransitionStorage,
 };
 use console::{
     prelude::*,
     program::{Ciphertext, Future, Identifier, Plaintext, ProgramID, Record},
-    types::{Field, Group},
+    types::{Address, Field, Group},
 };
 
 use aleo_std_storage::StorageMode;
@@ -47,6 +57,16 @@ pub struct TransitionDB<N: Network> {
     reverse_tcm_map: DataMap<Field<N>, N::TransitionID>,
     /// The signer commitments.
     scm_map: DataMap<N::TransitionID, Field<N>>,
+    /// The address index map.
+    address_index_map: NestedDataMap<Address<N>, u32, N::TransitionID>,
+    /// The locator index map.
+    locator_index_map: NestedDataMap<(ProgramID<N>, Identifier<N>), u32, N::TransitionID>,
+    /// The address index length map.
+    address_index_length_map: DataMap<Address<N>, u32>,
+    /// The locator index length map.
+    locator_index_length_map: DataMap<(ProgramID<N>, Identifier<N>), u32>,
+    /// The map of enabled secondary indexes.
+    indexes_map: DataMap<StorageIndex, bool>,
 }
 
 #[rustfmt::skip]
@@ -59,6 +79,11 @@ impl<N: Network> TransitionStorage<N> for TransitionDB<N> {
     type TCMMap = DataMap<N::TransitionID, Field<N>>;
     type ReverseTCMMap = DataMap<Field<N>, N::TransitionID>;
     type SCMMap = DataMap<N::TransitionID, Field<N>>;
+    type AddressIndexMap = NestedDataMap<Address<N>, u32, N::TransitionID>;
+    type LocatorIndexMap = NestedDataMap<(ProgramID<N>, Identifier<N>), u32, N::TransitionID>;
+    type AddressIndexLengthMap = DataMap<Address<N>, u32>;
+    type LocatorIndexLengthMap = DataMap<(ProgramID<N>, Identifier<N>), u32>;
+    type IndexesMap = DataMap<StorageIndex, bool>;
 
     /// Initializes the transition storage.
     fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
@@ -71,6 +96,11 @@ impl<N: Network> TransitionStorage<N> for TransitionDB<N> {
             tcm_map: rocksdb::RocksDB::open_map(N::ID, storage.clone(), MapID::Transition(TransitionMap::TCM))?,
             reverse_tcm_map: rocksdb::RocksDB::open_map(N::ID, storage.clone(),  MapID::Transition(TransitionMap::ReverseTCM))?,
             scm_map: rocksdb::RocksDB::open_map(N::ID, storage.clone(), MapID::Transition(TransitionMap::SCM))?,
+            address_index_map: rocksdb::RocksDB::open_nested_map(N::ID, storage.clone(), MapID::Transition(TransitionMap::AddressIndex))?,
+            locator_index_map: rocksdb::RocksDB::open_nested_map(N::ID, storage.clone(), MapID::Transition(TransitionMap::LocatorIndex))?,
+            address_index_length_map: rocksdb::RocksDB::open_map(N::ID, storage.clone(), MapID::Transition(TransitionMap::AddressIndexLength))?,
+            locator_index_length_map: rocksdb::RocksDB::open_map(N::ID, storage.clone(), MapID::Transition(TransitionMap::LocatorIndexLength))?,
+            indexes_map: rocksdb::RocksDB::open_map(N::ID, storage.clone(), MapID::Transition(TransitionMap::Indexes))?,
         })
     }
 
@@ -113,6 +143,31 @@ impl<N: Network> TransitionStorage<N> for TransitionDB<N> {
     fn scm_map(&self) -> &Self::SCMMap {
         &self.scm_map
     }
+
+    /// Returns the address index map.
+    fn address_index_map(&self) -> &Self::AddressIndexMap {
+        &self.address_index_map
+    }
+
+    /// Returns the locator index map.
+    fn locator_index_map(&self) -> &Self::LocatorIndexMap {
+        &self.locator_index_map
+    }
+
+    /// Returns the address index length map.
+    fn address_index_length_map(&self) -> &Self::AddressIndexLengthMap {
+        &self.address_index_length_map
+    }
+
+    /// Returns the locator index length map.
+    fn locator_index_length_map(&self) -> &Self::LocatorIndexLengthMap {
+        &self.locator_index_length_map
+    }
+
+    /// Returns the map of enabled secondary indexes.
+    fn indexes_map(&self) -> &Self::IndexesMap {
+        &self.indexes_map
+    }
 }
 
 /// An database transition input storage.
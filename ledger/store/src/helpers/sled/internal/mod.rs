@@ -0,0 +1,414 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+pub use crate::helpers::id::*;
+
+mod map;
+pub use map::*;
+
+mod nested_map;
+pub use nested_map::*;
+
+#[cfg(test)]
+mod tests;
+
+use aleo_std_storage::StorageMode;
+use anyhow::{bail, ensure, Result};
+use once_cell::sync::OnceCell;
+use parking_lot::Mutex;
+use serde::{de::DeserializeOwned, Serialize};
+use std::{
+    borrow::Borrow,
+    marker::PhantomData,
+    mem,
+    ops::Deref,
+    path::PathBuf,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+    },
+};
+
+pub const PREFIX_LEN: usize = 4; // N::ID (u16) + DataID (u16)
+
+/// The number of entries copied in each write batch of a migration.
+#[cfg(feature = "rocks")]
+const MIGRATION_BATCH_SIZE: usize = 10_000;
+
+/// A list of raw key-value operations, where a `None` value is a deletion.
+pub(super) type RawBatch = Vec<(Vec<u8>, Option<Vec<u8>>)>;
+
+pub trait Database {
+    /// Opens the database.
+    fn open<S: Clone + Into<StorageMode>>(network_id: u16, storage: S) -> Result<Self>
+    where
+        Self: Sized;
+
+    /// Opens the map with the given `network_id`, `storage mode`, and `map_id` from storage.
+    fn open_map<
+        S: Clone + Into<StorageMode>,
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+        T: Into<u16>,
+    >(
+        network_id: u16,
+        storage: S,
+        map_id: T,
+    ) -> Result<DataMap<K, V>>;
+
+    /// Opens the nested map with the given `network_id`, `storage mode`, and `map_id` from storage.
+    fn open_nested_map<
+        S: Clone + Into<StorageMode>,
+        M: Serialize + DeserializeOwned,
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+        T: Into<u16>,
+    >(
+        network_id: u16,
+        storage: S,
+        map_id: T,
+    ) -> Result<NestedDataMap<M, K, V>>;
+}
+
+/// An instance of a sled database.
+///
+/// The keys and values are laid out exactly as in the RocksDB backend, so that
+/// a RocksDB ledger can be migrated by copying its raw entries (see `migrate_from_rocksdb`).
+#[derive(Clone)]
+pub struct SledDB {
+    /// The sled instance.
+    sled: ::sled::Db,
+    /// The network ID.
+    network_id: u16,
+    /// The storage mode.
+    storage_mode: StorageMode,
+    /// The low-level database transaction that gets executed atomically at the end
+    /// of a real-run `atomic_finalize` or the outermost `atomic_batch_scope`.
+    pub(super) atomic_batch: Arc<Mutex<RawBatch>>,
+    /// The depth of the current atomic write batch; it gets incremented with every call
+    /// to `start_atomic` and decremented with each call to `finish_atomic`.
+    pub(super) atomic_depth: Arc<AtomicUsize>,
+    /// A flag indicating whether the atomic writes are currently paused.
+    pub(super) atomic_writes_paused: Arc<AtomicBool>,
+}
+
+impl Deref for SledDB {
+    type Target = ::sled::Db;
+
+    fn deref(&self) -> &Self::Target {
+        &self.sled
+    }
+}
+
+impl Database for SledDB {
+    /// Opens the database.
+    ///
+    /// In production mode, the database opens directory `~/.aleo/storage/ledger-{network}-sled`.
+    /// In development mode, the database opens directory `/path/to/repo/.ledger-{network}-{id}-sled`.
+    fn open<S: Clone + Into<StorageMode>>(network_id: u16, storage: S) -> Result<Self> {
+        static DB: OnceCell<SledDB> = OnceCell::new();
+
+        // Retrieve the database.
+        let database = DB
+            .get_or_try_init(|| {
+                let primary = sled_ledger_dir(network_id, storage.clone().into());
+                let sled = ::sled::Config::new().path(primary).open()?;
+
+                Ok::<_, anyhow::Error>(SledDB {
+                    sled,
+                    network_id,
+                    storage_mode: storage.clone().into(),
+                    atomic_batch: Default::default(),
+                    atomic_depth: Default::default(),
+                    atomic_writes_paused: Default::default(),
+                })
+            })?
+            .clone();
+
+        // Ensure the database network ID and storage mode match.
+        match database.network_id == network_id && database.storage_mode == storage.into() {
+            true => Ok(database),
+            false => bail!("Mismatching network ID or storage mode in the database"),
+        }
+    }
+
+    /// Opens the map with the given `network_id`, `storage mode`, and `map_id` from storage.
+    fn open_map<
+        S: Clone + Into<StorageMode>,
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+        T: Into<u16>,
+    >(
+        network_id: u16,
+        storage: S,
+        map_id: T,
+    ) -> Result<DataMap<K, V>> {
+        // Open the sled database.
+        let database = Self::open(network_id, storage)?;
+        // Return the DataMap.
+        Ok(database.data_map(map_id))
+    }
+
+    /// Opens the nested map with the given `network_id`, `storage mode`, and `map_id` from storage.
+    fn open_nested_map<
+        S: Clone + Into<StorageMode>,
+        M: Serialize + DeserializeOwned,
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+        T: Into<u16>,
+    >(
+        network_id: u16,
+        storage: S,
+        map_id: T,
+    ) -> Result<NestedDataMap<M, K, V>> {
+        // Open the sled database.
+        let database = Self::open(network_id, storage)?;
+        // Return the NestedDataMap.
+        Ok(database.nested_data_map(map_id))
+    }
+}
+
+impl SledDB {
+    /// Returns the map with the given `map_id` in this database.
+    fn data_map<K: Serialize + DeserializeOwned, V: Serialize + DeserializeOwned, T: Into<u16>>(
+        self,
+        map_id: T,
+    ) -> DataMap<K, V> {
+        // Combine contexts to create a new scope.
+        let context = self.map_context(map_id);
+
+        DataMap(Arc::new(InnerDataMap {
+            database: self,
+            context,
+            batch_in_progress: Default::default(),
+            atomic_batch: Default::default(),
+            checkpoints: Default::default(),
+        }))
+    }
+
+    /// Returns the nested map with the given `map_id` in this database.
+    fn nested_data_map<
+        M: Serialize + DeserializeOwned,
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+        T: Into<u16>,
+    >(
+        self,
+        map_id: T,
+    ) -> NestedDataMap<M, K, V> {
+        // Combine contexts to create a new scope.
+        let context = self.map_context(map_id);
+
+        NestedDataMap {
+            database: self,
+            context,
+            batch_in_progress: Default::default(),
+            atomic_batch: Default::default(),
+            checkpoints: Default::default(),
+        }
+    }
+
+    /// Returns the key prefix of the map with the given `map_id`.
+    fn map_context<T: Into<u16>>(&self, map_id: T) -> Vec<u8> {
+        let mut context = self.network_id.to_le_bytes().to_vec();
+        context.extend_from_slice(&(map_id.into()).to_le_bytes());
+        context
+    }
+
+    /// Writes the given raw operations to the database as a single atomic batch.
+    pub(super) fn write_raw_batch(&self, operations: RawBatch) -> Result<()> {
+        let mut batch = ::sled::Batch::default();
+        for (raw_key, raw_value) in operations {
+            match raw_value {
+                Some(raw_value) => batch.insert(raw_key, raw_value),
+                None => batch.remove(raw_key),
+            }
+        }
+        Ok(self.sled.apply_batch(batch)?)
+    }
+
+    /// Pause the execution of atomic writes for the entire database.
+    fn pause_atomic_writes(&self) -> Result<()> {
+        // This operation is only intended to be performed before or after
+        // atomic batches - never in the middle of them.
+        assert_eq!(self.atomic_depth.load(Ordering::SeqCst), 0);
+
+        // Set the flag indicating that the pause is in effect.
+        let already_paused = self.atomic_writes_paused.swap(true, Ordering::SeqCst);
+        // Make sure that we haven't already paused atomic writes (which would
+        // indicate a logic bug).
+        assert!(!already_paused);
+
+        Ok(())
+    }
+
+    /// Unpause the execution of atomic writes for the entire database; this
+    /// executes all the writes that have been queued since they were paused.
+    fn unpause_atomic_writes<const DISCARD_BATCH: bool>(&self) -> Result<()> {
+        // Ensure the call to unpause is only performed before or after an atomic batch scope
+        // - and never in the middle of one (otherwise there is a fundamental logic bug).
+        // Note: In production, this `ensure` is a safety-critical invariant that never fails.
+        ensure!(self.atomic_depth.load(Ordering::SeqCst) == 0, "Atomic depth must be 0 to unpause atomic writes");
+
+        let currently_paused = self.atomic_writes_paused.load(Ordering::SeqCst);
+        // Ensure the database is paused (otherwise there is a fundamental logic bug).
+        // Note: In production, this `ensure` is a safety-critical invariant that never fails.
+        ensure!(currently_paused, "Atomic writes must be paused to unpause them");
+
+        // Every atomic storage operation that has accumulated from the moment
+        // the writes have been paused is executed as a single atomic batch.
+        let batch = mem::take(&mut *self.atomic_batch.lock());
+        if !DISCARD_BATCH {
+            self.write_raw_batch(batch)?;
+        }
+
+        // Unset the flag indicating that the pause is in effect.
+        self.atomic_writes_paused.store(false, Ordering::SeqCst);
+
+        Ok(())
+    }
+
+    /// Checks whether the atomic writes are currently paused.
+    fn are_atomic_writes_paused(&self) -> bool {
+        self.atomic_writes_paused.load(Ordering::SeqCst)
+    }
+
+    /// Opens the test database.
+    #[cfg(any(test, feature = "test"))]
+    pub fn open_testing(temp_dir: std::path::PathBuf, dev: Option<u16>) -> Result<Self> {
+        use console::prelude::{Rng, TestRng};
+
+        // Ensure the `temp_dir` is unique.
+        let temp_dir = temp_dir.join(Rng::gen::<u64>(&mut TestRng::default()).to_string());
+
+        // Construct the directory for the test database.
+        let primary = match dev {
+            Some(dev) => temp_dir.join(dev.to_string()),
+            None => temp_dir,
+        };
+
+        // Prepare the storage mode.
+        let storage_mode = StorageMode::from(primary.clone());
+
+        let database = SledDB {
+            sled: ::sled::Config::new().path(primary).open()?,
+            network_id: u16::MAX,
+            storage_mode: storage_mode.clone(),
+            atomic_batch: Default::default(),
+            atomic_depth: Default::default(),
+            atomic_writes_paused: Default::default(),
+        };
+
+        // Ensure the database storage mode match.
+        match database.storage_mode == storage_mode {
+            true => Ok(database),
+            false => bail!("Mismatching storage mode in the test database"),
+        }
+    }
+
+    /// Opens the test map.
+    #[cfg(any(test, feature = "test"))]
+    pub fn open_map_testing<K: Serialize + DeserializeOwned, V: Serialize + DeserializeOwned, T: Into<u16>>(
+        temp_dir: std::path::PathBuf,
+        dev: Option<u16>,
+        map_id: T,
+    ) -> Result<DataMap<K, V>> {
+        // Open the sled test database, and return the DataMap.
+        Ok(Self::open_testing(temp_dir, dev)?.data_map(map_id))
+    }
+
+    /// Opens the test nested map.
+    #[cfg(any(test, feature = "test"))]
+    pub fn open_nested_map_testing<
+        M: Serialize + DeserializeOwned,
+        K: Serialize + DeserializeOwned,
+        V: Serialize + DeserializeOwned,
+        T: Into<u16>,
+    >(
+        temp_dir: std::path::PathBuf,
+        dev: Option<u16>,
+        map_id: T,
+    ) -> Result<NestedDataMap<M, K, V>> {
+        // Open the sled test database, and return the NestedDataMap.
+        Ok(Self::open_testing(temp_dir, dev)?.nested_data_map(map_id))
+    }
+}
+
+#[cfg(feature = "rocks")]
+impl SledDB {
+    /// Copies the ledger with the given `network_id` and `storage mode` from RocksDB into sled,
+    /// and returns the number of copied entries.
+    ///
+    /// The RocksDB ledger is left untouched, and the sled ledger must not contain any entries of the network yet.
+    pub fn migrate_from_rocksdb<S: Clone + Into<StorageMode>>(network_id: u16, storage: S) -> Result<usize> {
+        use crate::helpers::rocksdb::Database as _;
+
+        // Open the source and destination databases.
+        let source = crate::helpers::rocksdb::RocksDB::open(network_id, storage.clone())?;
+        let destination = Self::open(network_id, storage)?;
+        // Copy the entries.
+        destination.import_rocksdb(&source)
+    }
+
+    /// Copies every entry of this database's network from the given RocksDB database,
+    /// and returns the number of copied entries.
+    pub fn import_rocksdb(&self, source: &crate::helpers::rocksdb::RocksDB) -> Result<usize> {
+        // Ensure no atomic batch is in progress, and the destination is empty.
+        ensure!(self.atomic_depth.load(Ordering::SeqCst) == 0, "Cannot migrate during an atomic batch");
+        let prefix = self.network_id.to_le_bytes();
+        ensure!(
+            self.scan_prefix(prefix).next().is_none(),
+            "The sled ledger for network {} is not empty",
+            self.network_id
+        );
+
+        // Iterate over the source entries of the network, which are ordered by key.
+        // Note: The source has a fixed-length prefix extractor, so a total order seek is required
+        // to iterate across the prefixes of every map, rather than within the prefix of the first map.
+        let mut readopts = ::rocksdb::ReadOptions::default();
+        readopts.set_total_order_seek(true);
+        let mode = ::rocksdb::IteratorMode::From(&prefix, ::rocksdb::Direction::Forward);
+        let mut num_entries = 0usize;
+        let mut batch = RawBatch::with_capacity(MIGRATION_BATCH_SIZE);
+        for entry in source.iterator_opt(mode, readopts) {
+            let (raw_key, raw_value) = entry?;
+            // Stop once the entries of the network have been visited.
+            if !raw_key.starts_with(&prefix) {
+                break;
+            }
+            // Copy the raw entry, as the key-value layout is the same in both databases.
+            batch.push((raw_key.into_vec(), Some(raw_value.into_vec())));
+            num_entries += 1;
+            // Write the entries in batches, to bound the memory usage.
+            if batch.len() == MIGRATION_BATCH_SIZE {
+                self.write_raw_batch(mem::take(&mut batch))?;
+            }
+        }
+        self.write_raw_batch(batch)?;
+
+        // Ensure the copied entries are persisted.
+        self.flush()?;
+        // Ensure every entry has been copied.
+        let num_copied = self.scan_prefix(prefix).count();
+        ensure!(num_copied == num_entries, "Copied {num_copied} out of {num_entries} entries from RocksDB");
+        Ok(num_entries)
+    }
+}
+
+/// Returns the directory of the sled ledger, which is next to the RocksDB ledger directory.
+fn sled_ledger_dir(network_id: u16, storage_mode: StorageMode) -> PathBuf {
+    let mut path = aleo_std_storage::aleo_ledger_dir(network_id, storage_mode).into_os_string();
+    path.push("-sled");
+    path.into()
+}
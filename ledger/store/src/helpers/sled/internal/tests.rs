@@ -0,0 +1,105 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::helpers::{
+    sled::{MapID, SledDB, TestMap as TestMapID},
+    Map,
+    MapRead,
+};
+
+use serial_test::serial;
+
+pub(crate) fn temp_dir() -> std::path::PathBuf {
+    tempfile::tempdir().expect("Failed to open temporary directory").into_path()
+}
+
+#[test]
+#[serial]
+fn test_open() {
+    let _storage = SledDB::open_testing(temp_dir(), None).expect("Failed to open storage");
+}
+
+#[test]
+#[serial]
+fn test_insert_and_get() {
+    let map =
+        SledDB::open_map_testing(temp_dir(), None, MapID::Test(TestMapID::Test)).expect("Failed to open data map");
+
+    map.insert(123456789, "123456789".to_string()).expect("Failed to insert");
+    assert_eq!(
+        Some("123456789".to_string()),
+        map.get_confirmed(&123456789).expect("Failed to get").map(|v| v.to_string())
+    );
+    assert_eq!(None, map.get_confirmed(&000000000).expect("Failed to get"));
+
+    map.remove(&123456789).expect("Failed to remove");
+    assert!(map.get_confirmed(&123456789).expect("Failed to get").is_none());
+}
+
+#[test]
+#[serial]
+fn test_iterator_ordering() {
+    let map =
+        SledDB::open_map_testing(temp_dir(), None, MapID::Test(TestMapID::Test)).expect("Failed to open data map");
+
+    // Insert values into the map.
+    for (key, value) in [(5, "d"), (6, "c"), (7, "b"), (8, "a"), (1, "h"), (2, "g"), (3, "f"), (4, "e")] {
+        map.insert(key, value.to_string()).expect("Failed to insert");
+    }
+
+    // Check that the order of the iterator is lexicographical.
+    let keys = map.keys_confirmed().map(|k| *k).collect::<Vec<usize>>();
+    assert_eq!(keys, (1..=8).collect::<Vec<_>>());
+    let values = map.values_confirmed().map(|v| v.to_string()).collect::<Vec<_>>();
+    assert_eq!(values, ["h", "g", "f", "e", "d", "c", "b", "a"]);
+}
+
+#[test]
+#[serial]
+#[cfg(feature = "rocks")]
+fn test_import_rocksdb() {
+    use crate::helpers::{rocksdb::RocksDB, NestedMapRead};
+
+    // Returns the raw key of the given map in the test database.
+    let raw_key = |map_id: TestMapID, key: &[u8]| {
+        let mut raw_key = u16::MAX.to_le_bytes().to_vec();
+        raw_key.extend_from_slice(&u16::from(MapID::Test(map_id)).to_le_bytes());
+        raw_key.extend_from_slice(key);
+        raw_key
+    };
+
+    // Populate a RocksDB database with the entries of a map and a nested map.
+    let source = RocksDB::open_testing(temp_dir(), None).expect("Failed to open a RocksDB database");
+    for i in 0..100u32 {
+        let value = bincode::serialize(&i.to_string()).unwrap();
+        source.put(raw_key(TestMapID::Test, &bincode::serialize(&i).unwrap()), &value).unwrap();
+        let map_key = [4u32.to_le_bytes(), (i % 3).to_le_bytes(), i.to_le_bytes()].concat();
+        source.put(raw_key(TestMapID::Test2, &map_key), &value).unwrap();
+    }
+
+    // Import the RocksDB entries into sled; this includes the storage schema version.
+    let destination = SledDB::open_testing(temp_dir(), None).expect("Failed to open a sled database");
+    assert_eq!(201, destination.import_rocksdb(&source).expect("Failed to import"));
+    // Ensure the import is only performed into an empty database.
+    assert!(destination.import_rocksdb(&source).is_err());
+
+    // Ensure the sled maps contain the same entries.
+    let map = destination.clone().data_map::<u32, String, _>(MapID::Test(TestMapID::Test));
+    let nested_map = destination.nested_data_map::<u32, u32, String, _>(MapID::Test(TestMapID::Test2));
+    for i in 0..100u32 {
+        assert_eq!(Some(i.to_string()), map.get_confirmed(&i).unwrap().map(|v| v.into_owned()));
+        assert_eq!(Some(i.to_string()), nested_map.get_value_confirmed(&(i % 3), &i).unwrap().map(|v| v.into_owned()));
+    }
+    assert_eq!(34, nested_map.len_map_confirmed(&0).unwrap());
+}
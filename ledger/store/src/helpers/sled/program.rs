@@ -0,0 +1,179 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![allow(clippy::type_complexity)]
+
+use crate::{
+    helpers::sled::{self, CommitteeMap, DataMap, Database, MapID, NestedDataMap, ProgramMap},
+    CommitteeStorage,
+    CommitteeStore,
+    FinalizeStorage,
+    MappingChange,
+};
+use console::{
+    prelude::*,
+    program::{Identifier, Plaintext, ProgramID, Value},
+};
+use ledger_committee::Committee;
+
+use aleo_std_storage::StorageMode;
+use indexmap::IndexSet;
+
+/// A sled finalize storage.
+#[derive(Clone)]
+pub struct FinalizeSled<N: Network> {
+    /// The committee store.
+    committee_store: CommitteeStore<N, CommitteeSled<N>>,
+    /// The program ID map.
+    program_id_map: DataMap<ProgramID<N>, IndexSet<Identifier<N>>>,
+    /// The key-value map.
+    key_value_map: NestedDataMap<(ProgramID<N>, Identifier<N>), Plaintext<N>, Value<N>>,
+    /// The history heights map.
+    history_heights_map: DataMap<u32, u32>,
+    /// The history map.
+    history_map: NestedDataMap<u32, u32, MappingChange<N>>,
+    /// The storage mode.
+    storage_mode: StorageMode,
+}
+
+#[rustfmt::skip]
+impl<N: Network> FinalizeStorage<N> for FinalizeSled<N> {
+    type CommitteeStorage = CommitteeSled<N>;
+    type ProgramIDMap = DataMap<ProgramID<N>, IndexSet<Identifier<N>>>;
+    type KeyValueMap = NestedDataMap<(ProgramID<N>, Identifier<N>), Plaintext<N>, Value<N>>;
+    type HistoryHeightsMap = DataMap<u32, u32>;
+    type HistoryMap = NestedDataMap<u32, u32, MappingChange<N>>;
+
+    /// Initializes the finalize storage.
+    fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
+        // Initialize the committee store.
+        let committee_store = CommitteeStore::<N, CommitteeSled<N>>::open(storage.clone())?;
+        // Return the finalize storage.
+        Ok(Self {
+            committee_store,
+            program_id_map: sled::SledDB::open_map(N::ID, storage.clone(), MapID::Program(ProgramMap::ProgramID))?,
+            key_value_map: sled::SledDB::open_nested_map(N::ID, storage.clone(), MapID::Program(ProgramMap::KeyValueID))?,
+            history_heights_map: sled::SledDB::open_map(N::ID, storage.clone(), MapID::Program(ProgramMap::HistoryHeights))?,
+            history_map: sled::SledDB::open_nested_map(N::ID, storage.clone(), MapID::Program(ProgramMap::History))?,
+            storage_mode: storage.into(),
+        })
+    }
+
+    /// Initializes the test-variant of the storage.
+    #[cfg(any(test, feature = "test"))]
+    fn open_testing(temp_dir: std::path::PathBuf, dev: Option<u16>) -> Result<Self> {
+        // Initialize the committee store.
+        let committee_store = CommitteeStore::<N, CommitteeSled<N>>::open_testing(temp_dir.clone(), dev)?;
+        // Return the finalize storage.
+        Ok(Self {
+            committee_store,
+            program_id_map: sled::SledDB::open_map_testing(temp_dir.clone(), dev, MapID::Program(ProgramMap::ProgramID))?,
+            key_value_map: sled::SledDB::open_nested_map_testing(temp_dir.clone(), dev, MapID::Program(ProgramMap::KeyValueID))?,
+            history_heights_map: sled::SledDB::open_map_testing(temp_dir.clone(), dev, MapID::Program(ProgramMap::HistoryHeights))?,
+            history_map: sled::SledDB::open_nested_map_testing(temp_dir, dev, MapID::Program(ProgramMap::History))?,
+            storage_mode: dev.into(),
+        })
+    }
+
+    /// Returns the committee store.
+    fn committee_store(&self) -> &CommitteeStore<N, Self::CommitteeStorage> {
+        &self.committee_store
+    }
+
+    /// Returns the program ID map.
+    fn program_id_map(&self) -> &Self::ProgramIDMap {
+        &self.program_id_map
+    }
+
+    /// Returns the key-value map.
+    fn key_value_map(&self) -> &Self::KeyValueMap {
+        &self.key_value_map
+    }
+
+    /// Returns the history heights map.
+    fn history_heights_map(&self) -> &Self::HistoryHeightsMap {
+        &self.history_heights_map
+    }
+
+    /// Returns the history map.
+    fn history_map(&self) -> &Self::HistoryMap {
+        &self.history_map
+    }
+
+    /// Returns the storage mode.
+    fn storage_mode(&self) -> &StorageMode {
+        &self.storage_mode
+    }
+}
+
+/// A sled committee storage.
+#[derive(Clone)]
+pub struct CommitteeSled<N: Network> {
+    /// The current round map.
+    current_round_map: DataMap<u8, u64>,
+    /// The round to height map.
+    round_to_height_map: DataMap<u64, u32>,
+    /// The committee map.
+    committee_map: DataMap<u32, Committee<N>>,
+    /// The storage mode.
+    storage_mode: StorageMode,
+}
+
+#[rustfmt::skip]
+impl<N: Network> CommitteeStorage<N> for CommitteeSled<N> {
+    type CurrentRoundMap = DataMap<u8, u64>;
+    type RoundToHeightMap = DataMap<u64, u32>;
+    type CommitteeMap = DataMap<u32, Committee<N>>;
+
+    /// Initializes the committee storage.
+    fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self> {
+        Ok(Self {
+            current_round_map: sled::SledDB::open_map(N::ID, storage.clone(), MapID::Committee(CommitteeMap::CurrentRound))?,
+            round_to_height_map: sled::SledDB::open_map(N::ID, storage.clone(), MapID::Committee(CommitteeMap::RoundToHeight))?,
+            committee_map: sled::SledDB::open_map(N::ID, storage.clone(), MapID::Committee(CommitteeMap::Committee))?,
+            storage_mode: storage.into(),
+        })
+    }
+
+    /// Initializes the test-variant of the storage.
+    #[cfg(any(test, feature = "test"))]
+    fn open_testing(temp_dir: std::path::PathBuf, dev: Option<u16>) -> Result<Self> {
+        Ok(Self {
+            current_round_map: sled::SledDB::open_map_testing(temp_dir.clone(), dev, MapID::Committee(CommitteeMap::CurrentRound))?,
+            round_to_height_map: sled::SledDB::open_map_testing(temp_dir.clone(), dev, MapID::Committee(CommitteeMap::RoundToHeight))?,
+            committee_map: sled::SledDB::open_map_testing(temp_dir, dev, MapID::Committee(CommitteeMap::Committee))?,
+            storage_mode: dev.into(),
+        })
+    }
+
+    /// Returns the current round map.
+    fn current_round_map(&self) -> &Self::CurrentRoundMap {
+        &self.current_round_map
+    }
+
+    /// Returns the round to height map.
+    fn round_to_height_map(&self) -> &Self::RoundToHeightMap {
+        &self.round_to_height_map
+    }
+
+    /// Returns the committee map.
+    fn committee_map(&self) -> &Self::CommitteeMap {
+        &self.committee_map
+    }
+
+    /// Returns the storage mode.
+    fn storage_mode(&self) -> &StorageMode {
+        &self.storage_mode
+    }
+}
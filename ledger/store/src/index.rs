@@ -0,0 +1,205 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::helpers::{Map, MapRead, NestedMap, NestedMapRead};
+use console::{
+    network::prelude::*,
+    program::{Argument, Future, Literal, Owner, Plaintext},
+    types::Address,
+};
+use ledger_block::{Input, Output, Transition};
+
+use core::hash::Hash;
+use indexmap::IndexSet;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// A secondary index which can be maintained by the storage.
+///
+/// The indexes are disabled by default. Once enabled, each index is populated as the
+/// transitions and transactions are inserted into (or removed from) the storage.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum StorageIndex {
+    /// The transitions with a public input or output of a given address.
+    Address,
+    /// The transitions of a given program ID and function name.
+    Locator,
+    /// The transactions whose fee is paid publicly by a given address.
+    FeePayer,
+}
+
+impl StorageIndex {
+    /// The list of all secondary indexes.
+    pub const ALL: [Self; 3] = [Self::Address, Self::Locator, Self::FeePayer];
+}
+
+impl Display for StorageIndex {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Address => write!(f, "address"),
+            Self::Locator => write!(f, "locator"),
+            Self::FeePayer => write!(f, "fee payer"),
+        }
+    }
+}
+
+/// Returns the addresses in the public inputs and outputs of the given transition.
+///
+/// This includes the addresses in public and constant plaintexts (including nested structs and arrays),
+/// in the arguments of an output future, and the owners of records with a public owner.
+pub fn transition_addresses<N: Network>(transition: &Transition<N>) -> IndexSet<Address<N>> {
+    let mut addresses = IndexSet::new();
+    for input in transition.inputs() {
+        match input {
+            Input::Constant(_, Some(plaintext)) | Input::Public(_, Some(plaintext)) => {
+                plaintext_addresses(plaintext, &mut addresses)
+            }
+            _ => (),
+        }
+    }
+    for output in transition.outputs() {
+        match output {
+            Output::Constant(_, Some(plaintext)) | Output::Public(_, Some(plaintext)) => {
+                plaintext_addresses(plaintext, &mut addresses)
+            }
+            Output::Record(_, _, Some(record)) => {
+                if let Owner::Public(address) = record.owner() {
+                    addresses.insert(*address);
+                }
+            }
+            Output::Future(_, Some(future)) => future_addresses(future, &mut addresses),
+            _ => (),
+        }
+    }
+    addresses
+}
+
+/// Collects the addresses in the given plaintext.
+fn plaintext_addresses<N: Network>(plaintext: &Plaintext<N>, addresses: &mut IndexSet<Address<N>>) {
+    match plaintext {
+        Plaintext::Literal(Literal::Address(address), _) => {
+            addresses.insert(*address);
+        }
+        Plaintext::Literal(..) => (),
+        Plaintext::Struct(members, _) => members.values().for_each(|member| plaintext_addresses(member, addresses)),
+        Plaintext::Array(elements, _) => elements.iter().for_each(|element| plaintext_addresses(element, addresses)),
+    }
+}
+
+/// Collects the addresses in the arguments of the given future.
+fn future_addresses<N: Network>(future: &Future<N>, addresses: &mut IndexSet<Address<N>>) {
+    for argument in future.arguments() {
+        match argument {
+            Argument::Plaintext(plaintext) => plaintext_addresses(plaintext, addresses),
+            Argument::Future(future) => future_addresses(future, addresses),
+        }
+    }
+}
+
+// Note: An index map stores the values of each key at consecutive positions, starting from 0,
+// and its length map stores the number of values of each key, which is updated in the same atomic batch.
+// The positions of a key are kept dense, so the values can be paginated with point lookups.
+// New values are appended in insertion order; removing a value shifts the later values down by one,
+// so the remaining values stay in the order they were indexed.
+
+/// Returns the number of values for the given key, including the pending values of the atomic batch.
+pub(crate) fn index_len_speculative<M, L>(lengths: &L, key: &M) -> Result<u32>
+where
+    M: 'static + Copy + Eq + Hash + Serialize + DeserializeOwned + Send + Sync,
+    L: for<'a> Map<'a, M, u32>,
+{
+    Ok(lengths.get_speculative(key)?.map_or(0, |length| *length))
+}
+
+/// Appends the given value to the values of the given key.
+pub(crate) fn index_push<M, V, T, L>(map: &T, lengths: &L, key: M, value: V) -> Result<()>
+where
+    M: 'static + Copy + Eq + Hash + Serialize + DeserializeOwned + Send + Sync,
+    V: 'static + Clone + Eq + Serialize + DeserializeOwned + Send + Sync,
+    T: for<'a> NestedMap<'a, M, u32, V>,
+    L: for<'a> Map<'a, M, u32>,
+{
+    let position = index_len_speculative(lengths, &key)?;
+    let Some(length) = position.checked_add(1) else { bail!("The index map is full for the given key") };
+    map.insert(key, position, value)?;
+    lengths.insert(key, length)
+}
+
+/// Removes the given value from the values of the given key, if it exists.
+/// The later values are shifted down by one position, to preserve their order.
+pub(crate) fn index_shift_remove<M, V, T, L>(map: &T, lengths: &L, key: &M, value: &V) -> Result<()>
+where
+    M: 'static + Copy + Eq + Hash + Serialize + DeserializeOwned + Send + Sync,
+    V: 'static + Clone + Eq + Serialize + DeserializeOwned + Send + Sync,
+    T: for<'a> NestedMap<'a, M, u32, V>,
+    L: for<'a> Map<'a, M, u32>,
+{
+    let length = index_len_speculative(lengths, key)?;
+    // Find the position of the value, starting from the most recent values.
+    let mut position = None;
+    for candidate in (0..length).rev() {
+        if map.get_value_speculative(key, &candidate)?.as_deref() == Some(value) {
+            position = Some(candidate);
+            break;
+        }
+    }
+    let Some(position) = position else {
+        return Ok(());
+    };
+    // Shift the later values down by one position.
+    for next in position + 1..length {
+        let Some(next_value) = map.get_value_speculative(key, &next)?.map(|value| value.into_owned()) else {
+            bail!("Missing position {next} of an index map (possible corruption)")
+        };
+        map.insert(*key, next - 1, next_value)?;
+    }
+    // Remove the last position, and update the length.
+    let last = length - 1;
+    map.remove_key(key, &last)?;
+    match last {
+        0 => lengths.remove(key),
+        _ => lengths.insert(*key, last),
+    }
+}
+
+/// Returns up to `limit` confirmed values of the given key, starting from the given offset.
+pub(crate) fn index_page<M, V, T, L>(map: &T, lengths: &L, key: &M, offset: u32, limit: u32) -> Result<Vec<V>>
+where
+    M: 'static + Copy + Eq + Hash + Serialize + DeserializeOwned + Send + Sync,
+    V: 'static + Clone + Eq + Serialize + DeserializeOwned + Send + Sync,
+    T: for<'a> NestedMap<'a, M, u32, V>,
+    L: for<'a> Map<'a, M, u32>,
+{
+    let length = lengths.get_confirmed(key)?.map_or(0, |length| *length);
+    let end = offset.saturating_add(limit).min(length);
+    (offset..end)
+        .map(|position| match map.get_value_confirmed(key, &position)? {
+            Some(value) => Ok(value.into_owned()),
+            None => bail!("Missing position {position} of an index map (possible corruption)"),
+        })
+        .collect()
+}
+
+/// Removes all of the values in the given index map, and its lengths.
+pub(crate) fn index_clear<M, V, T, L>(map: &T, lengths: &L) -> Result<()>
+where
+    M: 'static + Copy + Eq + Hash + Serialize + DeserializeOwned + Send + Sync,
+    V: 'static + Clone + Eq + Serialize + DeserializeOwned + Send + Sync,
+    T: for<'a> NestedMap<'a, M, u32, V>,
+    L: for<'a> Map<'a, M, u32>,
+{
+    let keys = map.keys_confirmed().map(|(key, _)| *key).collect::<IndexSet<_>>();
+    keys.iter().try_for_each(|key| map.remove_map(key))?;
+    let keys = lengths.keys_confirmed().map(|key| *key).collect::<IndexSet<_>>();
+    keys.iter().try_for_each(|key| lengths.remove(key))
+}
@@ -23,6 +23,9 @@ pub use block::*;
 mod consensus;
 pub use consensus::*;
 
+mod index;
+pub use index::*;
+
 mod program;
 pub use program::*;
 
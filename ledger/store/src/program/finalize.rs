@@ -17,7 +17,7 @@ use crate::{
     cow_to_cloned,
     cow_to_copied,
     helpers::{Map, MapRead, NestedMap, NestedMapRead},
-    program::{CommitteeStorage, CommitteeStore},
+    program::{CommitteeStorage, CommitteeStore, HistoryRetention, MappingChange},
 };
 use console::{
     network::prelude::*,
@@ -30,6 +30,8 @@ use aleo_std_storage::StorageMode;
 use anyhow::Result;
 use core::marker::PhantomData;
 use indexmap::IndexSet;
+use parking_lot::RwLock;
+use std::sync::Arc;
 
 /// TODO (howardwu): Remove this.
 /// Returns the mapping ID for the given `program ID` and `mapping name`.
@@ -77,6 +79,10 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
     type ProgramIDMap: for<'a> Map<'a, ProgramID<N>, IndexSet<Identifier<N>>>;
     /// The mapping of `(program ID, mapping name)` to `[(key, value)]`.
     type KeyValueMap: for<'a> NestedMap<'a, (ProgramID<N>, Identifier<N>), Plaintext<N>, Value<N>>;
+    /// The mapping of `block height` to the number of mapping changes recorded in the history of the block.
+    type HistoryHeightsMap: for<'a> Map<'a, u32, u32>;
+    /// The mapping of `block height` to `[mapping change]`, in the order the changes were made.
+    type HistoryMap: for<'a> NestedMap<'a, u32, u32, MappingChange<N>>;
 
     /// Initializes the program state storage.
     fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self>;
@@ -91,6 +97,10 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
     fn program_id_map(&self) -> &Self::ProgramIDMap;
     /// Returns the key-value map.
     fn key_value_map(&self) -> &Self::KeyValueMap;
+    /// Returns the history heights map.
+    fn history_heights_map(&self) -> &Self::HistoryHeightsMap;
+    /// Returns the history map.
+    fn history_map(&self) -> &Self::HistoryMap;
 
     /// Returns the storage mode.
     fn storage_mode(&self) -> &StorageMode;
@@ -100,6 +110,8 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         self.committee_store().start_atomic();
         self.program_id_map().start_atomic();
         self.key_value_map().start_atomic();
+        self.history_heights_map().start_atomic();
+        self.history_map().start_atomic();
     }
 
     /// Checks if an atomic batch is in progress.
@@ -107,6 +119,8 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         self.committee_store().is_atomic_in_progress()
             || self.program_id_map().is_atomic_in_progress()
             || self.key_value_map().is_atomic_in_progress()
+            || self.history_heights_map().is_atomic_in_progress()
+            || self.history_map().is_atomic_in_progress()
     }
 
     /// Checkpoints the atomic batch.
@@ -114,6 +128,8 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         self.committee_store().atomic_checkpoint();
         self.program_id_map().atomic_checkpoint();
         self.key_value_map().atomic_checkpoint();
+        self.history_heights_map().atomic_checkpoint();
+        self.history_map().atomic_checkpoint();
     }
 
     /// Clears the latest atomic batch checkpoint.
@@ -121,6 +137,8 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         self.committee_store().clear_latest_checkpoint();
         self.program_id_map().clear_latest_checkpoint();
         self.key_value_map().clear_latest_checkpoint();
+        self.history_heights_map().clear_latest_checkpoint();
+        self.history_map().clear_latest_checkpoint();
     }
 
     /// Rewinds the atomic batch to the previous checkpoint.
@@ -128,6 +146,8 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         self.committee_store().atomic_rewind();
         self.program_id_map().atomic_rewind();
         self.key_value_map().atomic_rewind();
+        self.history_heights_map().atomic_rewind();
+        self.history_map().atomic_rewind();
     }
 
     /// Aborts an atomic batch write operation.
@@ -135,13 +155,17 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
         self.committee_store().abort_atomic();
         self.program_id_map().abort_atomic();
         self.key_value_map().abort_atomic();
+        self.history_heights_map().abort_atomic();
+        self.history_map().abort_atomic();
     }
 
     /// Finishes an atomic batch write operation.
     fn finish_atomic(&self) -> Result<()> {
         self.committee_store().finish_atomic()?;
         self.program_id_map().finish_atomic()?;
-        self.key_value_map().finish_atomic()
+        self.key_value_map().finish_atomic()?;
+        self.history_heights_map().finish_atomic()?;
+        self.history_map().finish_atomic()
     }
 
     /// Initializes the given `program ID` and `mapping name` in storage.
@@ -528,6 +552,10 @@ pub trait FinalizeStorage<N: Network>: 'static + Clone + Send + Sync {
 pub struct FinalizeStore<N: Network, P: FinalizeStorage<N>> {
     /// The finalize storage.
     storage: P,
+    /// The retention policy of the mapping history.
+    history_retention: Arc<RwLock<HistoryRetention>>,
+    /// The height of the block whose mapping changes are being recorded, if any.
+    history_height: Arc<RwLock<Option<u32>>>,
     /// PhantomData.
     _phantom: PhantomData<N>,
 }
@@ -547,7 +575,12 @@ impl<N: Network, P: FinalizeStorage<N>> FinalizeStore<N, P> {
     /// Initializes a finalize store from storage.
     pub fn from(storage: P) -> Result<Self> {
         // Return the finalize store.
-        Ok(Self { storage, _phantom: PhantomData })
+        Ok(Self {
+            storage,
+            history_retention: Default::default(),
+            history_height: Default::default(),
+            _phantom: PhantomData,
+        })
     }
 
     /// Starts an atomic batch write operation.
@@ -578,10 +611,14 @@ impl<N: Network, P: FinalizeStorage<N>> FinalizeStore<N, P> {
     /// Aborts an atomic batch write operation.
     pub fn abort_atomic(&self) {
         self.storage.abort_atomic();
+        // Stop recording the mapping history.
+        *self.history_height.write() = None;
     }
 
     /// Finishes an atomic batch write operation.
     pub fn finish_atomic(&self) -> Result<()> {
+        // Stop recording the mapping history.
+        *self.history_height.write() = None;
         self.storage.finish_atomic()
     }
 
@@ -634,7 +671,11 @@ impl<N: Network, P: FinalizeStorage<N>> FinalizeStoreTrait<N> for FinalizeStore<
         key: Plaintext<N>,
         value: Value<N>,
     ) -> Result<FinalizeOperation<N>> {
-        self.storage.insert_key_value(program_id, mapping_name, key, value)
+        // Prepare the mapping change, if the history is being recorded.
+        let change = self.prepare_key_change(program_id, mapping_name, &key)?;
+        let operation = self.storage.insert_key_value(program_id, mapping_name, key, value)?;
+        self.record_history(change)?;
+        Ok(operation)
     }
 
     /// Stores the given `(key, value)` pair at the given `program ID` and `mapping name` in storage.
@@ -648,7 +689,11 @@ impl<N: Network, P: FinalizeStorage<N>> FinalizeStoreTrait<N> for FinalizeStore<
         key: Plaintext<N>,
         value: Value<N>,
     ) -> Result<FinalizeOperation<N>> {
-        self.storage.update_key_value(program_id, mapping_name, key, value)
+        // Prepare the mapping change, if the history is being recorded.
+        let change = self.prepare_key_change(program_id, mapping_name, &key)?;
+        let operation = self.storage.update_key_value(program_id, mapping_name, key, value)?;
+        self.record_history(change)?;
+        Ok(operation)
     }
 
     /// Removes the key-value pair for the given `program ID`, `mapping name`, and `key` from storage.
@@ -658,7 +703,14 @@ impl<N: Network, P: FinalizeStorage<N>> FinalizeStoreTrait<N> for FinalizeStore<
         mapping_name: Identifier<N>,
         key: &Plaintext<N>,
     ) -> Result<Option<FinalizeOperation<N>>> {
-        self.storage.remove_key_value(program_id, mapping_name, key)
+        // Prepare the mapping change, if the history is being recorded.
+        let change = self.prepare_key_change(program_id, mapping_name, key)?;
+        let operation = self.storage.remove_key_value(program_id, mapping_name, key)?;
+        // Record the mapping change, if the key was removed.
+        if operation.is_some() {
+            self.record_history(change)?;
+        }
+        Ok(operation)
     }
 }
 
@@ -670,7 +722,12 @@ impl<N: Network, P: FinalizeStorage<N>> FinalizeStore<N, P> {
         program_id: ProgramID<N>,
         mapping_name: Identifier<N>,
     ) -> Result<FinalizeOperation<N>> {
-        self.storage.initialize_mapping(program_id, mapping_name)
+        let operation = self.storage.initialize_mapping(program_id, mapping_name)?;
+        // Record the mapping change, if the history is being recorded.
+        let change =
+            self.history_height().map(|height| (height, MappingChange::InitializeMapping(program_id, mapping_name)));
+        self.record_history(change)?;
+        Ok(operation)
     }
 
     /// Replaces the mapping for the given `program ID` and `mapping name` from storage,
@@ -681,7 +738,17 @@ impl<N: Network, P: FinalizeStorage<N>> FinalizeStore<N, P> {
         mapping_name: Identifier<N>,
         entries: Vec<(Plaintext<N>, Value<N>)>,
     ) -> Result<FinalizeOperation<N>> {
-        self.storage.replace_mapping(program_id, mapping_name, entries)
+        // Prepare the mapping change, if the history is being recorded.
+        let change = match self.history_height() {
+            Some(height) => {
+                let previous = self.storage.get_mapping_speculative(program_id, mapping_name)?;
+                Some((height, MappingChange::ReplaceMapping(program_id, mapping_name, previous)))
+            }
+            None => None,
+        };
+        let operation = self.storage.replace_mapping(program_id, mapping_name, entries)?;
+        self.record_history(change)?;
+        Ok(operation)
     }
 
     /// Removes the mapping for the given `program ID` and `mapping name` from storage,
@@ -691,13 +758,35 @@ impl<N: Network, P: FinalizeStorage<N>> FinalizeStore<N, P> {
         program_id: ProgramID<N>,
         mapping_name: Identifier<N>,
     ) -> Result<FinalizeOperation<N>> {
-        self.storage.remove_mapping(program_id, mapping_name)
+        // Prepare the mapping change, if the history is being recorded.
+        let change = match self.history_height() {
+            Some(height) => {
+                let previous = self.storage.get_mapping_speculative(program_id, mapping_name)?;
+                Some((height, MappingChange::RemoveMapping(program_id, mapping_name, previous)))
+            }
+            None => None,
+        };
+        let operation = self.storage.remove_mapping(program_id, mapping_name)?;
+        self.record_history(change)?;
+        Ok(operation)
     }
 
     /// Removes the program for the given `program ID` from storage,
     /// along with all associated mappings and key-value pairs in storage.
     pub fn remove_program(&self, program_id: &ProgramID<N>) -> Result<()> {
-        self.storage.remove_program(program_id)
+        // Prepare the mapping changes, if the history is being recorded.
+        let mut changes = Vec::new();
+        if let Some(height) = self.history_height() {
+            // Note: The mappings are recorded in reverse order, so that undoing the changes
+            // (in reverse order) restores the mapping names in their original order.
+            let mapping_names = self.storage.get_mapping_names_speculative(program_id)?.unwrap_or_default();
+            for mapping_name in mapping_names.into_iter().rev() {
+                let previous = self.storage.get_mapping_speculative(*program_id, mapping_name)?;
+                changes.push((height, MappingChange::RemoveMapping(*program_id, mapping_name, previous)));
+            }
+        }
+        self.storage.remove_program(program_id)?;
+        changes.into_iter().try_for_each(|change| self.record_history(Some(change)))
     }
 }
 
@@ -768,6 +857,259 @@ impl<N: Network, P: FinalizeStorage<N>> FinalizeStore<N, P> {
     }
 }
 
+impl<N: Network, P: FinalizeStorage<N>> FinalizeStore<N, P> {
+    /// Returns the retention policy of the mapping history.
+    pub fn history_retention(&self) -> HistoryRetention {
+        *self.history_retention.read()
+    }
+
+    /// Sets the retention policy of the mapping history, which applies from the next block onwards.
+    /// Note: The retention policy is not persisted, and must be set each time the storage is opened.
+    pub fn set_history_retention(&self, retention: HistoryRetention) {
+        *self.history_retention.write() = retention;
+    }
+
+    /// Starts recording the mapping changes in the history of the given block height (if the history is enabled),
+    /// and prunes the history of the blocks which are no longer retained.
+    /// The recording stops once the current atomic batch is finished or aborted.
+    pub fn start_history(&self, height: u32) -> Result<()> {
+        // Determine the oldest block height to retain.
+        let oldest_height = match self.history_retention() {
+            HistoryRetention::Disabled | HistoryRetention::Blocks(0) => return Ok(()),
+            HistoryRetention::Blocks(num_blocks) => height.saturating_sub(num_blocks - 1),
+            HistoryRetention::Unlimited => 0,
+        };
+        // Ensure the history is recorded within an atomic batch.
+        ensure!(self.is_atomic_in_progress(), "The mapping history must be recorded within an atomic batch");
+
+        // Prune the history of the blocks which are no longer retained.
+        let expired_heights = self
+            .storage
+            .history_heights_map()
+            .keys_confirmed()
+            .map(|block_height| cow_to_copied!(block_height))
+            .filter(|block_height| *block_height < oldest_height)
+            .collect::<Vec<_>>();
+        for block_height in expired_heights {
+            self.storage.history_heights_map().remove(&block_height)?;
+            self.storage.history_map().remove_map(&block_height)?;
+        }
+
+        // Mark the block as recorded, and start recording its mapping changes.
+        self.storage.history_heights_map().insert(height, 0)?;
+        *self.history_height.write() = Some(height);
+        Ok(())
+    }
+
+    /// Returns the value for the given `program ID`, `mapping name`, and `key`, as of the given block height.
+    ///
+    /// Note: This method requires the mapping history of every block after the given height.
+    pub fn get_value_at_height(
+        &self,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        key: &Plaintext<N>,
+        height: u32,
+    ) -> Result<Option<Value<N>>> {
+        // Start from the confirmed value, and undo the changes of each subsequent block.
+        let mut value = self.get_value_confirmed(program_id, mapping_name, key)?;
+        self.undo_history(program_id, mapping_name, height, |change| match change {
+            MappingChange::InitializeMapping(..) => value = None,
+            MappingChange::UpdateKeyValue(_, _, changed_key, previous) => {
+                if changed_key == *key {
+                    value = previous;
+                }
+            }
+            MappingChange::ReplaceMapping(.., previous) | MappingChange::RemoveMapping(.., previous) => {
+                value = previous.into_iter().find(|(entry_key, _)| entry_key == key).map(|(_, value)| value);
+            }
+        })?;
+        Ok(value)
+    }
+
+    /// Returns the mapping entries for the given `program ID` and `mapping name`, as of the given block height.
+    /// If the mapping was not initialized at the given height, `None` is returned.
+    ///
+    /// Note: This method requires the mapping history of every block after the given height.
+    pub fn get_mapping_at_height(
+        &self,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        height: u32,
+    ) -> Result<Option<Vec<(Plaintext<N>, Value<N>)>>> {
+        // Start from the confirmed entries, and undo the changes of each subsequent block.
+        let mut entries = match self.contains_mapping_confirmed(&program_id, &mapping_name)? {
+            true => Some(self.get_mapping_confirmed(program_id, mapping_name)?),
+            false => None,
+        };
+        self.undo_history(program_id, mapping_name, height, |change| match change {
+            MappingChange::InitializeMapping(..) => entries = None,
+            MappingChange::UpdateKeyValue(_, _, key, previous) => {
+                let entries = entries.get_or_insert_with(Vec::new);
+                let position = entries.iter().position(|(entry_key, _)| *entry_key == key);
+                match (position, previous) {
+                    (Some(position), Some(value)) => entries[position] = (key, value),
+                    (Some(position), None) => {
+                        entries.remove(position);
+                    }
+                    (None, Some(value)) => entries.push((key, value)),
+                    (None, None) => (),
+                }
+            }
+            MappingChange::ReplaceMapping(.., previous) | MappingChange::RemoveMapping(.., previous) => {
+                entries = Some(previous)
+            }
+        })?;
+        Ok(entries)
+    }
+
+    /// Returns `true` if the mapping history of the given block height is available.
+    pub fn contains_history(&self, height: u32) -> Result<bool> {
+        self.storage.history_heights_map().contains_key_confirmed(&height)
+    }
+
+    /// Reverts the mappings to their state at the end of the previous block, by undoing the recorded
+    /// changes of the given block height, and removes the mapping history and committee of the block.
+    ///
+    /// Note: The given block height must be the latest block height.
+    pub fn revert_history(&self, height: u32) -> Result<()> {
+        // Ensure the block is the latest block.
+        let latest_height = self.committee_store().current_height()?;
+        ensure!(height == latest_height, "Cannot revert block {height}, as the latest block is {latest_height}");
+        // Retrieve the number of changes recorded for the block.
+        let Some(num_changes) = self.storage.history_heights_map().get_confirmed(&height)? else {
+            bail!("The mapping history of block {height} is not available")
+        };
+        let num_changes = cow_to_copied!(num_changes);
+
+        atomic_batch_scope!(self, {
+            // Undo the changes, in the reverse order they were made.
+            for position in (0..num_changes).rev() {
+                let Some(change) = self.storage.history_map().get_value_confirmed(&height, &position)? else {
+                    bail!("Missing mapping change {position} of block {height} (possible corruption)")
+                };
+                self.undo_change(cow_to_cloned!(change))?;
+            }
+            // Remove the mapping history of the block.
+            self.storage.history_map().remove_map(&height)?;
+            self.storage.history_heights_map().remove(&height)?;
+            // Remove the committee of the block.
+            self.committee_store().remove(height)
+        })
+    }
+
+    /// Returns the height of the block whose mapping changes are being recorded, if any.
+    fn history_height(&self) -> Option<u32> {
+        *self.history_height.read()
+    }
+
+    /// Returns the mapping change for the given key, if the history is being recorded.
+    fn prepare_key_change(
+        &self,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        key: &Plaintext<N>,
+    ) -> Result<Option<(u32, MappingChange<N>)>> {
+        match self.history_height() {
+            Some(height) => {
+                let previous = self.storage.get_value_speculative(program_id, mapping_name, key)?;
+                Ok(Some((height, MappingChange::UpdateKeyValue(program_id, mapping_name, key.clone(), previous))))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Appends the given mapping change (if any) to the history of its block.
+    fn record_history(&self, change: Option<(u32, MappingChange<N>)>) -> Result<()> {
+        let Some((height, change)) = change else {
+            return Ok(());
+        };
+        // Retrieve the number of changes recorded for the block.
+        let position = match self.storage.history_heights_map().get_speculative(&height)? {
+            Some(num_changes) => cow_to_copied!(num_changes),
+            None => 0,
+        };
+        // Append the mapping change.
+        self.storage.history_map().insert(height, position, change)?;
+        self.storage.history_heights_map().insert(height, position.saturating_add(1))
+    }
+
+    /// Undoes the given mapping change, by restoring the state it replaced.
+    fn undo_change(&self, change: MappingChange<N>) -> Result<()> {
+        match change {
+            MappingChange::InitializeMapping(program_id, mapping_name) => {
+                // Remove the mapping name, and the program ID if it has no other mappings.
+                let Some(mapping_names) = self.storage.program_id_map().get_speculative(&program_id)? else {
+                    bail!("Cannot undo the initialization of '{program_id}/{mapping_name}' - missing program ID")
+                };
+                let mut mapping_names = cow_to_cloned!(mapping_names);
+                mapping_names.shift_remove(&mapping_name);
+                match mapping_names.is_empty() {
+                    true => self.storage.program_id_map().remove(&program_id)?,
+                    false => self.storage.program_id_map().insert(program_id, mapping_names)?,
+                }
+                self.storage.key_value_map().remove_map(&(program_id, mapping_name))
+            }
+            MappingChange::UpdateKeyValue(program_id, mapping_name, key, Some(value)) => {
+                self.storage.key_value_map().insert((program_id, mapping_name), key, value)
+            }
+            MappingChange::UpdateKeyValue(program_id, mapping_name, key, None) => {
+                self.storage.key_value_map().remove_key(&(program_id, mapping_name), &key)
+            }
+            MappingChange::ReplaceMapping(program_id, mapping_name, entries) => {
+                self.storage.key_value_map().remove_map(&(program_id, mapping_name))?;
+                for (key, value) in entries {
+                    self.storage.key_value_map().insert((program_id, mapping_name), key, value)?;
+                }
+                Ok(())
+            }
+            MappingChange::RemoveMapping(program_id, mapping_name, entries) => {
+                // Restore the mapping name.
+                let mut mapping_names = match self.storage.program_id_map().get_speculative(&program_id)? {
+                    Some(mapping_names) => cow_to_cloned!(mapping_names),
+                    None => IndexSet::new(),
+                };
+                mapping_names.insert(mapping_name);
+                self.storage.program_id_map().insert(program_id, mapping_names)?;
+                for (key, value) in entries {
+                    self.storage.key_value_map().insert((program_id, mapping_name), key, value)?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Passes the recorded changes of the given mapping to `undo`, from the latest block down to
+    /// (but excluding) the given block height, in the reverse order they were made.
+    fn undo_history(
+        &self,
+        program_id: ProgramID<N>,
+        mapping_name: Identifier<N>,
+        height: u32,
+        mut undo: impl FnMut(MappingChange<N>),
+    ) -> Result<()> {
+        // Retrieve the latest block height.
+        let latest_height = self.committee_store().current_height()?;
+        ensure!(height <= latest_height, "Block height {height} is greater than the latest height {latest_height}");
+
+        for block_height in (height + 1..=latest_height).rev() {
+            let Some(num_changes) = self.storage.history_heights_map().get_confirmed(&block_height)? else {
+                bail!("The mapping history of block {block_height} is not available")
+            };
+            for position in (0..cow_to_copied!(num_changes)).rev() {
+                let Some(change) = self.storage.history_map().get_value_confirmed(&block_height, &position)? else {
+                    bail!("Missing mapping change {position} of block {block_height} (possible corruption)")
+                };
+                let change = cow_to_cloned!(change);
+                if *change.program_id() == program_id && *change.mapping_name() == mapping_name {
+                    undo(change);
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1391,4 +1733,123 @@ mod tests {
         finalize_store.remove_program(&program_id).unwrap();
         println!("FinalizeStore::remove_program - {} μs", timer.elapsed().as_micros());
     }
+
+    /// Finalizes a block at the given height, which applies the given operations to the finalize store.
+    fn finalize_block<N: Network>(
+        finalize_store: &FinalizeStore<N, FinalizeMemory<N>>,
+        height: u32,
+        operations: impl FnOnce(),
+        rng: &mut TestRng,
+    ) {
+        finalize_store.start_atomic();
+        finalize_store.start_history(height).unwrap();
+        operations();
+        let committee = ledger_committee::test_helpers::sample_committee_for_round(height as u64, rng);
+        finalize_store.committee_store().insert(height, committee).unwrap();
+        finalize_store.finish_atomic().unwrap();
+    }
+
+    #[test]
+    fn test_mapping_history() {
+        let rng = &mut TestRng::default();
+
+        // Initialize a program ID and mapping name.
+        let program_id = ProgramID::<CurrentNetwork>::from_str("hello.aleo").unwrap();
+        let mapping_name = Identifier::from_str("account").unwrap();
+        // Prepare the keys and values.
+        let key_0 = Plaintext::from_str("0field").unwrap();
+        let key_1 = Plaintext::from_str("1field").unwrap();
+        let value = |amount: u64| Value::from_str(&format!("{amount}u64")).unwrap();
+
+        // Initialize a new finalize store, with the mapping history enabled.
+        let finalize_store = FinalizeStore::from(FinalizeMemory::open(None).unwrap()).unwrap();
+        finalize_store.set_history_retention(HistoryRetention::Unlimited);
+
+        // Finalize the blocks.
+        finalize_block(
+            &finalize_store,
+            0,
+            || {
+                finalize_store.initialize_mapping(program_id, mapping_name).unwrap();
+                finalize_store.insert_key_value(program_id, mapping_name, key_0.clone(), value(1)).unwrap();
+            },
+            rng,
+        );
+        finalize_block(
+            &finalize_store,
+            1,
+            || {
+                finalize_store.update_key_value(program_id, mapping_name, key_0.clone(), value(2)).unwrap();
+                finalize_store.update_key_value(program_id, mapping_name, key_1.clone(), value(10)).unwrap();
+            },
+            rng,
+        );
+        finalize_block(
+            &finalize_store,
+            2,
+            || {
+                finalize_store.remove_key_value(program_id, mapping_name, &key_0).unwrap();
+                // Ensure a rewound change is not recorded.
+                finalize_store.atomic_checkpoint();
+                finalize_store.update_key_value(program_id, mapping_name, key_1.clone(), value(99)).unwrap();
+                finalize_store.atomic_rewind();
+            },
+            rng,
+        );
+        finalize_block(
+            &finalize_store,
+            3,
+            || {
+                let entries = vec![(key_1.clone(), value(20))];
+                finalize_store.replace_mapping(program_id, mapping_name, entries).unwrap();
+            },
+            rng,
+        );
+        assert_eq!(finalize_store.storage.history_map().len_map_confirmed(&2).unwrap(), 1);
+
+        // Ensure the values are returned as of each height.
+        let value_at = |key: &Plaintext<_>, height| {
+            finalize_store.get_value_at_height(program_id, mapping_name, key, height).unwrap()
+        };
+        assert_eq!(value_at(&key_0, 0), Some(value(1)));
+        assert_eq!(value_at(&key_0, 1), Some(value(2)));
+        assert_eq!(value_at(&key_0, 2), None);
+        assert_eq!(value_at(&key_0, 3), None);
+        assert_eq!(value_at(&key_1, 0), None);
+        assert_eq!(value_at(&key_1, 1), Some(value(10)));
+        assert_eq!(value_at(&key_1, 2), Some(value(10)));
+        assert_eq!(value_at(&key_1, 3), Some(value(20)));
+        // Ensure a height after the latest block fails.
+        assert!(finalize_store.get_value_at_height(program_id, mapping_name, &key_0, 4).is_err());
+
+        // Ensure the mappings are returned as of each height.
+        let mapping_at = |height| {
+            let mut entries = finalize_store.get_mapping_at_height(program_id, mapping_name, height).unwrap();
+            // Sort the entries, as the order of the entries is not preserved.
+            entries.iter_mut().for_each(|entries| entries.sort_by_key(|(key, _)| key.to_string()));
+            entries
+        };
+        assert_eq!(mapping_at(0), Some(vec![(key_0.clone(), value(1))]));
+        assert_eq!(mapping_at(1), Some(vec![(key_0.clone(), value(2)), (key_1.clone(), value(10))]));
+        assert_eq!(mapping_at(2), Some(vec![(key_1.clone(), value(10))]));
+        assert_eq!(mapping_at(3), Some(vec![(key_1.clone(), value(20))]));
+
+        // Retain the history of the 2 most recent blocks, and finalize another block.
+        finalize_store.set_history_retention(HistoryRetention::Blocks(2));
+        finalize_block(
+            &finalize_store,
+            4,
+            || {
+                finalize_store.update_key_value(program_id, mapping_name, key_1.clone(), value(30)).unwrap();
+            },
+            rng,
+        );
+        // Ensure the retained history is still available.
+        assert_eq!(value_at(&key_1, 2), Some(value(10)));
+        assert_eq!(value_at(&key_1, 3), Some(value(20)));
+        assert_eq!(value_at(&key_1, 4), Some(value(30)));
+        // Ensure the pruned history is no longer available.
+        assert!(finalize_store.get_value_at_height(program_id, mapping_name, &key_1, 1).is_err());
+        assert!(finalize_store.get_mapping_at_height(program_id, mapping_name, 1).is_err());
+    }
 }
@@ -0,0 +1,74 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use console::{
+    network::prelude::*,
+    program::{Identifier, Plaintext, ProgramID, Value},
+};
+
+use serde::{Deserialize, Serialize};
+
+/// A change to the mappings in the finalize storage, along with the state it replaced.
+///
+/// The changes of each block are recorded in the order they were made, so the mappings
+/// can be reverted to the end of a previous block by undoing the changes in reverse order.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MappingChange<N: Network> {
+    /// The mapping was initialized.
+    InitializeMapping(ProgramID<N>, Identifier<N>),
+    /// The value of the key was inserted, updated, or removed, and previously had the given value (if any).
+    UpdateKeyValue(ProgramID<N>, Identifier<N>, Plaintext<N>, Option<Value<N>>),
+    /// The mapping was replaced, and previously had the given entries.
+    ReplaceMapping(ProgramID<N>, Identifier<N>, Vec<(Plaintext<N>, Value<N>)>),
+    /// The mapping was removed, and previously had the given entries.
+    RemoveMapping(ProgramID<N>, Identifier<N>, Vec<(Plaintext<N>, Value<N>)>),
+}
+
+impl<N: Network> MappingChange<N> {
+    /// Returns the program ID of the changed mapping.
+    pub const fn program_id(&self) -> &ProgramID<N> {
+        match self {
+            Self::InitializeMapping(program_id, ..)
+            | Self::UpdateKeyValue(program_id, ..)
+            | Self::ReplaceMapping(program_id, ..)
+            | Self::RemoveMapping(program_id, ..) => program_id,
+        }
+    }
+
+    /// Returns the name of the changed mapping.
+    pub const fn mapping_name(&self) -> &Identifier<N> {
+        match self {
+            Self::InitializeMapping(_, mapping_name)
+            | Self::UpdateKeyValue(_, mapping_name, ..)
+            | Self::ReplaceMapping(_, mapping_name, _)
+            | Self::RemoveMapping(_, mapping_name, _) => mapping_name,
+        }
+    }
+}
+
+/// The retention policy of the mapping history.
+///
+/// The history is disabled by default. Once enabled, the changes to the mappings
+/// are recorded with each new block, and the mappings can be queried at the heights
+/// of the recorded blocks.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum HistoryRetention {
+    /// The history is not recorded.
+    #[default]
+    Disabled,
+    /// The history of the given number of most recent blocks is retained.
+    Blocks(u32),
+    /// The history of every block is retained.
+    Unlimited,
+}
@@ -24,13 +24,19 @@ pub use fee::*;
 use crate::{
     atomic_batch_scope,
     cow_to_copied,
-    helpers::{Map, MapRead},
+    helpers::{Map, MapRead, NestedMap},
+    index_clear,
+    index_page,
+    index_push,
+    index_shift_remove,
+    StorageIndex,
     TransitionStorage,
     TransitionStore,
 };
 use console::{
     network::prelude::*,
     program::{Identifier, ProgramID},
+    types::Address,
 };
 use ledger_block::{Deployment, Execution, Transaction};
 use synthesizer_program::Program;
@@ -63,6 +69,10 @@ pub trait TransactionStorage<N: Network>: Clone + Send + Sync {
     type FeeStorage: FeeStorage<N, TransitionStorage = Self::TransitionStorage>;
     /// The transition storage.
     type TransitionStorage: TransitionStorage<N>;
+    /// The mapping of `address` to the IDs of the transactions whose fee is paid publicly by the address.
+    type FeePayerIndexMap: for<'a> NestedMap<'a, Address<N>, u32, N::TransactionID>;
+    /// The mapping of `address` to the number of entries in the fee payer index.
+    type FeePayerIndexLengthMap: for<'a> Map<'a, Address<N>, u32>;
 
     /// Initializes the transaction storage.
     fn open(transition_store: TransitionStore<N, Self::TransitionStorage>) -> Result<Self>;
@@ -75,6 +85,10 @@ pub trait TransactionStorage<N: Network>: Clone + Send + Sync {
     fn execution_store(&self) -> &ExecutionStore<N, Self::ExecutionStorage>;
     /// Returns the fee store.
     fn fee_store(&self) -> &FeeStore<N, Self::FeeStorage>;
+    /// Returns the fee payer index map.
+    fn fee_payer_index_map(&self) -> &Self::FeePayerIndexMap;
+    /// Returns the fee payer index length map.
+    fn fee_payer_index_length_map(&self) -> &Self::FeePayerIndexLengthMap;
     /// Returns the transition store.
     fn transition_store(&self) -> &TransitionStore<N, Self::TransitionStorage> {
         debug_assert!(self.deployment_store().storage_mode() == self.execution_store().storage_mode());
@@ -93,6 +107,8 @@ pub trait TransactionStorage<N: Network>: Clone + Send + Sync {
         self.deployment_store().start_atomic();
         self.execution_store().start_atomic();
         self.fee_store().start_atomic();
+        self.fee_payer_index_map().start_atomic();
+        self.fee_payer_index_length_map().start_atomic();
     }
 
     /// Checks if an atomic batch is in progress.
@@ -101,6 +117,8 @@ pub trait TransactionStorage<N: Network>: Clone + Send + Sync {
             || self.deployment_store().is_atomic_in_progress()
             || self.execution_store().is_atomic_in_progress()
             || self.fee_store().is_atomic_in_progress()
+            || self.fee_payer_index_map().is_atomic_in_progress()
+            || self.fee_payer_index_length_map().is_atomic_in_progress()
     }
 
     /// Checkpoints the atomic batch.
@@ -109,6 +127,8 @@ pub trait TransactionStorage<N: Network>: Clone + Send + Sync {
         self.deployment_store().atomic_checkpoint();
         self.execution_store().atomic_checkpoint();
         self.fee_store().atomic_checkpoint();
+        self.fee_payer_index_map().atomic_checkpoint();
+        self.fee_payer_index_length_map().atomic_checkpoint();
     }
 
     /// Clears the latest atomic batch checkpoint.
@@ -117,6 +137,8 @@ pub trait TransactionStorage<N: Network>: Clone + Send + Sync {
         self.deployment_store().clear_latest_checkpoint();
         self.execution_store().clear_latest_checkpoint();
         self.fee_store().clear_latest_checkpoint();
+        self.fee_payer_index_map().clear_latest_checkpoint();
+        self.fee_payer_index_length_map().clear_latest_checkpoint();
     }
 
     /// Rewinds the atomic batch to the previous checkpoint.
@@ -125,6 +147,8 @@ pub trait TransactionStorage<N: Network>: Clone + Send + Sync {
         self.deployment_store().atomic_rewind();
         self.execution_store().atomic_rewind();
         self.fee_store().atomic_rewind();
+        self.fee_payer_index_map().atomic_rewind();
+        self.fee_payer_index_length_map().atomic_rewind();
     }
 
     /// Aborts an atomic batch write operation.
@@ -133,6 +157,8 @@ pub trait TransactionStorage<N: Network>: Clone + Send + Sync {
         self.deployment_store().abort_atomic();
         self.execution_store().abort_atomic();
         self.fee_store().abort_atomic();
+        self.fee_payer_index_map().abort_atomic();
+        self.fee_payer_index_length_map().abort_atomic();
     }
 
     /// Finishes an atomic batch write operation.
@@ -140,7 +166,9 @@ pub trait TransactionStorage<N: Network>: Clone + Send + Sync {
         self.id_map().finish_atomic()?;
         self.deployment_store().finish_atomic()?;
         self.execution_store().finish_atomic()?;
-        self.fee_store().finish_atomic()
+        self.fee_store().finish_atomic()?;
+        self.fee_payer_index_map().finish_atomic()?;
+        self.fee_payer_index_length_map().finish_atomic()
     }
 
     /// Stores the given `transaction` into storage.
@@ -166,6 +194,10 @@ pub trait TransactionStorage<N: Network>: Clone + Send + Sync {
                     self.fee_store().insert(transaction.id(), fee)?;
                 }
             }
+            // Store the transaction in the fee payer index, if it is enabled.
+            if self.transition_store().is_index_enabled(StorageIndex::FeePayer)? {
+                self.insert_index_entries(StorageIndex::FeePayer, transaction)?;
+            }
             Ok(())
         })
     }
@@ -177,8 +209,22 @@ pub trait TransactionStorage<N: Network>: Clone + Send + Sync {
             Some(transaction_type) => cow_to_copied!(transaction_type),
             None => bail!("Failed to get the type for transaction '{transaction_id}'"),
         };
+        // Retrieve the fee payer, if the fee payer index is enabled.
+        let fee_payer = match self.transition_store().is_index_enabled(StorageIndex::FeePayer)? {
+            true => self.fee_store().get_fee(transaction_id)?.and_then(|fee| fee.payer()),
+            false => None,
+        };
 
         atomic_batch_scope!(self, {
+            // Remove the transaction from the fee payer index.
+            if let Some(fee_payer) = &fee_payer {
+                index_shift_remove(
+                    self.fee_payer_index_map(),
+                    self.fee_payer_index_length_map(),
+                    fee_payer,
+                    transaction_id,
+                )?;
+            }
             // Remove the transaction type.
             self.id_map().remove(transaction_id)?;
             // Remove the transaction.
@@ -194,6 +240,41 @@ pub trait TransactionStorage<N: Network>: Clone + Send + Sync {
         })
     }
 
+    /// Removes all of the entries of the given secondary index, and disables it.
+    fn reset_index(&self, index: StorageIndex) -> Result<()> {
+        atomic_batch_scope!(self, {
+            match index {
+                StorageIndex::FeePayer => index_clear(self.fee_payer_index_map(), self.fee_payer_index_length_map())?,
+                _ => self.transition_store().clear_index(index)?,
+            }
+            self.transition_store().set_index_enabled(index, false)
+        })
+    }
+
+    /// Stores the given `transaction` into the given secondary index.
+    fn insert_index_entries(&self, index: StorageIndex, transaction: &Transaction<N>) -> Result<()> {
+        atomic_batch_scope!(self, {
+            match index {
+                StorageIndex::FeePayer => {
+                    if let Some(fee_payer) = transaction.fee_transition().and_then(|fee| fee.payer()) {
+                        index_push(
+                            self.fee_payer_index_map(),
+                            self.fee_payer_index_length_map(),
+                            fee_payer,
+                            transaction.id(),
+                        )?;
+                    }
+                }
+                _ => {
+                    for transition in transaction.transitions() {
+                        self.transition_store().insert_index_entries(index, transition)?;
+                    }
+                }
+            }
+            Ok(())
+        })
+    }
+
     /// Returns the transaction ID that contains the given `transition ID`.
     fn find_transaction_id_from_transition_id(
         &self,
@@ -272,6 +353,16 @@ impl<N: Network, T: TransactionStorage<N>> TransactionStore<N, T> {
         self.storage.transition_store()
     }
 
+    /// Removes all of the entries of the given secondary index, and disables it.
+    pub fn reset_index(&self, index: StorageIndex) -> Result<()> {
+        self.storage.reset_index(index)
+    }
+
+    /// Stores the given `transaction` into the given secondary index.
+    pub fn insert_index_entries(&self, index: StorageIndex, transaction: &Transaction<N>) -> Result<()> {
+        self.storage.insert_index_entries(index, transaction)
+    }
+
     /// Starts an atomic batch write operation.
     pub fn start_atomic(&self) {
         self.storage.start_atomic();
@@ -410,6 +501,18 @@ impl<N: Network, T: TransactionStorage<N>> TransactionStore<N, T> {
 }
 
 impl<N: Network, T: TransactionStorage<N>> TransactionStore<N, T> {
+    /// Returns up to `limit` IDs of the transactions whose fee is paid publicly by the given address,
+    /// starting from the given offset, in the order they were indexed.
+    pub fn find_transaction_ids_by_fee_payer(
+        &self,
+        fee_payer: &Address<N>,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<N::TransactionID>> {
+        let (map, lengths) = (self.storage.fee_payer_index_map(), self.storage.fee_payer_index_length_map());
+        index_page(map, lengths, fee_payer, offset, limit)
+    }
+
     /// Returns the transaction ID that contains the given `program ID`.
     pub fn find_transaction_id_from_program_id(&self, program_id: &ProgramID<N>) -> Result<Option<N::TransactionID>> {
         self.storage.deployment_store().find_transaction_id_from_program_id(program_id)
@@ -22,12 +22,18 @@ use crate::{
     atomic_batch_scope,
     cow_to_cloned,
     cow_to_copied,
-    helpers::{Map, MapRead},
+    helpers::{Map, MapRead, NestedMap},
+    index_clear,
+    index_page,
+    index_push,
+    index_shift_remove,
+    transition_addresses,
+    StorageIndex,
 };
 use console::{
     network::prelude::*,
     program::{Ciphertext, Identifier, Plaintext, ProgramID, Record},
-    types::{Field, Group},
+    types::{Address, Field, Group},
 };
 use ledger_block::{Input, Output, Transition};
 
@@ -53,6 +59,16 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
     type ReverseTCMMap: for<'a> Map<'a, Field<N>, N::TransitionID>;
     /// The signer commitments.
     type SCMMap: for<'a> Map<'a, N::TransitionID, Field<N>>;
+    /// The mapping of `address` to the IDs of the transitions with a public input or output of the address.
+    type AddressIndexMap: for<'a> NestedMap<'a, Address<N>, u32, N::TransitionID>;
+    /// The mapping of `(program ID, function name)` to the IDs of the transitions of the function.
+    type LocatorIndexMap: for<'a> NestedMap<'a, (ProgramID<N>, Identifier<N>), u32, N::TransitionID>;
+    /// The mapping of `address` to the number of entries in the address index.
+    type AddressIndexLengthMap: for<'a> Map<'a, Address<N>, u32>;
+    /// The mapping of `(program ID, function name)` to the number of entries in the locator index.
+    type LocatorIndexLengthMap: for<'a> Map<'a, (ProgramID<N>, Identifier<N>), u32>;
+    /// The mapping of `secondary index` to its status, for the enabled secondary indexes.
+    type IndexesMap: for<'a> Map<'a, StorageIndex, bool>;
 
     /// Initializes the transition storage.
     fn open<S: Clone + Into<StorageMode>>(storage: S) -> Result<Self>;
@@ -73,6 +89,16 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
     fn reverse_tcm_map(&self) -> &Self::ReverseTCMMap;
     /// Returns the signer commitments map.
     fn scm_map(&self) -> &Self::SCMMap;
+    /// Returns the address index map.
+    fn address_index_map(&self) -> &Self::AddressIndexMap;
+    /// Returns the locator index map.
+    fn locator_index_map(&self) -> &Self::LocatorIndexMap;
+    /// Returns the address index length map.
+    fn address_index_length_map(&self) -> &Self::AddressIndexLengthMap;
+    /// Returns the locator index length map.
+    fn locator_index_length_map(&self) -> &Self::LocatorIndexLengthMap;
+    /// Returns the map of enabled secondary indexes.
+    fn indexes_map(&self) -> &Self::IndexesMap;
 
     /// Returns the storage mode.
     fn storage_mode(&self) -> &StorageMode {
@@ -90,6 +116,11 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
         self.tcm_map().start_atomic();
         self.reverse_tcm_map().start_atomic();
         self.scm_map().start_atomic();
+        self.address_index_map().start_atomic();
+        self.locator_index_map().start_atomic();
+        self.address_index_length_map().start_atomic();
+        self.locator_index_length_map().start_atomic();
+        self.indexes_map().start_atomic();
     }
 
     /// Checks if an atomic batch is in progress.
@@ -102,6 +133,11 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
             || self.tcm_map().is_atomic_in_progress()
             || self.reverse_tcm_map().is_atomic_in_progress()
             || self.scm_map().is_atomic_in_progress()
+            || self.address_index_map().is_atomic_in_progress()
+            || self.locator_index_map().is_atomic_in_progress()
+            || self.address_index_length_map().is_atomic_in_progress()
+            || self.locator_index_length_map().is_atomic_in_progress()
+            || self.indexes_map().is_atomic_in_progress()
     }
 
     /// Checkpoints the atomic batch.
@@ -114,6 +150,11 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
         self.tcm_map().atomic_checkpoint();
         self.reverse_tcm_map().atomic_checkpoint();
         self.scm_map().atomic_checkpoint();
+        self.address_index_map().atomic_checkpoint();
+        self.locator_index_map().atomic_checkpoint();
+        self.address_index_length_map().atomic_checkpoint();
+        self.locator_index_length_map().atomic_checkpoint();
+        self.indexes_map().atomic_checkpoint();
     }
 
     /// Clears the latest atomic batch checkpoint.
@@ -126,6 +167,11 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
         self.tcm_map().clear_latest_checkpoint();
         self.reverse_tcm_map().clear_latest_checkpoint();
         self.scm_map().clear_latest_checkpoint();
+        self.address_index_map().clear_latest_checkpoint();
+        self.locator_index_map().clear_latest_checkpoint();
+        self.address_index_length_map().clear_latest_checkpoint();
+        self.locator_index_length_map().clear_latest_checkpoint();
+        self.indexes_map().clear_latest_checkpoint();
     }
 
     /// Rewinds the atomic batch to the previous checkpoint.
@@ -138,6 +184,11 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
         self.tcm_map().atomic_rewind();
         self.reverse_tcm_map().atomic_rewind();
         self.scm_map().atomic_rewind();
+        self.address_index_map().atomic_rewind();
+        self.locator_index_map().atomic_rewind();
+        self.address_index_length_map().atomic_rewind();
+        self.locator_index_length_map().atomic_rewind();
+        self.indexes_map().atomic_rewind();
     }
 
     /// Aborts an atomic batch write operation.
@@ -150,6 +201,11 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
         self.tcm_map().abort_atomic();
         self.reverse_tcm_map().abort_atomic();
         self.scm_map().abort_atomic();
+        self.address_index_map().abort_atomic();
+        self.locator_index_map().abort_atomic();
+        self.address_index_length_map().abort_atomic();
+        self.locator_index_length_map().abort_atomic();
+        self.indexes_map().abort_atomic();
     }
 
     /// Finishes an atomic batch write operation.
@@ -161,7 +217,12 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
         self.reverse_tpk_map().finish_atomic()?;
         self.tcm_map().finish_atomic()?;
         self.reverse_tcm_map().finish_atomic()?;
-        self.scm_map().finish_atomic()
+        self.scm_map().finish_atomic()?;
+        self.address_index_map().finish_atomic()?;
+        self.locator_index_map().finish_atomic()?;
+        self.address_index_length_map().finish_atomic()?;
+        self.locator_index_length_map().finish_atomic()?;
+        self.indexes_map().finish_atomic()
     }
 
     /// Stores the given `transition` into storage.
@@ -185,6 +246,12 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
             self.reverse_tcm_map().insert(*transition.tcm(), transition_id)?;
             // Store `scm`.
             self.scm_map().insert(transition_id, *transition.scm())?;
+            // Store the transition in the enabled secondary indexes.
+            for index in [StorageIndex::Address, StorageIndex::Locator] {
+                if self.is_index_enabled(index)? {
+                    self.insert_index_entries(index, transition)?;
+                }
+            }
 
             Ok(())
         })
@@ -202,6 +269,17 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
             Some(tcm) => cow_to_copied!(tcm),
             None => return Ok(()),
         };
+        // Retrieve the enabled secondary indexes, and the transition if it is indexed.
+        let mut indexes = Vec::new();
+        for index in [StorageIndex::Address, StorageIndex::Locator] {
+            if self.is_index_enabled(index)? {
+                indexes.push(index);
+            }
+        }
+        let indexed_transition = match indexes.is_empty() {
+            true => None,
+            false => self.get(transition_id)?,
+        };
 
         atomic_batch_scope!(self, {
             // Remove the program ID and function name.
@@ -220,11 +298,75 @@ pub trait TransitionStorage<N: Network>: Clone + Send + Sync {
             self.reverse_tcm_map().remove(&tcm)?;
             // Remove `scm`.
             self.scm_map().remove(transition_id)?;
+            // Remove the transition from the enabled secondary indexes.
+            if let Some(transition) = &indexed_transition {
+                for index in &indexes {
+                    self.remove_index_entries(*index, transition)?;
+                }
+            }
 
             Ok(())
         })
     }
 
+    /// Returns `true` if the given secondary index is enabled.
+    fn is_index_enabled(&self, index: StorageIndex) -> Result<bool> {
+        Ok(self.indexes_map().get_speculative(&index)?.is_some_and(|enabled| *enabled))
+    }
+
+    /// Enables or disables the given secondary index.
+    /// Note: This method does not populate or clear the entries of the index.
+    fn set_index_enabled(&self, index: StorageIndex, enabled: bool) -> Result<()> {
+        match enabled {
+            true => self.indexes_map().insert(index, true),
+            false => self.indexes_map().remove(&index),
+        }
+    }
+
+    /// Removes all of the entries of the given secondary index.
+    fn clear_index(&self, index: StorageIndex) -> Result<()> {
+        atomic_batch_scope!(self, {
+            match index {
+                StorageIndex::Address => index_clear(self.address_index_map(), self.address_index_length_map()),
+                StorageIndex::Locator => index_clear(self.locator_index_map(), self.locator_index_length_map()),
+                // Note: The fee payer index is maintained by the transaction storage.
+                StorageIndex::FeePayer => Ok(()),
+            }
+        })
+    }
+
+    /// Stores the given `transition` into the given secondary index.
+    fn insert_index_entries(&self, index: StorageIndex, transition: &Transition<N>) -> Result<()> {
+        let transition_id = *transition.id();
+        match index {
+            StorageIndex::Address => transition_addresses(transition).into_iter().try_for_each(|address| {
+                index_push(self.address_index_map(), self.address_index_length_map(), address, transition_id)
+            }),
+            StorageIndex::Locator => {
+                let locator = (*transition.program_id(), *transition.function_name());
+                index_push(self.locator_index_map(), self.locator_index_length_map(), locator, transition_id)
+            }
+            // Note: The fee payer index is maintained by the transaction storage.
+            StorageIndex::FeePayer => Ok(()),
+        }
+    }
+
+    /// Removes the given `transition` from the given secondary index.
+    fn remove_index_entries(&self, index: StorageIndex, transition: &Transition<N>) -> Result<()> {
+        let transition_id = transition.id();
+        match index {
+            StorageIndex::Address => transition_addresses(transition).iter().try_for_each(|address| {
+                index_shift_remove(self.address_index_map(), self.address_index_length_map(), address, transition_id)
+            }),
+            StorageIndex::Locator => {
+                let locator = (*transition.program_id(), *transition.function_name());
+                index_shift_remove(self.locator_index_map(), self.locator_index_length_map(), &locator, transition_id)
+            }
+            // Note: The fee payer index is maintained by the transaction storage.
+            StorageIndex::FeePayer => Ok(()),
+        }
+    }
+
     /// Returns the transition for the given `transition ID`.
     fn get(&self, transition_id: &N::TransitionID) -> Result<Option<Transition<N>>> {
         // Retrieve the program ID and function name.
@@ -285,6 +427,14 @@ pub struct TransitionStore<N: Network, T: TransitionStorage<N>> {
     reverse_tcm: T::ReverseTCMMap,
     /// The map of signer commitments.
     scm: T::SCMMap,
+    /// The address index map.
+    address_index: T::AddressIndexMap,
+    /// The locator index map.
+    locator_index: T::LocatorIndexMap,
+    /// The address index length map.
+    address_index_length: T::AddressIndexLengthMap,
+    /// The locator index length map.
+    locator_index_length: T::LocatorIndexLengthMap,
     /// The transition storage.
     storage: T,
 }
@@ -304,6 +454,10 @@ impl<N: Network, T: TransitionStorage<N>> TransitionStore<N, T> {
             tcm: storage.tcm_map().clone(),
             reverse_tcm: storage.reverse_tcm_map().clone(),
             scm: storage.scm_map().clone(),
+            address_index: storage.address_index_map().clone(),
+            locator_index: storage.locator_index_map().clone(),
+            address_index_length: storage.address_index_length_map().clone(),
+            locator_index_length: storage.locator_index_length_map().clone(),
             storage,
         })
     }
@@ -319,6 +473,10 @@ impl<N: Network, T: TransitionStorage<N>> TransitionStore<N, T> {
             tcm: storage.tcm_map().clone(),
             reverse_tcm: storage.reverse_tcm_map().clone(),
             scm: storage.scm_map().clone(),
+            address_index: storage.address_index_map().clone(),
+            locator_index: storage.locator_index_map().clone(),
+            address_index_length: storage.address_index_length_map().clone(),
+            locator_index_length: storage.locator_index_length_map().clone(),
             storage,
         }
     }
@@ -333,6 +491,27 @@ impl<N: Network, T: TransitionStorage<N>> TransitionStore<N, T> {
         self.storage.remove(transition_id)
     }
 
+    /// Returns `true` if the given secondary index is enabled.
+    pub fn is_index_enabled(&self, index: StorageIndex) -> Result<bool> {
+        self.storage.is_index_enabled(index)
+    }
+
+    /// Enables or disables the given secondary index.
+    /// Note: This method does not populate or clear the entries of the index.
+    pub fn set_index_enabled(&self, index: StorageIndex, enabled: bool) -> Result<()> {
+        self.storage.set_index_enabled(index, enabled)
+    }
+
+    /// Removes all of the entries of the given secondary index.
+    pub fn clear_index(&self, index: StorageIndex) -> Result<()> {
+        self.storage.clear_index(index)
+    }
+
+    /// Stores the given `transition` into the given secondary index.
+    pub fn insert_index_entries(&self, index: StorageIndex, transition: &Transition<N>) -> Result<()> {
+        self.storage.insert_index_entries(index, transition)
+    }
+
     /// Starts an atomic batch write operation.
     pub fn start_atomic(&self) {
         self.storage.start_atomic();
@@ -390,6 +569,32 @@ impl<N: Network, T: TransitionStorage<N>> TransitionStore<N, T> {
     }
 }
 
+impl<N: Network, T: TransitionStorage<N>> TransitionStore<N, T> {
+    /// Returns up to `limit` IDs of the transitions with a public input or output of the given address,
+    /// starting from the given offset, in the order they were indexed.
+    pub fn find_transition_ids_by_address(
+        &self,
+        address: &Address<N>,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<N::TransitionID>> {
+        index_page(&self.address_index, &self.address_index_length, address, offset, limit)
+    }
+
+    /// Returns up to `limit` IDs of the transitions of the given function,
+    /// starting from the given offset, in the order they were indexed.
+    pub fn find_transition_ids_by_function(
+        &self,
+        program_id: &ProgramID<N>,
+        function_name: &Identifier<N>,
+        offset: u32,
+        limit: u32,
+    ) -> Result<Vec<N::TransitionID>> {
+        let locator = (*program_id, *function_name);
+        index_page(&self.locator_index, &self.locator_index_length, &locator, offset, limit)
+    }
+}
+
 impl<N: Network, T: TransitionStorage<N>> TransitionStore<N, T> {
     /// Returns the transition for the given `transition ID`.
     pub fn get_transition(&self, transition_id: &N::TransitionID) -> Result<Option<Transition<N>>> {
@@ -29,6 +29,34 @@ impl<N: Network> Process<N> {
         self.get_stack(program_id)?.authorize::<A, R>(private_key, function_name, inputs, rng)
     }
 
+    /// Prepares a threshold request for a call to the program function for the given inputs,
+    /// to be jointly signed by the participants of the given threshold account.
+    #[inline]
+    pub fn prepare_threshold_request(
+        &self,
+        account: &ThresholdAccount<N>,
+        program_id: impl TryInto<ProgramID<N>>,
+        function_name: impl TryInto<Identifier<N>>,
+        inputs: impl ExactSizeIterator<Item = impl TryInto<Value<N>>>,
+    ) -> Result<ThresholdRequest<N>> {
+        // Prepare the threshold request.
+        self.get_stack(program_id)?.prepare_threshold_request(account, function_name, inputs)
+    }
+
+    /// Authorizes a call to the program function from the signature shares of the participants
+    /// in the signing package of the given threshold request.
+    #[inline]
+    pub fn authorize_threshold<A: circuit::Aleo<Network = N>, R: Rng + CryptoRng>(
+        &self,
+        request: &ThresholdRequest<N>,
+        package: &SigningPackage<N>,
+        shares: &[SignatureShare<N>],
+        rng: &mut R,
+    ) -> Result<Authorization<N>> {
+        // Authorize the call.
+        self.get_stack(*request.program_id())?.authorize_threshold::<A, R>(request, package, shares, rng)
+    }
+
     /// Authorizes the fee given the credits record, the fee amount (in microcredits),
     /// and the deployment or execution ID.
     #[inline]
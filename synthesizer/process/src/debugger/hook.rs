@@ -0,0 +1,76 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use parking_lot::Mutex;
+use std::sync::Arc;
+
+/// A shared reference to a step hook, as carried by the call stack.
+pub type StepHookRef<N> = Arc<Mutex<dyn StepHook<N>>>;
+
+/// A hook that is called as closures, functions, and finalize blocks are evaluated.
+///
+/// The hook is called by the evaluation itself, so it observes exactly the statements that are evaluated.
+/// If a method returns an error, the evaluation halts with that error.
+pub trait StepHook<N: Network>: Send {
+    /// Called when the evaluation enters a closure, function, or finalize block.
+    fn enter(&mut self, _program_id: &ProgramID<N>, _name: &Identifier<N>) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called before each instruction or command is evaluated.
+    fn before_step(&mut self, step: &Step<N>) -> Result<()>;
+
+    /// Called when the evaluation leaves a closure, function, or finalize block.
+    fn exit(&mut self, _program_id: &ProgramID<N>, _name: &Identifier<N>) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// The instruction or command about to be evaluated, as seen by a step hook.
+pub struct Step<'a, N: Network> {
+    /// The location of the instruction or command.
+    location: Location<N>,
+    /// The instruction or command.
+    statement: &'a dyn Display,
+    /// Loads the value of a register in the current scope.
+    load: &'a dyn Fn(&Register<N>) -> Result<Value<N>>,
+}
+
+impl<'a, N: Network> Step<'a, N> {
+    /// Initializes a new step.
+    pub fn new(
+        location: Location<N>,
+        statement: &'a dyn Display,
+        load: &'a dyn Fn(&Register<N>) -> Result<Value<N>>,
+    ) -> Self {
+        Self { location, statement, load }
+    }
+
+    /// Returns the location of the instruction or command.
+    pub const fn location(&self) -> &Location<N> {
+        &self.location
+    }
+
+    /// Returns the instruction or command, as a string.
+    pub fn statement(&self) -> String {
+        self.statement.to_string()
+    }
+
+    /// Returns the value of the given register in the current scope.
+    pub fn load(&self, register: &Register<N>) -> Result<Value<N>> {
+        (self.load)(register)
+    }
+}
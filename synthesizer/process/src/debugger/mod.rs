@@ -0,0 +1,351 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod hook;
+pub use hook::*;
+
+mod session;
+pub use session::*;
+
+use console::{
+    network::prelude::*,
+    program::{Identifier, ProgramID, Register, Value},
+};
+
+use indexmap::{IndexMap, IndexSet};
+
+/// The position of an instruction in a closure or function, or of a command in a finalize block.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Location<N: Network> {
+    /// The program ID.
+    program_id: ProgramID<N>,
+    /// The closure or function name.
+    name: Identifier<N>,
+    /// The index of the instruction or command.
+    index: usize,
+}
+
+impl<N: Network> Location<N> {
+    /// Initializes a new location.
+    pub const fn new(program_id: ProgramID<N>, name: Identifier<N>, index: usize) -> Self {
+        Self { program_id, name, index }
+    }
+
+    /// Returns the program ID.
+    pub const fn program_id(&self) -> &ProgramID<N> {
+        &self.program_id
+    }
+
+    /// Returns the closure or function name.
+    pub const fn name(&self) -> &Identifier<N> {
+        &self.name
+    }
+
+    /// Returns the index of the instruction or command.
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl<N: Network> Display for Location<N> {
+    /// Prints the location as `program_id/name #index`.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}/{} #{}", self.program_id, self.name, self.index)
+    }
+}
+
+/// The reason a debugger paused.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DebugEvent<N: Network> {
+    /// The debugger completed a single step, or is about to evaluate the first instruction or command.
+    Step,
+    /// The debugger reached a breakpoint, before evaluating the instruction or command at the location.
+    Breakpoint(Location<N>),
+    /// The value of a watched register changed.
+    Watch(Register<N>, Value<N>),
+}
+
+/// The breakpoints and watched registers of a debugger.
+#[derive(Clone)]
+pub struct Watchpoints<N: Network> {
+    /// The breakpoints.
+    breakpoints: IndexSet<Location<N>>,
+    /// The watched registers, with the value seen after the last step.
+    watches: IndexMap<Register<N>, Option<Value<N>>>,
+}
+
+impl<N: Network> Default for Watchpoints<N> {
+    /// Initializes an empty set of breakpoints and watched registers.
+    fn default() -> Self {
+        Self { breakpoints: Default::default(), watches: Default::default() }
+    }
+}
+
+impl<N: Network> Watchpoints<N> {
+    /// Returns the breakpoints.
+    pub fn breakpoints(&self) -> impl '_ + Iterator<Item = &Location<N>> {
+        self.breakpoints.iter()
+    }
+
+    /// Returns the watched registers.
+    pub fn registers(&self) -> impl '_ + Iterator<Item = &Register<N>> {
+        self.watches.keys()
+    }
+
+    /// Adds a breakpoint at the given location, returning `false` if it already exists.
+    pub fn add_breakpoint(&mut self, location: Location<N>) -> bool {
+        self.breakpoints.insert(location)
+    }
+
+    /// Removes the breakpoint at the given location, returning `false` if it does not exist.
+    pub fn remove_breakpoint(&mut self, location: &Location<N>) -> bool {
+        self.breakpoints.shift_remove(location)
+    }
+
+    /// Watches the given register, starting from the given value.
+    pub fn watch(&mut self, register: Register<N>, value: Option<Value<N>>) {
+        self.watches.insert(register, value);
+    }
+
+    /// Stops watching the given register, returning `false` if it was not watched.
+    pub fn unwatch(&mut self, register: &Register<N>) -> bool {
+        self.watches.shift_remove(register).is_some()
+    }
+
+    /// Updates the values of the watched registers, and returns the first register whose value changed.
+    /// Registers that are not assigned in the current scope keep their previous value.
+    fn update(&mut self, step: &Step<N>) -> Option<(Register<N>, Value<N>)> {
+        let mut changed = None;
+        for (register, previous) in self.watches.iter_mut() {
+            if let Ok(current) = step.load(register) {
+                if previous.as_ref() != Some(&current) {
+                    if changed.is_none() {
+                        changed = Some((register.clone(), current.clone()));
+                    }
+                    *previous = Some(current);
+                }
+            }
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Authorization, Process};
+    use console::{
+        account::{Address, PrivateKey},
+        network::MainnetV0,
+        program::{Literal, Plaintext},
+    };
+    use ledger_store::{helpers::memory::FinalizeMemory, FinalizeStore};
+    use synthesizer_program::{FinalizeGlobalState, Program};
+
+    use parking_lot::Mutex;
+    use std::sync::Arc;
+
+    type CurrentNetwork = MainnetV0;
+    type CurrentAleo = circuit::network::AleoV0;
+
+    /// The locations and events of each pause.
+    type Pauses = Arc<Mutex<Vec<(Location<CurrentNetwork>, DebugEvent<CurrentNetwork>)>>>;
+
+    const PROGRAM: &str = r"
+program example.aleo;
+
+mapping counts:
+    key as address.public;
+    value as u64.public;
+
+closure twice:
+    input r0 as u64;
+    add r0 r0 into r1;
+    output r1 as u64;
+
+function compute:
+    input r0 as u64.public;
+    call twice r0 into r1;
+    add r1 1u64 into r2;
+    async compute self.caller r2 into r3;
+    output r2 as u64.public;
+    output r3 as example.aleo/compute.future;
+
+finalize compute:
+    input r0 as address.public;
+    input r1 as u64.public;
+    get.or_use counts[r0] 0u64 into r2;
+    add r2 r1 into r3;
+    set r3 into counts[r0];
+";
+
+    /// Samples an authorization for `compute` with the input `3u64`.
+    fn sample_authorization(
+        process: &Process<CurrentNetwork>,
+        private_key: &PrivateKey<CurrentNetwork>,
+        rng: &mut TestRng,
+    ) -> Authorization<CurrentNetwork> {
+        let program_id = ProgramID::from_str("example.aleo").unwrap();
+        let function_name = Identifier::from_str("compute").unwrap();
+        let inputs = [Value::<CurrentNetwork>::from_str("3u64").unwrap()];
+        process.authorize::<CurrentAleo, _>(private_key, program_id, function_name, inputs.iter(), rng).unwrap()
+    }
+
+    /// Returns a debugger that records each pause, and resumes with the command from `on_pause`,
+    /// which is given the number of previous pauses.
+    fn sample_debugger<F>(mut on_pause: F) -> (StepHookRef<CurrentNetwork>, Pauses)
+    where
+        F: 'static + Send + FnMut(usize, &Step<CurrentNetwork>, &mut Watchpoints<CurrentNetwork>) -> DebugCommand,
+    {
+        let pauses = Pauses::default();
+        let recorded = pauses.clone();
+        let debugger = Debugger::new(move |step: &Step<CurrentNetwork>, event, watchpoints: &mut Watchpoints<_>| {
+            let count = recorded.lock().len();
+            recorded.lock().push((step.location().clone(), event));
+            Ok(on_pause(count, step, watchpoints))
+        });
+        (debugger.into_hook(), pauses)
+    }
+
+    fn location(name: &str, index: usize) -> Location<CurrentNetwork> {
+        Location::new(ProgramID::from_str("example.aleo").unwrap(), Identifier::from_str(name).unwrap(), index)
+    }
+
+    fn register(register: &str) -> Register<CurrentNetwork> {
+        Register::from_str(register).unwrap()
+    }
+
+    fn value(value: &str) -> Value<CurrentNetwork> {
+        Value::from_str(value).unwrap()
+    }
+
+    #[test]
+    fn test_step_into_closure() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::new(rng).unwrap();
+        let process = crate::test_helpers::sample_process(&Program::from_str(PROGRAM).unwrap());
+        let authorization = sample_authorization(&process, &private_key, rng);
+
+        // Step into the closure, then step over the remaining instructions.
+        let (hook, pauses) = sample_debugger(|count, step, _| match count {
+            0 => {
+                assert_eq!(step.load(&register("r0")).unwrap(), value("3u64"));
+                DebugCommand::StepInto
+            }
+            1 => {
+                assert_eq!(step.statement(), "add r0 r0 into r1;");
+                DebugCommand::Step
+            }
+            2 => {
+                assert_eq!(step.load(&register("r1")).unwrap(), value("6u64"));
+                DebugCommand::Step
+            }
+            _ => DebugCommand::Step,
+        });
+        let response = process.evaluate_with_hook::<CurrentAleo>(authorization, hook).unwrap();
+        assert_eq!(response.outputs()[0], value("7u64"));
+
+        let expected = [location("compute", 0), location("twice", 0), location("compute", 1), location("compute", 2)];
+        let pauses = pauses.lock();
+        assert_eq!(pauses.iter().map(|(location, _)| location.clone()).collect::<Vec<_>>(), expected);
+        assert!(pauses.iter().all(|(_, event)| *event == DebugEvent::Step));
+    }
+
+    #[test]
+    fn test_breakpoints_and_watches() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::new(rng).unwrap();
+        let process = crate::test_helpers::sample_process(&Program::from_str(PROGRAM).unwrap());
+
+        // Continue to a breakpoint in the closure, then to the end.
+        let (hook, pauses) = sample_debugger(|count, _, watchpoints| {
+            match count {
+                0 => {
+                    assert!(watchpoints.add_breakpoint(location("twice", 0)));
+                    assert!(!watchpoints.add_breakpoint(location("twice", 0)));
+                }
+                _ => assert!(watchpoints.remove_breakpoint(&location("twice", 0))),
+            }
+            DebugCommand::Continue
+        });
+        let authorization = sample_authorization(&process, &private_key, rng);
+        process.evaluate_with_hook::<CurrentAleo>(authorization, hook).unwrap();
+        assert_eq!(*pauses.lock(), vec![
+            (location("compute", 0), DebugEvent::Step),
+            (location("twice", 0), DebugEvent::Breakpoint(location("twice", 0))),
+        ]);
+
+        // Continue until a watched register changes.
+        let (hook, pauses) = sample_debugger(|count, step, watchpoints| {
+            match count {
+                0 => watchpoints.watch(register("r2"), step.load(&register("r2")).ok()),
+                _ => assert!(watchpoints.unwatch(&register("r2"))),
+            }
+            DebugCommand::Continue
+        });
+        let authorization = sample_authorization(&process, &private_key, rng);
+        process.evaluate_with_hook::<CurrentAleo>(authorization, hook).unwrap();
+        assert_eq!(*pauses.lock(), vec![
+            (location("compute", 0), DebugEvent::Step),
+            (location("compute", 2), DebugEvent::Watch(register("r2"), value("7u64"))),
+        ]);
+    }
+
+    #[test]
+    fn test_step_finalize() {
+        let rng = &mut TestRng::default();
+        let private_key = PrivateKey::new(rng).unwrap();
+        let process = crate::test_helpers::sample_process(&Program::from_str(PROGRAM).unwrap());
+
+        // Evaluate the function.
+        let authorization = sample_authorization(&process, &private_key, rng);
+        let response = process.evaluate::<CurrentAleo>(authorization).unwrap();
+        let future = match &response.outputs()[1] {
+            Value::Future(future) => future.clone(),
+            output => panic!("Expected a future, found '{output}'"),
+        };
+
+        // Initialize a scratch finalize store.
+        let program_id = ProgramID::from_str("example.aleo").unwrap();
+        let mapping_name = Identifier::from_str("counts").unwrap();
+        let store = FinalizeStore::<CurrentNetwork, FinalizeMemory<_>>::open(None).unwrap();
+        store.initialize_mapping(program_id, mapping_name).unwrap();
+
+        // Step through the finalize block.
+        let (hook, pauses) = sample_debugger(|count, step, watchpoints| {
+            match count {
+                0 => {
+                    assert_eq!(step.load(&register("r1")).unwrap(), value("7u64"));
+                    watchpoints.watch(register("r3"), step.load(&register("r3")).ok());
+                }
+                2 => assert_eq!(step.statement(), "set r3 into counts[r0];"),
+                _ => {}
+            }
+            DebugCommand::Step
+        });
+        let state = FinalizeGlobalState::new_genesis::<CurrentNetwork>().unwrap();
+        let operations = process.finalize_with_hook(state, &store, &future, hook).unwrap();
+        assert_eq!(operations.len(), 1);
+        assert_eq!(*pauses.lock(), vec![
+            (location("compute", 0), DebugEvent::Step),
+            (location("compute", 1), DebugEvent::Step),
+            (location("compute", 2), DebugEvent::Watch(register("r3"), value("7u64"))),
+        ]);
+
+        // Ensure the store was updated.
+        let key = Plaintext::from(Literal::Address(Address::try_from(&private_key).unwrap()));
+        let count = store.get_value_speculative(program_id, mapping_name, &key).unwrap();
+        assert_eq!(count, Some(value("7u64")));
+    }
+}
@@ -0,0 +1,123 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use parking_lot::Mutex;
+use std::sync::Arc;
+
+/// The command that resumes evaluation after the debugger pauses.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DebugCommand {
+    /// Evaluates the next instruction or command, evaluating any call (or await) in full.
+    Step,
+    /// Evaluates the next instruction or command, entering the scope of any call (or await).
+    StepInto,
+    /// Evaluates until a breakpoint is reached or a watched register changes.
+    Continue,
+}
+
+/// When the debugger pauses next, besides breakpoints and watched registers.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Pause {
+    /// Pause before the next instruction or command.
+    Next,
+    /// Pause before the next instruction or command at (or above) the given depth.
+    AtDepth(usize),
+    /// Pause only at breakpoints and watched registers.
+    Never,
+}
+
+/// A step-through debugger, which is driven by the evaluation as a step hook.
+///
+/// The debugger pauses before the first instruction (or command), at breakpoints, when a watched
+/// register changes, and as requested by the last `DebugCommand`. On each pause, it calls `on_pause`
+/// with the next step, the reason for the pause, and the watchpoints, which `on_pause` may modify.
+/// To stop the evaluation, `on_pause` returns an error.
+pub struct Debugger<N: Network, F> {
+    /// The number of closures, functions, and finalize blocks entered.
+    depth: usize,
+    /// When the debugger pauses next.
+    pause: Pause,
+    /// The breakpoints and watched registers.
+    watchpoints: Watchpoints<N>,
+    /// The callback on each pause.
+    on_pause: F,
+}
+
+impl<N: Network, F> Debugger<N, F>
+where
+    F: Send + FnMut(&Step<N>, DebugEvent<N>, &mut Watchpoints<N>) -> Result<DebugCommand>,
+{
+    /// Initializes a new debugger with the given callback on each pause.
+    pub fn new(on_pause: F) -> Self {
+        Self { depth: 0, pause: Pause::Next, watchpoints: Default::default(), on_pause }
+    }
+
+    /// Returns the number of closures, functions, and finalize blocks entered.
+    pub const fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the breakpoints and watched registers.
+    pub const fn watchpoints(&self) -> &Watchpoints<N> {
+        &self.watchpoints
+    }
+
+    /// Returns the debugger as a step hook, for `Process::evaluate_with_hook` or `Process::finalize_with_hook`.
+    pub fn into_hook(self) -> StepHookRef<N>
+    where
+        F: 'static,
+    {
+        Arc::new(Mutex::new(self))
+    }
+}
+
+impl<N: Network, F> StepHook<N> for Debugger<N, F>
+where
+    F: Send + FnMut(&Step<N>, DebugEvent<N>, &mut Watchpoints<N>) -> Result<DebugCommand>,
+{
+    /// Enters the scope of a closure, function, or finalize block.
+    fn enter(&mut self, _program_id: &ProgramID<N>, _name: &Identifier<N>) -> Result<()> {
+        self.depth += 1;
+        Ok(())
+    }
+
+    /// Pauses before the given step, if a breakpoint, a watched register, or the last command requires it.
+    fn before_step(&mut self, step: &Step<N>) -> Result<()> {
+        let event = match self.watchpoints.update(step) {
+            Some((register, value)) => DebugEvent::Watch(register, value),
+            None if self.watchpoints.breakpoints.contains(step.location()) => {
+                DebugEvent::Breakpoint(step.location().clone())
+            }
+            None => match self.pause {
+                Pause::Next => DebugEvent::Step,
+                Pause::AtDepth(depth) if self.depth <= depth => DebugEvent::Step,
+                _ => return Ok(()),
+            },
+        };
+        self.pause = match (self.on_pause)(step, event, &mut self.watchpoints)? {
+            DebugCommand::Step => Pause::AtDepth(self.depth),
+            DebugCommand::StepInto => Pause::Next,
+            DebugCommand::Continue => Pause::Never,
+        };
+        Ok(())
+    }
+
+    /// Leaves the scope of a closure, function, or finalize block.
+    fn exit(&mut self, _program_id: &ProgramID<N>, _name: &Identifier<N>) -> Result<()> {
+        self.depth = self.depth.saturating_sub(1);
+        Ok(())
+    }
+}
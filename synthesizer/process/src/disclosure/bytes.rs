@@ -0,0 +1,68 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::*;
+
+impl<N: Network> FromBytes for RecordDisclosure<N> {
+    /// Reads the record disclosure from a buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the version.
+        let version = u8::read_le(&mut reader)?;
+        // Ensure the version is valid.
+        if version != 1 {
+            return Err(error("Invalid record disclosure version"));
+        }
+
+        // Read the statement.
+        let program_id = FromBytes::read_le(&mut reader)?;
+        let record_name = FromBytes::read_le(&mut reader)?;
+        let entry = FromBytes::read_le(&mut reader)?;
+        let predicate = FromBytes::read_le(&mut reader)?;
+        let global_state_root = FromBytes::read_le(&mut reader)?;
+        let owner = FromBytes::read_le(&mut reader)?;
+        let serial_number = match u8::read_le(&mut reader)? {
+            0 => None,
+            1 => Some(FromBytes::read_le(&mut reader)?),
+            _ => return Err(error("Invalid record disclosure serial number flag")),
+        };
+        // Read the proof.
+        let proof = FromBytes::read_le(&mut reader)?;
+
+        // Return the record disclosure.
+        Ok(Self::new(program_id, record_name, entry, predicate, global_state_root, owner, serial_number, proof))
+    }
+}
+
+impl<N: Network> ToBytes for RecordDisclosure<N> {
+    /// Writes the record disclosure to a buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        // Write the version.
+        1u8.write_le(&mut writer)?;
+        // Write the statement.
+        self.program_id.write_le(&mut writer)?;
+        self.record_name.write_le(&mut writer)?;
+        self.entry.write_le(&mut writer)?;
+        self.predicate.write_le(&mut writer)?;
+        self.global_state_root.write_le(&mut writer)?;
+        self.owner.write_le(&mut writer)?;
+        match &self.serial_number {
+            None => 0u8.write_le(&mut writer)?,
+            Some(serial_number) => {
+                1u8.write_le(&mut writer)?;
+                serial_number.write_le(&mut writer)?;
+            }
+        }
+        // Write the proof.
+        self.proof.write_le(&mut writer)
+    }
+}
@@ -0,0 +1,453 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod bytes;
+mod predicate;
+mod serialize;
+mod string;
+
+pub use predicate::*;
+
+use super::*;
+use console::{
+    account::Address,
+    program::{Entry, StatePath, TransactionLeaf, TransitionLeaf, TRANSACTION_DEPTH, TRANSITION_DEPTH},
+    types::Group,
+};
+use synthesizer_program::StackMatches;
+use synthesizer_snark::Proof;
+
+/// A selective disclosure of a record, without revealing the record, its commitment, or the view key of its owner.
+///
+/// The proof asserts that a record committed at the global state root is owned by the address,
+/// that the serial number (if revealed) belongs to the record, and that the entry of the record satisfies
+/// the predicate.
+/// Note: The verifier is responsible for checking the global state root exists, and the serial number is unspent.
+///
+/// Warning: A revealed serial number links the disclosure to the transition that spends the record,
+/// so the serial number should only be revealed to a verifier who must check that the record is unspent.
+#[derive(Clone, PartialEq, Eq)]
+pub struct RecordDisclosure<N: Network> {
+    /// The program ID of the record.
+    program_id: ProgramID<N>,
+    /// The name of the record.
+    record_name: Identifier<N>,
+    /// The name of the disclosed entry.
+    entry: Identifier<N>,
+    /// The predicate satisfied by the disclosed entry.
+    predicate: DisclosurePredicate,
+    /// The global state root the record is committed at.
+    global_state_root: N::StateRoot,
+    /// The owner of the record.
+    owner: Address<N>,
+    /// The serial number of the record, if revealed.
+    serial_number: Option<Field<N>>,
+    /// The proof of the disclosure.
+    proof: Proof<N>,
+}
+
+impl<N: Network> RecordDisclosure<N> {
+    /// Initializes a new record disclosure.
+    pub const fn new(
+        program_id: ProgramID<N>,
+        record_name: Identifier<N>,
+        entry: Identifier<N>,
+        predicate: DisclosurePredicate,
+        global_state_root: N::StateRoot,
+        owner: Address<N>,
+        serial_number: Option<Field<N>>,
+        proof: Proof<N>,
+    ) -> Self {
+        Self { program_id, record_name, entry, predicate, global_state_root, owner, serial_number, proof }
+    }
+
+    /// Returns the program ID of the record.
+    pub const fn program_id(&self) -> &ProgramID<N> {
+        &self.program_id
+    }
+
+    /// Returns the name of the record.
+    pub const fn record_name(&self) -> &Identifier<N> {
+        &self.record_name
+    }
+
+    /// Returns the name of the disclosed entry.
+    pub const fn entry(&self) -> &Identifier<N> {
+        &self.entry
+    }
+
+    /// Returns the predicate satisfied by the disclosed entry.
+    pub const fn predicate(&self) -> &DisclosurePredicate {
+        &self.predicate
+    }
+
+    /// Returns the global state root the record is committed at.
+    pub const fn global_state_root(&self) -> N::StateRoot {
+        self.global_state_root
+    }
+
+    /// Returns the owner of the record.
+    pub const fn owner(&self) -> &Address<N> {
+        &self.owner
+    }
+
+    /// Returns the serial number of the record, if revealed.
+    pub const fn serial_number(&self) -> Option<&Field<N>> {
+        self.serial_number.as_ref()
+    }
+
+    /// Returns the proof of the disclosure.
+    pub const fn proof(&self) -> &Proof<N> {
+        &self.proof
+    }
+
+    /// Returns the verifier inputs of the disclosure, in the order they are injected in the circuit.
+    fn to_verifier_inputs(&self) -> Vec<N::Field> {
+        let mut inputs = vec![
+            N::Field::one(),
+            **self.global_state_root,
+            *self.owner.to_x_coordinate(),
+            *self.owner.to_y_coordinate(),
+        ];
+        inputs.extend(self.serial_number.map(|serial_number| *serial_number));
+        inputs.push(*Field::<N>::from_u64(self.predicate.bound()));
+        inputs
+    }
+
+    /// Returns the name of the disclosure circuit, as `program_id/record_name.entry <operator>`,
+    /// with a `+serial_number` suffix if the serial number is revealed.
+    fn circuit_name(
+        program_id: &ProgramID<N>,
+        record_name: &Identifier<N>,
+        entry: &Identifier<N>,
+        predicate: DisclosurePredicate,
+        reveal_serial_number: bool,
+    ) -> String {
+        let suffix = if reveal_serial_number { "+serial_number" } else { "" };
+        format!("{program_id}/{record_name}.{entry} {}{suffix}", predicate.operator())
+    }
+}
+
+impl<N: Network> Process<N> {
+    /// Returns a disclosure that the given record, committed at the global state root of the state path,
+    /// is owned by the given private key, and that its entry satisfies the given predicate.
+    ///
+    /// If `reveal_serial_number` is set, the disclosure includes the serial number of the record,
+    /// so that the verifier can check the record is unspent.
+    /// Warning: A revealed serial number links the disclosure to the transition that spends the record.
+    pub fn prove_record_disclosure<A: circuit::Aleo<Network = N>, R: Rng + CryptoRng>(
+        &self,
+        private_key: &PrivateKey<N>,
+        program_id: &ProgramID<N>,
+        record_name: &Identifier<N>,
+        record: &Record<N, Plaintext<N>>,
+        state_path: StatePath<N>,
+        entry: &Identifier<N>,
+        predicate: DisclosurePredicate,
+        reveal_serial_number: bool,
+        rng: &mut R,
+    ) -> Result<RecordDisclosure<N>> {
+        // Retrieve the stack, and ensure the record matches its record type.
+        let stack = self.get_stack(program_id)?;
+        stack.matches_record(record, record_name)?;
+
+        // Ensure the record is owned by the private key.
+        let owner = Address::try_from(private_key)?;
+        ensure!(**record.owner() == owner, "The record is not owned by '{owner}'");
+        // Ensure the record is committed at the global state root.
+        let commitment = record.to_commitment(program_id, record_name)?;
+        ensure!(state_path.transition_leaf().id() == commitment, "The state path is not for the record commitment");
+        state_path.verify(true, Field::zero())?;
+        // Ensure the entry satisfies the predicate.
+        let value = entry_value(record, entry)?;
+        ensure!(predicate.is_satisfied_by(value), "The record entry '{entry}' does not satisfy '{predicate}'");
+
+        // Compute the serial number, if revealed.
+        let serial_number = match reveal_serial_number {
+            true => Some(Record::<N, Plaintext<N>>::serial_number(*private_key, commitment)?),
+            false => None,
+        };
+        let global_state_root = state_path.global_state_root();
+
+        // Synthesize the assignment.
+        let assignment = DisclosureAssignment {
+            private_key: *private_key,
+            program_id: *program_id,
+            record_name: *record_name,
+            record: record.clone(),
+            state_path,
+            entry: *entry,
+            predicate,
+            reveal_serial_number,
+        }
+        .to_circuit_assignment::<A>()?;
+
+        // Compute the proof.
+        let (proving_key, _) =
+            self.get_or_synthesize_disclosure_key::<A>(stack, record_name, entry, predicate, reveal_serial_number)?;
+        let name = RecordDisclosure::circuit_name(program_id, record_name, entry, predicate, reveal_serial_number);
+        let proof = proving_key.prove(&name, &assignment, rng)?;
+
+        Ok(RecordDisclosure::new(
+            *program_id,
+            *record_name,
+            *entry,
+            predicate,
+            global_state_root,
+            owner,
+            serial_number,
+            proof,
+        ))
+    }
+
+    /// Verifies the given record disclosure. On failure, returns an error.
+    ///
+    /// Note: This does *not* check that the global state root exists in the ledger,
+    /// or that the serial number (if revealed) is unspent.
+    pub fn verify_record_disclosure<A: circuit::Aleo<Network = N>>(
+        &self,
+        disclosure: &RecordDisclosure<N>,
+    ) -> Result<()> {
+        // Retrieve the stack.
+        let stack = self.get_stack(disclosure.program_id())?;
+        // Retrieve the verifying key.
+        let reveal_serial_number = disclosure.serial_number().is_some();
+        let (_, verifying_key) = self.get_or_synthesize_disclosure_key::<A>(
+            stack,
+            disclosure.record_name(),
+            disclosure.entry(),
+            *disclosure.predicate(),
+            reveal_serial_number,
+        )?;
+        // Verify the proof.
+        let name = RecordDisclosure::circuit_name(
+            disclosure.program_id(),
+            disclosure.record_name(),
+            disclosure.entry(),
+            *disclosure.predicate(),
+            reveal_serial_number,
+        );
+        ensure!(
+            verifying_key.verify(&name, &disclosure.to_verifier_inputs(), disclosure.proof()),
+            "Failed to verify the disclosure of '{name}'"
+        );
+        Ok(())
+    }
+
+    /// Returns the `(proving_key, verifying_key)` of the disclosure circuit for the given record entry and predicate.
+    ///
+    /// The keys are synthesized once per circuit, and cached in the process.
+    fn get_or_synthesize_disclosure_key<A: circuit::Aleo<Network = N>>(
+        &self,
+        stack: &Stack<N>,
+        record_name: &Identifier<N>,
+        entry: &Identifier<N>,
+        predicate: DisclosurePredicate,
+        reveal_serial_number: bool,
+    ) -> Result<(ProvingKey<N>, VerifyingKey<N>)> {
+        // The keys only depend on the predicate operator, as the bound is a public input.
+        let name =
+            RecordDisclosure::circuit_name(stack.program_id(), record_name, entry, predicate, reveal_serial_number);
+        // Return the cached keys, if they exist.
+        if let Some(keys) = self.disclosure_keys.read().get(&name) {
+            return Ok(keys.clone());
+        }
+        // Synthesize and cache the keys.
+        let keys = self.synthesize_disclosure_key::<A>(stack, record_name, entry, predicate, reveal_serial_number)?;
+        self.disclosure_keys.write().insert(name, keys.clone());
+        Ok(keys)
+    }
+
+    /// Synthesizes the `(proving_key, verifying_key)` of the disclosure circuit for the given record entry and predicate.
+    ///
+    /// The circuit is synthesized from a sampled record, so that the prover and verifier derive the same keys.
+    fn synthesize_disclosure_key<A: circuit::Aleo<Network = N>>(
+        &self,
+        stack: &Stack<N>,
+        record_name: &Identifier<N>,
+        entry: &Identifier<N>,
+        predicate: DisclosurePredicate,
+        reveal_serial_number: bool,
+    ) -> Result<(ProvingKey<N>, VerifyingKey<N>)> {
+        let rng = &mut rand::thread_rng();
+
+        // Sample a burner private key, and a record owned by it.
+        let private_key = PrivateKey::new(rng)?;
+        let record = stack.sample_record(&Address::try_from(&private_key)?, record_name, Group::rand(rng), rng)?;
+        // Sample a state path for the record.
+        let state_path = sample_state_path(record.to_commitment(stack.program_id(), record_name)?)?;
+
+        // Synthesize the assignment.
+        let assignment = DisclosureAssignment {
+            private_key,
+            program_id: *stack.program_id(),
+            record_name: *record_name,
+            record,
+            state_path,
+            entry: *entry,
+            predicate,
+            reveal_serial_number,
+        }
+        .to_circuit_assignment::<A>()?;
+
+        // Synthesize the proving and verifying key.
+        let name =
+            RecordDisclosure::circuit_name(stack.program_id(), record_name, entry, predicate, reveal_serial_number);
+        self.universal_srs.to_circuit_key(&name, &assignment)
+    }
+}
+
+/// The witness of a record disclosure.
+struct DisclosureAssignment<N: Network> {
+    private_key: PrivateKey<N>,
+    program_id: ProgramID<N>,
+    record_name: Identifier<N>,
+    record: Record<N, Plaintext<N>>,
+    state_path: StatePath<N>,
+    entry: Identifier<N>,
+    predicate: DisclosurePredicate,
+    reveal_serial_number: bool,
+}
+
+impl<N: Network> DisclosureAssignment<N> {
+    /// The circuit for record disclosure.
+    ///
+    /// # Diagram
+    /// The `[[ ]]` notation is used to denote public inputs.
+    /// ```ignore
+    ///   [[ global_state_root ]]
+    ///              |
+    ///         state_path
+    ///             |
+    ///     commitment := Commit( program_id || record_name || record )
+    ///                                                          |
+    ///          [[ owner ]] == record.owner == Address( private_key )
+    ///                                                          |
+    /// [[ serial_number ]] := SerialNumber( private_key, commitment )    (if revealed)
+    ///                                                          |
+    ///                          Predicate( record.entry, [[ bound ]] )
+    /// ```
+    fn to_circuit_assignment<A: circuit::Aleo<Network = N>>(&self) -> Result<circuit::Assignment<N::Field>> {
+        use circuit::{
+            traits::{Compare, Equal, ToField},
+            Inject,
+        };
+
+        // Ensure the entry is a `u64`, before the circuit environment is modified.
+        entry_value(&self.record, &self.entry)?;
+        // Compute the serial number, if revealed.
+        let serial_number = match self.reveal_serial_number {
+            true => {
+                let commitment = self.record.to_commitment(&self.program_id, &self.record_name)?;
+                Some(Record::<N, Plaintext<N>>::serial_number(self.private_key, commitment)?)
+            }
+            false => None,
+        };
+
+        // Ensure the circuit environment is clean.
+        assert_eq!(A::count(), (0, 1, 0, 0, (0, 0, 0)));
+        A::reset();
+
+        // Inject the state path as `Mode::Private` (with a global state root as `Mode::Public`).
+        let state_path = circuit::StatePath::<A>::new(circuit::Mode::Private, self.state_path.clone());
+        // Inject the owner as `Mode::Public`.
+        let owner = circuit::Address::<A>::new(circuit::Mode::Public, **self.record.owner());
+        // Inject the serial number as `Mode::Public`, if revealed.
+        let serial_number =
+            serial_number.map(|serial_number| circuit::Field::<A>::new(circuit::Mode::Public, serial_number));
+        // Inject the bound as `Mode::Public`.
+        let bound = self.predicate.bound();
+        let public_bound = circuit::Field::<A>::new(circuit::Mode::Public, Field::from_u64(bound));
+
+        // Inject the private key, record, and bound as `Mode::Private`.
+        let private_key = circuit::PrivateKey::<A>::new(circuit::Mode::Private, self.private_key);
+        let record = circuit::Record::<A, circuit::Plaintext<A>>::new(circuit::Mode::Private, self.record.clone());
+        let bound = circuit::U64::<A>::new(circuit::Mode::Private, U64::new(bound));
+        // Enforce the private bound is equal to the public bound.
+        A::assert_eq(bound.to_field(), public_bound);
+
+        // Compute the record commitment.
+        let program_id = circuit::ProgramID::<A>::constant(self.program_id);
+        let record_name = circuit::Identifier::<A>::constant(self.record_name);
+        let commitment = record.to_commitment(&program_id, &record_name);
+        // Enforce the starting leaf is the record commitment.
+        A::assert_eq(state_path.transition_leaf().id(), &commitment);
+        // Enforce the state path from leaf to global state root is correct.
+        A::assert(state_path.verify(&circuit::Boolean::constant(true), &circuit::Field::constant(Field::zero())));
+
+        // Enforce the record owner is the address of the private key, and is the public owner.
+        let address = private_key.to_compute_key().to_address();
+        A::assert((**record.owner()).is_equal(&address));
+        A::assert(address.is_equal(&owner));
+        // Enforce the serial number (if revealed) is derived from the private key and record commitment.
+        if let Some(serial_number) = serial_number {
+            let candidate_serial_number =
+                circuit::Record::<A, circuit::Plaintext<A>>::serial_number(private_key, commitment);
+            A::assert_eq(candidate_serial_number, serial_number);
+        }
+
+        // Retrieve the entry.
+        let value = match record.find(&[circuit::Access::Member(circuit::Identifier::constant(self.entry))])? {
+            circuit::Entry::Constant(circuit::Plaintext::Literal(circuit::Literal::U64(value), _))
+            | circuit::Entry::Public(circuit::Plaintext::Literal(circuit::Literal::U64(value), _))
+            | circuit::Entry::Private(circuit::Plaintext::Literal(circuit::Literal::U64(value), _)) => value,
+            _ => bail!("The record entry '{}' is not a 'u64'", self.entry),
+        };
+        // Enforce the entry satisfies the predicate.
+        A::assert(match self.predicate {
+            DisclosurePredicate::Equal(..) => value.is_equal(&bound),
+            DisclosurePredicate::GreaterThanOrEqual(..) => value.is_greater_than_or_equal(&bound),
+            DisclosurePredicate::LessThanOrEqual(..) => value.is_less_than_or_equal(&bound),
+        });
+
+        #[cfg(debug_assertions)]
+        Stack::log_circuit::<A, _>(&format!("Disclosure of {}/{}.{}", self.program_id, self.record_name, self.entry));
+
+        // Eject the assignment and reset the circuit environment.
+        Ok(A::eject_assignment_and_reset())
+    }
+}
+
+/// Returns the `u64` value of the given entry in the record.
+fn entry_value<N: Network>(record: &Record<N, Plaintext<N>>, entry: &Identifier<N>) -> Result<u64> {
+    match record.find(&[*entry])? {
+        Entry::Constant(Plaintext::Literal(Literal::U64(value), _))
+        | Entry::Public(Plaintext::Literal(Literal::U64(value), _))
+        | Entry::Private(Plaintext::Literal(Literal::U64(value), _)) => Ok(*value),
+        _ => bail!("The record entry '{entry}' is not a 'u64'"),
+    }
+}
+
+/// Returns a state path for the given commitment, with the same shape as a state path to a global state root.
+fn sample_state_path<N: Network>(commitment: Field<N>) -> Result<StatePath<N>> {
+    // Construct the transition path.
+    let transition_leaf = TransitionLeaf::new_with_version(0, 3, commitment);
+    let transition_tree = N::merkle_tree_bhp::<TRANSITION_DEPTH>(&[transition_leaf.to_bits_le()])?;
+    let transition_path = transition_tree.prove(0, &transition_leaf.to_bits_le())?;
+    // Construct the transaction path.
+    let transaction_leaf = TransactionLeaf::new_execution(0, Field::zero());
+    let transaction_tree = N::merkle_tree_bhp::<TRANSACTION_DEPTH>(&[transaction_leaf.to_bits_le()])?;
+    let transaction_path = transaction_tree.prove(0, &transaction_leaf.to_bits_le())?;
+    // Construct the state path.
+    StatePath::new_local(
+        Field::zero().into(),
+        (*transaction_tree.root()).into(),
+        transaction_path,
+        transaction_leaf,
+        *transition_tree.root(),
+        Field::zero(),
+        transition_path,
+        transition_leaf,
+    )
+}
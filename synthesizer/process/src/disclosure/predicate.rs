@@ -0,0 +1,135 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// A predicate over a `u64` record entry, against a public bound.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum DisclosurePredicate {
+    /// The entry is equal to the bound.
+    Equal(u64),
+    /// The entry is greater than or equal to the bound.
+    GreaterThanOrEqual(u64),
+    /// The entry is less than or equal to the bound.
+    LessThanOrEqual(u64),
+}
+
+impl DisclosurePredicate {
+    /// Returns the public bound of the predicate.
+    pub const fn bound(&self) -> u64 {
+        match self {
+            Self::Equal(bound) | Self::GreaterThanOrEqual(bound) | Self::LessThanOrEqual(bound) => *bound,
+        }
+    }
+
+    /// Returns `true` if the given value satisfies the predicate.
+    pub const fn is_satisfied_by(&self, value: u64) -> bool {
+        match self {
+            Self::Equal(bound) => value == *bound,
+            Self::GreaterThanOrEqual(bound) => value >= *bound,
+            Self::LessThanOrEqual(bound) => value <= *bound,
+        }
+    }
+
+    /// Returns the variant of the predicate.
+    const fn variant(&self) -> u8 {
+        match self {
+            Self::Equal(..) => 0,
+            Self::GreaterThanOrEqual(..) => 1,
+            Self::LessThanOrEqual(..) => 2,
+        }
+    }
+
+    /// Returns the comparison operator of the predicate.
+    pub(super) const fn operator(&self) -> &'static str {
+        match self {
+            Self::Equal(..) => "==",
+            Self::GreaterThanOrEqual(..) => ">=",
+            Self::LessThanOrEqual(..) => "<=",
+        }
+    }
+}
+
+impl FromBytes for DisclosurePredicate {
+    /// Reads the predicate from a buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        let variant = u8::read_le(&mut reader)?;
+        let bound = u64::read_le(&mut reader)?;
+        match variant {
+            0 => Ok(Self::Equal(bound)),
+            1 => Ok(Self::GreaterThanOrEqual(bound)),
+            2 => Ok(Self::LessThanOrEqual(bound)),
+            _ => Err(error("Invalid disclosure predicate variant")),
+        }
+    }
+}
+
+impl ToBytes for DisclosurePredicate {
+    /// Writes the predicate to a buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        self.variant().write_le(&mut writer)?;
+        self.bound().write_le(&mut writer)
+    }
+}
+
+impl FromStr for DisclosurePredicate {
+    type Err = Error;
+
+    /// Parses a predicate of the form `<operator> <bound>`, such as `>= 100`.
+    fn from_str(predicate: &str) -> Result<Self, Self::Err> {
+        let Some((operator, bound)) = predicate.trim().split_once(' ') else {
+            bail!("Invalid disclosure predicate '{predicate}'")
+        };
+        let bound = bound.trim().trim_end_matches("u64").parse::<u64>()?;
+        match operator {
+            "==" => Ok(Self::Equal(bound)),
+            ">=" => Ok(Self::GreaterThanOrEqual(bound)),
+            "<=" => Ok(Self::LessThanOrEqual(bound)),
+            _ => bail!("Invalid disclosure predicate operator '{operator}'"),
+        }
+    }
+}
+
+impl Display for DisclosurePredicate {
+    /// Prints the predicate as `<operator> <bound>`.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.operator(), self.bound())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_predicate() -> Result<()> {
+        for predicate in [
+            DisclosurePredicate::Equal(5),
+            DisclosurePredicate::GreaterThanOrEqual(5),
+            DisclosurePredicate::LessThanOrEqual(5),
+        ] {
+            // Check the string and byte round trips.
+            assert_eq!(predicate, DisclosurePredicate::from_str(&predicate.to_string())?);
+            assert_eq!(predicate, DisclosurePredicate::read_le(&predicate.to_bytes_le()?[..])?);
+            // Check the bound is inclusive.
+            assert!(predicate.is_satisfied_by(5));
+        }
+        assert!(!DisclosurePredicate::Equal(5).is_satisfied_by(6));
+        assert!(!DisclosurePredicate::GreaterThanOrEqual(5).is_satisfied_by(4));
+        assert!(!DisclosurePredicate::LessThanOrEqual(5).is_satisfied_by(6));
+        assert_eq!(DisclosurePredicate::GreaterThanOrEqual(7), DisclosurePredicate::from_str(">= 7u64")?);
+        assert!(DisclosurePredicate::from_str("> 7").is_err());
+        Ok(())
+    }
+}
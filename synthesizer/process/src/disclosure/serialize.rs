@@ -0,0 +1,61 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use super::*;
+
+impl<N: Network> Serialize for RecordDisclosure<N> {
+    /// Serializes the record disclosure into string or bytes.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => {
+                let mut disclosure = serializer.serialize_struct("RecordDisclosure", 8)?;
+                disclosure.serialize_field("program_id", &self.program_id)?;
+                disclosure.serialize_field("record_name", &self.record_name)?;
+                disclosure.serialize_field("entry", &self.entry)?;
+                disclosure.serialize_field("predicate", &self.predicate.to_string())?;
+                disclosure.serialize_field("global_state_root", &self.global_state_root)?;
+                disclosure.serialize_field("owner", &self.owner)?;
+                disclosure.serialize_field("serial_number", &self.serial_number)?;
+                disclosure.serialize_field("proof", &self.proof)?;
+                disclosure.end()
+            }
+            false => ToBytesSerializer::serialize_with_size_encoding(self, serializer),
+        }
+    }
+}
+
+impl<'de, N: Network> Deserialize<'de> for RecordDisclosure<N> {
+    /// Deserializes the record disclosure from a string or bytes.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match deserializer.is_human_readable() {
+            true => {
+                // Parse the record disclosure from a string into a value.
+                let mut disclosure = serde_json::Value::deserialize(deserializer)?;
+                // Retrieve the predicate.
+                let predicate: String = DeserializeExt::take_from_value::<D>(&mut disclosure, "predicate")?;
+                // Recover the record disclosure.
+                Ok(Self::new(
+                    DeserializeExt::take_from_value::<D>(&mut disclosure, "program_id")?,
+                    DeserializeExt::take_from_value::<D>(&mut disclosure, "record_name")?,
+                    DeserializeExt::take_from_value::<D>(&mut disclosure, "entry")?,
+                    DisclosurePredicate::from_str(&predicate).map_err(de::Error::custom)?,
+                    DeserializeExt::take_from_value::<D>(&mut disclosure, "global_state_root")?,
+                    DeserializeExt::take_from_value::<D>(&mut disclosure, "owner")?,
+                    DeserializeExt::take_from_value::<D>(&mut disclosure, "serial_number")?,
+                    DeserializeExt::take_from_value::<D>(&mut disclosure, "proof")?,
+                ))
+            }
+            false => FromBytesDeserializer::<Self>::deserialize_with_size_encoding(deserializer, "record disclosure"),
+        }
+    }
+}
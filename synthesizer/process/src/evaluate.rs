@@ -36,4 +36,17 @@ impl<N: Network> Process<N> {
 
         response
     }
+
+    /// Evaluates a program function on the given request, calling the given step hook before each instruction.
+    #[inline]
+    pub fn evaluate_with_hook<A: circuit::Aleo<Network = N>>(
+        &self,
+        authorization: Authorization<N>,
+        hook: StepHookRef<N>,
+    ) -> Result<Response<N>> {
+        // Retrieve the stack.
+        let stack = self.get_stack(authorization.peek_next()?.program_id())?;
+        // Evaluate the function.
+        stack.evaluate_function::<A>(CallStack::debug(authorization, hook)?, None)
+    }
 }
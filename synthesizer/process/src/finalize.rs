@@ -149,6 +149,23 @@ impl<N: Network> Process<N> {
             result
         })
     }
+
+    /// Finalizes the given future, calling the given step hook before each command.
+    /// Awaited futures are finalized with a default transition ID, as there is no call graph.
+    /// This method should **only** be used for debugging.
+    #[inline]
+    pub fn finalize_with_hook<P: FinalizeStorage<N>>(
+        &self,
+        state: FinalizeGlobalState,
+        store: &FinalizeStore<N, P>,
+        future: &Future<N>,
+        hook: StepHookRef<N>,
+    ) -> Result<Vec<FinalizeOperation<N>>> {
+        // Retrieve the stack.
+        let stack = self.get_stack(future.program_id())?;
+        // Finalize the future.
+        finalize_future(state, store, stack, future, N::TransitionID::default(), None, Some(&hook))
+    }
 }
 
 /// Finalizes the given fee transition.
@@ -201,6 +218,22 @@ fn finalize_transition<N: Network, P: FinalizeStorage<N>>(
         "The program ID and function name of the future do not match the transition"
     );
 
+    // Finalize the future.
+    finalize_future(state, store, stack, future, *transition.id(), Some(&call_graph), None)
+}
+
+/// Finalizes the given future, calling the step hook (if any) before each command.
+///
+/// If no call graph is given, awaited futures are finalized with a default transition ID.
+fn finalize_future<N: Network, P: FinalizeStorage<N>>(
+    state: FinalizeGlobalState,
+    store: &FinalizeStore<N, P>,
+    stack: &Stack<N>,
+    future: &Future<N>,
+    transition_id: N::TransitionID,
+    call_graph: Option<&HashMap<N::TransitionID, Vec<N::TransitionID>>>,
+    hook: Option<&StepHookRef<N>>,
+) -> Result<Vec<FinalizeOperation<N>>> {
     // Initialize a list for finalize operations.
     let mut finalize_operations = Vec::new();
 
@@ -208,7 +241,11 @@ fn finalize_transition<N: Network, P: FinalizeStorage<N>>(
     let mut states = Vec::new();
 
     // Initialize the top-level finalize state.
-    states.push(initialize_finalize_state(state, future, stack, *transition.id())?);
+    let top_level_state = initialize_finalize_state(state, future, stack, transition_id)?;
+    if let Some(hook) = hook {
+        hook.lock().enter(top_level_state.stack.program_id(), top_level_state.finalize.name())?;
+    }
+    states.push(top_level_state);
 
     // While there are active finalize states, finalize them.
     'outer: while let Some(FinalizeState {
@@ -224,6 +261,12 @@ fn finalize_transition<N: Network, P: FinalizeStorage<N>>(
         while counter < finalize.commands().len() {
             // Retrieve the command.
             let command = &finalize.commands()[counter];
+            // If the finalize is being debugged, call the step hook.
+            if let Some(hook) = hook {
+                let location = Location::new(*stack.program_id(), *finalize.name(), counter);
+                let load = |register: &Register<N>| registers.load(stack, &Operand::Register(register.clone()));
+                hook.lock().before_step(&Step::new(location, command, &load))?;
+            }
             // Finalize the command.
             match &command {
                 Command::BranchEq(branch_eq) => {
@@ -233,9 +276,15 @@ fn finalize_transition<N: Network, P: FinalizeStorage<N>>(
                             counter = new_counter;
                         }
                         // If the evaluation fails, bail and return the error.
-                        Ok(Err(error)) => bail!("'finalize' failed to evaluate command ({command}): {error}"),
+                        Ok(Err(error)) => bail!(
+                            "'finalize' failed to evaluate command ({command}){}: {error}",
+                            located_at(stack.get_finalize_span(finalize.name(), counter))
+                        ),
                         // If the evaluation fails, bail and return the error.
-                        Err(_) => bail!("'finalize' failed to evaluate command ({command})"),
+                        Err(_) => bail!(
+                            "'finalize' failed to evaluate command ({command}){}",
+                            located_at(stack.get_finalize_span(finalize.name(), counter))
+                        ),
                     }
                 }
                 Command::BranchNeq(branch_neq) => {
@@ -245,9 +294,15 @@ fn finalize_transition<N: Network, P: FinalizeStorage<N>>(
                             counter = new_counter;
                         }
                         // If the evaluation fails, bail and return the error.
-                        Ok(Err(error)) => bail!("'finalize' failed to evaluate command ({command}): {error}"),
+                        Ok(Err(error)) => bail!(
+                            "'finalize' failed to evaluate command ({command}){}: {error}",
+                            located_at(stack.get_finalize_span(finalize.name(), counter))
+                        ),
                         // If the evaluation fails, bail and return the error.
-                        Err(_) => bail!("'finalize' failed to evaluate command ({command})"),
+                        Err(_) => bail!(
+                            "'finalize' failed to evaluate command ({command}){}",
+                            located_at(stack.get_finalize_span(finalize.name(), counter))
+                        ),
                     }
                 }
                 Command::Await(await_) => {
@@ -265,12 +320,13 @@ fn finalize_transition<N: Network, P: FinalizeStorage<N>>(
                     // Get the current transition ID.
                     let transition_id = registers.transition_id();
                     // Get the child transition ID.
-                    let child_transition_id = match call_graph.get(transition_id) {
-                        Some(transitions) => match transitions.get(call_counter) {
+                    let child_transition_id = match call_graph.map(|call_graph| call_graph.get(transition_id)) {
+                        Some(Some(transitions)) => match transitions.get(call_counter) {
                             Some(transition_id) => *transition_id,
                             None => bail!("Child transition ID not found."),
                         },
-                        None => bail!("Transition ID '{transition_id}' not found in call graph"),
+                        Some(None) => bail!("Transition ID '{transition_id}' not found in call graph"),
+                        None => N::TransitionID::default(),
                     };
 
                     // Set up the finalize state for the await.
@@ -278,9 +334,15 @@ fn finalize_transition<N: Network, P: FinalizeStorage<N>>(
                         match try_vm_runtime!(|| setup_await(state, await_, stack, &registers, child_transition_id)) {
                             Ok(Ok(callee_state)) => callee_state,
                             // If the evaluation fails, bail and return the error.
-                            Ok(Err(error)) => bail!("'finalize' failed to evaluate command ({command}): {error}"),
+                            Ok(Err(error)) => bail!(
+                                "'finalize' failed to evaluate command ({command}){}: {error}",
+                                located_at(stack.get_finalize_span(finalize.name(), counter))
+                            ),
                             // If the evaluation fails, bail and return the error.
-                            Err(_) => bail!("'finalize' failed to evaluate command ({command})"),
+                            Err(_) => bail!(
+                                "'finalize' failed to evaluate command ({command}){}",
+                                located_at(stack.get_finalize_span(finalize.name(), counter))
+                            ),
                         };
 
                     // Increment the call counter.
@@ -296,6 +358,9 @@ fn finalize_transition<N: Network, P: FinalizeStorage<N>>(
                     // Push the caller state onto the stack.
                     states.push(caller_state);
                     // Push the callee state onto the stack.
+                    if let Some(hook) = hook {
+                        hook.lock().enter(callee_state.stack.program_id(), callee_state.finalize.name())?;
+                    }
                     states.push(callee_state);
 
                     continue 'outer;
@@ -308,9 +373,15 @@ fn finalize_transition<N: Network, P: FinalizeStorage<N>>(
                         // If the evaluation succeeds with no operation, continue.
                         Ok(Ok(None)) => {}
                         // If the evaluation fails, bail and return the error.
-                        Ok(Err(error)) => bail!("'finalize' failed to evaluate command ({command}): {error}"),
+                        Ok(Err(error)) => bail!(
+                            "'finalize' failed to evaluate command ({command}){}: {error}",
+                            located_at(stack.get_finalize_span(finalize.name(), counter))
+                        ),
                         // If the evaluation fails, bail and return the error.
-                        Err(_) => bail!("'finalize' failed to evaluate command ({command})"),
+                        Err(_) => bail!(
+                            "'finalize' failed to evaluate command ({command}){}",
+                            located_at(stack.get_finalize_span(finalize.name(), counter))
+                        ),
                     }
                     counter += 1;
                 }
@@ -328,6 +399,10 @@ fn finalize_transition<N: Network, P: FinalizeStorage<N>>(
             "The following future registers have not been awaited: {}",
             unawaited.iter().map(|r| r.to_string()).collect::<Vec<_>>().join(", ")
         );
+        // If the finalize is being debugged, call the step hook.
+        if let Some(hook) = hook {
+            hook.lock().exit(stack.program_id(), finalize.name())?;
+        }
     }
 
     // Return the finalize operations.
@@ -21,6 +21,18 @@
 mod cost;
 pub use cost::*;
 
+mod debugger;
+pub use debugger::*;
+
+mod disclosure;
+pub use disclosure::*;
+
+#[cfg(any(test, feature = "prop-tests"))]
+pub mod prop_tests;
+
+mod source_map;
+pub use source_map::*;
+
 mod stack;
 pub use stack::*;
 
@@ -43,9 +55,20 @@ mod verify_fee;
 mod tests;
 
 use console::{
-    account::PrivateKey,
+    account::{PrivateKey, SignatureShare, SigningPackage, ThresholdAccount},
     network::prelude::*,
-    program::{compute_function_id, Identifier, Literal, Locator, Plaintext, ProgramID, Record, Response, Value},
+    program::{
+        compute_function_id,
+        Identifier,
+        Literal,
+        Locator,
+        Plaintext,
+        ProgramID,
+        Record,
+        Response,
+        ThresholdRequest,
+        Value,
+    },
     types::{Field, U16, U64},
 };
 use ledger_block::{Deployment, Execution, Fee, Input, Transition};
@@ -79,6 +102,8 @@ pub struct Process<N: Network> {
     universal_srs: Arc<UniversalSRS<N>>,
     /// The mapping of program IDs to stacks.
     stacks: IndexMap<ProgramID<N>, Arc<Stack<N>>>,
+    /// The mapping of disclosure circuit names to their proving and verifying keys.
+    disclosure_keys: Arc<RwLock<HashMap<String, (ProvingKey<N>, VerifyingKey<N>)>>>,
 }
 
 impl<N: Network> Process<N> {
@@ -88,7 +113,11 @@ impl<N: Network> Process<N> {
         let timer = timer!("Process:setup");
 
         // Initialize the process.
-        let mut process = Self { universal_srs: Arc::new(UniversalSRS::load()?), stacks: IndexMap::new() };
+        let mut process = Self {
+            universal_srs: Arc::new(UniversalSRS::load()?),
+            stacks: IndexMap::new(),
+            disclosure_keys: Default::default(),
+        };
         lap!(timer, "Initialize process");
 
         // Initialize the 'credits.aleo' program.
@@ -134,6 +163,20 @@ impl<N: Network> Process<N> {
         // Add the stack to the process.
         self.stacks.insert(*stack.program_id(), Arc::new(stack));
     }
+
+    /// Removes the stack of the given program from the process, returning it if it exists.
+    /// Note: The stacks of the programs that import the given program are not removed.
+    #[inline]
+    pub fn remove_stack(&mut self, program_id: &ProgramID<N>) -> Option<Arc<Stack<N>>> {
+        self.stacks.shift_remove(program_id)
+    }
+
+    /// Adds the given source map to the stack of its program, so that errors
+    /// report the original source locations.
+    #[inline]
+    pub fn add_source_map(&self, source_map: SourceMap<N>) -> Result<()> {
+        self.get_stack(*source_map.program_id())?.set_source_map(source_map)
+    }
 }
 
 impl<N: Network> Process<N> {
@@ -143,7 +186,11 @@ impl<N: Network> Process<N> {
         let timer = timer!("Process::load");
 
         // Initialize the process.
-        let mut process = Self { universal_srs: Arc::new(UniversalSRS::load()?), stacks: IndexMap::new() };
+        let mut process = Self {
+            universal_srs: Arc::new(UniversalSRS::load()?),
+            stacks: IndexMap::new(),
+            disclosure_keys: Default::default(),
+        };
         lap!(timer, "Initialize process");
 
         // Initialize the 'credits.aleo' program.
@@ -181,7 +228,11 @@ impl<N: Network> Process<N> {
     #[cfg(feature = "wasm")]
     pub fn load_web() -> Result<Self> {
         // Initialize the process.
-        let mut process = Self { universal_srs: Arc::new(UniversalSRS::load()?), stacks: IndexMap::new() };
+        let mut process = Self {
+            universal_srs: Arc::new(UniversalSRS::load()?),
+            stacks: IndexMap::new(),
+            disclosure_keys: Default::default(),
+        };
 
         // Initialize the 'credits.aleo' program.
         let program = Program::credits()?;
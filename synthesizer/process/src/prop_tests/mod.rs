@@ -0,0 +1,159 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Differential tests between the evaluation of a function over console types, and its execution over circuit types.
+//!
+//! Any divergence between `Stack::evaluate_function` and `Stack::execute_function` is a consensus bug, so the
+//! properties here generate random well-typed programs and inputs, and check that both agree on the outputs, and
+//! that the evaluation halts if and only if the execution halts or is not satisfied.
+//!
+//! Proptest shrinks a failing case to a minimal program, and persists its seed under `proptest-regressions`.
+//! As seeds are tied to the generator, minimal cases should also be added to the corpus in `src/prop_tests/corpus`,
+//! which is replayed on every test run. A failing case is printed in the corpus file format, so it can be saved as-is.
+
+mod program;
+pub use program::*;
+
+use crate::{traits::*, Assignments, CallStack, Process, Stack};
+use circuit::Environment;
+use console::{
+    account::PrivateKey,
+    network::{prelude::*, MainnetV0},
+    program::{Identifier, Literal, LiteralType, Plaintext, Value},
+};
+use synthesizer_program::Program;
+use utilities::try_vm_runtime;
+
+use once_cell::sync::OnceCell;
+use std::path::{Path, PathBuf};
+
+type CurrentNetwork = MainnetV0;
+type CurrentAleo = circuit::network::AleoV0;
+
+/// The prefix of the comment that declares the inputs in a corpus file.
+const INPUTS_PREFIX: &str = "// inputs: ";
+
+/// The outcome of executing a function over circuit types.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Execution {
+    /// The circuit is satisfied, with the given outputs.
+    Satisfied(Vec<Value<CurrentNetwork>>),
+    /// The circuit is not satisfied.
+    Unsatisfied,
+    /// The execution halted with the given error.
+    Halted(String),
+}
+
+/// Evaluates and executes the `main` function of the given case, returning the outcomes.
+pub fn run_case(case: &FuzzCase) -> Result<(Result<Vec<Value<CurrentNetwork>>, String>, Execution)> {
+    static PROCESS: OnceCell<Process<CurrentNetwork>> = OnceCell::new();
+
+    // Initialize the stack, without adding the program to the process.
+    let process = PROCESS.get_or_try_init(Process::load)?;
+    let stack = Stack::new(process, case.program())?;
+
+    // Authorize the call.
+    let rng = &mut TestRng::fixed(1);
+    let private_key = PrivateKey::<CurrentNetwork>::new(rng)?;
+    let function_name = Identifier::from_str("main")?;
+    let authorization = stack.authorize::<CurrentAleo, _>(&private_key, function_name, case.inputs().iter(), rng)?;
+    let request = authorization.replicate().next()?;
+
+    // Evaluate the function, catching any halts.
+    let call_stack = CallStack::evaluate(authorization)?;
+    let evaluation = match try_vm_runtime!(|| stack.evaluate_function::<CurrentAleo>(call_stack, None)) {
+        Ok(Ok(response)) => Ok(response.outputs().to_vec()),
+        Ok(Err(error)) => Err(error.to_string()),
+        Err(_) => Err("The evaluation panicked".to_string()),
+    };
+
+    // Execute the function, catching any halts.
+    let call_stack = CallStack::PackageRun(vec![request], private_key, Assignments::<CurrentNetwork>::default());
+    let execution = match try_vm_runtime!(|| stack.execute_function::<CurrentAleo, _>(call_stack, None, None, rng)) {
+        Ok(Ok(response)) => Execution::Satisfied(response.outputs().to_vec()),
+        Ok(Err(_)) if CurrentAleo::num_constraints() > 0 && !CurrentAleo::is_satisfied() => Execution::Unsatisfied,
+        Ok(Err(error)) => Execution::Halted(error.to_string()),
+        Err(_) => Execution::Halted("The execution panicked".to_string()),
+    };
+    CurrentAleo::reset();
+
+    Ok((evaluation, execution))
+}
+
+/// Ensures the evaluation and execution of the `main` function of the given case agree.
+pub fn check_case(case: &FuzzCase) -> Result<()> {
+    match run_case(case)? {
+        // Ensure the outputs match.
+        (Ok(expected), Execution::Satisfied(candidate)) => {
+            ensure!(expected == candidate, "Evaluated to {expected:?}, but executed to {candidate:?}");
+        }
+        // Ensure the evaluation halts if and only if the execution halts or is not satisfied.
+        (Ok(outputs), execution) => bail!("Evaluated to {outputs:?}, but the execution is {execution:?}"),
+        (Err(error), Execution::Satisfied(outputs)) => {
+            bail!("Evaluation halted ({error}), but executed to {outputs:?}")
+        }
+        (Err(_), Execution::Unsatisfied | Execution::Halted(_)) => (),
+    }
+    Ok(())
+}
+
+/// Returns the paths of the cases in the corpus.
+pub fn corpus() -> Result<Vec<PathBuf>> {
+    let directory = Path::new(env!("CARGO_MANIFEST_DIR")).join("src").join("prop_tests").join("corpus");
+    let mut paths = std::fs::read_dir(directory)?
+        .map(|entry| Ok(entry?.path()))
+        .filter(|path| !matches!(path, Ok(path) if path.extension().map_or(true, |extension| extension != "aleo")))
+        .collect::<Result<Vec<_>>>()?;
+    paths.sort();
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::{any, ProptestConfig};
+    use test_strategy::proptest;
+
+    #[proptest(ProptestConfig { cases: 16, ..ProptestConfig::default() })]
+    fn evaluate_and_execute_agree(case: FuzzCase) {
+        if let Err(error) = check_case(&case) {
+            panic!("{error}\n{case}");
+        }
+    }
+
+    #[test]
+    fn test_corpus() -> Result<()> {
+        let paths = corpus()?;
+        assert!(!paths.is_empty(), "The corpus is empty");
+        for path in paths {
+            let case = FuzzCase::from_str(&std::fs::read_to_string(&path)?)?;
+            check_case(&case).map_err(|error| anyhow!("'{}' failed: {error}", path.display()))?;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_case_round_trip() -> Result<()> {
+        use proptest::strategy::{Strategy, ValueTree};
+
+        let mut runner = proptest::test_runner::TestRunner::deterministic();
+        for _ in 0..16 {
+            let expected = any::<FuzzCase>().new_tree(&mut runner).map_err(|error| anyhow!("{error}"))?.current();
+            let candidate = FuzzCase::from_str(&expected.to_string())?;
+            assert_eq!(expected.program(), candidate.program());
+            assert_eq!(expected.inputs(), candidate.inputs());
+        }
+        Ok(())
+    }
+}
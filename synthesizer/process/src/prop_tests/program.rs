@@ -0,0 +1,378 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use synthesizer_program::{
+    AbsOperation,
+    AbsWrappedOperation,
+    AddOperation,
+    AddWrappedOperation,
+    AndOperation,
+    DivOperation,
+    DivWrappedOperation,
+    DoubleOperation,
+    GreaterThanOperation,
+    GreaterThanOrEqualOperation,
+    InvOperation,
+    LessThanOperation,
+    LessThanOrEqualOperation,
+    ModuloOperation,
+    MulOperation,
+    MulWrappedOperation,
+    NandOperation,
+    NegOperation,
+    NorOperation,
+    NotOperation,
+    Operation,
+    OrOperation,
+    PowOperation,
+    PowWrappedOperation,
+    RemOperation,
+    RemWrappedOperation,
+    ShlOperation,
+    ShlWrappedOperation,
+    ShrOperation,
+    ShrWrappedOperation,
+    SquareOperation,
+    SquareRootOperation,
+    SubOperation,
+    SubWrappedOperation,
+    XorOperation,
+};
+
+use proptest::{
+    collection::vec,
+    prelude::{any, prop_oneof, Arbitrary, BoxedStrategy, Strategy},
+};
+
+/// The literal types used in generated programs.
+const LITERAL_TYPES: [LiteralType; 14] = [
+    LiteralType::Boolean,
+    LiteralType::Field,
+    LiteralType::Group,
+    LiteralType::Scalar,
+    LiteralType::I8,
+    LiteralType::I16,
+    LiteralType::I32,
+    LiteralType::I64,
+    LiteralType::I128,
+    LiteralType::U8,
+    LiteralType::U16,
+    LiteralType::U32,
+    LiteralType::U64,
+    LiteralType::U128,
+];
+
+/// The visibilities used for the inputs and outputs of generated programs, as functions do not support constant inputs.
+const VISIBILITIES: [&str; 2] = ["public", "private"];
+
+/// The maximum number of inputs of a generated program.
+const MAX_INPUTS: usize = 4;
+
+/// The maximum number of instructions of a generated program.
+const MAX_INSTRUCTIONS: usize = 12;
+
+type UnaryType = fn(&[LiteralType; 1]) -> Result<LiteralType>;
+type BinaryType = fn(&[LiteralType; 2]) -> Result<LiteralType>;
+
+/// The shape of an opcode, which determines how its operands are chosen.
+#[derive(Copy, Clone)]
+enum Shape {
+    /// An operation over one literal.
+    Unary(UnaryType),
+    /// An operation over two literals.
+    Binary(BinaryType),
+    /// A `ternary` operation, over a boolean and two literals of the same type.
+    Ternary,
+    /// An `is.eq` or `is.neq` comparison, over two literals of the same type.
+    Is,
+    /// A hash of one literal into a field element.
+    Hash,
+}
+
+macro_rules! unary {
+    ($opcode:expr, $operation:ident) => {
+        ($opcode, Shape::Unary(<$operation<CurrentNetwork> as Operation<_, Literal<_>, LiteralType, 1>>::output_type))
+    };
+}
+
+macro_rules! binary {
+    ($opcode:expr, $operation:ident) => {
+        ($opcode, Shape::Binary(<$operation<CurrentNetwork> as Operation<_, Literal<_>, LiteralType, 2>>::output_type))
+    };
+}
+
+/// The opcodes used in generated programs.
+const OPCODES: [(&str, Shape); 41] = [
+    unary!("abs", AbsOperation),
+    unary!("abs.w", AbsWrappedOperation),
+    unary!("double", DoubleOperation),
+    unary!("inv", InvOperation),
+    unary!("neg", NegOperation),
+    unary!("not", NotOperation),
+    unary!("square", SquareOperation),
+    unary!("sqrt", SquareRootOperation),
+    binary!("add", AddOperation),
+    binary!("add.w", AddWrappedOperation),
+    binary!("and", AndOperation),
+    binary!("div", DivOperation),
+    binary!("div.w", DivWrappedOperation),
+    binary!("gt", GreaterThanOperation),
+    binary!("gte", GreaterThanOrEqualOperation),
+    binary!("lt", LessThanOperation),
+    binary!("lte", LessThanOrEqualOperation),
+    binary!("mod", ModuloOperation),
+    binary!("mul", MulOperation),
+    binary!("mul.w", MulWrappedOperation),
+    binary!("nand", NandOperation),
+    binary!("nor", NorOperation),
+    binary!("or", OrOperation),
+    binary!("pow", PowOperation),
+    binary!("pow.w", PowWrappedOperation),
+    binary!("rem", RemOperation),
+    binary!("rem.w", RemWrappedOperation),
+    binary!("shl", ShlOperation),
+    binary!("shl.w", ShlWrappedOperation),
+    binary!("shr", ShrOperation),
+    binary!("shr.w", ShrWrappedOperation),
+    binary!("sub", SubOperation),
+    binary!("sub.w", SubWrappedOperation),
+    binary!("xor", XorOperation),
+    ("ternary", Shape::Ternary),
+    ("is.eq", Shape::Is),
+    ("is.neq", Shape::Is),
+    ("hash.bhp256", Shape::Hash),
+    ("hash.psd2", Shape::Hash),
+    ("hash.ped64", Shape::Hash),
+    ("hash.keccak256", Shape::Hash),
+];
+
+/// A randomly generated program, with inputs for its `main` function.
+///
+/// A case is generated from a recipe of raw choices, which proptest shrinks towards fewer inputs,
+/// fewer instructions, and earlier opcodes and operands. Every recipe yields a well-typed program,
+/// as choices that do not type-check are skipped.
+#[derive(Clone)]
+pub struct FuzzCase {
+    /// The program.
+    program: Program<CurrentNetwork>,
+    /// The inputs for the `main` function.
+    inputs: Vec<Value<CurrentNetwork>>,
+}
+
+impl FuzzCase {
+    /// Initializes a new case from the given program and inputs.
+    pub fn new(program: Program<CurrentNetwork>, inputs: Vec<Value<CurrentNetwork>>) -> Self {
+        Self { program, inputs }
+    }
+
+    /// Returns the program.
+    pub const fn program(&self) -> &Program<CurrentNetwork> {
+        &self.program
+    }
+
+    /// Returns the inputs for the `main` function.
+    pub fn inputs(&self) -> &[Value<CurrentNetwork>] {
+        &self.inputs
+    }
+}
+
+impl Debug for FuzzCase {
+    /// Prints the case in the corpus file format, so that failures can be added to the corpus as-is.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "\n{self}")
+    }
+}
+
+impl Display for FuzzCase {
+    /// Prints the case in the corpus file format, i.e. the inputs as a comment, followed by the program.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let inputs = self.inputs.iter().map(|input| input.to_string()).collect::<Vec<_>>().join(" ");
+        write!(f, "{INPUTS_PREFIX}{inputs}\n{}", self.program)
+    }
+}
+
+impl FromStr for FuzzCase {
+    type Err = Error;
+
+    /// Parses a case from the corpus file format.
+    fn from_str(string: &str) -> Result<Self> {
+        let (inputs, program) = string
+            .split_once('\n')
+            .and_then(|(inputs, program)| Some((inputs.strip_prefix(INPUTS_PREFIX)?, program)))
+            .ok_or_else(|| anyhow!("Expected the first line to start with '{INPUTS_PREFIX}'"))?;
+        let inputs = inputs.split_whitespace().map(Value::from_str).collect::<Result<Vec<_>>>()?;
+        Ok(Self::new(Program::from_str(program)?, inputs))
+    }
+}
+
+impl Arbitrary for FuzzCase {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<FuzzCase>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        let input = (any::<u8>(), any::<u8>(), any_value_recipe());
+        (vec(input, 1..=MAX_INPUTS), vec(any::<[u16; 3]>(), 0..=MAX_INSTRUCTIONS), any::<[bool; 2]>())
+            .prop_map(|(inputs, instructions, outputs)| build_case(&inputs, &instructions, outputs))
+            .boxed()
+    }
+}
+
+/// The raw choice for a literal value: either an edge case, or a random value.
+#[derive(Clone, Debug)]
+enum ValueRecipe {
+    Edge(u8),
+    Random(u64),
+}
+
+/// Returns a strategy for the raw choice of a literal value.
+fn any_value_recipe() -> impl Strategy<Value = ValueRecipe> {
+    prop_oneof![any::<u8>().prop_map(ValueRecipe::Edge), any::<u64>().prop_map(ValueRecipe::Random)]
+}
+
+/// Builds a case from the given recipe, i.e. the raw choices for the type, visibility, and value of each input,
+/// the raw choices for each instruction, and the visibility of each output.
+fn build_case(inputs: &[(u8, u8, ValueRecipe)], instructions: &[[u16; 3]], outputs: [bool; 2]) -> FuzzCase {
+    let mut source = String::from("program fuzz.aleo;\n\nfunction main:\n");
+    // The types of the registers, indexed by locator.
+    let mut types = Vec::new();
+    let mut values = Vec::new();
+
+    // Declare the inputs.
+    for (literal_type, visibility, value) in inputs {
+        let literal_type = LITERAL_TYPES[*literal_type as usize % LITERAL_TYPES.len()];
+        let visibility = VISIBILITIES[*visibility as usize % VISIBILITIES.len()];
+        source.push_str(&format!("    input r{} as {literal_type}.{visibility};\n", types.len()));
+        values.push(Value::Plaintext(Plaintext::from(sample_literal(literal_type, value))));
+        types.push(literal_type);
+    }
+
+    // Declare the instructions, skipping any that do not type-check.
+    for choices in instructions {
+        if let Some((instruction, suffix, output_type)) = build_instruction(&types, choices) {
+            source.push_str(&format!("    {instruction} into r{}{suffix};\n", types.len()));
+            types.push(output_type);
+        }
+    }
+
+    // Output the last registers.
+    for (index, choice) in outputs.iter().enumerate().take(types.len()) {
+        let locator = types.len() - 1 - index;
+        let visibility = VISIBILITIES[*choice as usize];
+        source.push_str(&format!("    output r{locator} as {}.{visibility};\n", types[locator]));
+    }
+
+    let program = Program::from_str(&source).expect("Generated an ill-formed program");
+    FuzzCase::new(program, values)
+}
+
+/// Builds an instruction over the given registers, returning its operands (i.e. the part before the destination),
+/// the part after the destination, and its output type.
+fn build_instruction(
+    types: &[LiteralType],
+    [opcode, first, second]: &[u16; 3],
+) -> Option<(String, &'static str, LiteralType)> {
+    let (opcode, shape) = OPCODES[*opcode as usize % OPCODES.len()];
+    // Returns the registers whose type satisfies the given predicate.
+    let registers = |predicate: &dyn Fn(LiteralType) -> bool| {
+        types.iter().enumerate().filter(|(_, t)| predicate(**t)).map(|(locator, _)| locator).collect::<Vec<_>>()
+    };
+    // Chooses one of the given registers.
+    let choose = |candidates: &[usize], choice: u16| match candidates.is_empty() {
+        true => None,
+        false => Some(candidates[choice as usize % candidates.len()]),
+    };
+
+    match shape {
+        Shape::Unary(output_type) => {
+            let a = choose(&registers(&|t| output_type(&[t]).is_ok()), *first)?;
+            Some((format!("{opcode} r{a}"), "", output_type(&[types[a]]).ok()?))
+        }
+        Shape::Binary(output_type) => {
+            let a = *first as usize % types.len();
+            // The second operand is either a register or a literal, of a type that type-checks.
+            let literal_types = LITERAL_TYPES.iter().filter(|t| output_type(&[types[a], **t]).is_ok());
+            let candidates = registers(&|t| output_type(&[types[a], t]).is_ok())
+                .into_iter()
+                .map(|b| (format!("r{b}"), types[b]))
+                .chain(literal_types.map(|t| (small_literal(*t, *second).to_string(), *t)))
+                .collect::<Vec<_>>();
+            if candidates.is_empty() {
+                return None;
+            }
+            let (b, second_type) = &candidates[*second as usize % candidates.len()];
+            Some((format!("{opcode} r{a} {b}"), "", output_type(&[types[a], *second_type]).ok()?))
+        }
+        Shape::Ternary => {
+            let condition = choose(&registers(&|t| t == LiteralType::Boolean), *first)?;
+            let a = *second as usize % types.len();
+            let b = choose(&registers(&|t| t == types[a]), first.wrapping_add(*second))?;
+            Some((format!("{opcode} r{condition} r{a} r{b}"), "", types[a]))
+        }
+        Shape::Is => {
+            let a = *first as usize % types.len();
+            let b = choose(&registers(&|t| t == types[a]), *second)?;
+            Some((format!("{opcode} r{a} r{b}"), "", LiteralType::Boolean))
+        }
+        Shape::Hash => {
+            let a = *first as usize % types.len();
+            Some((format!("{opcode} r{a}"), " as field", LiteralType::Field))
+        }
+    }
+}
+
+/// Returns a literal of the given type for the given recipe.
+fn sample_literal(literal_type: LiteralType, recipe: &ValueRecipe) -> Literal<CurrentNetwork> {
+    match recipe {
+        ValueRecipe::Edge(choice) => edge_literal(literal_type, *choice),
+        ValueRecipe::Random(seed) => Literal::sample(literal_type, &mut TestRng::fixed(*seed)),
+    }
+}
+
+/// Returns a small literal of the given type, used as a constant operand.
+fn small_literal(literal_type: LiteralType, choice: u16) -> Literal<CurrentNetwork> {
+    edge_literal(literal_type, (choice % 4) as u8)
+}
+
+/// Returns a literal at the edge of the domain of the given type, i.e. zero, one, or an extreme value.
+fn edge_literal(literal_type: LiteralType, choice: u8) -> Literal<CurrentNetwork> {
+    macro_rules! integer {
+        ($type:ty) => {
+            match choice % 5 {
+                0 => format!("0{literal_type}"),
+                1 => format!("1{literal_type}"),
+                2 => format!("2{literal_type}"),
+                3 => format!("{}{literal_type}", <$type>::MAX),
+                _ => format!("{}{literal_type}", <$type>::MIN),
+            }
+        };
+    }
+    let string = match literal_type {
+        LiteralType::Boolean => format!("{}", choice % 2 == 0),
+        LiteralType::Field | LiteralType::Scalar => format!("{}{literal_type}", choice % 3),
+        LiteralType::Group => "0group".to_string(),
+        LiteralType::I8 => integer!(i8),
+        LiteralType::I16 => integer!(i16),
+        LiteralType::I32 => integer!(i32),
+        LiteralType::I64 => integer!(i64),
+        LiteralType::I128 => integer!(i128),
+        LiteralType::U8 => integer!(u8),
+        LiteralType::U16 => integer!(u16),
+        LiteralType::U32 => integer!(u32),
+        LiteralType::U64 => integer!(u64),
+        LiteralType::U128 => integer!(u128),
+        _ => unreachable!("Unsupported literal type '{literal_type}'"),
+    };
+    Literal::from_str(&string).expect("Generated an ill-formed literal")
+}
@@ -0,0 +1,234 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod parse;
+
+use console::{
+    network::prelude::*,
+    program::{Identifier, ProgramID},
+};
+use synthesizer_program::Program;
+
+use indexmap::IndexMap;
+
+/// A location in the original (high-level) source code, i.e. `src/main.leo:12:5`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SourceSpan {
+    /// The file path.
+    file: String,
+    /// The line number, starting at 1.
+    line: u32,
+    /// The column number, starting at 1.
+    column: u32,
+}
+
+impl SourceSpan {
+    /// Initializes a new source span.
+    pub fn new(file: String, line: u32, column: u32) -> Self {
+        Self { file, line, column }
+    }
+
+    /// Returns the file path.
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    /// Returns the line number.
+    pub const fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// Returns the column number.
+    pub const fn column(&self) -> u32 {
+        self.column
+    }
+}
+
+impl Display for SourceSpan {
+    /// Prints the source span as `file:line:column`.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}:{}:{}", self.file, self.line, self.column)
+    }
+}
+
+/// The source spans of a closure or function, and of its finalize block.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct FunctionSourceMap {
+    /// The span of the closure or function declaration, if known.
+    span: Option<SourceSpan>,
+    /// The mapping of instruction indices to spans.
+    instructions: IndexMap<usize, SourceSpan>,
+    /// The mapping of finalize command indices to spans.
+    finalize: IndexMap<usize, SourceSpan>,
+}
+
+impl FunctionSourceMap {
+    /// Initializes a new function source map.
+    pub fn new(
+        span: Option<SourceSpan>,
+        instructions: IndexMap<usize, SourceSpan>,
+        finalize: IndexMap<usize, SourceSpan>,
+    ) -> Self {
+        Self { span, instructions, finalize }
+    }
+
+    /// Returns the span of the declaration, if known.
+    pub const fn span(&self) -> Option<&SourceSpan> {
+        self.span.as_ref()
+    }
+
+    /// Returns the mapping of instruction indices to spans.
+    pub const fn instructions(&self) -> &IndexMap<usize, SourceSpan> {
+        &self.instructions
+    }
+
+    /// Returns the mapping of finalize command indices to spans.
+    pub const fn finalize(&self) -> &IndexMap<usize, SourceSpan> {
+        &self.finalize
+    }
+}
+
+/// Debug metadata mapping the instructions of a program to the high-level source code it was compiled from.
+///
+/// A source map is loaded from a sidecar file, keyed by closure or function name and instruction index,
+/// and is used to report the original file, line, and column in errors.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SourceMap<N: Network> {
+    /// The program ID.
+    program_id: ProgramID<N>,
+    /// The mapping of closure and function names to their source maps.
+    functions: IndexMap<Identifier<N>, FunctionSourceMap>,
+}
+
+impl<N: Network> SourceMap<N> {
+    /// Initializes a new source map.
+    pub fn new(program_id: ProgramID<N>, functions: IndexMap<Identifier<N>, FunctionSourceMap>) -> Self {
+        Self { program_id, functions }
+    }
+
+    /// Returns the program ID.
+    pub const fn program_id(&self) -> &ProgramID<N> {
+        &self.program_id
+    }
+
+    /// Returns the mapping of closure and function names to their source maps.
+    pub const fn functions(&self) -> &IndexMap<Identifier<N>, FunctionSourceMap> {
+        &self.functions
+    }
+
+    /// Returns the span of the given closure or function, if known.
+    pub fn get_function_span(&self, name: &Identifier<N>) -> Option<&SourceSpan> {
+        self.functions.get(name).and_then(|function| function.span())
+    }
+
+    /// Returns the span of the instruction at the given index in the given closure or function, if known.
+    pub fn get_instruction_span(&self, name: &Identifier<N>, index: usize) -> Option<&SourceSpan> {
+        self.functions.get(name).and_then(|function| function.instructions().get(&index))
+    }
+
+    /// Returns the span of the command at the given index in the finalize block of the given function, if known.
+    pub fn get_finalize_span(&self, name: &Identifier<N>, index: usize) -> Option<&SourceSpan> {
+        self.functions.get(name).and_then(|function| function.finalize().get(&index))
+    }
+
+    /// Ensures the source map refers to the given program, and only to closures, functions,
+    /// instructions, and commands that exist in it.
+    pub fn check(&self, program: &Program<N>) -> Result<()> {
+        // Ensure the program ID matches.
+        ensure!(
+            &self.program_id == program.id(),
+            "The source map is for '{}', but the program is '{}'",
+            self.program_id,
+            program.id()
+        );
+        for (name, function) in &self.functions {
+            // Retrieve the number of instructions and finalize commands.
+            let (num_instructions, num_commands) = match (program.closures().get(name), program.functions().get(name)) {
+                (Some(closure), _) => (closure.instructions().len(), 0),
+                (_, Some(function)) => (
+                    function.instructions().len(),
+                    function.finalize_logic().map_or(0, |finalize| finalize.commands().len()),
+                ),
+                _ => bail!("The source map refers to '{name}', which does not exist in '{}'", program.id()),
+            };
+            // Ensure the indices are in range.
+            if let Some(index) = function.instructions().keys().find(|index| **index >= num_instructions) {
+                bail!("The source map refers to instruction {index} in '{name}', which has {num_instructions}")
+            }
+            if let Some(index) = function.finalize().keys().find(|index| **index >= num_commands) {
+                bail!("The source map refers to finalize command {index} in '{name}', which has {num_commands}")
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns ` at <span>` if the span is known, and an empty string otherwise, for use in error messages.
+pub(crate) fn located_at(span: Option<SourceSpan>) -> String {
+    span.map(|span| format!(" at {span}")).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use console::network::MainnetV0;
+
+    type CurrentNetwork = MainnetV0;
+
+    const SOURCE_MAP: &str = r#"{
+  "program": "credits.aleo",
+  "functions": {
+    "transfer_public": {
+      "span": "src/main.leo:12:5",
+      "instructions": {
+        "0": "src/main.leo:13:9"
+      },
+      "finalize": {
+        "1": "src/main.leo:20:9"
+      }
+    }
+  }
+}"#;
+
+    #[test]
+    fn test_source_map() -> Result<()> {
+        let source_map = SourceMap::<CurrentNetwork>::from_str(SOURCE_MAP)?;
+        let name = Identifier::from_str("transfer_public")?;
+        assert_eq!(source_map.get_function_span(&name).unwrap().to_string(), "src/main.leo:12:5");
+        assert_eq!(source_map.get_instruction_span(&name, 0).unwrap().to_string(), "src/main.leo:13:9");
+        assert_eq!(source_map.get_finalize_span(&name, 1).unwrap().to_string(), "src/main.leo:20:9");
+        assert!(source_map.get_instruction_span(&name, 1).is_none());
+        assert!(source_map.get_finalize_span(&name, 0).is_none());
+
+        // Ensure the source map matches the program.
+        source_map.check(&Program::credits()?)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_source_map_rejects_mismatched_program() -> Result<()> {
+        let program = Program::<CurrentNetwork>::credits()?;
+
+        // Ensure an unknown function is rejected.
+        let source_map = SourceMap::<CurrentNetwork>::from_str(&SOURCE_MAP.replace("transfer_public", "unknown"))?;
+        assert!(source_map.check(&program).is_err());
+        // Ensure an out-of-range instruction is rejected.
+        let source_map = SourceMap::<CurrentNetwork>::from_str(&SOURCE_MAP.replace("\"0\"", "\"100\""))?;
+        assert!(source_map.check(&program).is_err());
+        // Ensure a different program is rejected.
+        let source_map = SourceMap::<CurrentNetwork>::from_str(&SOURCE_MAP.replace("credits.aleo", "other.aleo"))?;
+        assert!(source_map.check(&program).is_err());
+        Ok(())
+    }
+}
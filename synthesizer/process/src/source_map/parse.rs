@@ -0,0 +1,150 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use serde_json::{json, Map, Value};
+
+impl FromStr for SourceSpan {
+    type Err = Error;
+
+    /// Parses a source span of the form `file:line:column`.
+    fn from_str(string: &str) -> Result<Self> {
+        // Split from the right, as the file path may contain a ':'.
+        let mut parts = string.rsplitn(3, ':');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(column), Some(line), Some(file)) if !file.is_empty() => {
+                Ok(Self::new(file.to_string(), line.parse()?, column.parse()?))
+            }
+            _ => bail!("Invalid source span '{string}', expected 'file:line:column'"),
+        }
+    }
+}
+
+impl<N: Network> FromStr for SourceMap<N> {
+    type Err = Error;
+
+    /// Parses a source map from its JSON sidecar file format.
+    fn from_str(string: &str) -> Result<Self> {
+        let json: Value = serde_json::from_str(string)?;
+
+        // Retrieve the program ID.
+        let program_id = json["program"].as_str().ok_or_else(|| anyhow!("Program ID not found."))?;
+        let program_id = ProgramID::from_str(program_id)?;
+
+        // Retrieve the functions.
+        let mut functions = IndexMap::new();
+        if let Some(entries) = json.get("functions") {
+            let entries = entries.as_object().ok_or_else(|| anyhow!("Expected 'functions' to be an object"))?;
+            for (name, function) in entries {
+                // Retrieve the span of the declaration.
+                let span = match function.get("span") {
+                    Some(span) => Some(parse_span(span)?),
+                    None => None,
+                };
+                // Retrieve the spans of the instructions and finalize commands.
+                let instructions = parse_spans(function.get("instructions"))?;
+                let finalize = parse_spans(function.get("finalize"))?;
+
+                let name = Identifier::from_str(name)?;
+                ensure!(!functions.contains_key(&name), "Found a duplicate entry for '{name}' in the source map");
+                functions.insert(name, FunctionSourceMap::new(span, instructions, finalize));
+            }
+        }
+        Ok(Self::new(program_id, functions))
+    }
+}
+
+impl<N: Network> Display for SourceMap<N> {
+    /// Prints the source map in its JSON sidecar file format.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let to_json = |spans: &IndexMap<usize, SourceSpan>| {
+            Value::Object(spans.iter().map(|(index, span)| (index.to_string(), json!(span.to_string()))).collect())
+        };
+
+        let mut functions = Map::new();
+        for (name, function) in &self.functions {
+            let mut entry = Map::new();
+            if let Some(span) = function.span() {
+                entry.insert("span".to_string(), json!(span.to_string()));
+            }
+            entry.insert("instructions".to_string(), to_json(function.instructions()));
+            entry.insert("finalize".to_string(), to_json(function.finalize()));
+            functions.insert(name.to_string(), Value::Object(entry));
+        }
+        let json = json!({ "program": self.program_id.to_string(), "functions": functions });
+
+        write!(f, "{}", serde_json::to_string_pretty(&json).map_err(|_| fmt::Error)?)
+    }
+}
+
+/// Parses a source span from a JSON string.
+fn parse_span(value: &Value) -> Result<SourceSpan> {
+    SourceSpan::from_str(value.as_str().ok_or_else(|| anyhow!("Expected a source span string, found '{value}'"))?)
+}
+
+/// Parses a mapping of indices to source spans from an optional JSON object.
+fn parse_spans(value: Option<&Value>) -> Result<IndexMap<usize, SourceSpan>> {
+    let mut spans = IndexMap::new();
+    if let Some(value) = value {
+        let entries =
+            value.as_object().ok_or_else(|| anyhow!("Expected an object of source spans, found '{value}'"))?;
+        for (index, span) in entries {
+            spans.insert(index.parse()?, parse_span(span)?);
+        }
+    }
+    Ok(spans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use console::network::MainnetV0;
+
+    type CurrentNetwork = MainnetV0;
+
+    #[test]
+    fn test_parse_span() -> Result<()> {
+        let span = SourceSpan::from_str("C:\\src\\main.leo:3:14")?;
+        assert_eq!(span.file(), "C:\\src\\main.leo");
+        assert_eq!(span.line(), 3);
+        assert_eq!(span.column(), 14);
+
+        assert!(SourceSpan::from_str("main.leo:3").is_err());
+        assert!(SourceSpan::from_str(":3:14").is_err());
+        assert!(SourceSpan::from_str("main.leo:three:14").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_string_round_trip() -> Result<()> {
+        let mut instructions = IndexMap::new();
+        instructions.insert(0, SourceSpan::new("src/main.leo".to_string(), 4, 9));
+        instructions.insert(2, SourceSpan::new("src/main.leo".to_string(), 5, 9));
+        let function = FunctionSourceMap::new(
+            Some(SourceSpan::new("src/main.leo".to_string(), 3, 5)),
+            instructions,
+            IndexMap::new(),
+        );
+
+        let mut functions = IndexMap::new();
+        functions.insert(Identifier::from_str("main")?, function);
+        functions.insert(Identifier::from_str("helper")?, FunctionSourceMap::default());
+        let expected = SourceMap::<CurrentNetwork>::new(ProgramID::from_str("hello.aleo")?, functions);
+
+        let candidate = SourceMap::<CurrentNetwork>::from_str(&expected.to_string())?;
+        assert_eq!(expected, candidate);
+        Ok(())
+    }
+}
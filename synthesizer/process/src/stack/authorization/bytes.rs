@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use super::*;
+use console::network::Multipart;
 
 impl<N: Network> FromBytes for Authorization<N> {
     /// Reads the authorization from a buffer.
@@ -68,9 +69,17 @@ impl<N: Network> ToBytes for Authorization<N> {
     }
 }
 
+impl<N: Network> Multipart<N> for Authorization<N> {
+    /// Returns the human-readable prefix of each part of an authorization.
+    fn multipart_prefix() -> &'static str {
+        "authorization"
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use console::network::MULTIPART_FRAGMENT_SIZE;
 
     #[test]
     fn test_bytes() -> Result<()> {
@@ -82,6 +91,11 @@ mod tests {
         // Check the byte representation.
         let expected_bytes = expected.to_bytes_le()?;
         assert_eq!(expected, Authorization::read_le(&expected_bytes[..])?);
+
+        // Check the multipart representation.
+        let parts = expected.to_multipart(MULTIPART_FRAGMENT_SIZE)?;
+        assert!(parts.len() > 1);
+        assert_eq!(expected, Authorization::from_multipart(&parts)?);
         Ok(())
     }
 }
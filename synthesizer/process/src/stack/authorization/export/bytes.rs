@@ -0,0 +1,101 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use console::network::Multipart;
+
+impl<N: Network> FromBytes for AuthorizationExport<N> {
+    /// Reads the authorization export from a buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the version.
+        let version = u8::read_le(&mut reader)?;
+        // Ensure the version is valid.
+        if version != 1 {
+            return Err(error("Invalid authorization export version"));
+        }
+
+        // Read the number of programs.
+        let num_programs = u16::read_le(&mut reader)?;
+        // Read the programs.
+        let programs = (0..num_programs).map(|_| Program::read_le(&mut reader)).collect::<IoResult<Vec<_>>>()?;
+        // Read the authorization.
+        let authorization = Authorization::read_le(&mut reader)?;
+        // Read the fee.
+        let fee = match u8::read_le(&mut reader)? {
+            0 => None,
+            1 => Some(Authorization::read_le(&mut reader)?),
+            _ => return Err(error("Invalid fee variant in the authorization export")),
+        };
+
+        // Return the new `AuthorizationExport` instance.
+        Self::new(programs, authorization, fee).map_err(error)
+    }
+}
+
+impl<N: Network> ToBytes for AuthorizationExport<N> {
+    /// Writes the authorization export to a buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        // Write the version.
+        1u8.write_le(&mut writer)?;
+        // Write the number of programs.
+        u16::try_from(self.programs.len()).map_err(error)?.write_le(&mut writer)?;
+        // Write the programs.
+        self.programs.iter().try_for_each(|program| program.write_le(&mut writer))?;
+        // Write the authorization.
+        self.authorization.write_le(&mut writer)?;
+        // Write the fee.
+        match &self.fee {
+            None => 0u8.write_le(&mut writer),
+            Some(fee) => {
+                1u8.write_le(&mut writer)?;
+                fee.write_le(&mut writer)
+            }
+        }
+    }
+}
+
+impl<N: Network> Multipart<N> for AuthorizationExport<N> {
+    /// Returns the human-readable prefix of each part of an authorization export.
+    fn multipart_prefix() -> &'static str {
+        "export"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use console::network::MULTIPART_FRAGMENT_SIZE;
+
+    #[test]
+    fn test_bytes() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        // Construct a new authorization export.
+        let expected = test_helpers::sample_authorization_export(rng);
+
+        // Check the byte representation.
+        let expected_bytes = expected.to_bytes_le()?;
+        assert_eq!(expected, AuthorizationExport::read_le(&expected_bytes[..])?);
+
+        // Check the multipart representation.
+        let parts = expected.to_multipart(MULTIPART_FRAGMENT_SIZE)?;
+        assert!(parts.len() > 1);
+        assert_eq!(expected, AuthorizationExport::from_multipart(&parts)?);
+
+        // Ensure the export without a fee round trips.
+        let expected = AuthorizationExport::new(expected.programs().to_vec(), expected.authorization().clone(), None)?;
+        assert_eq!(expected, AuthorizationExport::read_le(&expected.to_bytes_le()?[..])?);
+        Ok(())
+    }
+}
@@ -0,0 +1,278 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod bytes;
+mod serialize;
+mod string;
+
+use super::*;
+use crate::Process;
+use console::program::{Literal, Plaintext, ProgramID, Value};
+use synthesizer_program::Program;
+
+use indexmap::IndexSet;
+
+/// An authorization that is self-contained, so that it can be proven on another machine.
+///
+/// The export bundles the authorization of an execution, the authorization of its fee (if any),
+/// and the programs that are required to execute it, in dependency order.
+/// Note: `credits.aleo` is never bundled, as every prover already has it.
+#[derive(Clone, PartialEq, Eq)]
+pub struct AuthorizationExport<N: Network> {
+    /// The programs required to execute the authorization, in dependency order.
+    programs: Vec<Program<N>>,
+    /// The authorization of the execution.
+    authorization: Authorization<N>,
+    /// The authorization of the fee, if one is paid.
+    fee: Option<Authorization<N>>,
+}
+
+impl<N: Network> AuthorizationExport<N> {
+    /// Initializes a new authorization export, from the given programs, execution authorization, and fee authorization.
+    pub fn new(
+        programs: Vec<Program<N>>,
+        authorization: Authorization<N>,
+        fee: Option<Authorization<N>>,
+    ) -> Result<Self> {
+        // Ensure the execution authorization is complete, and is not for a fee.
+        ensure_is_complete(&authorization)?;
+        ensure!(
+            !authorization.is_fee_private() && !authorization.is_fee_public(),
+            "The exported authorization must not be for a fee"
+        );
+
+        // Ensure the programs are unique, and each program is preceded by its imports.
+        let credits_program_id = ProgramID::<N>::from_str("credits.aleo")?;
+        let program_ids = ensure_programs_are_ordered(&programs)?;
+        // Ensure the programs of all requests are exported.
+        for request in authorization.requests.read().iter() {
+            ensure!(
+                request.program_id() == &credits_program_id || program_ids.contains(request.program_id()),
+                "Program '{}' is missing from the export",
+                request.program_id()
+            );
+        }
+
+        // Ensure the fee, if it exists, pays for this execution.
+        if let Some(fee) = &fee {
+            ensure!(
+                fee.is_fee_private() || fee.is_fee_public(),
+                "The exported fee must be for 'credits.aleo/fee_private' or 'credits.aleo/fee_public'"
+            );
+            ensure_is_complete(fee)?;
+            // Note: The execution ID is the last input of both fee functions.
+            let execution_id = authorization.to_execution_id()?;
+            match fee.peek_next()?.inputs().last() {
+                Some(Value::Plaintext(Plaintext::Literal(Literal::Field(id), _))) if *id == execution_id => (),
+                _ => bail!("The exported fee is not for execution '{execution_id}'"),
+            }
+        }
+
+        Ok(Self { programs, authorization, fee })
+    }
+
+    /// Returns the programs required to execute the authorization, in dependency order.
+    pub fn programs(&self) -> &[Program<N>] {
+        &self.programs
+    }
+
+    /// Returns the authorization of the execution.
+    pub const fn authorization(&self) -> &Authorization<N> {
+        &self.authorization
+    }
+
+    /// Returns the authorization of the fee, if one is paid.
+    pub const fn fee(&self) -> Option<&Authorization<N>> {
+        self.fee.as_ref()
+    }
+}
+
+impl<N: Network> Authorization<N> {
+    /// Returns an export of the authorization and the given fee authorization,
+    /// bundled with the programs in the process that are required to execute it.
+    pub fn export(&self, process: &Process<N>, fee: Option<Authorization<N>>) -> Result<AuthorizationExport<N>> {
+        // Retrieve the programs of all requests, in dependency order.
+        let program_ids = self.requests.read().iter().map(|request| *request.program_id()).collect::<Vec<_>>();
+        let programs = required_programs(process, &program_ids)?;
+        // Return the export.
+        AuthorizationExport::new(programs, self.replicate(), fee)
+    }
+}
+
+/// Returns the programs in the process that are required to execute the given programs, in dependency order.
+/// Note: `credits.aleo` is never included.
+pub(super) fn required_programs<N: Network>(
+    process: &Process<N>,
+    program_ids: &[ProgramID<N>],
+) -> Result<Vec<Program<N>>> {
+    // Collect the program IDs, in dependency order.
+    let credits_program_id = ProgramID::<N>::from_str("credits.aleo")?;
+    let mut ordered_program_ids = IndexSet::new();
+    for program_id in program_ids {
+        insert_program_id(process, program_id, &credits_program_id, &mut ordered_program_ids)?;
+    }
+    // Retrieve the programs.
+    ordered_program_ids.into_iter().map(|program_id| process.get_program(program_id).cloned()).collect()
+}
+
+/// Ensures the given programs are unique, exclude `credits.aleo`, and that each program is preceded by its imports.
+/// Returns the program IDs, in order.
+pub(super) fn ensure_programs_are_ordered<N: Network>(programs: &[Program<N>]) -> Result<IndexSet<ProgramID<N>>> {
+    let credits_program_id = ProgramID::<N>::from_str("credits.aleo")?;
+    let mut program_ids = IndexSet::with_capacity(programs.len());
+    for program in programs {
+        ensure!(program.id() != &credits_program_id, "The programs must not include 'credits.aleo'");
+        for import in program.imports().keys() {
+            ensure!(
+                import == &credits_program_id || program_ids.contains(import),
+                "Program '{import}' must be bundled before '{}'",
+                program.id()
+            );
+        }
+        ensure!(program_ids.insert(*program.id()), "Program '{}' is bundled more than once", program.id());
+    }
+    Ok(program_ids)
+}
+
+/// Inserts the given program ID after the program IDs of its imports, skipping `credits.aleo`.
+fn insert_program_id<N: Network>(
+    process: &Process<N>,
+    program_id: &ProgramID<N>,
+    credits_program_id: &ProgramID<N>,
+    program_ids: &mut IndexSet<ProgramID<N>>,
+) -> Result<()> {
+    if program_id == credits_program_id || program_ids.contains(program_id) {
+        return Ok(());
+    }
+    for import in process.get_program(*program_id)?.imports().keys() {
+        insert_program_id(process, import, credits_program_id, program_ids)?;
+    }
+    program_ids.insert(*program_id);
+    Ok(())
+}
+
+/// Ensures the given authorization is nonempty, and contains a transition for each request.
+fn ensure_is_complete<N: Network>(authorization: &Authorization<N>) -> Result<()> {
+    let num_requests = authorization.requests.read().len();
+    let num_transitions = authorization.transitions.read().len();
+    ensure!(
+        num_requests > 0 && num_requests == num_transitions,
+        "The authorization is incomplete ({num_requests} requests, {num_transitions} transitions)"
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod test_helpers {
+    use super::*;
+    use console::account::PrivateKey;
+
+    type CurrentNetwork = console::network::MainnetV0;
+    type CurrentAleo = circuit::AleoV0;
+
+    /// Returns a sample process, with a program `parent.aleo` that imports `child.aleo`.
+    pub fn sample_process() -> Process<CurrentNetwork> {
+        let child = Program::from_str(
+            r"
+program child.aleo;
+
+function double:
+    input r0 as u64.public;
+    add r0 r0 into r1;
+    output r1 as u64.public;",
+        )
+        .unwrap();
+        let parent = Program::from_str(
+            r"
+import child.aleo;
+
+program parent.aleo;
+
+function sum:
+    input r0 as u64.public;
+    input r1 as u64.private;
+    add r0 r1 into r2;
+    output r2 as u64.private;",
+        )
+        .unwrap();
+
+        let mut process = Process::<CurrentNetwork>::load().unwrap();
+        process.add_program(&child).unwrap();
+        process.add_program(&parent).unwrap();
+        process
+    }
+
+    /// Returns a sample authorization export, of a call to `parent.aleo/sum` with a public fee.
+    pub fn sample_authorization_export(rng: &mut TestRng) -> AuthorizationExport<CurrentNetwork> {
+        let process = sample_process();
+        let private_key = PrivateKey::new(rng).unwrap();
+
+        // Authorize the execution.
+        let authorization = process
+            .authorize::<CurrentAleo, _>(&private_key, "parent.aleo", "sum", ["1u64", "2u64"].into_iter(), rng)
+            .unwrap();
+        // Authorize the fee.
+        let fee = process
+            .authorize_fee_public::<CurrentAleo, _>(
+                &private_key,
+                rng.gen_range(1_000_000..u64::MAX / 2),
+                0,
+                authorization.to_execution_id().unwrap(),
+                rng,
+            )
+            .unwrap();
+
+        authorization.export(&process, Some(fee)).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use console::account::PrivateKey;
+
+    type CurrentNetwork = console::network::MainnetV0;
+    type CurrentAleo = circuit::AleoV0;
+
+    #[test]
+    fn test_export() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        // Ensure the imports are exported before the program, without 'credits.aleo'.
+        let export = test_helpers::sample_authorization_export(rng);
+        let program_ids = export.programs().iter().map(|program| program.id().to_string()).collect::<Vec<_>>();
+        assert_eq!(vec!["child.aleo", "parent.aleo"], program_ids);
+        assert_eq!(1, export.authorization().len());
+        assert!(export.fee().unwrap().is_fee_public());
+
+        // Ensure the programs must be in dependency order, and must include the program of each request.
+        let (authorization, fee) = (export.authorization().replicate(), export.fee().cloned());
+        let mut reversed = export.programs().to_vec();
+        reversed.reverse();
+        assert!(AuthorizationExport::new(reversed, authorization.clone(), fee.clone()).is_err());
+        assert!(AuthorizationExport::new(export.programs()[..1].to_vec(), authorization.clone(), fee.clone()).is_err());
+
+        // Ensure the fee must be for the exported execution.
+        let process = test_helpers::sample_process();
+        let private_key = PrivateKey::<CurrentNetwork>::new(rng)?;
+        let other_fee =
+            process.authorize_fee_public::<CurrentAleo, _>(&private_key, 1_000_000, 0, Field::rand(rng), rng)?;
+        assert!(authorization.export(&process, Some(other_fee)).is_err());
+        // Ensure a fee cannot be exported as the execution.
+        assert!(fee.unwrap().export(&process, None).is_err());
+        // Ensure the fee is optional.
+        assert!(authorization.export(&process, None)?.fee().is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+impl<N: Network> Serialize for AuthorizationExport<N> {
+    /// Serializes the authorization export into string or bytes.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => {
+                let mut export = serializer.serialize_struct("AuthorizationExport", 2 + self.fee.is_some() as usize)?;
+                export.serialize_field("programs", &self.programs)?;
+                export.serialize_field("authorization", &self.authorization)?;
+                if let Some(fee) = &self.fee {
+                    export.serialize_field("fee", fee)?;
+                }
+                export.end()
+            }
+            false => ToBytesSerializer::serialize_with_size_encoding(self, serializer),
+        }
+    }
+}
+
+impl<'de, N: Network> Deserialize<'de> for AuthorizationExport<N> {
+    /// Deserializes the authorization export from a string or bytes.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match deserializer.is_human_readable() {
+            true => {
+                // Parse the authorization export from a string into a value.
+                let mut export = serde_json::Value::deserialize(deserializer)?;
+                // Retrieve the programs.
+                let programs: Vec<_> = DeserializeExt::take_from_value::<D>(&mut export, "programs")?;
+                // Retrieve the authorization.
+                let authorization = DeserializeExt::take_from_value::<D>(&mut export, "authorization")?;
+                // Retrieve the fee, if it exists.
+                let fee = serde_json::from_value(export.get_mut("fee").unwrap_or(&mut serde_json::Value::Null).take())
+                    .map_err(de::Error::custom)?;
+                // Recover the authorization export.
+                Self::new(programs, authorization, fee).map_err(de::Error::custom)
+            }
+            false => {
+                FromBytesDeserializer::<Self>::deserialize_with_size_encoding(deserializer, "authorization export")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serde_json() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        // Sample the authorization export.
+        let expected = test_helpers::sample_authorization_export(rng);
+
+        // Serialize
+        let expected_string = &expected.to_string();
+        let candidate_string = serde_json::to_string(&expected)?;
+        assert_eq!(expected, serde_json::from_str(&candidate_string)?);
+
+        // Deserialize
+        assert_eq!(expected, AuthorizationExport::from_str(expected_string)?);
+        assert_eq!(expected, serde_json::from_str(&candidate_string)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bincode() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        // Sample the authorization export.
+        let expected = test_helpers::sample_authorization_export(rng);
+
+        // Serialize
+        let expected_bytes = expected.to_bytes_le()?;
+        let expected_bytes_with_size_encoding = bincode::serialize(&expected)?;
+        assert_eq!(&expected_bytes[..], &expected_bytes_with_size_encoding[8..]);
+
+        // Deserialize
+        assert_eq!(expected, AuthorizationExport::read_le(&expected_bytes[..])?);
+        assert_eq!(expected, bincode::deserialize(&expected_bytes_with_size_encoding[..])?);
+
+        Ok(())
+    }
+}
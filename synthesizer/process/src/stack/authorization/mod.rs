@@ -16,6 +16,12 @@ mod bytes;
 mod serialize;
 mod string;
 
+mod export;
+pub use export::*;
+
+mod offline;
+pub use offline::*;
+
 use console::{network::prelude::*, program::Request, types::Field};
 use ledger_block::{Transaction, Transition};
 
@@ -0,0 +1,131 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use console::network::Multipart;
+
+impl<N: Network> FromBytes for OfflineExecution<N> {
+    /// Reads the offline execution from a buffer.
+    fn read_le<R: Read>(mut reader: R) -> IoResult<Self> {
+        // Read the version.
+        let version = u8::read_le(&mut reader)?;
+        // Ensure the version is valid.
+        if version != 1 {
+            return Err(error("Invalid offline execution version"));
+        }
+
+        // Read the signer.
+        let signer = Address::read_le(&mut reader)?;
+        // Read the number of programs.
+        let num_programs = u16::read_le(&mut reader)?;
+        // Read the programs.
+        let programs = (0..num_programs).map(|_| Program::read_le(&mut reader)).collect::<IoResult<Vec<_>>>()?;
+        // Read the program ID.
+        let program_id = ProgramID::read_le(&mut reader)?;
+        // Read the function name.
+        let function_name = Identifier::read_le(&mut reader)?;
+        // Read the number of inputs.
+        let num_inputs = u16::read_le(&mut reader)?;
+        // Read the inputs.
+        let inputs = (0..num_inputs).map(|_| Value::read_le(&mut reader)).collect::<IoResult<Vec<_>>>()?;
+        // Read the base fee.
+        let base_fee_in_microcredits = u64::read_le(&mut reader)?;
+        // Read the priority fee.
+        let priority_fee_in_microcredits = u64::read_le(&mut reader)?;
+        // Read the fee record.
+        let fee_record = match u8::read_le(&mut reader)? {
+            0 => None,
+            1 => Some(Record::read_le(&mut reader)?),
+            _ => return Err(error("Invalid fee record variant in the offline execution")),
+        };
+
+        // Return the new `OfflineExecution` instance.
+        Self::new(
+            signer,
+            programs,
+            program_id,
+            function_name,
+            inputs,
+            base_fee_in_microcredits,
+            priority_fee_in_microcredits,
+            fee_record,
+        )
+        .map_err(error)
+    }
+}
+
+impl<N: Network> ToBytes for OfflineExecution<N> {
+    /// Writes the offline execution to a buffer.
+    fn write_le<W: Write>(&self, mut writer: W) -> IoResult<()> {
+        // Write the version.
+        1u8.write_le(&mut writer)?;
+        // Write the signer.
+        self.signer.write_le(&mut writer)?;
+        // Write the number of programs.
+        u16::try_from(self.programs.len()).map_err(error)?.write_le(&mut writer)?;
+        // Write the programs.
+        self.programs.iter().try_for_each(|program| program.write_le(&mut writer))?;
+        // Write the program ID.
+        self.program_id.write_le(&mut writer)?;
+        // Write the function name.
+        self.function_name.write_le(&mut writer)?;
+        // Write the number of inputs.
+        u16::try_from(self.inputs.len()).map_err(error)?.write_le(&mut writer)?;
+        // Write the inputs.
+        self.inputs.iter().try_for_each(|input| input.write_le(&mut writer))?;
+        // Write the base fee.
+        self.base_fee_in_microcredits.write_le(&mut writer)?;
+        // Write the priority fee.
+        self.priority_fee_in_microcredits.write_le(&mut writer)?;
+        // Write the fee record.
+        match &self.fee_record {
+            None => 0u8.write_le(&mut writer),
+            Some(record) => {
+                1u8.write_le(&mut writer)?;
+                record.write_le(&mut writer)
+            }
+        }
+    }
+}
+
+impl<N: Network> Multipart<N> for OfflineExecution<N> {
+    /// Returns the human-readable prefix of each part of an offline execution.
+    fn multipart_prefix() -> &'static str {
+        "offline"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use console::network::MULTIPART_FRAGMENT_SIZE;
+
+    #[test]
+    fn test_bytes() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        // Construct a new offline execution.
+        let (expected, _) = test_helpers::sample_offline_execution(rng);
+
+        // Check the byte representation.
+        let expected_bytes = expected.to_bytes_le()?;
+        assert_eq!(expected, OfflineExecution::read_le(&expected_bytes[..])?);
+
+        // Check the multipart representation.
+        let parts = expected.to_multipart(MULTIPART_FRAGMENT_SIZE)?;
+        assert!(parts.len() > 1);
+        assert_eq!(expected, OfflineExecution::from_multipart(&parts)?);
+        Ok(())
+    }
+}
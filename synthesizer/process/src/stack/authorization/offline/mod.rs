@@ -0,0 +1,379 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod bytes;
+mod serialize;
+mod string;
+
+use super::{
+    export::{ensure_programs_are_ordered, required_programs},
+    *,
+};
+use crate::Process;
+use console::{
+    account::{Address, PrivateKey},
+    program::{Identifier, Literal, Plaintext, ProgramID, Record, Value},
+};
+use synthesizer_program::Program;
+
+/// An execution that is prepared on an online machine, to be signed on an offline (air-gapped) machine.
+///
+/// The workflow has three steps:
+///  1. `Process::prepare_offline_execution` bundles the call, its fee, and the programs required to execute it.
+///  2. `Process::sign_offline_execution` authorizes the call and its fee on the offline machine,
+///     and returns an authorization export, without any proving.
+///  3. The online machine checks the export with `OfflineExecution::check_export`, and proves it.
+///
+/// Both the offline execution and the authorization export can be transferred as a sequence of QR codes,
+/// using their `Multipart` encoding.
+#[derive(Clone, PartialEq, Eq)]
+pub struct OfflineExecution<N: Network> {
+    /// The address of the signer.
+    signer: Address<N>,
+    /// The programs required to execute the call, in dependency order.
+    programs: Vec<Program<N>>,
+    /// The program ID of the call.
+    program_id: ProgramID<N>,
+    /// The function name of the call.
+    function_name: Identifier<N>,
+    /// The inputs of the call.
+    inputs: Vec<Value<N>>,
+    /// The base fee in microcredits.
+    base_fee_in_microcredits: u64,
+    /// The priority fee in microcredits.
+    priority_fee_in_microcredits: u64,
+    /// The record to pay the fee with, if the fee is private.
+    fee_record: Option<Record<N, Plaintext<N>>>,
+}
+
+impl<N: Network> OfflineExecution<N> {
+    /// Initializes a new offline execution.
+    pub fn new(
+        signer: Address<N>,
+        programs: Vec<Program<N>>,
+        program_id: ProgramID<N>,
+        function_name: Identifier<N>,
+        inputs: Vec<Value<N>>,
+        base_fee_in_microcredits: u64,
+        priority_fee_in_microcredits: u64,
+        fee_record: Option<Record<N, Plaintext<N>>>,
+    ) -> Result<Self> {
+        // Ensure the programs are unique, and each program is preceded by its imports.
+        let program_ids = ensure_programs_are_ordered(&programs)?;
+        // Retrieve the function of the call.
+        let function = match program_ids.get_index_of(&program_id) {
+            Some(index) => programs[index].get_function(&function_name)?,
+            None if program_id == ProgramID::from_str("credits.aleo")? => {
+                Program::credits()?.get_function(&function_name)?
+            }
+            None => bail!("Program '{program_id}' is missing from the offline execution"),
+        };
+        // Ensure the number of inputs matches the function.
+        ensure!(
+            function.inputs().len() == inputs.len(),
+            "Function '{program_id}/{function_name}' expects {} inputs, found {}",
+            function.inputs().len(),
+            inputs.len()
+        );
+        // Ensure the fee record, if it exists, is owned by the signer.
+        if let Some(record) = &fee_record {
+            ensure!(**record.owner() == signer, "The fee record is not owned by the signer '{signer}'");
+        }
+
+        Ok(Self {
+            signer,
+            programs,
+            program_id,
+            function_name,
+            inputs,
+            base_fee_in_microcredits,
+            priority_fee_in_microcredits,
+            fee_record,
+        })
+    }
+
+    /// Returns the address of the signer.
+    pub const fn signer(&self) -> &Address<N> {
+        &self.signer
+    }
+
+    /// Returns the programs required to execute the call, in dependency order.
+    pub fn programs(&self) -> &[Program<N>] {
+        &self.programs
+    }
+
+    /// Returns the program ID of the call.
+    pub const fn program_id(&self) -> &ProgramID<N> {
+        &self.program_id
+    }
+
+    /// Returns the function name of the call.
+    pub const fn function_name(&self) -> &Identifier<N> {
+        &self.function_name
+    }
+
+    /// Returns the inputs of the call.
+    pub fn inputs(&self) -> &[Value<N>] {
+        &self.inputs
+    }
+
+    /// Returns the base fee in microcredits.
+    pub const fn base_fee_in_microcredits(&self) -> u64 {
+        self.base_fee_in_microcredits
+    }
+
+    /// Returns the priority fee in microcredits.
+    pub const fn priority_fee_in_microcredits(&self) -> u64 {
+        self.priority_fee_in_microcredits
+    }
+
+    /// Returns the record to pay the fee with, if the fee is private.
+    pub const fn fee_record(&self) -> Option<&Record<N, Plaintext<N>>> {
+        self.fee_record.as_ref()
+    }
+
+    /// Ensures the given authorization export is a signed authorization of this offline execution.
+    pub fn check_export(&self, export: &AuthorizationExport<N>) -> Result<()> {
+        // Ensure the programs match.
+        ensure!(export.programs() == self.programs, "The exported programs do not match the offline execution");
+
+        // Ensure the call matches.
+        let request = export.authorization().peek_next()?;
+        ensure!(request.signer() == &self.signer, "The export is not signed by '{}'", self.signer);
+        ensure!(
+            request.program_id() == &self.program_id && request.function_name() == &self.function_name,
+            "The export is not a call to '{}/{}'",
+            self.program_id,
+            self.function_name
+        );
+        ensure!(request.inputs() == self.inputs, "The exported inputs do not match the offline execution");
+
+        // Ensure the fee matches.
+        let Some(fee) = export.fee() else { bail!("The export must include a fee authorization") };
+        let request = fee.peek_next()?;
+        ensure!(request.signer() == &self.signer, "The exported fee is not signed by '{}'", self.signer);
+        // Note: The fee inputs are the fee record (if private), the base fee, the priority fee, and the execution ID.
+        let fee_inputs = match (&self.fee_record, request.inputs()) {
+            (Some(record), [Value::Record(candidate), fee_inputs @ ..]) if fee.is_fee_private() => {
+                ensure!(record == candidate, "The exported fee record does not match the offline execution");
+                fee_inputs
+            }
+            (None, fee_inputs) if fee.is_fee_public() => fee_inputs,
+            _ => bail!("The exported fee does not match the fee type of the offline execution"),
+        };
+        match fee_inputs {
+            [Value::Plaintext(Plaintext::Literal(Literal::U64(base_fee), _)), Value::Plaintext(Plaintext::Literal(Literal::U64(priority_fee), _)), _] => {
+                ensure!(
+                    **base_fee == self.base_fee_in_microcredits && **priority_fee == self.priority_fee_in_microcredits,
+                    "The exported fee amounts do not match the offline execution"
+                )
+            }
+            _ => bail!("Failed to retrieve the fee amounts from the exported fee"),
+        }
+        Ok(())
+    }
+}
+
+impl<N: Network> Process<N> {
+    /// Returns an offline execution of a call to the given function, and its fee, to be signed by the given signer.
+    /// If a fee record is given, the fee is private, otherwise it is public.
+    pub fn prepare_offline_execution(
+        &self,
+        signer: Address<N>,
+        program_id: impl TryInto<ProgramID<N>>,
+        function_name: impl TryInto<Identifier<N>>,
+        inputs: impl ExactSizeIterator<Item = impl TryInto<Value<N>>>,
+        base_fee_in_microcredits: u64,
+        priority_fee_in_microcredits: u64,
+        fee_record: Option<Record<N, Plaintext<N>>>,
+    ) -> Result<OfflineExecution<N>> {
+        // Prepare the program ID, function name, and inputs.
+        let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
+        let function_name = function_name.try_into().map_err(|_| anyhow!("Invalid function name"))?;
+        let inputs = inputs
+            .map(|input| input.try_into().map_err(|_| anyhow!("Failed to parse input")))
+            .collect::<Result<Vec<_>>>()?;
+        // Retrieve the programs required to execute the call.
+        let programs = required_programs(self, &[program_id])?;
+        // Return the offline execution.
+        OfflineExecution::new(
+            signer,
+            programs,
+            program_id,
+            function_name,
+            inputs,
+            base_fee_in_microcredits,
+            priority_fee_in_microcredits,
+            fee_record,
+        )
+    }
+
+    /// Signs the given offline execution, and returns the authorization export of the call and its fee.
+    ///
+    /// This is intended to be called on the offline machine, and does not require any state:
+    /// the bundled programs are only loaded into a copy of the process, for the duration of this call.
+    pub fn sign_offline_execution<A: circuit::Aleo<Network = N>, R: Rng + CryptoRng>(
+        &self,
+        private_key: &PrivateKey<N>,
+        execution: &OfflineExecution<N>,
+        rng: &mut R,
+    ) -> Result<AuthorizationExport<N>> {
+        // Ensure the private key is of the signer.
+        ensure!(
+            Address::try_from(private_key)? == execution.signer,
+            "The private key is not of the signer '{}'",
+            execution.signer
+        );
+
+        // Load the bundled programs into a copy of the process.
+        let mut process = self.clone();
+        for program in &execution.programs {
+            match process.contains_program(program.id()) {
+                true => ensure!(
+                    process.get_program(program.id())? == program,
+                    "The bundled program '{}' does not match the program in the process",
+                    program.id()
+                ),
+                false => process.add_program(program)?,
+            }
+        }
+
+        // Authorize the call.
+        let authorization = process.authorize::<A, R>(
+            private_key,
+            execution.program_id,
+            execution.function_name,
+            execution.inputs.iter(),
+            rng,
+        )?;
+        let execution_id = authorization.to_execution_id()?;
+        // Authorize the fee.
+        let fee = match &execution.fee_record {
+            Some(record) => process.authorize_fee_private::<A, R>(
+                private_key,
+                record.clone(),
+                execution.base_fee_in_microcredits,
+                execution.priority_fee_in_microcredits,
+                execution_id,
+                rng,
+            )?,
+            None => process.authorize_fee_public::<A, R>(
+                private_key,
+                execution.base_fee_in_microcredits,
+                execution.priority_fee_in_microcredits,
+                execution_id,
+                rng,
+            )?,
+        };
+
+        // Return the authorization export.
+        let export = AuthorizationExport::new(execution.programs.clone(), authorization, Some(fee))?;
+        execution.check_export(&export)?;
+        Ok(export)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test_helpers {
+    use super::*;
+
+    type CurrentNetwork = console::network::MainnetV0;
+
+    /// Returns a sample offline execution, of a call to `parent.aleo/sum` with a public fee, and the private key of the signer.
+    pub fn sample_offline_execution(
+        rng: &mut TestRng,
+    ) -> (OfflineExecution<CurrentNetwork>, PrivateKey<CurrentNetwork>) {
+        let process = crate::stack::authorization::export::test_helpers::sample_process();
+        let private_key = PrivateKey::new(rng).unwrap();
+        let execution = process
+            .prepare_offline_execution(
+                Address::try_from(&private_key).unwrap(),
+                "parent.aleo",
+                "sum",
+                ["1u64", "2u64"].into_iter(),
+                1_000_000,
+                10,
+                None,
+            )
+            .unwrap();
+        (execution, private_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type CurrentNetwork = console::network::MainnetV0;
+    type CurrentAleo = circuit::AleoV0;
+
+    #[test]
+    fn test_offline_execution() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        // Prepare the offline execution.
+        let (execution, private_key) = test_helpers::sample_offline_execution(rng);
+        let program_ids = execution.programs().iter().map(|program| program.id().to_string()).collect::<Vec<_>>();
+        assert_eq!(vec!["child.aleo", "parent.aleo"], program_ids);
+
+        // Ensure the offline execution is signed with only 'credits.aleo' in the process.
+        let process = Process::<CurrentNetwork>::load()?;
+        let export = process.sign_offline_execution::<CurrentAleo, _>(&private_key, &execution, rng)?;
+        execution.check_export(&export)?;
+        assert!(export.fee().unwrap().is_fee_public());
+
+        // Ensure the offline execution cannot be signed by another private key.
+        let other_private_key = PrivateKey::<CurrentNetwork>::new(rng)?;
+        assert!(process.sign_offline_execution::<CurrentAleo, _>(&other_private_key, &execution, rng).is_err());
+
+        // Ensure an export of a different call is rejected.
+        let other = OfflineExecution::new(
+            *execution.signer(),
+            execution.programs().to_vec(),
+            *execution.program_id(),
+            *execution.function_name(),
+            vec![Value::from_str("1u64")?, Value::from_str("3u64")?],
+            execution.base_fee_in_microcredits(),
+            execution.priority_fee_in_microcredits(),
+            None,
+        )?;
+        assert!(other.check_export(&export).is_err());
+        // Ensure an export with a different fee is rejected.
+        let other = OfflineExecution::new(
+            *execution.signer(),
+            execution.programs().to_vec(),
+            *execution.program_id(),
+            *execution.function_name(),
+            execution.inputs().to_vec(),
+            execution.base_fee_in_microcredits() + 1,
+            execution.priority_fee_in_microcredits(),
+            None,
+        )?;
+        assert!(other.check_export(&export).is_err());
+
+        // Ensure the number of inputs is checked.
+        assert!(OfflineExecution::new(
+            *execution.signer(),
+            execution.programs().to_vec(),
+            *execution.program_id(),
+            *execution.function_name(),
+            execution.inputs()[..1].to_vec(),
+            execution.base_fee_in_microcredits(),
+            execution.priority_fee_in_microcredits(),
+            None,
+        )
+        .is_err());
+        Ok(())
+    }
+}
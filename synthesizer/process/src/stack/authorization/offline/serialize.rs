@@ -0,0 +1,118 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+impl<N: Network> Serialize for OfflineExecution<N> {
+    /// Serializes the offline execution into string or bytes.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match serializer.is_human_readable() {
+            true => {
+                let mut execution =
+                    serializer.serialize_struct("OfflineExecution", 7 + self.fee_record.is_some() as usize)?;
+                execution.serialize_field("signer", &self.signer)?;
+                execution.serialize_field("programs", &self.programs)?;
+                execution.serialize_field("program_id", &self.program_id)?;
+                execution.serialize_field("function_name", &self.function_name)?;
+                execution.serialize_field("inputs", &self.inputs)?;
+                execution.serialize_field("base_fee_in_microcredits", &self.base_fee_in_microcredits)?;
+                execution.serialize_field("priority_fee_in_microcredits", &self.priority_fee_in_microcredits)?;
+                if let Some(fee_record) = &self.fee_record {
+                    execution.serialize_field("fee_record", fee_record)?;
+                }
+                execution.end()
+            }
+            false => ToBytesSerializer::serialize_with_size_encoding(self, serializer),
+        }
+    }
+}
+
+impl<'de, N: Network> Deserialize<'de> for OfflineExecution<N> {
+    /// Deserializes the offline execution from a string or bytes.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match deserializer.is_human_readable() {
+            true => {
+                // Parse the offline execution from a string into a value.
+                let mut execution = serde_json::Value::deserialize(deserializer)?;
+                // Retrieve the signer.
+                let signer = DeserializeExt::take_from_value::<D>(&mut execution, "signer")?;
+                // Retrieve the programs.
+                let programs: Vec<_> = DeserializeExt::take_from_value::<D>(&mut execution, "programs")?;
+                // Retrieve the program ID.
+                let program_id = DeserializeExt::take_from_value::<D>(&mut execution, "program_id")?;
+                // Retrieve the function name.
+                let function_name = DeserializeExt::take_from_value::<D>(&mut execution, "function_name")?;
+                // Retrieve the inputs.
+                let inputs: Vec<_> = DeserializeExt::take_from_value::<D>(&mut execution, "inputs")?;
+                // Retrieve the base fee.
+                let base_fee = DeserializeExt::take_from_value::<D>(&mut execution, "base_fee_in_microcredits")?;
+                // Retrieve the priority fee.
+                let priority_fee =
+                    DeserializeExt::take_from_value::<D>(&mut execution, "priority_fee_in_microcredits")?;
+                // Retrieve the fee record, if it exists.
+                let fee_record = serde_json::from_value(
+                    execution.get_mut("fee_record").unwrap_or(&mut serde_json::Value::Null).take(),
+                )
+                .map_err(de::Error::custom)?;
+                // Recover the offline execution.
+                Self::new(signer, programs, program_id, function_name, inputs, base_fee, priority_fee, fee_record)
+                    .map_err(de::Error::custom)
+            }
+            false => FromBytesDeserializer::<Self>::deserialize_with_size_encoding(deserializer, "offline execution"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serde_json() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        // Sample the offline execution.
+        let (expected, _) = test_helpers::sample_offline_execution(rng);
+
+        // Serialize
+        let expected_string = &expected.to_string();
+        let candidate_string = serde_json::to_string(&expected)?;
+        assert_eq!(expected, serde_json::from_str(&candidate_string)?);
+
+        // Deserialize
+        assert_eq!(expected, OfflineExecution::from_str(expected_string)?);
+        assert_eq!(expected, serde_json::from_str(&candidate_string)?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_bincode() -> Result<()> {
+        let rng = &mut TestRng::default();
+
+        // Sample the offline execution.
+        let (expected, _) = test_helpers::sample_offline_execution(rng);
+
+        // Serialize
+        let expected_bytes = expected.to_bytes_le()?;
+        let expected_bytes_with_size_encoding = bincode::serialize(&expected)?;
+        assert_eq!(&expected_bytes[..], &expected_bytes_with_size_encoding[8..]);
+
+        // Deserialize
+        assert_eq!(expected, OfflineExecution::read_le(&expected_bytes[..])?);
+        assert_eq!(expected, bincode::deserialize(&expected_bytes_with_size_encoding[..])?);
+
+        Ok(())
+    }
+}
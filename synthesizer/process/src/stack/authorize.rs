@@ -55,4 +55,86 @@ impl<N: Network> Stack<N> {
         // Return the authorization.
         Ok(authorization)
     }
+
+    /// Prepares a threshold request for a call to the program function for the given inputs,
+    /// to be jointly signed by the participants of the given threshold account.
+    ///
+    /// Note: The function must not call other functions, as each call requires its own signed request.
+    #[inline]
+    pub fn prepare_threshold_request(
+        &self,
+        account: &ThresholdAccount<N>,
+        function_name: impl TryInto<Identifier<N>>,
+        inputs: impl ExactSizeIterator<Item = impl TryInto<Value<N>>>,
+    ) -> Result<ThresholdRequest<N>> {
+        // Get the program ID.
+        let program_id = *self.program.id();
+        // Prepare the function name.
+        let function_name = function_name.try_into().map_err(|_| anyhow!("Invalid function name"))?;
+        // Ensure the function does not call other functions.
+        ensure!(
+            self.get_number_of_calls(&function_name)? == 1,
+            "Threshold authorization of '{program_id}/{function_name}' is not supported, as it calls other functions"
+        );
+        // Retrieve the input types.
+        let input_types = self.get_function(&function_name)?.input_types();
+
+        // Prepare the inputs.
+        let inputs = inputs
+            .enumerate()
+            .map(|(index, input)| {
+                input.try_into().map_err(|_| anyhow!("Failed to parse input #{index} for '{function_name}'"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // This is the root request and does not have a root_tvk.
+        ThresholdRequest::new(account.clone(), program_id, function_name, inputs, input_types, None, true)
+    }
+
+    /// Authorizes a call to the program function from the signature shares of the participants
+    /// in the signing package of the given threshold request.
+    #[inline]
+    pub fn authorize_threshold<A: circuit::Aleo<Network = N>, R: Rng + CryptoRng>(
+        &self,
+        threshold_request: &ThresholdRequest<N>,
+        package: &SigningPackage<N>,
+        shares: &[SignatureShare<N>],
+        rng: &mut R,
+    ) -> Result<Authorization<N>> {
+        let timer = timer!("Stack::authorize_threshold");
+
+        // Ensure the threshold request is for a root request to this program.
+        ensure!(
+            threshold_request.program_id() == self.program.id() && threshold_request.is_root(),
+            "The threshold request is not a root request to '{}'",
+            self.program.id()
+        );
+        // Ensure the function does not call other functions.
+        let function_name = threshold_request.function_name();
+        ensure!(
+            self.get_number_of_calls(function_name)? == 1,
+            "Threshold authorization of '{}/{function_name}' is not supported, as it calls other functions",
+            self.program.id()
+        );
+        // Aggregate the signature shares into the request.
+        let request = threshold_request.aggregate(package, shares)?;
+        lap!(timer, "Aggregate the request");
+
+        // This is the root request and does not have a caller.
+        let caller = None;
+        // This is the root request and we do not have a root_tvk to pass on.
+        let root_tvk = None;
+        // Initialize the authorization.
+        let authorization = Authorization::new(request.clone());
+        // Construct the call stack.
+        // Note: The private key is only used to sign requests for function calls, which the function does not have.
+        let burner_private_key = PrivateKey::new(rng)?;
+        let call_stack = CallStack::Authorize(vec![request], burner_private_key, authorization.clone());
+        // Construct the authorization from the function.
+        let _response = self.execute_function::<A, R>(call_stack, caller, root_tvk, rng)?;
+        finish!(timer, "Construct the authorization from the function");
+
+        // Return the authorization.
+        Ok(authorization)
+    }
 }
@@ -345,8 +345,8 @@ impl<N: Network> CallTrait<N> for Call<N> {
                         // Return the request and response.
                         (request, response)
                     }
-                    // If the circuit is in evaluate mode, then throw an error.
-                    CallStack::Evaluate(..) => {
+                    // If the circuit is in evaluate or debug mode, then throw an error.
+                    CallStack::Evaluate(..) | CallStack::Debug(..) => {
                         bail!("Cannot 'execute' a function in 'evaluate' mode.")
                     }
                     // If the circuit is in execute mode, then evaluate and execute the instructions.
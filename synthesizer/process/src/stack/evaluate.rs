@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use super::*;
+use console::program::Register;
 
 impl<N: Network> StackEvaluate<N> for Stack<N> {
     /// Evaluates a program closure on the given inputs.
@@ -36,6 +37,9 @@ impl<N: Network> StackEvaluate<N> for Stack<N> {
             bail!("Expected {} inputs, found {}", closure.inputs().len(), inputs.len())
         }
 
+        // Retrieve the step hook, if the evaluation is being debugged.
+        let hook = call_stack.step_hook().cloned();
+
         // Initialize the registers.
         let mut registers = Registers::<N, A>::new(call_stack, self.get_register_types(closure.name())?.clone());
         // Set the transition signer.
@@ -54,12 +58,25 @@ impl<N: Network> StackEvaluate<N> for Stack<N> {
         lap!(timer, "Store the inputs");
 
         // Evaluate the instructions.
-        for instruction in closure.instructions() {
+        if let Some(hook) = &hook {
+            hook.lock().enter(self.program_id(), closure.name())?;
+        }
+        for (index, instruction) in closure.instructions().iter().enumerate() {
+            // If the evaluation is being debugged, call the step hook.
+            if let Some(hook) = &hook {
+                let location = Location::new(*self.program_id(), *closure.name(), index);
+                let load = |register: &Register<N>| registers.load(self, &Operand::Register(register.clone()));
+                hook.lock().before_step(&Step::new(location, instruction, &load))?;
+            }
             // If the evaluation fails, bail and return the error.
             if let Err(error) = instruction.evaluate(self, &mut registers) {
-                bail!("Failed to evaluate instruction ({instruction}): {error}");
+                let location = located_at(self.get_instruction_span(closure.name(), index));
+                bail!("Failed to evaluate instruction ({instruction}){location}: {error}");
             }
         }
+        if let Some(hook) = &hook {
+            hook.lock().exit(self.program_id(), closure.name())?;
+        }
         lap!(timer, "Evaluate the instructions");
 
         // Load the outputs.
@@ -107,7 +124,9 @@ impl<N: Network> StackEvaluate<N> for Stack<N> {
 
         // Retrieve the next request, based on the call stack mode.
         let (request, call_stack) = match &call_stack {
-            CallStack::Evaluate(authorization) => (authorization.next()?, call_stack),
+            CallStack::Evaluate(authorization) | CallStack::Debug(authorization, _) => {
+                (authorization.next()?, call_stack)
+            }
             // If the evaluation is performed in the `Execute` mode, create a new `Evaluate` mode.
             // This is done to ensure that evaluation during execution is performed consistently.
             CallStack::Execute(authorization, _) => {
@@ -118,7 +137,9 @@ impl<N: Network> StackEvaluate<N> for Stack<N> {
                 let call_stack = CallStack::Evaluate(authorization);
                 (request, call_stack)
             }
-            _ => bail!("Illegal operation: call stack must be `Evaluate` or `Execute` in `evaluate_function`."),
+            _ => {
+                bail!("Illegal operation: call stack must be `Evaluate`, `Execute`, or `Debug` in `evaluate_function`.")
+            }
         };
         lap!(timer, "Retrieve the next request");
 
@@ -154,6 +175,9 @@ impl<N: Network> StackEvaluate<N> for Stack<N> {
         }
         lap!(timer, "Perform input checks");
 
+        // Retrieve the step hook, if the evaluation is being debugged.
+        let hook = call_stack.step_hook().cloned();
+
         // Initialize the registers.
         let mut registers = Registers::<N, A>::new(call_stack, self.get_register_types(function.name())?.clone());
         // Set the transition signer.
@@ -177,7 +201,16 @@ impl<N: Network> StackEvaluate<N> for Stack<N> {
 
         // Evaluate the instructions.
         // Note: We handle the `call` instruction separately, as it requires special handling.
-        for instruction in function.instructions() {
+        if let Some(hook) = &hook {
+            hook.lock().enter(self.program_id(), function.name())?;
+        }
+        for (index, instruction) in function.instructions().iter().enumerate() {
+            // If the evaluation is being debugged, call the step hook.
+            if let Some(hook) = &hook {
+                let location = Location::new(*self.program_id(), *function.name(), index);
+                let load = |register: &Register<N>| registers.load(self, &Operand::Register(register.clone()));
+                hook.lock().before_step(&Step::new(location, instruction, &load))?;
+            }
             // Evaluate the instruction.
             let result = match instruction {
                 // If the instruction is a `call` instruction, we need to handle it separately.
@@ -187,9 +220,13 @@ impl<N: Network> StackEvaluate<N> for Stack<N> {
             };
             // If the evaluation fails, bail and return the error.
             if let Err(error) = result {
-                bail!("Failed to evaluate instruction ({instruction}): {error}");
+                let location = located_at(self.get_instruction_span(function.name(), index));
+                bail!("Failed to evaluate instruction ({instruction}){location}: {error}");
             }
         }
+        if let Some(hook) = &hook {
+            hook.lock().exit(self.program_id(), function.name())?;
+        }
         lap!(timer, "Evaluate the instructions");
 
         // Retrieve the output operands.
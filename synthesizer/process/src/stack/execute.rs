@@ -32,7 +32,10 @@ impl<N: Network> StackExecute<N> for Stack<N> {
         let timer = timer!("Stack::execute_closure");
 
         // Ensure the call stack is not `Evaluate`.
-        ensure!(!matches!(call_stack, CallStack::Evaluate(..)), "Illegal operation: cannot evaluate in execute mode");
+        ensure!(
+            !matches!(call_stack, CallStack::Evaluate(..) | CallStack::Debug(..)),
+            "Illegal operation: cannot evaluate in execute mode"
+        );
 
         // Ensure the number of inputs matches the number of input statements.
         if closure.inputs().len() != inputs.len() {
@@ -67,12 +70,13 @@ impl<N: Network> StackExecute<N> for Stack<N> {
         lap!(timer, "Store the inputs");
 
         // Execute the instructions.
-        for instruction in closure.instructions() {
+        for (index, instruction) in closure.instructions().iter().enumerate() {
             // If the circuit is in execute mode, then evaluate the instructions.
             if let CallStack::Execute(..) = registers.call_stack() {
                 // If the evaluation fails, bail and return the error.
                 if let Err(error) = instruction.evaluate(self, &mut registers) {
-                    bail!("Failed to evaluate instruction ({instruction}): {error}");
+                    let location = located_at(self.get_instruction_span(closure.name(), index));
+                    bail!("Failed to evaluate instruction ({instruction}){location}: {error}");
                 }
             }
             // Execute the instruction.
@@ -282,7 +286,7 @@ impl<N: Network> StackExecute<N> for Stack<N> {
         let mut contains_function_call = false;
 
         // Execute the instructions.
-        for instruction in function.instructions() {
+        for (index, instruction) in function.instructions().iter().enumerate() {
             // If the circuit is in execute mode, then evaluate the instructions.
             if let CallStack::Execute(..) = registers.call_stack() {
                 // Evaluate the instruction.
@@ -294,7 +298,8 @@ impl<N: Network> StackExecute<N> for Stack<N> {
                 };
                 // If the evaluation fails, bail and return the error.
                 if let Err(error) = result {
-                    bail!("Failed to evaluate instruction ({instruction}): {error}");
+                    let location = located_at(self.get_instruction_span(function.name(), index));
+                    bail!("Failed to evaluate instruction ({instruction}){location}: {error}");
                 }
             }
 
@@ -307,7 +312,8 @@ impl<N: Network> StackExecute<N> for Stack<N> {
             };
             // If the execution fails, bail and return the error.
             if let Err(error) = result {
-                bail!("Failed to execute instruction ({instruction}): {error}");
+                let location = located_at(self.get_instruction_span(function.name(), index));
+                bail!("Failed to execute instruction ({instruction}){location}: {error}");
             }
 
             // If the instruction was a function call, then set the tracker to `true`.
@@ -36,9 +36,21 @@ mod evaluate;
 mod execute;
 mod helpers;
 
-use crate::{cost_in_microcredits, traits::*, CallMetrics, Process, Trace};
+use crate::{
+    cost_in_microcredits,
+    located_at,
+    traits::*,
+    CallMetrics,
+    Location,
+    Process,
+    SourceMap,
+    SourceSpan,
+    Step,
+    StepHookRef,
+    Trace,
+};
 use console::{
-    account::{Address, PrivateKey},
+    account::{Address, PrivateKey, SignatureShare, SigningPackage, ThresholdAccount},
     network::prelude::*,
     program::{
         Argument,
@@ -58,6 +70,7 @@ use console::{
         RegisterType,
         Request,
         Response,
+        ThresholdRequest,
         Value,
         ValueType,
     },
@@ -84,6 +97,7 @@ pub enum CallStack<N: Network> {
     CheckDeployment(Vec<Request<N>>, PrivateKey<N>, Assignments<N>, Option<u64>, Option<u64>),
     Evaluate(Authorization<N>),
     Execute(Authorization<N>, Arc<RwLock<Trace<N>>>),
+    Debug(Authorization<N>, StepHookRef<N>),
     PackageRun(Vec<Request<N>>, PrivateKey<N>, Assignments<N>),
 }
 
@@ -97,6 +111,19 @@ impl<N: Network> CallStack<N> {
     pub fn execute(authorization: Authorization<N>, trace: Arc<RwLock<Trace<N>>>) -> Result<Self> {
         Ok(CallStack::Execute(authorization, trace))
     }
+
+    /// Initializes a call stack as `Self::Debug`, which evaluates while calling the given step hook.
+    pub fn debug(authorization: Authorization<N>, hook: StepHookRef<N>) -> Result<Self> {
+        Ok(CallStack::Debug(authorization, hook))
+    }
+
+    /// Returns the step hook, if the call stack is in `Self::Debug` mode.
+    pub fn step_hook(&self) -> Option<&StepHookRef<N>> {
+        match self {
+            CallStack::Debug(_, hook) => Some(hook),
+            _ => None,
+        }
+    }
 }
 
 impl<N: Network> CallStack<N> {
@@ -122,6 +149,7 @@ impl<N: Network> CallStack<N> {
             CallStack::Execute(authorization, trace) => {
                 CallStack::Execute(authorization.replicate(), Arc::new(RwLock::new(trace.read().clone())))
             }
+            CallStack::Debug(authorization, hook) => CallStack::Debug(authorization.replicate(), hook.clone()),
             CallStack::PackageRun(requests, private_key, assignments) => {
                 CallStack::PackageRun(requests.clone(), *private_key, Arc::new(RwLock::new(assignments.read().clone())))
             }
@@ -136,7 +164,7 @@ impl<N: Network> CallStack<N> {
             | CallStack::CheckDeployment(requests, ..)
             | CallStack::PackageRun(requests, ..) => requests.push(request),
             CallStack::Evaluate(authorization) => authorization.push(request),
-            CallStack::Execute(authorization, ..) => authorization.push(request),
+            CallStack::Execute(authorization, ..) | CallStack::Debug(authorization, ..) => authorization.push(request),
         }
         Ok(())
     }
@@ -151,7 +179,7 @@ impl<N: Network> CallStack<N> {
                 requests.pop().ok_or_else(|| anyhow!("No more requests on the stack"))
             }
             CallStack::Evaluate(authorization) => authorization.next(),
-            CallStack::Execute(authorization, ..) => authorization.next(),
+            CallStack::Execute(authorization, ..) | CallStack::Debug(authorization, ..) => authorization.next(),
         }
     }
 
@@ -165,7 +193,7 @@ impl<N: Network> CallStack<N> {
                 requests.last().cloned().ok_or_else(|| anyhow!("No more requests on the stack"))
             }
             CallStack::Evaluate(authorization) => authorization.peek_next(),
-            CallStack::Execute(authorization, ..) => authorization.peek_next(),
+            CallStack::Execute(authorization, ..) | CallStack::Debug(authorization, ..) => authorization.peek_next(),
         }
     }
 }
@@ -186,6 +214,8 @@ pub struct Stack<N: Network> {
     proving_keys: Arc<RwLock<IndexMap<Identifier<N>, ProvingKey<N>>>>,
     /// The mapping of function name to verifying key.
     verifying_keys: Arc<RwLock<IndexMap<Identifier<N>, VerifyingKey<N>>>>,
+    /// The source map, if one was provided.
+    source_map: Arc<RwLock<Option<SourceMap<N>>>>,
     /// The mapping of function names to the number of calls.
     number_of_calls: IndexMap<Identifier<N>, usize>,
     /// The mapping of function names to finalize cost.
@@ -438,6 +468,40 @@ impl<N: Network> Stack<N> {
     pub fn remove_verifying_key(&self, function_name: &Identifier<N>) {
         self.verifying_keys.write().shift_remove(function_name);
     }
+
+    /// Returns the source map, if one was provided.
+    #[inline]
+    pub fn source_map(&self) -> Option<SourceMap<N>> {
+        self.source_map.read().clone()
+    }
+
+    /// Sets the source map for the program.
+    #[inline]
+    pub fn set_source_map(&self, source_map: SourceMap<N>) -> Result<()> {
+        // Ensure the source map matches the program.
+        source_map.check(&self.program)?;
+        // Set the source map.
+        *self.source_map.write() = Some(source_map);
+        Ok(())
+    }
+
+    /// Returns the source span of the given closure or function, if known.
+    #[inline]
+    pub fn get_function_span(&self, name: &Identifier<N>) -> Option<SourceSpan> {
+        self.source_map.read().as_ref().and_then(|source_map| source_map.get_function_span(name).cloned())
+    }
+
+    /// Returns the source span of the instruction at the given index in the given closure or function, if known.
+    #[inline]
+    pub fn get_instruction_span(&self, name: &Identifier<N>, index: usize) -> Option<SourceSpan> {
+        self.source_map.read().as_ref().and_then(|source_map| source_map.get_instruction_span(name, index).cloned())
+    }
+
+    /// Returns the source span of the finalize command at the given index in the given function, if known.
+    #[inline]
+    pub fn get_finalize_span(&self, name: &Identifier<N>, index: usize) -> Option<SourceSpan> {
+        self.source_map.read().as_ref().and_then(|source_map| source_map.get_finalize_span(name, index).cloned())
+    }
 }
 
 impl<N: Network> Stack<N> {
@@ -16,12 +16,13 @@ use crate::{
     traits::{StackEvaluate, StackExecute},
     CallStack,
     Process,
+    SourceMap,
     Stack,
     Trace,
 };
 use circuit::{network::AleoV0, Aleo};
 use console::{
-    account::{Address, PrivateKey, ViewKey},
+    account::{Address, PrivateKey, ThresholdAccount, ViewKey},
     network::{prelude::*, MainnetV0},
     program::{Identifier, Literal, Plaintext, ProgramID, Record, Value},
     types::{Field, U64},
@@ -155,6 +156,54 @@ function foo:
     assert_eq!(expected, candidate[0]);
 }
 
+#[test]
+fn test_program_evaluate_function_with_source_map() {
+    let program = Program::<CurrentNetwork>::from_str(
+        r"
+program example.aleo;
+
+function foo:
+    input r0 as field.public;
+    input r1 as field.private;
+    assert.eq r0 r1;
+",
+    )
+    .unwrap();
+
+    // Declare the function name.
+    let function_name = Identifier::from_str("foo").unwrap();
+    // Declare the function inputs.
+    let inputs = [
+        Value::<CurrentNetwork>::Plaintext(Plaintext::from_str("2field").unwrap()),
+        Value::Plaintext(Plaintext::from_str("3field").unwrap()),
+    ];
+
+    // Construct the process.
+    let process = crate::test_helpers::sample_process(&program);
+    // Add the source map.
+    let source_map = SourceMap::from_str(
+        r#"{ "program": "example.aleo", "functions": { "foo": { "span": "src/main.leo:3:5", "instructions": { "0": "src/main.leo:4:9" } } } }"#,
+    )
+    .unwrap();
+    process.add_source_map(source_map).unwrap();
+
+    // Authorize the function call.
+    let rng = &mut TestRng::default();
+    let caller_private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+    let authorization = process
+        .authorize::<CurrentAleo, _>(&caller_private_key, program.id(), function_name, inputs.iter(), rng)
+        .unwrap();
+
+    // Ensure the error reports the source location.
+    let stack = process.get_stack(program.id()).unwrap();
+    let error = stack.evaluate_function::<CurrentAleo>(CallStack::evaluate(authorization).unwrap(), None).unwrap_err();
+    assert!(error.to_string().contains(") at src/main.leo:4:9: "), "{error}");
+
+    // Ensure a source map for a different program is rejected.
+    let source_map = SourceMap::from_str(r#"{ "program": "credits.aleo", "functions": { "foo": {} } }"#).unwrap();
+    assert!(process.add_source_map(source_map).is_err());
+}
+
 #[test]
 fn test_program_evaluate_struct_and_function() {
     // Initialize a new program.
@@ -2645,3 +2694,86 @@ fn test_program_exceeding_transaction_spend_limit() {
     let result = Stack::initialize(&process, &program);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_process_authorize_threshold() {
+    // Initialize a new program.
+    let program = Program::<CurrentNetwork>::from_str(
+        r"import credits.aleo;
+
+program threshold.aleo;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+function split:
+    input r0 as token.record;
+    input r1 as u64.private;
+    sub r0.amount r1 into r2;
+    cast r0.owner r1 into r3 as token.record;
+    cast r0.owner r2 into r4 as token.record;
+    output r3 as token.record;
+    output r4 as token.record;
+
+function pay:
+    input r0 as address.public;
+    input r1 as u64.public;
+    call credits.aleo/transfer_public r0 r1 into r2;
+    async pay r2 into r3;
+    output r3 as threshold.aleo/pay.future;
+
+finalize pay:
+    input r0 as credits.aleo/transfer_public.future;
+    await r0;",
+    )
+    .unwrap();
+
+    // Initialize the RNG.
+    let rng = &mut TestRng::default();
+
+    // Construct the process.
+    let process = crate::test_helpers::sample_process(&program);
+
+    // Split a new account among 3 participants, of which any 2 can sign.
+    let private_key = PrivateKey::<CurrentNetwork>::new(rng).unwrap();
+    let view_key = ViewKey::try_from(&private_key).unwrap();
+    let (account, key_shares) = ThresholdAccount::new_trusted_dealer(&private_key, 2, 3, rng).unwrap();
+    let owner = account.address();
+
+    // Prepare the threshold request.
+    let record = format!("{{ owner: {owner}.private, amount: 100u64.private, _nonce: 0group.public }}");
+    let inputs = [Value::from_str(&record).unwrap(), Value::from_str("40u64").unwrap()];
+    let request = process.prepare_threshold_request(&account, program.id(), "split", inputs.iter()).unwrap();
+
+    // Sign the request with participants 1 and 3.
+    let signers = [&key_shares[0], &key_shares[2]];
+    let (nonces, commitments): (Vec<_>, Vec<_>) =
+        signers.iter().map(|key_share| request.commit(key_share, rng).unwrap()).unzip();
+    let package = request.signing_package(commitments).unwrap();
+    let shares = signers
+        .iter()
+        .zip_eq(nonces)
+        .map(|(key_share, nonces)| request.sign_share(key_share, nonces, &package).unwrap())
+        .collect::<Vec<_>>();
+
+    // Authorize the function call.
+    let authorization = process.authorize_threshold::<CurrentAleo, _>(&request, &package, &shares, rng).unwrap();
+    assert_eq!(authorization.len(), 1);
+    let signed_request = authorization.peek_next().unwrap();
+    assert_eq!(owner, *signed_request.signer());
+
+    // Ensure the transition can be decrypted with the view key of the account.
+    let transition = authorization.transitions().into_values().next().unwrap();
+    assert_eq!((*transition.tpk() * *view_key).to_x_coordinate(), *signed_request.tvk());
+
+    // Ensure the evaluated and executed outputs match.
+    let expected = process.evaluate::<CurrentAleo>(authorization.replicate()).unwrap();
+    let (response, _trace) = process.execute::<CurrentAleo, _>(authorization, rng).unwrap();
+    assert_eq!(expected.outputs(), response.outputs());
+    assert_eq!(2, response.outputs().len());
+
+    // Ensure a function that calls another function cannot be authorized by a threshold account.
+    let inputs = [Value::from_str(&owner.to_string()).unwrap(), Value::from_str("1u64").unwrap()];
+    assert!(process.prepare_threshold_request(&account, program.id(), "pay", inputs.iter()).is_err());
+}
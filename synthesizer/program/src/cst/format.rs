@@ -0,0 +1,187 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// The indentation of statements inside a declaration.
+const INDENT: &str = "    ";
+
+/// A statement, comment, or blank line in the source.
+enum Element {
+    /// A statement, with any comments found inside it, and the comment trailing it on the same line.
+    Statement { words: Vec<String>, terminator: char, inner_comments: Vec<String>, trailing_comment: Option<String> },
+    /// A comment on its own line.
+    Comment(String),
+    /// One or more blank lines.
+    BlankLine,
+}
+
+/// A line of formatted output.
+enum Line {
+    /// A declaration header, i.e. `function foo:`, or the program statement.
+    Header(String),
+    /// A statement.
+    Statement(String),
+    /// A comment on its own line.
+    Comment(String),
+    /// A blank line.
+    Blank,
+}
+
+impl SyntaxTree {
+    /// Returns the source in its canonical layout, retaining comments.
+    ///
+    /// The canonical layout matches the `Display` implementation of `Program`:
+    /// declarations are separated by a blank line, statements inside a declaration are indented by four spaces,
+    /// and words are separated by a single space. The words themselves, and their order, are never changed.
+    pub fn format(&self) -> Result<String> {
+        let elements = self.elements()?;
+
+        // Determine whether each element is inside a declaration.
+        let mut lines = Vec::new();
+        let mut in_declaration = false;
+        for (index, element) in elements.iter().enumerate() {
+            match element {
+                Element::Statement { words, terminator, inner_comments, trailing_comment } => {
+                    let is_header = *terminator == ':';
+                    let is_program = words.first().map(|word| word.as_str()) == Some("program");
+                    in_declaration |= is_header;
+                    let indent = if in_declaration && !is_header { INDENT } else { "" };
+
+                    // Ensure headers (and the program statement) are preceded by a blank line,
+                    // before any comments attached to them.
+                    if is_header || is_program {
+                        let start = lines.len()
+                            - lines.iter().rev().take_while(|line| matches!(line, Line::Comment(_))).count();
+                        if start > 0 && !matches!(lines[start - 1], Line::Blank) {
+                            lines.insert(start, Line::Blank);
+                        }
+                    }
+                    // Write any comments from inside the statement before it.
+                    lines.extend(inner_comments.iter().map(|comment| Line::Comment(format!("{indent}{comment}"))));
+
+                    // Write the statement.
+                    let mut line = format!("{indent}{}{terminator}", words.join(" "));
+                    if let Some(comment) = trailing_comment {
+                        line.push_str(&format!(" {comment}"));
+                    }
+                    match is_header || is_program {
+                        true => lines.push(Line::Header(line)),
+                        false => lines.push(Line::Statement(line)),
+                    }
+                }
+                Element::Comment(comment) => {
+                    // A comment directly preceding a header is attached to it, and is not indented.
+                    let next = elements[index + 1..].iter().find(|element| !matches!(element, Element::Comment(_)));
+                    let is_attached = matches!(next, Some(Element::Statement { terminator: ':', .. }));
+                    let indent = if in_declaration && !is_attached { INDENT } else { "" };
+                    lines.push(Line::Comment(format!("{indent}{comment}")));
+                }
+                Element::BlankLine => {
+                    // Collapse blank lines, and remove them at the start of the source and after headers.
+                    if matches!(lines.last(), Some(Line::Statement(_)) | Some(Line::Comment(_))) {
+                        lines.push(Line::Blank);
+                    }
+                }
+            }
+        }
+        // Remove any trailing blank lines.
+        while matches!(lines.last(), Some(Line::Blank)) {
+            lines.pop();
+        }
+
+        // Print the lines.
+        let mut output = String::new();
+        for line in lines {
+            match line {
+                Line::Header(line) | Line::Statement(line) | Line::Comment(line) => output.push_str(line.trim_end()),
+                Line::Blank => (),
+            }
+            output.push('\n');
+        }
+        Ok(output)
+    }
+
+    /// Returns the statements, comments, and blank lines in the source.
+    fn elements(&self) -> Result<Vec<Element>> {
+        let mut elements = Vec::new();
+        // The words and comments of the current statement.
+        let mut words = Vec::new();
+        let mut inner_comments = Vec::new();
+        // The number of line breaks since the last statement or comment.
+        let mut newlines = 0;
+
+        for token in &self.tokens {
+            match token {
+                Token::Whitespace(_) => (),
+                Token::Newline(_) => {
+                    newlines += 1;
+                    if newlines == 2 && words.is_empty() {
+                        elements.push(Element::BlankLine);
+                    }
+                }
+                Token::LineComment(comment) | Token::BlockComment(comment) => {
+                    let comment = comment.trim_end().to_string();
+                    match elements.last_mut() {
+                        // A comment inside a statement.
+                        _ if !words.is_empty() => inner_comments.push(comment),
+                        // A comment on the same line as the preceding statement.
+                        Some(Element::Statement { trailing_comment, .. }) if newlines == 0 => match trailing_comment {
+                            Some(trailing_comment) => {
+                                trailing_comment.push(' ');
+                                trailing_comment.push_str(&comment);
+                            }
+                            None => *trailing_comment = Some(comment),
+                        },
+                        // A comment on its own line.
+                        _ => elements.push(Element::Comment(comment)),
+                    }
+                    newlines = 0;
+                }
+                Token::Word(word) => {
+                    words.push(normalize_word(word));
+                    newlines = 0;
+                }
+                Token::Terminator(terminator) => {
+                    ensure!(!words.is_empty(), "Found '{terminator}' without a preceding statement");
+                    elements.push(Element::Statement {
+                        words: std::mem::take(&mut words),
+                        terminator: *terminator,
+                        inner_comments: std::mem::take(&mut inner_comments),
+                        trailing_comment: None,
+                    });
+                    newlines = 0;
+                }
+            }
+        }
+        ensure!(words.is_empty(), "Found an unterminated statement: '{}'", words.join(" "));
+        Ok(elements)
+    }
+}
+
+/// Returns the word with the whitespace inside any brackets normalized, i.e. `[u8;4u32]` becomes `[u8; 4u32]`.
+fn normalize_word(word: &str) -> String {
+    // Words without brackets do not contain whitespace.
+    if !word.contains('[') || word.contains('"') {
+        return word.to_string();
+    }
+    let mut output = String::with_capacity(word.len());
+    for character in word.chars().filter(|character| !character.is_whitespace()) {
+        output.push(character);
+        if character == ';' {
+            output.push(' ');
+        }
+    }
+    output
+}
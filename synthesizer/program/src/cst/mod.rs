@@ -0,0 +1,190 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod format;
+mod tokenize;
+
+use crate::Program;
+use console::network::prelude::*;
+
+/// A token in Aleo instructions source code, including whitespace and comments.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Token {
+    /// A run of spaces and tabs.
+    Whitespace(String),
+    /// A line break, i.e. `\n` or `\r\n`.
+    Newline(String),
+    /// A line comment, i.e. `// comment`, excluding the line break.
+    LineComment(String),
+    /// A block comment, i.e. `/* comment */`.
+    BlockComment(String),
+    /// A word, such as an opcode, operand, type, or keyword.
+    Word(String),
+    /// A statement terminator, i.e. `;` or `:`.
+    Terminator(char),
+}
+
+impl Display for Token {
+    /// Prints the token exactly as it appeared in the source.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Whitespace(string)
+            | Self::Newline(string)
+            | Self::LineComment(string)
+            | Self::BlockComment(string)
+            | Self::Word(string) => write!(f, "{string}"),
+            Self::Terminator(terminator) => write!(f, "{terminator}"),
+        }
+    }
+}
+
+/// A lossless concrete syntax tree for Aleo instructions source code.
+///
+/// Unlike the `Program` parser, which discards whitespace and comments, the syntax tree retains every
+/// character of the source, so that printing it reproduces the source exactly. It is used to format
+/// programs without losing their comments.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SyntaxTree {
+    /// The tokens of the source, in order.
+    tokens: Vec<Token>,
+}
+
+impl SyntaxTree {
+    /// Returns the tokens of the syntax tree.
+    pub fn tokens(&self) -> &[Token] {
+        &self.tokens
+    }
+}
+
+impl FromStr for SyntaxTree {
+    type Err = Error;
+
+    /// Returns the syntax tree for the given source code.
+    fn from_str(source: &str) -> Result<Self> {
+        Ok(Self { tokens: tokenize::tokenize(source)? })
+    }
+}
+
+impl Display for SyntaxTree {
+    /// Prints the syntax tree exactly as the original source.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        self.tokens.iter().try_for_each(|token| Display::fmt(token, f))
+    }
+}
+
+impl<N: Network> Program<N> {
+    /// Returns the given program source in its canonical layout, retaining comments.
+    ///
+    /// # Errors
+    /// This method will halt if the source is not a valid program, or if the formatted source does not parse
+    /// to the same program.
+    pub fn format_source(source: &str) -> Result<String> {
+        // Ensure the source is a valid program.
+        let program = Self::from_str(source)?;
+        // Format the source.
+        let formatted = SyntaxTree::from_str(source)?.format()?;
+        // Ensure the formatted source parses to the same program.
+        ensure!(program == Self::from_str(&formatted)?, "Formatting changed the program '{}'", program.id());
+        Ok(formatted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use console::network::MainnetV0;
+
+    type CurrentNetwork = MainnetV0;
+
+    const SOURCE: &str = r"// A test program.
+import   credits.aleo;
+program   format.aleo;
+// The counters.
+mapping counter:
+  key as address.public;
+      value as u64.public;
+struct message:
+    first as [u8;  4u32];
+    second as field; // The second member.
+
+
+
+function compute :
+    input r0 as message.private;
+    /* Add the members. */
+    add 1field   r0.second into r1;
+    output r1 as field.private;
+finalize compute:
+    input r0 as u64.public;
+";
+
+    const EXPECTED: &str = r"// A test program.
+import credits.aleo;
+
+program format.aleo;
+
+// The counters.
+mapping counter:
+    key as address.public;
+    value as u64.public;
+
+struct message:
+    first as [u8; 4u32];
+    second as field; // The second member.
+
+function compute:
+    input r0 as message.private;
+    /* Add the members. */
+    add 1field r0.second into r1;
+    output r1 as field.private;
+
+finalize compute:
+    input r0 as u64.public;
+";
+
+    #[test]
+    fn test_syntax_tree_is_lossless() -> Result<()> {
+        for source in [SOURCE, EXPECTED, include_str!("../resources/credits.aleo")] {
+            assert_eq!(source, SyntaxTree::from_str(source)?.to_string());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_format() -> Result<()> {
+        let formatted = SyntaxTree::from_str(SOURCE)?.format()?;
+        assert_eq!(EXPECTED, formatted);
+        // Ensure formatting is idempotent.
+        assert_eq!(EXPECTED, SyntaxTree::from_str(&formatted)?.format()?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_matches_display() -> Result<()> {
+        // The canonical layout of a program without comments matches its `Display` implementation,
+        // except that `Display` prints a space before the terminator of `assert` instructions.
+        let program = Program::<CurrentNetwork>::credits()?;
+        let formatted = Program::<CurrentNetwork>::format_source(&program.to_string())?;
+        assert_eq!(program.to_string().replace(" ;\n", ";\n"), formatted);
+        assert_eq!(program, Program::from_str(&formatted)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_format_rejects_invalid_source() {
+        assert!(SyntaxTree::from_str("program foo.aleo; /* unterminated").is_err());
+        assert!(SyntaxTree::from_str("program foo.aleo").unwrap().format().is_err());
+        assert!(Program::<CurrentNetwork>::format_source("program foo.aleo;").is_err());
+    }
+}
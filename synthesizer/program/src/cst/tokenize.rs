@@ -0,0 +1,116 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// Splits the given source into tokens, such that concatenating the tokens reproduces the source.
+pub(super) fn tokenize(source: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut rest = source;
+
+    while let Some(character) = rest.chars().next() {
+        let (token, length) = match character {
+            ' ' | '\t' => {
+                let length = rest.find(|c: char| c != ' ' && c != '\t').unwrap_or(rest.len());
+                (Token::Whitespace(rest[..length].to_string()), length)
+            }
+            '\n' => (Token::Newline("\n".to_string()), 1),
+            '\r' if rest.starts_with("\r\n") => (Token::Newline("\r\n".to_string()), 2),
+            '\r' => (Token::Whitespace("\r".to_string()), 1),
+            ';' | ':' => (Token::Terminator(character), 1),
+            '/' if rest.starts_with("//") => {
+                let length = rest.find(['\r', '\n']).unwrap_or(rest.len());
+                (Token::LineComment(rest[..length].to_string()), length)
+            }
+            '/' if rest.starts_with("/*") => match rest[2..].find("*/") {
+                Some(end) => (Token::BlockComment(rest[..end + 4].to_string()), end + 4),
+                None => bail!("Found an unterminated block comment"),
+            },
+            _ => {
+                let length = word_length(rest)?;
+                (Token::Word(rest[..length].to_string()), length)
+            }
+        };
+        tokens.push(token);
+        rest = &rest[length..];
+    }
+    Ok(tokens)
+}
+
+/// Returns the length of the word at the start of the given string.
+///
+/// A word ends at whitespace, a terminator, or a comment, unless it is inside brackets
+/// (i.e. `[u8; 4u32]`) or a string literal, which may contain whitespace and terminators.
+fn word_length(string: &str) -> Result<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+
+    for (index, character) in string.char_indices() {
+        if in_string {
+            match character {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => (),
+            }
+            continue;
+        }
+        match character {
+            '"' => in_string = true,
+            '[' => depth += 1,
+            ']' => depth = depth.checked_sub(1).ok_or_else(|| anyhow!("Found an unmatched ']'"))?,
+            ' ' | '\t' | '\r' | '\n' | ';' | ':' if depth == 0 => return Ok(index),
+            '/' if depth == 0 && (string[index..].starts_with("//") || string[index..].starts_with("/*")) => {
+                return Ok(index);
+            }
+            _ => (),
+        }
+    }
+    ensure!(!in_string, "Found an unterminated string literal");
+    ensure!(depth == 0, "Found an unmatched '['");
+    Ok(string.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() -> Result<()> {
+        let tokens = tokenize("input r0 as [u8; 2u32].private; // comment\r\n")?;
+        assert_eq!(tokens, vec![
+            Token::Word("input".to_string()),
+            Token::Whitespace(" ".to_string()),
+            Token::Word("r0".to_string()),
+            Token::Whitespace(" ".to_string()),
+            Token::Word("as".to_string()),
+            Token::Whitespace(" ".to_string()),
+            Token::Word("[u8; 2u32].private".to_string()),
+            Token::Terminator(';'),
+            Token::Whitespace(" ".to_string()),
+            Token::LineComment("// comment".to_string()),
+            Token::Newline("\r\n".to_string()),
+        ]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_tokenize_rejects_unterminated() {
+        assert!(tokenize("/* comment").is_err());
+        assert!(tokenize("input r0 as [u8; 2u32.private;").is_err());
+        assert!(tokenize("input r0 as u8].private;").is_err());
+        assert!(tokenize("\"string").is_err());
+    }
+}
@@ -24,6 +24,9 @@ pub type Closure<N> = crate::ClosureCore<N, Instruction<N>>;
 mod closure;
 pub use closure::*;
 
+mod cst;
+pub use cst::*;
+
 pub mod finalize;
 pub use finalize::*;
 
@@ -33,6 +36,9 @@ pub use function::*;
 mod import;
 pub use import::*;
 
+mod lint;
+pub use lint::*;
+
 pub mod logic;
 pub use logic::*;
 
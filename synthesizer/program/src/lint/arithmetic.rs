@@ -0,0 +1,83 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{CallOperator, Opcode};
+
+use std::collections::HashSet;
+
+/// Flags every `cast.lossy` instruction, as it silently truncates values that do not fit the target type.
+pub(super) fn check_lossy_casts<N: Network>(program: &Program<N>, warnings: &mut Vec<LintWarning<N>>) {
+    for block in blocks(program) {
+        for (index, statement) in block.statements.iter().enumerate() {
+            if let Some(Instruction::CastLossy(cast)) = statement.instruction() {
+                let message = format!("'{cast}' silently truncates values that do not fit in the target type");
+                warnings.push(LintWarning::new(LintKind::LossyCast, block.scope.clone(), Some(index), message));
+            }
+        }
+    }
+}
+
+/// Flags wrapping arithmetic (i.e. `add.w`, `sub.w`) on values derived from balance-like record entries,
+/// struct members, or mappings, as an overflow on a balance silently mints or burns value.
+pub(super) fn check_wrapped_arithmetic<N: Network>(program: &Program<N>, warnings: &mut Vec<LintWarning<N>>) {
+    for block in blocks(program) {
+        // The locators of the registers holding balance-like values.
+        let mut balances = HashSet::new();
+
+        for (index, statement) in block.statements.iter().enumerate() {
+            // Determine if the statement reads a balance-like value.
+            let reads_balance = statement.operands().iter().any(|operand| match operand_register(operand) {
+                Some(register) => {
+                    balances.contains(&register.locator())
+                        || first_member(register).map_or(false, |member| is_balance_like(&member.to_string()))
+                }
+                None => false,
+            });
+            // Determine if the statement loads a balance-like value from a mapping.
+            let loads_balance = match statement {
+                Statement::Command(Command::Get(get)) => is_balance_mapping(get.mapping()),
+                Statement::Command(Command::GetOrUse(get_or_use)) => is_balance_mapping(get_or_use.mapping()),
+                _ => false,
+            };
+
+            if reads_balance {
+                // Report the instruction, if it wraps around.
+                if let Some(instruction) = statement.instruction() {
+                    if matches!(instruction.opcode(), Opcode::Literal(opcode) if opcode.ends_with(".w")) {
+                        let message = format!("'{instruction}' may silently wrap around on a balance");
+                        warnings.push(LintWarning::new(
+                            LintKind::WrappedArithmetic,
+                            block.scope.clone(),
+                            Some(index),
+                            message,
+                        ));
+                    }
+                }
+            }
+            // Propagate the balance to the destinations.
+            if reads_balance || loads_balance {
+                balances.extend(statement.destinations().iter().map(|register| register.locator()));
+            }
+        }
+    }
+}
+
+/// Returns `true` if the given mapping operator refers to a balance-like mapping.
+fn is_balance_mapping<N: Network>(mapping: &CallOperator<N>) -> bool {
+    match mapping {
+        CallOperator::Locator(locator) => is_balance_like(&locator.resource().to_string()),
+        CallOperator::Resource(name) => is_balance_like(&name.to_string()),
+    }
+}
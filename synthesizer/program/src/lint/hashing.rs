@@ -0,0 +1,74 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::Opcode;
+
+use std::collections::{HashMap, HashSet};
+
+/// Flags assertions that follow an expensive hash or commitment they do not depend on.
+/// Such assertions can be moved before the hash, so that invalid inputs fail without paying for it.
+pub(super) fn check_assert_after_hash<N: Network>(program: &Program<N>, warnings: &mut Vec<LintWarning<N>>) {
+    // Note: Finalize blocks are not proven, so the ordering of hashes in finalize is not a concern.
+    for block in blocks(program).into_iter().filter(|block| !matches!(block.scope, LintScope::Finalize(_))) {
+        // Map each register locator to the index of the statement that assigns it.
+        let mut producers = HashMap::new();
+        for (index, statement) in block.statements.iter().enumerate() {
+            producers.extend(statement.destinations().iter().map(|register| (register.locator(), index)));
+        }
+
+        for (index, statement) in block.statements.iter().enumerate() {
+            // Ensure the statement is an assertion.
+            let is_assert =
+                statement.instruction().map_or(false, |instruction| matches!(instruction.opcode(), Opcode::Assert(_)));
+            if !is_assert {
+                continue;
+            }
+            // Collect the statements the assertion (transitively) depends on.
+            let dependencies = dependencies(&block.statements, &producers, index);
+            // Find the first expensive statement preceding the assertion, that it does not depend on.
+            let hash = block.statements[..index].iter().enumerate().find(|(hash_index, statement)| {
+                !dependencies.contains(hash_index)
+                    && statement.instruction().map_or(false, |instruction| {
+                        matches!(instruction.opcode(), Opcode::Hash(_) | Opcode::Commit(_))
+                    })
+            });
+            if let Some((hash_index, hash)) = hash {
+                let message =
+                    format!("'{statement}' does not depend on '{hash}' (#{hash_index}) and can be moved before it");
+                warnings.push(LintWarning::new(LintKind::AssertAfterHash, block.scope.clone(), Some(index), message));
+            }
+        }
+    }
+}
+
+/// Returns the indices of the statements that the statement at the given index (transitively) depends on.
+fn dependencies<N: Network>(
+    statements: &[Statement<N>],
+    producers: &HashMap<u64, usize>,
+    index: usize,
+) -> HashSet<usize> {
+    let mut dependencies = HashSet::new();
+    let mut stack = vec![index];
+    while let Some(index) = stack.pop() {
+        for operand in statements[index].operands() {
+            if let Some(producer) = operand_register(&operand).and_then(|register| producers.get(&register.locator())) {
+                if dependencies.insert(*producer) {
+                    stack.push(*producer);
+                }
+            }
+        }
+    }
+    dependencies
+}
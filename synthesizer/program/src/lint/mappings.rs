@@ -0,0 +1,66 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::CallOperator;
+
+use std::collections::HashSet;
+
+/// Flags `set` and `remove` commands on a mapping that is not read earlier in the same finalize block.
+/// Blind writes overwrite state that concurrent transactions may depend on, and are often a missing check.
+pub(super) fn check_writes_without_reads<N: Network>(program: &Program<N>, warnings: &mut Vec<LintWarning<N>>) {
+    for block in blocks(program).into_iter().filter(|block| matches!(block.scope, LintScope::Finalize(_))) {
+        // The names of the local mappings that have been read.
+        let mut reads = HashSet::new();
+
+        for (index, statement) in block.statements.iter().enumerate() {
+            let Statement::Command(command) = statement else {
+                continue;
+            };
+            match command {
+                Command::Contains(contains) => reads.extend(local_mapping(contains.mapping())),
+                Command::Get(get) => reads.extend(local_mapping(get.mapping())),
+                Command::GetOrUse(get_or_use) => reads.extend(local_mapping(get_or_use.mapping())),
+                Command::Set(set) if !reads.contains(set.mapping_name()) => {
+                    let message = format!("'{command}' writes to '{}' without reading it first", set.mapping_name());
+                    warnings.push(LintWarning::new(
+                        LintKind::WriteWithoutRead,
+                        block.scope.clone(),
+                        Some(index),
+                        message,
+                    ));
+                }
+                Command::Remove(remove) if !reads.contains(remove.mapping_name()) => {
+                    let message =
+                        format!("'{command}' removes from '{}' without reading it first", remove.mapping_name());
+                    warnings.push(LintWarning::new(
+                        LintKind::WriteWithoutRead,
+                        block.scope.clone(),
+                        Some(index),
+                        message,
+                    ));
+                }
+                _ => continue,
+            }
+        }
+    }
+}
+
+/// Returns the name of the mapping, if the operator refers to a mapping in this program.
+fn local_mapping<N: Network>(mapping: &CallOperator<N>) -> Option<Identifier<N>> {
+    match mapping {
+        CallOperator::Resource(name) => Some(*name),
+        CallOperator::Locator(_) => None,
+    }
+}
@@ -0,0 +1,457 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+mod arithmetic;
+mod hashing;
+mod mappings;
+mod records;
+mod registers;
+
+use crate::{
+    traits::{CommandTrait, InstructionTrait},
+    Command,
+    Instruction,
+    Operand,
+    Program,
+};
+use console::{
+    network::prelude::*,
+    program::{Access, Identifier, Register},
+};
+
+/// The category of a lint warning.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LintKind {
+    /// A register is assigned (or declared as an input) but never read.
+    UnusedRegister,
+    /// A `cast.lossy` instruction may silently truncate its operand.
+    LossyCast,
+    /// A wrapping arithmetic instruction operates on a balance-like value.
+    WrappedArithmetic,
+    /// A record type is produced by the program, but no function in the program consumes it.
+    UnconsumedRecord,
+    /// An assertion is performed after an expensive hash that it does not depend on.
+    AssertAfterHash,
+    /// A public output or finalize argument is derived from a private record entry.
+    PrivateRecordLeak,
+    /// A mapping is written in finalize without first being read.
+    WriteWithoutRead,
+}
+
+impl LintKind {
+    /// Returns the name of the lint, i.e. `unused-register`.
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Self::UnusedRegister => "unused-register",
+            Self::LossyCast => "lossy-cast",
+            Self::WrappedArithmetic => "wrapped-arithmetic",
+            Self::UnconsumedRecord => "unconsumed-record",
+            Self::AssertAfterHash => "assert-after-hash",
+            Self::PrivateRecordLeak => "private-record-leak",
+            Self::WriteWithoutRead => "write-without-read",
+        }
+    }
+}
+
+impl Display for LintKind {
+    /// Prints the lint kind as a string.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// The scope in which a lint warning was raised.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LintScope<N: Network> {
+    /// The program as a whole.
+    Program,
+    /// The closure with the given name.
+    Closure(Identifier<N>),
+    /// The function with the given name.
+    Function(Identifier<N>),
+    /// The finalize block of the function with the given name.
+    Finalize(Identifier<N>),
+}
+
+impl<N: Network> Display for LintScope<N> {
+    /// Prints the lint scope as a string.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Program => write!(f, "program"),
+            Self::Closure(name) => write!(f, "closure '{name}'"),
+            Self::Function(name) => write!(f, "function '{name}'"),
+            Self::Finalize(name) => write!(f, "finalize '{name}'"),
+        }
+    }
+}
+
+/// A warning raised by the linter for a legal, but potentially dangerous, pattern in a program.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct LintWarning<N: Network> {
+    /// The category of the warning.
+    kind: LintKind,
+    /// The scope in which the warning was raised.
+    scope: LintScope<N>,
+    /// The index of the offending instruction or command, if applicable.
+    index: Option<usize>,
+    /// The human-readable description of the warning.
+    message: String,
+}
+
+impl<N: Network> LintWarning<N> {
+    /// Initializes a new lint warning.
+    pub fn new(kind: LintKind, scope: LintScope<N>, index: Option<usize>, message: String) -> Self {
+        Self { kind, scope, index, message }
+    }
+
+    /// Returns the category of the warning.
+    pub const fn kind(&self) -> LintKind {
+        self.kind
+    }
+
+    /// Returns the scope in which the warning was raised.
+    pub const fn scope(&self) -> &LintScope<N> {
+        &self.scope
+    }
+
+    /// Returns the index of the offending instruction or command, if applicable.
+    pub const fn index(&self) -> Option<usize> {
+        self.index
+    }
+
+    /// Returns the description of the warning.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl<N: Network> Display for LintWarning<N> {
+    /// Prints the lint warning as a string, i.e. `warning[lossy-cast]: function 'foo' (#2): ...`.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.index {
+            Some(index) => write!(f, "warning[{}]: {} (#{index}): {}", self.kind, self.scope, self.message),
+            None => write!(f, "warning[{}]: {}: {}", self.kind, self.scope, self.message),
+        }
+    }
+}
+
+impl<N: Network> Program<N> {
+    /// Returns the lint warnings for the program.
+    ///
+    /// Unlike the checks performed when a program is parsed or added to a stack, the linter
+    /// does not reject a program. It flags legal patterns that are likely to be mistakes.
+    pub fn lint(&self) -> Vec<LintWarning<N>> {
+        let mut warnings = Vec::new();
+        registers::check_unused_registers(self, &mut warnings);
+        arithmetic::check_lossy_casts(self, &mut warnings);
+        arithmetic::check_wrapped_arithmetic(self, &mut warnings);
+        records::check_unconsumed_records(self, &mut warnings);
+        hashing::check_assert_after_hash(self, &mut warnings);
+        records::check_private_record_leaks(self, &mut warnings);
+        mappings::check_writes_without_reads(self, &mut warnings);
+        warnings
+    }
+}
+
+/// A statement in a closure, function, or finalize block.
+#[derive(Copy, Clone)]
+enum Statement<'a, N: Network> {
+    /// An instruction in a closure or function.
+    Instruction(&'a Instruction<N>),
+    /// A command in a finalize block.
+    Command(&'a Command<N>),
+}
+
+impl<'a, N: Network> Statement<'a, N> {
+    /// Returns the instruction, if the statement is (or wraps) an instruction.
+    fn instruction(&self) -> Option<&'a Instruction<N>> {
+        match self {
+            Self::Instruction(instruction) => Some(instruction),
+            Self::Command(Command::Instruction(instruction)) => Some(instruction),
+            Self::Command(_) => None,
+        }
+    }
+
+    /// Returns the operands of the statement.
+    fn operands(&self) -> Vec<Operand<N>> {
+        match self {
+            Self::Instruction(instruction) => instruction.operands().to_vec(),
+            Self::Command(command) => command.operands(),
+        }
+    }
+
+    /// Returns the destination registers of the statement.
+    fn destinations(&self) -> Vec<Register<N>> {
+        match self {
+            Self::Instruction(instruction) => instruction.destinations(),
+            Self::Command(command) => command.destinations(),
+        }
+    }
+}
+
+impl<'a, N: Network> Display for Statement<'a, N> {
+    /// Prints the statement as a string.
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Instruction(instruction) => Display::fmt(instruction, f),
+            Self::Command(command) => Display::fmt(command, f),
+        }
+    }
+}
+
+/// A closure, function, or finalize block, flattened for analysis.
+struct Block<'a, N: Network> {
+    /// The scope of the block.
+    scope: LintScope<N>,
+    /// The input registers of the block.
+    inputs: Vec<&'a Register<N>>,
+    /// The statements of the block, in order of execution.
+    statements: Vec<Statement<'a, N>>,
+    /// The output operands of the block.
+    outputs: Vec<&'a Operand<N>>,
+}
+
+/// Returns every closure, function, and finalize block in the program.
+fn blocks<N: Network>(program: &Program<N>) -> Vec<Block<N>> {
+    let mut blocks = Vec::new();
+    for closure in program.closures().values() {
+        blocks.push(Block {
+            scope: LintScope::Closure(*closure.name()),
+            inputs: closure.inputs().iter().map(|input| input.register()).collect(),
+            statements: closure.instructions().iter().map(Statement::Instruction).collect(),
+            outputs: closure.outputs().iter().map(|output| output.operand()).collect(),
+        });
+    }
+    for function in program.functions().values() {
+        blocks.push(Block {
+            scope: LintScope::Function(*function.name()),
+            inputs: function.inputs().iter().map(|input| input.register()).collect(),
+            statements: function.instructions().iter().map(Statement::Instruction).collect(),
+            outputs: function.outputs().iter().map(|output| output.operand()).collect(),
+        });
+        if let Some(finalize) = function.finalize_logic() {
+            blocks.push(Block {
+                scope: LintScope::Finalize(*finalize.name()),
+                inputs: finalize.inputs().iter().map(|input| input.register()).collect(),
+                statements: finalize.commands().iter().map(Statement::Command).collect(),
+                outputs: Vec::new(),
+            });
+        }
+    }
+    blocks
+}
+
+/// Returns the register read by the given operand, if any.
+fn operand_register<N: Network>(operand: &Operand<N>) -> Option<&Register<N>> {
+    match operand {
+        Operand::Register(register) => Some(register),
+        _ => None,
+    }
+}
+
+/// Returns the first member accessed by the given register, if any.
+fn first_member<N: Network>(register: &Register<N>) -> Option<&Identifier<N>> {
+    match register {
+        Register::Access(_, accesses) => match accesses.first() {
+            Some(Access::Member(member)) => Some(member),
+            _ => None,
+        },
+        Register::Locator(_) => None,
+    }
+}
+
+/// Returns `true` if the given name suggests that it holds a balance.
+fn is_balance_like(name: &str) -> bool {
+    const KEYWORDS: &[&str] = &["account", "amount", "balance", "credits", "supply"];
+    let name = name.to_lowercase();
+    KEYWORDS.iter().any(|keyword| name.contains(keyword))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use console::network::MainnetV0;
+
+    type CurrentNetwork = MainnetV0;
+
+    /// Returns the lint kinds raised for the given program.
+    fn lint(program: &str) -> Vec<LintKind> {
+        let program = Program::<CurrentNetwork>::from_str(program).unwrap();
+        program.lint().into_iter().map(|warning| warning.kind()).collect()
+    }
+
+    #[test]
+    fn test_lint_clean_program() {
+        let kinds = lint(
+            r"
+program clean.aleo;
+
+function compute:
+    input r0 as u64.private;
+    input r1 as u64.private;
+    add r0 r1 into r2;
+    output r2 as u64.private;",
+        );
+        assert!(kinds.is_empty(), "{kinds:?}");
+    }
+
+    #[test]
+    fn test_lint_credits_program() {
+        // The only warning in `credits.aleo` is the unused committee state, read in `unbond_public`.
+        let program = Program::<CurrentNetwork>::credits().unwrap();
+        let expected = LintWarning::new(
+            LintKind::UnusedRegister,
+            LintScope::Finalize(Identifier::from_str("unbond_public").unwrap()),
+            Some(40),
+            "register 'r29' is assigned but never used".to_string(),
+        );
+        assert_eq!(program.lint(), vec![expected]);
+    }
+
+    #[test]
+    fn test_lint_unused_register() {
+        let kinds = lint(
+            r"
+program unused.aleo;
+
+function compute:
+    input r0 as u64.private;
+    input r1 as u64.private;
+    add r0 r0 into r2;
+    output r2 as u64.private;",
+        );
+        assert_eq!(kinds, vec![LintKind::UnusedRegister]);
+    }
+
+    #[test]
+    fn test_lint_lossy_cast() {
+        let kinds = lint(
+            r"
+program lossy.aleo;
+
+function compute:
+    input r0 as u64.private;
+    cast.lossy r0 into r1 as u8;
+    output r1 as u8.private;",
+        );
+        assert_eq!(kinds, vec![LintKind::LossyCast]);
+    }
+
+    #[test]
+    fn test_lint_wrapped_arithmetic() {
+        let kinds = lint(
+            r"
+program wrapped.aleo;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+function spend:
+    input r0 as token.record;
+    input r1 as u64.private;
+    sub.w r0.amount r1 into r2;
+    cast r0.owner r2 into r3 as token.record;
+    output r3 as token.record;",
+        );
+        assert_eq!(kinds, vec![LintKind::WrappedArithmetic]);
+    }
+
+    #[test]
+    fn test_lint_unconsumed_record() {
+        let kinds = lint(
+            r"
+program unconsumed.aleo;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+function mint:
+    input r0 as u64.private;
+    cast self.caller r0 into r1 as token.record;
+    output r1 as token.record;",
+        );
+        assert_eq!(kinds, vec![LintKind::UnconsumedRecord]);
+    }
+
+    #[test]
+    fn test_lint_assert_after_hash() {
+        let kinds = lint(
+            r"
+program hashing.aleo;
+
+function compute:
+    input r0 as field.private;
+    input r1 as field.private;
+    hash.bhp256 r0 into r2 as field;
+    assert.neq r1 0field;
+    output r2 as field.private;",
+        );
+        assert_eq!(kinds, vec![LintKind::AssertAfterHash]);
+    }
+
+    #[test]
+    fn test_lint_private_record_leak() {
+        let kinds = lint(
+            r"
+program leak.aleo;
+
+record token:
+    owner as address.private;
+    amount as u64.private;
+
+function reveal:
+    input r0 as token.record;
+    cast r0.owner r0.amount into r1 as token.record;
+    output r1 as token.record;
+    output r0.amount as u64.public;",
+        );
+        assert_eq!(kinds, vec![LintKind::PrivateRecordLeak]);
+    }
+
+    #[test]
+    fn test_lint_write_without_read() {
+        let kinds = lint(
+            r"
+program writes.aleo;
+
+mapping counter:
+    key as address.public;
+    value as u64.public;
+
+function bump:
+    input r0 as u64.public;
+    async bump self.caller r0 into r1;
+    output r1 as writes.aleo/bump.future;
+
+finalize bump:
+    input r0 as address.public;
+    input r1 as u64.public;
+    set r1 into counter[r0];",
+        );
+        assert_eq!(kinds, vec![LintKind::WriteWithoutRead]);
+    }
+
+    #[test]
+    fn test_lint_warning_display() {
+        let warning = LintWarning::<CurrentNetwork>::new(
+            LintKind::LossyCast,
+            LintScope::Function(Identifier::from_str("foo").unwrap()),
+            Some(2),
+            "message".to_string(),
+        );
+        assert_eq!(warning.to_string(), "warning[lossy-cast]: function 'foo' (#2): message");
+    }
+}
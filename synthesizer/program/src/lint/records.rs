@@ -0,0 +1,121 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use console::program::{EntryType, ValueType};
+
+use std::collections::{HashMap, HashSet};
+
+/// Flags record types that are output by a function in the program, but never taken as input by any of them.
+/// As only the program that declares a record can consume it, such records can never be spent.
+pub(super) fn check_unconsumed_records<N: Network>(program: &Program<N>, warnings: &mut Vec<LintWarning<N>>) {
+    for name in program.records().keys() {
+        let record_type = ValueType::Record(*name);
+        // Determine if any function outputs the record.
+        let is_produced = program
+            .functions()
+            .values()
+            .any(|function| function.outputs().iter().any(|output| *output.value_type() == record_type));
+        // Determine if any function consumes the record.
+        let is_consumed = program
+            .functions()
+            .values()
+            .any(|function| function.inputs().iter().any(|input| *input.value_type() == record_type));
+
+        if is_produced && !is_consumed {
+            let message = format!("record '{name}' is produced, but no function in the program consumes it");
+            warnings.push(LintWarning::new(LintKind::UnconsumedRecord, LintScope::Program, None, message));
+        }
+    }
+}
+
+/// Flags public (or constant) outputs and finalize arguments that are derived from private record entries.
+pub(super) fn check_private_record_leaks<N: Network>(program: &Program<N>, warnings: &mut Vec<LintWarning<N>>) {
+    for function in program.functions().values() {
+        let scope = LintScope::Function(*function.name());
+
+        // Map the locators of the record inputs to their record types.
+        let records = function
+            .inputs()
+            .iter()
+            .filter_map(|input| match input.value_type() {
+                ValueType::Record(name) => {
+                    program.get_record(name).ok().map(|record| (input.register().locator(), record))
+                }
+                _ => None,
+            })
+            .collect::<HashMap<_, _>>();
+        // The locators of the registers derived from private record entries.
+        let mut private = HashSet::<u64>::new();
+
+        // Returns `true` if the operand holds a value derived from a private record entry.
+        let is_private = |private: &HashSet<u64>, operand: &Operand<N>| {
+            let Some(register) = operand_register(operand) else {
+                return false;
+            };
+            if private.contains(&register.locator()) {
+                return true;
+            }
+            match (records.get(&register.locator()), first_member(register)) {
+                (Some(record), Some(member)) if member.to_string() == "owner" => record.owner().is_private(),
+                (Some(record), Some(member)) => matches!(record.entries().get(member), Some(EntryType::Private(_))),
+                _ => false,
+            }
+        };
+
+        for (index, instruction) in function.instructions().iter().enumerate() {
+            match instruction {
+                // Arguments to finalize are public.
+                Instruction::Async(async_) => {
+                    for operand in async_.operands().iter().filter(|operand| is_private(&private, operand)) {
+                        let message = format!(
+                            "'{operand}' is derived from a private record entry, but is passed to finalize publicly"
+                        );
+                        warnings.push(LintWarning::new(
+                            LintKind::PrivateRecordLeak,
+                            scope.clone(),
+                            Some(index),
+                            message,
+                        ));
+                    }
+                }
+                // Commitments are hiding, so they do not leak their inputs.
+                Instruction::CommitBHP256(_)
+                | Instruction::CommitBHP512(_)
+                | Instruction::CommitBHP768(_)
+                | Instruction::CommitBHP1024(_)
+                | Instruction::CommitPED64(_)
+                | Instruction::CommitPED128(_) => continue,
+                // Propagate the private values to the destinations.
+                instruction => {
+                    if instruction.operands().iter().any(|operand| is_private(&private, operand)) {
+                        private.extend(instruction.destinations().iter().map(|register| register.locator()));
+                    }
+                }
+            }
+        }
+
+        for output in function.outputs() {
+            let is_public = matches!(output.value_type(), ValueType::Public(_) | ValueType::Constant(_));
+            if is_public && is_private(&private, output.operand()) {
+                let message = format!(
+                    "output '{}' is derived from a private record entry, but is declared as '{}'",
+                    output.operand(),
+                    output.value_type()
+                );
+                warnings.push(LintWarning::new(LintKind::PrivateRecordLeak, scope.clone(), None, message));
+            }
+        }
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+use std::collections::HashSet;
+
+/// Flags registers that are declared as inputs or assigned by a statement, but never read.
+pub(super) fn check_unused_registers<N: Network>(program: &Program<N>, warnings: &mut Vec<LintWarning<N>>) {
+    for block in blocks(program) {
+        // Collect the locators of the registers that are read.
+        let reads = block
+            .statements
+            .iter()
+            .flat_map(|statement| statement.operands())
+            .chain(block.outputs.iter().map(|operand| (*operand).clone()))
+            .filter_map(|operand| operand_register(&operand).map(|register| register.locator()))
+            .collect::<HashSet<_>>();
+
+        // Report the unused inputs.
+        for register in block.inputs.iter().filter(|register| !reads.contains(&register.locator())) {
+            let message = format!("input register '{register}' is never used");
+            warnings.push(LintWarning::new(LintKind::UnusedRegister, block.scope.clone(), None, message));
+        }
+        // Report the unused destinations.
+        for (index, statement) in block.statements.iter().enumerate() {
+            for register in statement.destinations().iter().filter(|register| !reads.contains(&register.locator())) {
+                let message = format!("register '{register}' is assigned but never used");
+                warnings.push(LintWarning::new(LintKind::UnusedRegister, block.scope.clone(), Some(index), message));
+            }
+        }
+    }
+}
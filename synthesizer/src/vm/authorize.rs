@@ -115,6 +115,32 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         finish!(timer, "Compute the authorization");
         result
     }
+
+    /// Returns an offline execution of a call to the program function for the given inputs, and its fee,
+    /// to be signed on an offline machine with `Process::sign_offline_execution`.
+    /// If a fee record is given, the fee is private, otherwise it is public.
+    ///
+    /// This only requires the address of the signer, so it can be called on a watch-only machine.
+    pub fn prepare_offline_execution(
+        &self,
+        signer: Address<N>,
+        program_id: impl TryInto<ProgramID<N>>,
+        function_name: impl TryInto<Identifier<N>>,
+        inputs: impl IntoIterator<IntoIter = impl ExactSizeIterator<Item = impl TryInto<Value<N>>>>,
+        base_fee_in_microcredits: u64,
+        priority_fee_in_microcredits: u64,
+        fee_record: Option<Record<N, Plaintext<N>>>,
+    ) -> Result<OfflineExecution<N>> {
+        self.process.read().prepare_offline_execution(
+            signer,
+            program_id,
+            function_name,
+            inputs.into_iter(),
+            base_fee_in_microcredits,
+            priority_fee_in_microcredits,
+            fee_record,
+        )
+    }
 }
 
 impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
@@ -0,0 +1,194 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
+    /// Returns a disclosure that the given record is owned by the private key, and that its entry satisfies the predicate.
+    /// The record is proven to be committed at the latest global state root.
+    ///
+    /// If `reveal_serial_number` is set, the disclosure includes the serial number of the record,
+    /// so that the verifier can check the record is unspent.
+    /// Warning: A revealed serial number links the disclosure to the transition that spends the record.
+    pub fn prove_record_disclosure<R: Rng + CryptoRng>(
+        &self,
+        private_key: &PrivateKey<N>,
+        program_id: impl TryInto<ProgramID<N>>,
+        record_name: impl TryInto<Identifier<N>>,
+        record: &Record<N, Plaintext<N>>,
+        entry: impl TryInto<Identifier<N>>,
+        predicate: DisclosurePredicate,
+        reveal_serial_number: bool,
+        rng: &mut R,
+    ) -> Result<RecordDisclosure<N>> {
+        let timer = timer!("VM::prove_record_disclosure");
+
+        // Prepare the program ID, record name, and entry.
+        let program_id = program_id.try_into().map_err(|_| anyhow!("Invalid program ID"))?;
+        let record_name = record_name.try_into().map_err(|_| anyhow!("Invalid record name"))?;
+        let entry = entry.try_into().map_err(|_| anyhow!("Invalid entry name"))?;
+        // Retrieve the state path of the record.
+        let commitment = record.to_commitment(&program_id, &record_name)?;
+        let state_path = self.block_store().get_state_path_for_commitment(&commitment)?;
+        lap!(timer, "Retrieve the state path");
+
+        macro_rules! logic {
+            ($process:expr, $network:path, $aleo:path) => {{
+                // Prove the disclosure.
+                let disclosure = $process.prove_record_disclosure::<$aleo, _>(
+                    cast_ref!(&private_key as PrivateKey<$network>),
+                    cast_ref!(program_id as ProgramID<$network>),
+                    cast_ref!(record_name as Identifier<$network>),
+                    cast_ref!(&record as Record<$network, Plaintext<$network>>),
+                    cast_ref!(state_path as StatePath<$network>).clone(),
+                    cast_ref!(entry as Identifier<$network>),
+                    predicate,
+                    reveal_serial_number,
+                    rng,
+                )?;
+                // Prepare the disclosure.
+                Ok(cast_ref!(disclosure as RecordDisclosure<N>).clone())
+            }};
+        }
+
+        // Prove the disclosure.
+        let result = process!(self, logic);
+        finish!(timer, "Prove the disclosure");
+        result
+    }
+
+    /// Verifies the given record disclosure against the ledger. On failure, returns an error.
+    ///
+    /// In addition to the proof, this checks that the global state root exists,
+    /// and that the record is unspent, if its serial number is revealed.
+    pub fn verify_record_disclosure(&self, disclosure: &RecordDisclosure<N>) -> Result<()> {
+        // Ensure the global state root exists.
+        ensure!(
+            self.block_store().contains_state_root(&disclosure.global_state_root())?,
+            "The disclosed global state root '{}' does not exist",
+            disclosure.global_state_root()
+        );
+        // Ensure the record is unspent, if its serial number is revealed.
+        if let Some(serial_number) = disclosure.serial_number() {
+            ensure!(
+                !self.transition_store().contains_serial_number(serial_number)?,
+                "The disclosed record is already spent"
+            );
+        }
+        // Verify the proof.
+        self.verify_record_disclosure_proof(disclosure)
+    }
+
+    /// Verifies the proof of the given record disclosure. On failure, returns an error.
+    ///
+    /// Note: This does *not* check that the global state root exists, or that the record is unspent.
+    pub fn verify_record_disclosure_proof(&self, disclosure: &RecordDisclosure<N>) -> Result<()> {
+        macro_rules! logic {
+            ($process:expr, $network:path, $aleo:path) => {{
+                // Verify the disclosure.
+                $process.verify_record_disclosure::<$aleo>(cast_ref!(&disclosure as RecordDisclosure<$network>))
+            }};
+        }
+
+        // Verify the disclosure.
+        process!(self, logic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use console::{account::ViewKey, program::Entry};
+    use ledger_block::Transition;
+
+    type CurrentNetwork = console::network::MainnetV0;
+
+    #[test]
+    fn test_record_disclosure() {
+        let rng = &mut TestRng::default();
+
+        // Initialize a new caller.
+        let caller_private_key = crate::vm::test_helpers::sample_genesis_private_key(rng);
+        let caller_view_key = ViewKey::try_from(&caller_private_key).unwrap();
+
+        // Initialize the VM, and select a genesis record.
+        let vm = crate::vm::test_helpers::sample_vm_with_genesis_block(rng);
+        let genesis = crate::vm::test_helpers::sample_genesis_block(rng);
+        let records = genesis.transitions().cloned().flat_map(Transition::into_records).collect::<IndexMap<_, _>>();
+        let record = records.values().next().unwrap().decrypt(&caller_view_key).unwrap();
+        let microcredits = match record.find(&[Identifier::<CurrentNetwork>::from_str("microcredits").unwrap()]) {
+            Ok(Entry::Private(Plaintext::Literal(Literal::U64(microcredits), _))) => *microcredits,
+            _ => panic!("Expected a 'u64' microcredits entry"),
+        };
+
+        // Disclose a lower bound on the record balance.
+        let prove = |predicate, reveal_serial_number, rng: &mut TestRng| {
+            vm.prove_record_disclosure(
+                &caller_private_key,
+                "credits.aleo",
+                "credits",
+                &record,
+                "microcredits",
+                predicate,
+                reveal_serial_number,
+                rng,
+            )
+        };
+        let disclosure = prove(DisclosurePredicate::GreaterThanOrEqual(microcredits), true, rng).unwrap();
+        assert_eq!(disclosure.owner(), &Address::try_from(&caller_private_key).unwrap());
+        vm.verify_record_disclosure(&disclosure).unwrap();
+
+        // Ensure the serial number is only revealed on request.
+        let private_disclosure = prove(DisclosurePredicate::GreaterThanOrEqual(microcredits), false, rng).unwrap();
+        assert!(private_disclosure.serial_number().is_none());
+        vm.verify_record_disclosure(&private_disclosure).unwrap();
+        let candidate =
+            RecordDisclosure::<CurrentNetwork>::from_bytes_le(&private_disclosure.to_bytes_le().unwrap()).unwrap();
+        assert_eq!(private_disclosure, candidate);
+        // Ensure the proof does not verify with a serial number attached.
+        let tampered = RecordDisclosure::new(
+            *private_disclosure.program_id(),
+            *private_disclosure.record_name(),
+            *private_disclosure.entry(),
+            *private_disclosure.predicate(),
+            private_disclosure.global_state_root(),
+            *private_disclosure.owner(),
+            disclosure.serial_number().copied(),
+            private_disclosure.proof().clone(),
+        );
+        assert!(vm.verify_record_disclosure(&tampered).is_err());
+
+        // Ensure the disclosure round trips through JSON and bytes.
+        let candidate = RecordDisclosure::<CurrentNetwork>::from_str(&disclosure.to_string()).unwrap();
+        assert_eq!(disclosure, candidate);
+        let candidate = RecordDisclosure::<CurrentNetwork>::from_bytes_le(&disclosure.to_bytes_le().unwrap()).unwrap();
+        assert_eq!(disclosure, candidate);
+
+        // Ensure a false predicate cannot be proven.
+        assert!(prove(DisclosurePredicate::GreaterThanOrEqual(microcredits + 1), true, rng).is_err());
+        // Ensure the proof does not verify for a different predicate.
+        let tampered = RecordDisclosure::new(
+            *disclosure.program_id(),
+            *disclosure.record_name(),
+            *disclosure.entry(),
+            DisclosurePredicate::GreaterThanOrEqual(microcredits + 1),
+            disclosure.global_state_root(),
+            *disclosure.owner(),
+            disclosure.serial_number().copied(),
+            disclosure.proof().clone(),
+        );
+        assert!(vm.verify_record_disclosure(&tampered).is_err());
+    }
+}
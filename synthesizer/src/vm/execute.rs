@@ -94,6 +94,121 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         Transaction::from_execution(execution, fee)
     }
 
+    /// Returns a new execute transaction for the given authorization export.
+    ///
+    /// This is the entry point for a prover that is delegated the proving of an authorization,
+    /// e.g. by a light client. The exported programs that are already in the VM must match,
+    /// while the remaining exported programs are only loaded for the duration of this call.
+    pub fn execute_authorization_export<R: Rng + CryptoRng>(
+        &self,
+        export: &AuthorizationExport<N>,
+        query: Option<Query<N, C::BlockStorage>>,
+        rng: &mut R,
+    ) -> Result<Transaction<N>> {
+        let timer = timer!("VM::execute_authorization_export");
+
+        // Ensure a fee is included, unless the authorization is for a split.
+        ensure!(
+            export.fee().is_some() || export.authorization().is_split(),
+            "The authorization export must include a fee authorization"
+        );
+
+        // Construct the locator of the main function.
+        let locator = {
+            let request = export.authorization().peek_next()?;
+            Locator::new(*request.program_id(), *request.function_name()).to_string()
+        };
+        // Prepare the query.
+        let query = match query {
+            Some(query) => query,
+            None => Query::VM(self.block_store().clone()),
+        };
+        lap!(timer, "Prepare the query");
+
+        macro_rules! logic {
+            ($process:expr, $network:path, $aleo:path) => {{
+                // Prepare the authorization export.
+                let export = cast_ref!(&export as AuthorizationExport<$network>);
+
+                // Load the exported programs into a copy of the process.
+                let mut process = $process.clone();
+                for program in export.programs() {
+                    match process.contains_program(program.id()) {
+                        true => ensure!(
+                            process.get_program(program.id())? == program,
+                            "The exported program '{}' does not match the program in the VM",
+                            program.id()
+                        ),
+                        false => process.add_program(program)?,
+                    }
+                }
+                lap!(timer, "Load the exported programs");
+
+                // Execute the call.
+                let (_, mut trace) = process.execute::<$aleo, _>(export.authorization().replicate(), rng)?;
+                lap!(timer, "Execute the call");
+
+                // Prepare the assignments.
+                cast_mut_ref!(trace as Trace<N>).prepare(query.clone())?;
+                lap!(timer, "Prepare the assignments");
+
+                // Compute the proof and construct the execution.
+                let execution = trace.prove_execution::<$aleo, _>(&locator, rng)?;
+                lap!(timer, "Compute the proof");
+
+                // Compute the minimum execution cost.
+                let (minimum_execution_cost, (_, _)) = execution_cost(&process, &execution)?;
+
+                // Return the execution and its cost.
+                Ok::<_, Error>((cast_ref!(execution as Execution<N>).clone(), minimum_execution_cost))
+            }};
+        }
+
+        // Execute the authorization.
+        let (execution, minimum_execution_cost) = process!(self, logic)?;
+
+        // Compute the fee.
+        let fee = match export.fee() {
+            Some(authorization) => {
+                // Ensure the base fee covers the execution cost.
+                // Note: The base fee is the third-to-last input of both fee functions.
+                let request = authorization.peek_next()?;
+                let base_fee_in_microcredits = match request.inputs().iter().rev().nth(2) {
+                    Some(Value::Plaintext(Plaintext::Literal(Literal::U64(base_fee), _))) => **base_fee,
+                    _ => bail!("Failed to retrieve the base fee from the fee authorization"),
+                };
+                ensure!(
+                    base_fee_in_microcredits >= minimum_execution_cost,
+                    "The base fee ({base_fee_in_microcredits} microcredits) does not cover the execution cost ({minimum_execution_cost} microcredits)"
+                );
+                Some(self.execute_fee_authorization_raw(authorization.replicate(), Some(query), rng)?)
+            }
+            None => None,
+        };
+        finish!(timer, "Execute the authorization export");
+
+        // Return the execute transaction.
+        Transaction::from_execution(execution, fee)
+    }
+
+    /// Returns a new execute transaction for the given offline execution, and the authorization export
+    /// that was signed offline with `Process::sign_offline_execution`.
+    ///
+    /// This is the final step of the offline signing workflow, which ensures the export authorizes
+    /// exactly the prepared call and fee, before proving it.
+    pub fn execute_offline_execution<R: Rng + CryptoRng>(
+        &self,
+        execution: &OfflineExecution<N>,
+        export: &AuthorizationExport<N>,
+        query: Option<Query<N, C::BlockStorage>>,
+        rng: &mut R,
+    ) -> Result<Transaction<N>> {
+        // Ensure the export is of the offline execution.
+        execution.check_export(export)?;
+        // Prove the export.
+        self.execute_authorization_export(export, query, rng)
+    }
+
     /// Returns a new fee for the given authorization.
     pub fn execute_fee_authorization<R: Rng + CryptoRng>(
         &self,
@@ -209,7 +324,7 @@ mod tests {
 
     use console::{
         account::{Address, ViewKey},
-        network::MainnetV0,
+        network::{MainnetV0, Multipart, MULTIPART_FRAGMENT_SIZE},
         program::{Ciphertext, Value},
         types::Field,
     };
@@ -464,6 +579,127 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_execute_authorization_export() {
+        let rng = &mut TestRng::default();
+
+        // Initialize a new caller.
+        let caller_private_key = crate::vm::test_helpers::sample_genesis_private_key(rng);
+        let address = Address::try_from(&caller_private_key).unwrap();
+
+        // Prepare the VM and records.
+        let (vm, _) = prepare_vm(rng).unwrap();
+
+        // Authorize a transfer and its fee, and export them.
+        let authorize = |base_fee_in_microcredits: u64, rng: &mut TestRng| {
+            let inputs = [
+                Value::<CurrentNetwork>::from_str(&address.to_string()).unwrap(),
+                Value::<CurrentNetwork>::from_str("1u64").unwrap(),
+            ];
+            let authorization =
+                vm.authorize(&caller_private_key, "credits.aleo", "transfer_public", inputs, rng).unwrap();
+            let execution_id = authorization.to_execution_id().unwrap();
+            let fee =
+                vm.authorize_fee_public(&caller_private_key, base_fee_in_microcredits, 0, execution_id, rng).unwrap();
+            authorization.export(&vm.process().read(), Some(fee)).unwrap()
+        };
+
+        // Ensure the export is proven into a valid transaction.
+        let export = authorize(1_000_000, rng);
+        assert!(export.programs().is_empty());
+        let transaction = vm.execute_authorization_export(&export, None, rng).unwrap();
+        assert_eq!(
+            export.authorization().to_execution_id().unwrap(),
+            transaction.execution().unwrap().to_execution_id().unwrap()
+        );
+        vm.check_transaction(&transaction, None, rng).unwrap();
+
+        // Ensure a base fee below the execution cost is rejected.
+        let export = authorize(1, rng);
+        assert!(vm.execute_authorization_export(&export, None, rng).is_err());
+
+        // Ensure a program that is not in the VM is loaded only for the execution.
+        let program = Program::<CurrentNetwork>::from_str(
+            r"
+program delegated.aleo;
+
+function double:
+    input r0 as u64.public;
+    add r0 r0 into r1;
+    output r1 as u64.public;",
+        )
+        .unwrap();
+        let mut process = vm.process().read().clone();
+        process.add_program(&program).unwrap();
+        let authorization = process
+            .authorize::<circuit::AleoV0, _>(&caller_private_key, "delegated.aleo", "double", ["2u64"].into_iter(), rng)
+            .unwrap();
+        let fee = vm
+            .authorize_fee_public(&caller_private_key, 1_000_000, 0, authorization.to_execution_id().unwrap(), rng)
+            .unwrap();
+        let export = authorization.export(&process, Some(fee)).unwrap();
+        assert_eq!(vec![program.clone()], export.programs());
+        let transaction = vm.execute_authorization_export(&export, None, rng).unwrap();
+        assert!(transaction.is_execute());
+        assert!(!vm.contains_program(program.id()));
+
+        // Ensure an export without a fee is rejected.
+        let export = AuthorizationExport::new(vec![program], export.authorization().clone(), None).unwrap();
+        assert!(vm.execute_authorization_export(&export, None, rng).is_err());
+    }
+
+    #[test]
+    fn test_execute_offline_execution() {
+        let rng = &mut TestRng::default();
+
+        // Initialize a new caller.
+        let caller_private_key = crate::vm::test_helpers::sample_genesis_private_key(rng);
+        let caller_view_key = ViewKey::try_from(&caller_private_key).unwrap();
+        let address = Address::try_from(&caller_private_key).unwrap();
+
+        // Prepare the VM and records.
+        let (vm, records) = prepare_vm(rng).unwrap();
+        let record = records.values().next().unwrap().decrypt(&caller_view_key).unwrap();
+
+        // Prepare a transfer with a private fee, on the online machine.
+        let prepare = |priority_fee_in_microcredits: u64| {
+            let inputs = [
+                Value::<CurrentNetwork>::from_str(&address.to_string()).unwrap(),
+                Value::<CurrentNetwork>::from_str("1u64").unwrap(),
+            ];
+            vm.prepare_offline_execution(
+                address,
+                "credits.aleo",
+                "transfer_public",
+                inputs,
+                1_000_000,
+                priority_fee_in_microcredits,
+                Some(record.clone()),
+            )
+            .unwrap()
+        };
+        let execution = prepare(0);
+
+        // Transfer the offline execution to the offline machine, and sign it.
+        let parts = execution.to_multipart(MULTIPART_FRAGMENT_SIZE).unwrap();
+        let candidate = OfflineExecution::from_multipart(&parts).unwrap();
+        let export = vm
+            .process()
+            .read()
+            .sign_offline_execution::<circuit::AleoV0, _>(&caller_private_key, &candidate, rng)
+            .unwrap();
+
+        // Transfer the export back to the online machine, and prove it.
+        let parts = export.to_multipart(MULTIPART_FRAGMENT_SIZE).unwrap();
+        let export = AuthorizationExport::from_multipart(&parts).unwrap();
+        let transaction = vm.execute_offline_execution(&execution, &export, None, rng).unwrap();
+        assert!(transaction.fee_transition().unwrap().is_fee_private());
+        vm.check_transaction(&transaction, None, rng).unwrap();
+
+        // Ensure the export is rejected for a different offline execution.
+        assert!(vm.execute_offline_execution(&prepare(1), &export, None, rng).is_err());
+    }
+
     #[test]
     fn test_transfer_public_as_signer_transaction_size() {
         let rng = &mut TestRng::default();
@@ -556,6 +556,12 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
             // Retrieve the finalize store.
             let store = self.finalize_store();
 
+            // Record the mapping changes of this block in the mapping history, if it is enabled.
+            // Note: On failure, this will abort the entire atomic batch.
+            store
+                .start_history(state.block_height())
+                .map_err(|e| format!("Failed to start the mapping history - {e}"))?;
+
             /* Perform the ratifications before finalize. */
 
             match Self::atomic_pre_ratify(store, state, pre_ratifications) {
@@ -17,6 +17,7 @@ pub use helpers::*;
 
 mod authorize;
 mod deploy;
+mod disclose;
 mod execute;
 mod finalize;
 mod verify;
@@ -25,7 +26,18 @@ use crate::{cast_mut_ref, cast_ref, convert, process, Restrictions};
 use console::{
     account::{Address, PrivateKey},
     network::prelude::*,
-    program::{Argument, Identifier, Literal, Locator, Plaintext, ProgramID, ProgramOwner, Record, Value},
+    program::{
+        Argument,
+        Identifier,
+        Literal,
+        Locator,
+        Plaintext,
+        ProgramID,
+        ProgramOwner,
+        Record,
+        StatePath,
+        Value,
+    },
     types::{Field, Group, U64},
 };
 use ledger_block::{
@@ -57,7 +69,17 @@ use ledger_store::{
     TransactionStore,
     TransitionStore,
 };
-use synthesizer_process::{deployment_cost, execution_cost, Authorization, Process, Trace};
+use synthesizer_process::{
+    deployment_cost,
+    execution_cost,
+    Authorization,
+    AuthorizationExport,
+    DisclosurePredicate,
+    OfflineExecution,
+    Process,
+    RecordDisclosure,
+    Trace,
+};
 use synthesizer_program::{FinalizeGlobalState, FinalizeOperation, FinalizeStoreTrait, Program};
 use utilities::try_vm_runtime;
 
@@ -406,12 +428,12 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
         )?;
 
         // Pause the atomic writes, so that both the insertion and finalization belong to a single batch.
-        #[cfg(feature = "rocks")]
+        #[cfg(any(feature = "rocks", feature = "sled"))]
         self.block_store().pause_atomic_writes()?;
 
         // First, insert the block.
         if let Err(insert_error) = self.block_store().insert(block) {
-            if cfg!(feature = "rocks") {
+            if cfg!(any(feature = "rocks", feature = "sled")) {
                 // Clear all pending atomic operations so that unpausing the atomic writes
                 // doesn't execute any of the queued storage operations.
                 self.block_store().abort_atomic();
@@ -423,16 +445,19 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
             return Err(insert_error);
         };
 
-        // Next, finalize the transactions.
-        match self.finalize(state, block.ratifications(), block.solutions(), block.transactions()) {
-            Ok(_ratified_finalize_operations) => {
+        // Next, finalize the transactions, and store the finalize operations of the ratifications.
+        let finalize_result = self
+            .finalize(state, block.ratifications(), block.solutions(), block.transactions())
+            .and_then(|operations| self.block_store().insert_ratified_finalize_operations(&block.hash(), operations));
+        match finalize_result {
+            Ok(()) => {
                 // Unpause the atomic writes, executing the ones queued from block insertion and finalization.
-                #[cfg(feature = "rocks")]
+                #[cfg(any(feature = "rocks", feature = "sled"))]
                 self.block_store().unpause_atomic_writes::<false>()?;
                 Ok(())
             }
             Err(finalize_error) => {
-                if cfg!(feature = "rocks") {
+                if cfg!(any(feature = "rocks", feature = "sled")) {
                     // Clear all pending atomic operations so that unpausing the atomic writes
                     // doesn't execute any of the queued storage operations.
                     self.block_store().abort_atomic();
@@ -461,6 +486,124 @@ impl<N: Network, C: ConsensusStorage<N>> VM<N, C> {
             }
         }
     }
+
+    /// Rolls back the VM to the given block height, by removing each block after it (along with its
+    /// transactions, transitions, and committee), and by undoing its finalize effects on the mappings.
+    ///
+    /// Note: This method requires the mapping history of every removed block (see `HistoryRetention`).
+    /// If a block fails to be removed, the blocks after it are removed, and it is left in place with
+    /// its finalize effects intact.
+    pub fn rollback_to(&self, height: u32) -> Result<()> {
+        // Acquire the block lock, which is needed to ensure this function is not called concurrently
+        // with `add_next_block`.
+        // Note: This lock must be held for the entire scope of this function.
+        let _block_lock = self.block_lock.lock();
+
+        // Retrieve the latest block height.
+        let latest_height = self.block_store().current_block_height();
+        ensure!(height <= latest_height, "Cannot roll back to block {height}, as the latest block is {latest_height}");
+        // Ensure the mapping history of each block to remove is available, before any block is removed.
+        for block_height in height + 1..=latest_height {
+            if !self.finalize_store().contains_history(block_height)? {
+                bail!("Cannot roll back block {block_height}, as its mapping history is not available")
+            }
+        }
+
+        // Remove the blocks, starting from the latest block.
+        for block_height in (height + 1..=latest_height).rev() {
+            self.remove_latest_block(block_height)?;
+        }
+        Ok(())
+    }
+
+    /// Removes the latest block, which has the given height, and undoes its finalize effects on the mappings.
+    fn remove_latest_block(&self, height: u32) -> Result<()> {
+        // Retrieve the block.
+        let Some(block_hash) = self.block_store().get_block_hash(height)? else {
+            bail!("Failed to roll back block {height}: missing block hash")
+        };
+        let Some(block) = self.block_store().get_block(&block_hash)? else {
+            bail!("Failed to roll back block {height}: missing block '{block_hash}'")
+        };
+
+        // Acquire the atomic lock, as the finalize store is written to.
+        let atomic_lock = self.atomic_lock.lock();
+
+        // Pause the atomic writes, so that both the reversion and the removal belong to a single batch.
+        #[cfg(any(feature = "rocks", feature = "sled"))]
+        self.block_store().pause_atomic_writes()?;
+
+        // Undo the finalize effects of the block, and then remove the block.
+        let mut is_reverted = false;
+        let result = self.finalize_store().revert_history(height).and_then(|_| {
+            is_reverted = true;
+            self.block_store().remove_last_n(1)
+        });
+        if let Err(error) = result {
+            let error = error.context(format!("Failed to roll back block {height}"));
+            if cfg!(any(feature = "rocks", feature = "sled")) {
+                // Clear all pending atomic operations so that unpausing the atomic writes
+                // doesn't execute any of the queued storage operations.
+                self.block_store().abort_atomic();
+                self.finalize_store().abort_atomic();
+                // Disable the atomic batch override.
+                // Note: This call is guaranteed to succeed (without error), because `DISCARD_BATCH == true`.
+                self.block_store().unpause_atomic_writes::<true>()?;
+            } else if is_reverted {
+                // Release the atomic lock, as it is acquired again to finalize the block.
+                drop(atomic_lock);
+                // Redo the finalize effects of the block, as the reversion was already written.
+                self.refinalize_latest_block(&block).map_err(|refinalize_error| {
+                    // Log the rollback error.
+                    error!("{error:?}");
+                    // Return the refinalize error.
+                    refinalize_error
+                })?;
+            }
+            return Err(error);
+        }
+
+        // Unpause the atomic writes, executing the ones queued from the finalize reversion and block removal.
+        #[cfg(any(feature = "rocks", feature = "sled"))]
+        self.block_store().unpause_atomic_writes::<false>()?;
+
+        // Remove the stacks of the programs deployed in the block.
+        self.remove_deployed_stacks(&block);
+        Ok(())
+    }
+
+    /// Finalizes the given block again, which is the latest block, after its finalize effects were reverted.
+    ///
+    /// Note: This is only needed on the memory backend, where the reversion and the block removal in
+    /// `remove_latest_block` are not written in a single batch.
+    fn refinalize_latest_block(&self, block: &Block<N>) -> Result<()> {
+        // Construct the finalize state.
+        let state = FinalizeGlobalState::new::<N>(
+            block.round(),
+            block.height(),
+            block.cumulative_weight(),
+            block.cumulative_proof_target(),
+            block.previous_hash(),
+        )?;
+        // Remove the stacks of the programs deployed in the block, as finalize adds them again.
+        self.remove_deployed_stacks(block);
+        // Finalize the block, and ensure the finalize operations of its ratifications are unchanged.
+        let operations = self.finalize(state, block.ratifications(), block.solutions(), block.transactions())?;
+        if let Some(expected) = self.block_store().get_ratified_finalize_operations(&block.hash())? {
+            ensure!(operations == expected, "Block {} finalized to different ratified operations", block.height());
+        }
+        Ok(())
+    }
+
+    /// Removes the stacks of the programs deployed in the given block.
+    fn remove_deployed_stacks(&self, block: &Block<N>) {
+        let mut process = self.process.write();
+        for confirmed in block.transactions().iter() {
+            if let ConfirmedTransaction::AcceptedDeploy(_, Transaction::Deploy(_, _, deployment, _), _) = confirmed {
+                process.remove_stack(deployment.program_id());
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -476,13 +619,15 @@ pub(crate) mod test_helpers {
     use ledger_store::helpers::memory::ConsensusMemory;
     #[cfg(feature = "rocks")]
     use ledger_store::helpers::rocksdb::ConsensusDB;
+    #[cfg(feature = "sled")]
+    use ledger_store::helpers::sled::ConsensusSled;
     use ledger_test_helpers::{large_transaction_program, small_transaction_program};
     use synthesizer_program::Program;
 
     use indexmap::IndexMap;
     use once_cell::sync::OnceCell;
     use std::borrow::Borrow;
-    #[cfg(feature = "rocks")]
+    #[cfg(any(feature = "rocks", feature = "sled"))]
     use std::path::Path;
     use synthesizer_snark::VerifyingKey;
 
@@ -504,6 +649,12 @@ pub(crate) mod test_helpers {
         VM::from(ConsensusStore::open(path.to_owned()).unwrap()).unwrap()
     }
 
+    #[cfg(feature = "sled")]
+    pub(crate) fn sample_vm_sled(path: &Path) -> VM<CurrentNetwork, ConsensusSled<CurrentNetwork>> {
+        // Initialize a new VM.
+        VM::from(ConsensusStore::open(path.to_owned()).unwrap()).unwrap()
+    }
+
     pub(crate) fn sample_genesis_private_key(rng: &mut TestRng) -> PrivateKey<CurrentNetwork> {
         static INSTANCE: OnceCell<PrivateKey<CurrentNetwork>> = OnceCell::new();
         *INSTANCE.get_or_init(|| {
@@ -2538,4 +2689,34 @@ finalize transfer_public_to_private:
         // It should still be possible to insert the 1st block afterwards.
         vm.add_next_block(&block1).unwrap();
     }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_atomic_unpause_on_error_sled() {
+        let rng = &mut TestRng::default();
+
+        // Initialize a genesis private key.
+        let genesis_private_key = sample_genesis_private_key(rng);
+
+        // Initialize the genesis block.
+        let genesis = sample_genesis_block(rng);
+
+        // Initialize a VM and sample 2 blocks using it.
+        let vm = sample_vm();
+        vm.add_next_block(&genesis).unwrap();
+        let block1 = sample_next_block(&vm, &genesis_private_key, &[], rng).unwrap();
+        vm.add_next_block(&block1).unwrap();
+        let block2 = sample_next_block(&vm, &genesis_private_key, &[], rng).unwrap();
+
+        // Create a new, sled-based VM shadowing the 1st one.
+        let tempdir = tempfile::tempdir().unwrap();
+        let vm = sample_vm_sled(tempdir.path());
+        vm.add_next_block(&genesis).unwrap();
+        // This time, however, try to insert the 2nd block first, which fails due to height.
+        assert!(vm.add_next_block(&block2).is_err());
+
+        // It should still be possible to insert the 1st block afterwards.
+        vm.add_next_block(&block1).unwrap();
+        assert_eq!(vm.block_store().current_block_height(), 1);
+    }
 }
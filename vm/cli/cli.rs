@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::cli::commands::{Build, Clean, Execute, New, Run, Update};
+use crate::cli::commands::{Build, Clean, Debug, Execute, Fmt, Keystore, Lint, New, Prove, Run, Update};
+#[cfg(feature = "rocks")]
+use crate::cli::commands::{Fsck, Replay};
 
 use anstyle::{AnsiColor, Color, Style};
 use anyhow::Result;
@@ -42,10 +44,26 @@ pub enum Command {
     Build(Build),
     #[clap(name = "clean")]
     Clean(Clean),
+    #[clap(name = "debug")]
+    Debug(Debug),
     #[clap(name = "execute")]
     Execute(Execute),
+    #[clap(name = "fmt")]
+    Fmt(Fmt),
+    #[cfg(feature = "rocks")]
+    #[clap(name = "fsck")]
+    Fsck(Fsck),
+    #[clap(name = "keystore", subcommand)]
+    Keystore(Keystore),
+    #[clap(name = "lint")]
+    Lint(Lint),
     #[clap(name = "new")]
     New(New),
+    #[clap(name = "prove")]
+    Prove(Prove),
+    #[cfg(feature = "rocks")]
+    #[clap(name = "replay")]
+    Replay(Replay),
     #[clap(name = "run")]
     Run(Run),
     #[clap(name = "update")]
@@ -58,8 +76,17 @@ impl Command {
         match self {
             Self::Build(command) => command.parse(),
             Self::Clean(command) => command.parse(),
+            Self::Debug(command) => command.parse(),
             Self::Execute(command) => command.parse(),
+            Self::Fmt(command) => command.parse(),
+            #[cfg(feature = "rocks")]
+            Self::Fsck(command) => command.parse(),
+            Self::Keystore(command) => command.parse(),
+            Self::Lint(command) => command.parse(),
             Self::New(command) => command.parse(),
+            Self::Prove(command) => command.parse(),
+            #[cfg(feature = "rocks")]
+            Self::Replay(command) => command.parse(),
             Self::Run(command) => command.parse(),
             Self::Update(command) => command.parse(),
         }
@@ -0,0 +1,273 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{
+    console::program::Register,
+    ledger::store::{helpers::memory::FinalizeMemory, FinalizeStore},
+    synthesizer::{
+        process::{DebugCommand, DebugEvent, Debugger, Location, Step, StepHookRef, Watchpoints},
+        program::FinalizeGlobalState,
+    },
+};
+
+use anyhow::anyhow;
+use std::{
+    io::{BufRead, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+const HELP: &str = "Commands:
+  step, s                      Evaluate the next statement, stepping over calls and awaits
+  into, i                      Evaluate the next statement, stepping into calls and awaits
+  continue, c                  Resume until a breakpoint, a watched register changes, or the end
+  break, b [program/]name idx  Add a breakpoint before the statement at the given index
+  delete, d [program/]name idx Remove a breakpoint
+  print, p register            Print the value of a register
+  watch, w register            Pause when the value of a register changes
+  unwatch, u register          Stop watching a register
+  where, l                     Print the next statement
+  quit, q                      Stop debugging";
+
+/// Debugs an Aleo program function, stepping through its instructions and finalize commands
+#[derive(Debug, Parser)]
+pub struct Debug {
+    /// The function name.
+    function: Identifier<CurrentNetwork>,
+    /// The function inputs.
+    inputs: Vec<Value<CurrentNetwork>>,
+}
+
+impl Debug {
+    /// Debugs an Aleo program function with the specified name.
+    pub fn parse(self) -> Result<String> {
+        // Derive the program directory path.
+        let path = std::env::current_dir()?;
+
+        // Load the package.
+        let package = Package::open(&path)?;
+        // Load the private key.
+        let private_key = crate::cli::helpers::dotenv_private_key()?;
+        // Ensure that the function exists.
+        if !package.program().contains_function(&self.function) {
+            bail!("Function '{}' does not exist.", self.function)
+        }
+
+        // Initialize an RNG.
+        let rng = &mut rand::thread_rng();
+
+        // Construct the process.
+        let process = package.get_process()?;
+        // Authorize the function call.
+        let authorization =
+            process.authorize::<Aleo, _>(&private_key, package.program_id(), self.function, self.inputs.iter(), rng)?;
+
+        println!(
+            "🐞 Debugging '{}'. Type 'help' for a list of commands.\n",
+            self.locator(&package)?.to_string().bold()
+        );
+
+        // Step through the function.
+        let stopped = Arc::new(AtomicBool::new(false));
+        let hook = debugger(*package.program_id(), &stopped);
+        let response = match process.evaluate_with_hook::<Aleo>(authorization, hook) {
+            Ok(response) => response,
+            Err(_) if stopped.load(Ordering::SeqCst) => {
+                return Ok(format!("✅ Stopped debugging '{}'", self.locator(&package)?.to_string().bold()));
+            }
+            Err(error) => return Err(error),
+        };
+
+        // Log the outputs.
+        println!("\n➡️  Outputs\n");
+        for output in response.outputs() {
+            println!(" • {output}");
+        }
+        println!();
+
+        // Step through the finalize block, if the function returned a future.
+        if let Some(Value::Future(future)) = response.outputs().last() {
+            println!("🐞 Debugging the finalize block of '{}/{}'.\n", future.program_id(), future.function_name());
+
+            // Initialize a scratch finalize store, so that no state is persisted.
+            let store = FinalizeStore::<CurrentNetwork, FinalizeMemory<CurrentNetwork>>::open(None)?;
+            let state = FinalizeGlobalState::new_genesis::<CurrentNetwork>()?;
+            let hook = debugger(*future.program_id(), &stopped);
+            let operations = match process.finalize_with_hook(state, &store, future, hook) {
+                Ok(operations) => operations,
+                Err(_) if stopped.load(Ordering::SeqCst) => {
+                    return Ok(format!("✅ Stopped debugging '{}'", self.locator(&package)?.to_string().bold()));
+                }
+                Err(error) => return Err(error),
+            };
+            println!("\nFinalized with {} operation(s).\n", operations.len());
+        }
+
+        Ok(format!("✅ Finished debugging '{}'", self.locator(&package)?.to_string().bold()))
+    }
+
+    /// Returns the locator of the function.
+    fn locator(&self, package: &Package<CurrentNetwork>) -> Result<Locator<CurrentNetwork>> {
+        Locator::<CurrentNetwork>::from_str(&format!("{}/{}", package.program_id(), self.function))
+    }
+}
+
+/// Returns a debugger that runs the REPL on standard input at each pause.
+/// If the user quits, the debugger sets `stopped` and halts the evaluation.
+fn debugger(program_id: ProgramID<CurrentNetwork>, stopped: &Arc<AtomicBool>) -> StepHookRef<CurrentNetwork> {
+    let stopped = stopped.clone();
+    let debugger = Debugger::new(move |step: &Step<CurrentNetwork>, event, watchpoints: &mut Watchpoints<_>| {
+        let command = repl(program_id, step, event, watchpoints, &mut std::io::stdin().lock())?;
+        if command.is_none() {
+            stopped.store(true, Ordering::SeqCst);
+        }
+        command.ok_or_else(|| anyhow!("Stopped debugging"))
+    });
+    debugger.into_hook()
+}
+
+/// Runs the debugger REPL at a pause, returning the command to resume with, or `None` if the user quits.
+fn repl(
+    program_id: ProgramID<CurrentNetwork>,
+    step: &Step<CurrentNetwork>,
+    event: DebugEvent<CurrentNetwork>,
+    watchpoints: &mut Watchpoints<CurrentNetwork>,
+    input: &mut impl BufRead,
+) -> Result<Option<DebugCommand>> {
+    print_event(step, event);
+    loop {
+        print!("(debug) ");
+        std::io::stdout().flush()?;
+
+        // Read the next command, stopping at the end of the input.
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let mut words = line.split_whitespace();
+        let (command, arguments) = match words.next() {
+            Some(command) => (command, words.collect::<Vec<_>>()),
+            None => continue,
+        };
+
+        match (command, arguments.as_slice()) {
+            ("step" | "s", []) => return Ok(Some(DebugCommand::Step)),
+            ("into" | "i", []) => return Ok(Some(DebugCommand::StepInto)),
+            ("continue" | "c", []) => return Ok(Some(DebugCommand::Continue)),
+            ("break" | "b", [name, index]) => match parse_location(program_id, name, index) {
+                Ok(location) => match watchpoints.add_breakpoint(location.clone()) {
+                    true => println!("Added a breakpoint at {location}"),
+                    false => println!("A breakpoint already exists at {location}"),
+                },
+                Err(error) => println!("{error}"),
+            },
+            ("delete" | "d", [name, index]) => match parse_location(program_id, name, index) {
+                Ok(location) => match watchpoints.remove_breakpoint(&location) {
+                    true => println!("Removed the breakpoint at {location}"),
+                    false => println!("No breakpoint exists at {location}"),
+                },
+                Err(error) => println!("{error}"),
+            },
+            ("print" | "p", [register]) => match Register::from_str(register) {
+                Ok(register) => match step.load(&register) {
+                    Ok(value) => println!("{register} = {value}"),
+                    Err(error) => println!("{error}"),
+                },
+                Err(error) => println!("{error}"),
+            },
+            ("watch" | "w", [register]) => match Register::from_str(register) {
+                Ok(register) => {
+                    println!("Watching {register}");
+                    let value = step.load(&register).ok();
+                    watchpoints.watch(register, value);
+                }
+                Err(error) => println!("{error}"),
+            },
+            ("unwatch" | "u", [register]) => match Register::from_str(register) {
+                Ok(register) => match watchpoints.unwatch(&register) {
+                    true => println!("Stopped watching {register}"),
+                    false => println!("{register} is not watched"),
+                },
+                Err(error) => println!("{error}"),
+            },
+            ("where" | "l", []) => print_statement(step),
+            ("quit" | "q", []) => return Ok(None),
+            ("help" | "h", _) => println!("{HELP}"),
+            _ => println!("Unknown command '{}'. Type 'help' for a list of commands.", line.trim()),
+        }
+    }
+}
+
+/// Parses a breakpoint location of the form `[program/]name index`.
+fn parse_location(program_id: ProgramID<CurrentNetwork>, name: &str, index: &str) -> Result<Location<CurrentNetwork>> {
+    let (program_id, name) = match name.split_once('/') {
+        Some((program_id, name)) => (ProgramID::from_str(program_id)?, Identifier::from_str(name)?),
+        None => (program_id, Identifier::from_str(name)?),
+    };
+    Ok(Location::new(program_id, name, index.parse()?))
+}
+
+/// Prints the reason the debugger paused, followed by the next statement.
+fn print_event(step: &Step<CurrentNetwork>, event: DebugEvent<CurrentNetwork>) {
+    match event {
+        DebugEvent::Step => (),
+        DebugEvent::Breakpoint(location) => println!("{}", format!("Reached the breakpoint at {location}").yellow()),
+        DebugEvent::Watch(register, value) => println!("{}", format!("{register} changed to {value}").yellow()),
+    }
+    print_statement(step);
+}
+
+/// Prints the location of the next statement, and the statement itself.
+fn print_statement(step: &Step<CurrentNetwork>) {
+    println!("{} {}", format!("[{}]", step.location()).dimmed(), step.statement());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cli::{CLI, Command},
+        prelude::{Identifier, Value},
+    };
+
+    #[test]
+    fn clap_snarkvm_debug() {
+        let arg_vec = vec!["snarkvm", "debug", "hello", "1u32", "2u32"];
+        let cli = CLI::parse_from(&arg_vec);
+
+        if let Command::Debug(debug) = cli.command {
+            assert_eq!(debug.function, Identifier::try_from(arg_vec[2]).unwrap());
+            assert_eq!(debug.inputs, vec![Value::try_from(arg_vec[3]).unwrap(), Value::try_from(arg_vec[4]).unwrap()]);
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+    }
+
+    #[test]
+    fn test_parse_location() {
+        let program_id = ProgramID::from_str("hello.aleo").unwrap();
+
+        let location = parse_location(program_id, "main", "3").unwrap();
+        assert_eq!(location, Location::new(program_id, Identifier::from_str("main").unwrap(), 3));
+
+        let location = parse_location(program_id, "token.aleo/transfer", "0").unwrap();
+        assert_eq!(location.program_id(), &ProgramID::from_str("token.aleo").unwrap());
+        assert_eq!(location.name(), &Identifier::from_str("transfer").unwrap());
+
+        assert!(parse_location(program_id, "main", "x").is_err());
+    }
+}
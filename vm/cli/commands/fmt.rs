@@ -0,0 +1,75 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// Formats an Aleo program into its canonical layout.
+#[derive(Debug, Parser)]
+pub struct Fmt {
+    /// Returns an error if the program is not formatted, instead of formatting it.
+    #[clap(long)]
+    check: bool,
+}
+
+impl Fmt {
+    /// Formats the Aleo program in the current package.
+    pub fn parse(self) -> Result<String> {
+        // Derive the program directory path.
+        let path = std::env::current_dir()?;
+
+        // Load the package.
+        let package = Package::<CurrentNetwork>::open(&path)?;
+
+        // Format the program.
+        let source = package.program_file().program_string();
+        let formatted = Program::<CurrentNetwork>::format_source(source)?;
+
+        // Prepare the path string.
+        let path_string = format!("(in \"{}\")", path.display());
+
+        if formatted == source {
+            return Ok(format!(
+                "✅ '{}' is already formatted {}",
+                package.program_id().to_string().bold(),
+                path_string.dimmed()
+            ));
+        }
+        if self.check {
+            bail!("'{}' is not formatted {}", AleoFile::<CurrentNetwork>::main_file_name(), path_string)
+        }
+
+        // Write the formatted program.
+        std::fs::write(path.join(AleoFile::<CurrentNetwork>::main_file_name()), formatted)?;
+
+        Ok(format!("✅ Formatted '{}' {}", package.program_id().to_string().bold(), path_string.dimmed()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Command, CLI};
+
+    #[test]
+    fn clap_snarkvm_fmt() {
+        let arg_vec = vec!["snarkvm", "fmt", "--check"];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Fmt(fmt) = cli.command {
+            assert!(fmt.check);
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+    }
+}
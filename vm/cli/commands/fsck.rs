@@ -0,0 +1,79 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{
+    console::network::Network,
+    ledger::{block::Block, store::helpers::rocksdb::ConsensusDB, Ledger},
+    utilities::FromBytes,
+};
+
+use std::path::PathBuf;
+
+/// Verifies the consistency of a ledger storage, and optionally repairs it
+#[derive(Debug, Parser)]
+pub struct Fsck {
+    /// The path of the ledger storage directory.
+    #[clap(long)]
+    path: PathBuf,
+    /// Removes the transactions and transitions which are not included in any block.
+    #[clap(long)]
+    repair: bool,
+}
+
+impl Fsck {
+    /// Verifies the ledger storage at the given path.
+    pub fn parse(self) -> Result<String> {
+        // Ensure a new ledger is not initialized at the given path.
+        if !self.path.exists() {
+            bail!("No ledger storage exists at \"{}\"", self.path.display())
+        }
+
+        // Load the ledger.
+        let genesis = Block::<CurrentNetwork>::from_bytes_le(CurrentNetwork::genesis_bytes())?;
+        let ledger =
+            Ledger::<CurrentNetwork, ConsensusDB<CurrentNetwork>>::load_unchecked(genesis, self.path.clone().into())?;
+
+        // Verify the storage.
+        let report = ledger.verify_storage(self.repair)?;
+        for issue in report.repaired() {
+            println!("🔧 Repaired: {issue}");
+        }
+        for issue in report.issues() {
+            println!("❌ {issue}");
+        }
+
+        // Prepare the path string.
+        let path_string = format!("(in \"{}\")", self.path.display());
+
+        if !report.is_consistent() {
+            let hint = match report.issues().iter().all(|issue| issue.is_repairable()) {
+                true => "run with '--repair' to remove the orphaned entries",
+                false => "the ledger must be resynced",
+            };
+            bail!("Found {} issues in the ledger storage {path_string} - {hint}", report.issues().len())
+        }
+
+        use num_format::ToFormattedString;
+        Ok(format!(
+            "✅ Verified {} blocks, {} transactions, and {} transitions {}\n\n  State root: {}\n  Finalize checksum: {}",
+            report.latest_height() + 1,
+            report.num_transactions().to_formatted_string(LOCALE),
+            report.num_transitions().to_formatted_string(LOCALE),
+            path_string.dimmed(),
+            report.state_root(),
+            report.finalize_checksum()
+        ))
+    }
+}
@@ -0,0 +1,144 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{
+    cli::helpers::{dotenv_private_key, keystore_password, unlock_keystore},
+    console::account::{Address, Keystore as AccountKeystore, PrivateKey},
+};
+
+use std::{
+    fs::OpenOptions,
+    io::{ErrorKind, Write},
+    path::{Path, PathBuf},
+};
+
+/// Creates or unlocks a password-encrypted private key keystore
+#[derive(Debug, Parser)]
+pub enum Keystore {
+    /// Encrypts a new private key, or the private key in the '.env' file, into a keystore.
+    #[clap(name = "new")]
+    New {
+        /// The path of the keystore file.
+        #[clap(long, default_value = "keystore.json")]
+        path: PathBuf,
+        /// Encrypts the private key in the '.env' file, instead of a new private key.
+        #[clap(long)]
+        import: bool,
+    },
+    /// Decrypts a keystore, to check its password.
+    #[clap(name = "unlock")]
+    Unlock {
+        /// The path of the keystore file.
+        #[clap(long, default_value = "keystore.json")]
+        path: PathBuf,
+    },
+}
+
+impl Keystore {
+    /// Creates or unlocks a keystore.
+    pub fn parse(self) -> Result<String> {
+        match self {
+            Self::New { path, import } => {
+                // Ensure an existing keystore is not overwritten.
+                if path.exists() {
+                    bail!("A keystore already exists at \"{}\"", path.display())
+                }
+
+                // Load or sample the private key.
+                let private_key = match import {
+                    true => dotenv_private_key()?,
+                    false => PrivateKey::<CurrentNetwork>::new(&mut rand::thread_rng())?,
+                };
+                let address = Address::try_from(&private_key)?;
+
+                // Prompt for the password twice, unless it is given in the environment.
+                let password = keystore_password(&format!("Enter a password for '{address}': "))?;
+                if password != keystore_password("Confirm the password: ")? {
+                    bail!("The passwords do not match")
+                }
+
+                // Encrypt and write the keystore.
+                let keystore = AccountKeystore::encrypt(
+                    &private_key,
+                    &password,
+                    AccountKeystore::<CurrentNetwork>::DEFAULT_LOG_N,
+                    &mut rand::thread_rng(),
+                )?;
+                write_keystore(&path, &keystore.to_string())?;
+
+                // Prepare the path string.
+                let path_string = format!("(in \"{}\")", path.display());
+
+                Ok(format!(
+                    "✅ Created a keystore for '{}' {}\n\nSet 'KEYSTORE={}' in your '.env' file to use it.",
+                    address.to_string().bold(),
+                    path_string.dimmed(),
+                    path.display()
+                ))
+            }
+            Self::Unlock { path } => {
+                // Decrypt the keystore.
+                let address = Address::try_from(unlock_keystore(&path)?)?;
+                Ok(format!("✅ Unlocked the keystore for '{}'", address.to_string().bold()))
+            }
+        }
+    }
+}
+
+/// Writes the keystore to a new file at the given path, which is only readable and writable by its owner.
+/// If a file already exists at the path, it is not overwritten.
+fn write_keystore(path: &Path, keystore: &str) -> Result<()> {
+    let mut options = OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+    let mut file = match options.open(path) {
+        Ok(file) => file,
+        Err(error) if error.kind() == ErrorKind::AlreadyExists => {
+            bail!("A keystore already exists at \"{}\"", path.display())
+        }
+        Err(error) => bail!("Failed to create the keystore at \"{}\": {error}", path.display()),
+    };
+    file.write_all(keystore.as_bytes())?;
+    Ok(file.sync_all()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_keystore() {
+        let directory = std::env::temp_dir().join(format!("snarkvm_keystore_{}", rand::random::<u64>()));
+        std::fs::create_dir_all(&directory).unwrap();
+        let path = directory.join("keystore.json");
+
+        // Ensure the keystore is written, and is only readable and writable by its owner.
+        write_keystore(&path, "keystore").unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "keystore");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            assert_eq!(std::fs::metadata(&path).unwrap().permissions().mode() & 0o777, 0o600);
+        }
+
+        // Ensure an existing keystore is not overwritten.
+        assert!(write_keystore(&path, "overwritten").is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "keystore");
+
+        std::fs::remove_dir_all(directory).unwrap();
+    }
+}
@@ -0,0 +1,82 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+
+/// Lints an Aleo program for legal, but potentially dangerous, patterns.
+#[derive(Debug, Parser)]
+pub struct Lint {
+    /// Returns an error if any warnings are found.
+    #[clap(long)]
+    deny_warnings: bool,
+}
+
+impl Lint {
+    /// Lints the Aleo program in the current package.
+    pub fn parse(self) -> Result<String> {
+        // Derive the program directory path.
+        let path = std::env::current_dir()?;
+
+        // Load the package.
+        let package = Package::<CurrentNetwork>::open(&path)?;
+
+        // Lint the program.
+        let warnings = package.program().lint();
+
+        // Log the warnings.
+        for warning in &warnings {
+            println!("⚠️  {warning}");
+        }
+        if !warnings.is_empty() {
+            println!();
+        }
+
+        // Prepare the path string.
+        let path_string = format!("(in \"{}\")", path.display());
+
+        match warnings.len() {
+            0 => Ok(format!(
+                "✅ Linted '{}' with no warnings {}",
+                package.program_id().to_string().bold(),
+                path_string.dimmed()
+            )),
+            num_warnings if self.deny_warnings => {
+                bail!("Linting '{}' found {num_warnings} warning(s) {}", package.program_id(), path_string)
+            }
+            num_warnings => Ok(format!(
+                "✅ Linted '{}' with {num_warnings} warning(s) {}",
+                package.program_id().to_string().bold(),
+                path_string.dimmed()
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{Command, CLI};
+
+    #[test]
+    fn clap_snarkvm_lint() {
+        let arg_vec = vec!["snarkvm", "lint", "--deny-warnings"];
+        let cli = CLI::parse_from(arg_vec);
+
+        if let Command::Lint(lint) = cli.command {
+            assert!(lint.deny_warnings);
+        } else {
+            panic!("Unexpected result of clap parsing!");
+        }
+    }
+}
@@ -18,12 +18,37 @@ pub use build::*;
 pub mod clean;
 pub use clean::*;
 
+pub mod debug;
+pub use debug::*;
+
 pub mod execute;
 pub use execute::*;
 
+pub mod fmt;
+pub use fmt::*;
+
+#[cfg(feature = "rocks")]
+pub mod fsck;
+#[cfg(feature = "rocks")]
+pub use fsck::*;
+
+pub mod keystore;
+pub use keystore::*;
+
+pub mod lint;
+pub use lint::*;
+
 pub mod new;
 pub use new::*;
 
+pub mod prove;
+pub use prove::*;
+
+#[cfg(feature = "rocks")]
+pub mod replay;
+#[cfg(feature = "rocks")]
+pub use replay::*;
+
 pub mod run;
 pub use run::*;
 
@@ -32,11 +57,13 @@ pub use update::*;
 
 use crate::{
     console::program::{Identifier, Locator, ProgramID, Value},
+    file::AleoFile,
     ledger::block::Transaction,
     package::Package,
+    synthesizer::program::Program,
 };
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::Parser;
 use colored::Colorize;
 use core::str::FromStr;
@@ -0,0 +1,63 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{
+    ledger::{
+        query::Query,
+        store::{helpers::memory::ConsensusMemory, ConsensusStore},
+    },
+    synthesizer::{AuthorizationExport, VM},
+};
+
+use std::path::PathBuf;
+
+/// Proves an exported authorization into a transaction
+#[derive(Debug, Parser)]
+pub struct Prove {
+    /// The path of the authorization export file.
+    path: PathBuf,
+    /// Uses the specified endpoint.
+    #[clap(default_value = "https://api.explorer.aleo.org/v1", long)]
+    endpoint: String,
+    /// Writes the transaction to the specified path, instead of printing it.
+    #[clap(long)]
+    output: Option<PathBuf>,
+}
+
+impl Prove {
+    /// Proves the authorization export at the specified path.
+    pub fn parse(self) -> Result<String> {
+        // Load the authorization export.
+        let export = AuthorizationExport::<CurrentNetwork>::from_str(&std::fs::read_to_string(&self.path)?)?;
+
+        // Initialize a VM, which loads the exported programs for the execution.
+        let vm = VM::from(ConsensusStore::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::open(None)?)?;
+
+        // Prove the authorization export.
+        let transaction =
+            vm.execute_authorization_export(&export, Some(Query::from(self.endpoint)), &mut rand::thread_rng())?;
+
+        // Print or write the transaction.
+        match &self.output {
+            Some(output) => std::fs::write(output, transaction.to_string())?,
+            None => println!("{transaction}\n"),
+        }
+
+        // Prepare the path string.
+        let path_string = format!("(from \"{}\")", self.path.display());
+
+        Ok(format!("✅ Proved transaction '{}' {}", transaction.id().to_string().bold(), path_string.dimmed()))
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{
+    console::network::Network,
+    ledger::{
+        block::Block,
+        store::helpers::{memory::ConsensusMemory, rocksdb::ConsensusDB},
+        Ledger,
+    },
+    utilities::FromBytes,
+};
+
+use std::path::PathBuf;
+
+/// Re-executes the blocks of a ledger (or of exported blocks) into a fresh ledger, and reports the first divergence
+#[derive(Debug, Parser)]
+pub struct Replay {
+    /// The path of the ledger storage directory to replay.
+    #[clap(long, conflicts_with = "blocks", required_unless_present = "blocks")]
+    path: Option<PathBuf>,
+    /// The path of a directory of exported blocks, named '{height}.block', to replay instead of a ledger.
+    #[clap(long)]
+    blocks: Option<PathBuf>,
+    /// The height of the first block to replay. The blocks before it are added without being replayed.
+    #[clap(long, default_value = "1")]
+    start: u32,
+    /// The height of the last block to replay. Defaults to the latest available block.
+    #[clap(long)]
+    end: Option<u32>,
+}
+
+impl Replay {
+    /// Replays the blocks, and returns the divergence found, if any.
+    pub fn parse(self) -> Result<String> {
+        // Ensure the replay does not start at the genesis block.
+        if self.start == 0 {
+            bail!("The genesis block can not be replayed, use '--start 1' instead")
+        }
+
+        // Load the source of the blocks.
+        let genesis = Block::<CurrentNetwork>::from_bytes_le(CurrentNetwork::genesis_bytes())?;
+        let source = match (&self.path, &self.blocks) {
+            (Some(path), _) => {
+                // Ensure a new ledger is not initialized at the given path.
+                if !path.exists() {
+                    bail!("No ledger storage exists at \"{}\"", path.display())
+                }
+                let ledger = Ledger::<CurrentNetwork, ConsensusDB<CurrentNetwork>>::load_unchecked(
+                    genesis.clone(),
+                    path.clone().into(),
+                )?;
+                BlockSource::Ledger(ledger)
+            }
+            (None, Some(blocks)) => match blocks.is_dir() {
+                true => BlockSource::Directory(blocks.clone()),
+                false => bail!("No block directory exists at \"{}\"", blocks.display()),
+            },
+            (None, None) => bail!("Specify either '--path' or '--blocks'"),
+        };
+
+        // Initialize a fresh ledger with the genesis block.
+        let ledger =
+            Ledger::<CurrentNetwork, ConsensusMemory<CurrentNetwork>>::load_unchecked(genesis, None::<u16>.into())?;
+        let rng = &mut rand::thread_rng();
+
+        let mut height = 1;
+        while self.end.map_or(true, |end| height <= end) {
+            // Retrieve the next block, or stop if there are no more blocks.
+            let Some(block) = source.get_block(height)? else {
+                break;
+            };
+
+            // Add the blocks before the start height without replaying them.
+            if height < self.start {
+                ledger.advance_to_next_block(&block)?;
+                height += 1;
+                continue;
+            }
+
+            // Replay the block, and stop at the first divergence.
+            if let Some(divergence) = ledger.replay_block(&block, rng)? {
+                println!("{divergence}");
+                bail!("❌ Block {height} diverges from its replay")
+            }
+            println!("✅ Replayed block {height} ('{}')", block.hash());
+            height += 1;
+        }
+
+        // Ensure at least one block was replayed.
+        if height <= self.start {
+            bail!("No block found at height {}", self.start)
+        }
+
+        Ok(format!(
+            "✅ Replayed blocks {} to {} without divergence\n\n  State root: {}",
+            self.start,
+            height - 1,
+            ledger.latest_state_root()
+        ))
+    }
+}
+
+/// The source of the blocks to replay.
+enum BlockSource {
+    /// A ledger storage.
+    Ledger(Ledger<CurrentNetwork, ConsensusDB<CurrentNetwork>>),
+    /// A directory of exported blocks.
+    Directory(PathBuf),
+}
+
+impl BlockSource {
+    /// Returns the block at the given height, or `None` if the source does not contain it.
+    fn get_block(&self, height: u32) -> Result<Option<Block<CurrentNetwork>>> {
+        match self {
+            Self::Ledger(ledger) => match height <= ledger.latest_height() {
+                true => Ok(Some(ledger.get_block(height)?)),
+                false => Ok(None),
+            },
+            Self::Directory(directory) => {
+                let path = directory.join(format!("{height}.block"));
+                match path.exists() {
+                    true => Ok(Some(Block::from_bytes_le(&std::fs::read(path)?)?)),
+                    false => Ok(None),
+                }
+            }
+        }
+    }
+}
@@ -41,11 +41,23 @@ impl Run {
         // Execute the request.
         let (response, metrics) = package.run::<Aleo, _>(&private_key, self.function, &self.inputs, rng)?;
 
+        // Load the source map, to report the source location of the program's functions.
+        let source_map = package.source_map()?;
+
         // Count the number of times a function is called.
         let mut program_frequency = HashMap::<String, usize>::new();
         for metric in metrics.iter() {
             // Prepare the function name string.
-            let function_name_string = format!("'{}/{}'", metric.program_id, metric.function_name).bold();
+            let mut function_name_string =
+                format!("'{}/{}'", metric.program_id, metric.function_name).bold().to_string();
+            // Include the source location, if known.
+            let source_span = source_map
+                .as_ref()
+                .filter(|source_map| source_map.program_id() == &metric.program_id)
+                .and_then(|source_map| source_map.get_function_span(&metric.function_name));
+            if let Some(source_span) = source_span {
+                function_name_string.push_str(&format!(" ({source_span})").dimmed().to_string());
+            }
 
             // Prepare the function constraints string
             let function_constraints_string = format!(
@@ -12,9 +12,13 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::{cli::CurrentNetwork, console::account::PrivateKey};
+use crate::{
+    cli::CurrentNetwork,
+    console::account::{Keystore, PrivateKey},
+};
 
 use anyhow::{anyhow, Result};
+use std::{path::Path, str::FromStr};
 
 fn env_template() -> String {
     r#"
@@ -43,11 +47,34 @@ pub fn dotenv_private_key() -> Result<PrivateKey<CurrentNetwork>> {
         let rng = &mut crate::utilities::TestRng::fixed(123456789);
         PrivateKey::<CurrentNetwork>::new(rng)
     } else {
-        use std::str::FromStr;
         dotenv_load()?;
+        // Load the private key from the keystore, if one is specified.
+        if let Ok(keystore) = dotenvy::var("KEYSTORE") {
+            return unlock_keystore(Path::new(&keystore));
+        }
         // Load the private key from the environment.
-        let private_key = dotenvy::var("PRIVATE_KEY").map_err(|e| anyhow!("Missing PRIVATE_KEY - {e}"))?;
+        let private_key =
+            dotenvy::var("PRIVATE_KEY").map_err(|e| anyhow!("Missing PRIVATE_KEY (or KEYSTORE) - {e}"))?;
         // Parse the private key.
         PrivateKey::<CurrentNetwork>::from_str(&private_key)
     }
 }
+
+/// Returns the keystore password from the `KEYSTORE_PASSWORD` environment variable, or else prompts for it.
+pub fn keystore_password(prompt: &str) -> Result<String> {
+    match dotenvy::var("KEYSTORE_PASSWORD") {
+        Ok(password) => Ok(password),
+        Err(_) => Ok(rpassword::prompt_password(prompt)?),
+    }
+}
+
+/// Returns the private key from the keystore at the given path, prompting for its password.
+pub fn unlock_keystore(path: &Path) -> Result<PrivateKey<CurrentNetwork>> {
+    // Read the keystore.
+    let keystore = std::fs::read_to_string(path)
+        .map_err(|e| anyhow!("Failed to read the keystore at \"{}\" - {e}", path.display()))?;
+    let keystore = Keystore::<CurrentNetwork>::from_str(&keystore)?;
+    // Decrypt the private key.
+    let password = keystore_password(&format!("Enter the password for '{}': ", keystore.address()))?;
+    keystore.decrypt(&password)
+}
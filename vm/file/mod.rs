@@ -27,5 +27,8 @@ pub use prover::ProverFile;
 mod readme_file;
 pub use readme_file::README;
 
+mod source_map;
+pub use source_map::SourceMapFile;
+
 mod verifier;
 pub use verifier::VerifierFile;
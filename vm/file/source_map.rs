@@ -0,0 +1,88 @@
+// Copyright (C) 2019-2023 Aleo Systems Inc.
+// This file is part of the snarkVM library.
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at:
+// http://www.apache.org/licenses/LICENSE-2.0
+
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{prelude::Network, synthesizer::process::SourceMap};
+
+use anyhow::{ensure, Result};
+use core::str::FromStr;
+use std::{
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+const SOURCE_MAP_FILE_NAME: &str = "main.aleo.map";
+
+pub struct SourceMapFile<N: Network> {
+    /// The file path.
+    path: PathBuf,
+    /// The source map.
+    source_map: SourceMap<N>,
+}
+
+impl<N: Network> SourceMapFile<N> {
+    /// Creates a new source map file with the given directory path and source map.
+    pub fn create(directory: &Path, source_map: SourceMap<N>) -> Result<Self> {
+        // Ensure the directory path exists.
+        ensure!(directory.exists(), "The program directory does not exist: '{}'", directory.display());
+
+        // Construct the file path.
+        let path = directory.join(SOURCE_MAP_FILE_NAME);
+        // Write the file.
+        File::create(&path)?.write_all(source_map.to_string().as_bytes())?;
+
+        // Return the source map file.
+        Ok(Self { path, source_map })
+    }
+
+    /// Opens the source map file for reading.
+    pub fn open(directory: &Path) -> Result<Self> {
+        // Ensure the directory path exists.
+        ensure!(directory.exists(), "The program directory does not exist: '{}'", directory.display());
+
+        // Construct the file path.
+        let path = directory.join(SOURCE_MAP_FILE_NAME);
+        // Ensure the file path exists.
+        ensure!(path.exists(), "Source map file is missing: '{}'", path.display());
+
+        // Read the file to a string, and parse the source map.
+        let source_map = SourceMap::from_str(&fs::read_to_string(&path)?)?;
+
+        // Return the source map file.
+        Ok(Self { path, source_map })
+    }
+
+    /// Returns `true` if the source map file exists at the given path.
+    pub fn exists_at(directory: &Path) -> bool {
+        // Construct the file path.
+        let path = directory.join(SOURCE_MAP_FILE_NAME);
+        // Return the result.
+        path.is_file() && path.exists()
+    }
+
+    /// Returns the source map file name.
+    pub const fn file_name() -> &'static str {
+        SOURCE_MAP_FILE_NAME
+    }
+
+    /// Returns the file path.
+    pub const fn path(&self) -> &PathBuf {
+        &self.path
+    }
+
+    /// Returns the source map.
+    pub const fn source_map(&self) -> &SourceMap<N> {
+        &self.source_map
+    }
+}
@@ -28,11 +28,11 @@ use crate::{
         network::Network,
         program::{Identifier, Locator, ProgramID, Response, Value},
     },
-    file::{AVMFile, AleoFile, Manifest, ProverFile, VerifierFile, README},
+    file::{AVMFile, AleoFile, Manifest, ProverFile, SourceMapFile, VerifierFile, README},
     ledger::{block::Execution, query::Query, store::helpers::memory::BlockMemory},
     prelude::{Deserialize, Deserializer, Serialize, SerializeStruct, Serializer},
     synthesizer::{
-        process::{Assignments, CallMetrics, CallStack, Process, StackExecute},
+        process::{Assignments, CallMetrics, CallStack, Process, SourceMap, StackExecute},
         program::{CallOperator, Instruction, Program},
         snark::{ProvingKey, VerifyingKey},
     },
@@ -147,6 +147,14 @@ impl<N: Network> Package<N> {
         self.directory.join("imports")
     }
 
+    /// Returns the source map of the program, if the package contains one.
+    pub fn source_map(&self) -> Result<Option<SourceMap<N>>> {
+        match SourceMapFile::<N>::exists_at(&self.directory) {
+            true => Ok(Some(SourceMapFile::open(&self.directory)?.source_map().clone())),
+            false => Ok(None),
+        }
+    }
+
     /// Returns a new process for the package.
     pub fn get_process(&self) -> Result<Process<N>> {
         // Create the process.
@@ -173,6 +181,11 @@ impl<N: Network> Package<N> {
         // Add the program to the process.
         process.add_program(self.program())?;
 
+        // Add the source map to the process, if it exists.
+        if let Some(source_map) = self.source_map()? {
+            process.add_source_map(source_map)?;
+        }
+
         Ok(process)
     }
 }